@@ -8,6 +8,7 @@ use crate::memory::{self, Memory, MemoryCategory};
 use crate::observability::{self, Observer, ObserverEvent, InterventionChain, InterventionVerdict, InterventionContext, MessageDirection, TripwireHandler, SingleActionHandler, DepthGuardHandler, ConvergenceDetector};
 use crate::providers::{self, ChatMessage, ChatRequest, ConversationMessage, Provider};
 use crate::runtime;
+use crate::security::tool_rules::RuleAction;
 use crate::security::SecurityPolicy;
 use crate::tools::{self, Tool, ToolSpec};
 use crate::util::truncate_with_ellipsis;
@@ -407,8 +408,8 @@ impl Agent {
             };
             let args_str = call.arguments.to_string();
             match self.intervention_chain.process(&args_str, &ctx) {
-                InterventionVerdict::Allow => {}
-                InterventionVerdict::Modify(_) => {} // tool args modification not supported yet
+                // tool args modification not supported yet
+                InterventionVerdict::Allow | InterventionVerdict::Modify(_) => {}
                 InterventionVerdict::Drop(reason) => {
                     tracing::warn!(tool = %call.name, reason = %reason, "ACT: tool invocation dropped");
                     return ToolExecutionResult {
@@ -441,6 +442,21 @@ impl Agent {
             };
         }
 
+        // Policy rules on tool arguments; there is no approver here, so
+        // calls that need approval are refused.
+        let rule = self
+            .security
+            .check_tool_args(&call.name, &call.arguments, "agent");
+        if rule.action != RuleAction::Allow {
+            tracing::warn!(tool = %call.name, reason = %rule.reason, "Tool denied by policy rule");
+            return ToolExecutionResult {
+                name: call.name.clone(),
+                output: format!("Error: {} ({})", rule.reason, rule.action),
+                success: false,
+                tool_call_id: call.tool_call_id.clone(),
+            };
+        }
+
         let result = if let Some(tool) = self.tools.iter().find(|t| t.name() == call.name) {
             match tool.execute(call.arguments.clone()).await {
                 Ok(r) => {
@@ -534,7 +550,9 @@ impl Agent {
         };
 
         // ══ STOP ══ Receive input, gate check via InterventionChain
-        let enriched = if !self.intervention_chain.is_empty() {
+        let enriched = if self.intervention_chain.is_empty() {
+            enriched
+        } else {
             let ctx = InterventionContext {
                 direction: MessageDirection::Inbound,
                 agent_id: self.security.agent_id.clone(),
@@ -554,8 +572,6 @@ impl Agent {
                     return Err(anyhow::anyhow!("HALT: {}", reason));
                 }
             }
-        } else {
-            enriched
         };
 
         // ── Three-layer guardrail: check_input ──
@@ -600,7 +616,9 @@ impl Agent {
                 };
 
                 // ══ THINK → output ══ Check LLM response via InterventionChain
-                let final_text = if !self.intervention_chain.is_empty() {
+                let final_text = if self.intervention_chain.is_empty() {
+                    final_text
+                } else {
                     let ctx = InterventionContext {
                         direction: MessageDirection::OutboundResponse,
                         agent_id: self.security.agent_id.clone(),
@@ -620,8 +638,6 @@ impl Agent {
                             return Err(anyhow::anyhow!("HALT: {}", reason));
                         }
                     }
-                } else {
-                    final_text
                 };
 
                 // ── Three-layer guardrail: check_output ──
@@ -722,6 +738,8 @@ pub async fn run(
     }
     effective_config.default_temperature = temperature;
 
+    crate::mcp::init(&effective_config).await;
+    crate::memory::embeddings::init(&effective_config.memory).await;
     let mut agent = Agent::from_config(&effective_config)?;
 
     let provider_name = effective_config
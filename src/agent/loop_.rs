@@ -1,10 +1,13 @@
-use crate::approval::{ApprovalManager, ApprovalRequest, ApprovalResponse};
-use crate::config::Config;
+use crate::approval::{audit_summary, ApprovalManager, ApprovalRequest, ApprovalResponse};
+use crate::config::{AutonomyConfig, Config};
 use crate::memory::{self, Memory, MemoryCategory};
 use crate::observability::{self, Observer, ObserverEvent, InterventionChain, InterventionVerdict, InterventionContext, MessageDirection, TripwireHandler, SingleActionHandler, DepthGuardHandler, ConvergenceDetector};
-use crate::providers::{self, ChatMessage, ChatRequest, Provider, ToolCall};
+use crate::providers::{self, ChatMessage, ChatRequest, ContentPart, Provider, ToolCall};
 use crate::runtime;
+use crate::security::audit::{self, AuditEvent, AuditEventType};
+use crate::security::tool_rules::RuleAction;
 use crate::security::SecurityPolicy;
+use crate::sessions::{self, SessionStore};
 use crate::tools::{self, Tool};
 use crate::util::truncate_with_ellipsis;
 use anyhow::Result;
@@ -42,7 +45,7 @@ static SENSITIVE_KV_REGEX: LazyLock<Regex> = LazyLock::new(|| {
 /// Scrub credentials from tool output to prevent accidental exfiltration.
 /// Replaces known credential patterns with a redacted placeholder while preserving
 /// a small prefix for context.
-fn scrub_credentials(input: &str) -> String {
+pub(crate) fn scrub_credentials(input: &str) -> String {
     SENSITIVE_KV_REGEX
         .replace_all(input, |caps: &regex::Captures| {
             let full_match = &caps[0];
@@ -76,6 +79,58 @@ fn scrub_credentials(input: &str) -> String {
         .to_string()
 }
 
+/// Image formats accepted by every vision-capable provider.
+const VISION_IMAGE_MIME_TYPES: [&str; 4] = ["image/png", "image/jpeg", "image/gif", "image/webp"];
+
+/// Pull inline `data:image/...;base64,` lines (as emitted by `image_info` and
+/// `screenshot`) out of tool output so they can be sent as image parts instead
+/// of text. Payloads that do not decode cleanly (e.g. truncated) stay in place.
+fn extract_inline_images(output: &str) -> (String, Vec<ContentPart>) {
+    use base64::Engine;
+
+    let mut text = String::with_capacity(output.len().min(4096));
+    let mut images = Vec::new();
+    for line in output.lines() {
+        let inline = line
+            .trim()
+            .strip_prefix("data:")
+            .and_then(|rest| rest.split_once(";base64,"))
+            .filter(|(mime, data)| {
+                VISION_IMAGE_MIME_TYPES.contains(mime)
+                    && base64::engine::general_purpose::STANDARD
+                        .decode(data)
+                        .is_ok()
+            });
+        if !text.is_empty() {
+            text.push('\n');
+        }
+        match inline {
+            Some((mime, data)) => {
+                let _ = write!(text, "[image attached: {mime}]");
+                images.push(ContentPart::image(mime, data));
+            }
+            None => text.push_str(line),
+        }
+    }
+    (text, images)
+}
+
+/// Replace image parts with a short note for providers without vision support,
+/// so the model still knows the user sent something it cannot see.
+fn strip_image_parts(history: &mut [ChatMessage]) {
+    for msg in history.iter_mut().filter(|m| !m.parts.is_empty()) {
+        let count = msg.parts.len();
+        msg.parts.clear();
+        if !msg.content.is_empty() {
+            msg.content.push('\n');
+        }
+        let _ = write!(
+            msg.content,
+            "[{count} image attachment(s) omitted: the current model does not accept image input]"
+        );
+    }
+}
+
 /// Default trigger for auto-compaction when non-system message count exceeds this threshold.
 /// Prefer passing the config-driven value via `run_tool_call_loop`; this constant is only
 /// used when callers omit the parameter.
@@ -207,7 +262,11 @@ async fn auto_compact_history(
 /// Build context preamble by searching memory for relevant entries.
 /// Entries with a hybrid score below `min_relevance_score` are dropped to
 /// prevent unrelated memories from bleeding into the conversation.
-async fn build_context(mem: &dyn Memory, user_msg: &str, min_relevance_score: f64) -> String {
+pub(crate) async fn build_context(
+    mem: &dyn Memory,
+    user_msg: &str,
+    min_relevance_score: f64,
+) -> String {
     let mut context = String::new();
 
     // Pull relevant memories for this message
@@ -223,7 +282,12 @@ async fn build_context(mem: &dyn Memory, user_msg: &str, min_relevance_score: f6
         if !relevant.is_empty() {
             context.push_str("[Memory context]\n");
             for entry in &relevant {
-                let _ = writeln!(context, "- {}: {}", entry.key, entry.content);
+                let _ = writeln!(
+                    context,
+                    "- {}: {}",
+                    memory::ingest::cite(&entry.key),
+                    entry.content
+                );
             }
             context.push('\n');
         }
@@ -277,6 +341,51 @@ fn find_tool<'a>(tools: &'a [Box<dyn Tool>], name: &str) -> Option<&'a dyn Tool>
     tools.iter().find(|t| t.name() == name).map(|t| t.as_ref())
 }
 
+/// Set `approved: true` on calls to tools whose schema has that flag.
+fn mark_approved(tools: &[Box<dyn Tool>], name: &str, arguments: &mut serde_json::Value) {
+    let accepts_flag = find_tool(tools, name).is_some_and(|tool| {
+        tool.parameters_schema()["properties"]
+            .get("approved")
+            .is_some()
+    });
+    if let (true, Some(args)) = (accepts_flag, arguments.as_object_mut()) {
+        args.insert("approved".into(), serde_json::Value::Bool(true));
+    }
+}
+
+pub(crate) fn elapsed_ms(start: Instant) -> u64 {
+    u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX)
+}
+
+/// Audit event for a tool call; the caller adds the outcome.
+fn tool_call_audit(
+    channel: &str,
+    call: &ParsedToolCall,
+    approved: bool,
+    allowed: bool,
+) -> AuditEvent {
+    tool_audit_event(channel, &call.name, &call.arguments, approved, allowed)
+}
+
+/// Audit event for a call to tool `name` made outside the agent loop (MCP
+/// server); the caller adds the outcome.
+pub(crate) fn tool_audit_event(
+    channel: &str,
+    name: &str,
+    arguments: &serde_json::Value,
+    approved: bool,
+    allowed: bool,
+) -> AuditEvent {
+    AuditEvent::new(AuditEventType::ToolCall)
+        .with_actor(channel.to_string(), None, None)
+        .with_tool(
+            name.to_string(),
+            audit_summary(arguments),
+            approved,
+            allowed,
+        )
+}
+
 fn parse_arguments_value(raw: Option<&serde_json::Value>) -> serde_json::Value {
     match raw {
         Some(serde_json::Value::String(s)) => serde_json::from_str::<serde_json::Value>(s)
@@ -354,6 +463,80 @@ fn find_first_tag<'a>(haystack: &str, tags: &'a [&'a str]) -> Option<(usize, &'a
         .min_by_key(|(idx, _)| *idx)
 }
 
+/// Markers that start a prompt-guided tool call inside streamed text.
+const STREAM_TOOL_CALL_MARKERS: [&str; 8] = [
+    "<tool_call>",
+    "<toolcall>",
+    "<tool-call>",
+    "<invoke>",
+    "```tool_call",
+    "```tool-call",
+    "```toolcall",
+    "```invoke",
+];
+
+/// Forwards streamed assistant text while holding back anything that may
+/// turn out to be a prompt-guided tool call.
+///
+/// Text is released up to the first tool-call marker; a trailing fragment
+/// that could still grow into a marker is held until the next delta. A
+/// response that opens with raw JSON is never streamed because it may be a
+/// `tool_calls` payload.
+#[derive(Debug, Default)]
+struct ToolCallStreamGate {
+    received: String,
+    emitted: usize,
+    halted: bool,
+}
+
+impl ToolCallStreamGate {
+    /// Feed a raw delta. Returns the text that is safe to forward.
+    fn push(&mut self, delta: &str) -> Option<String> {
+        if self.halted {
+            return None;
+        }
+        self.received.push_str(delta);
+
+        if self.emitted == 0 {
+            let leading = self.received.trim_start();
+            if leading.is_empty() {
+                return None;
+            }
+            if leading.starts_with('{') || leading.starts_with('[') {
+                self.halted = true;
+                return None;
+            }
+        }
+
+        let pending = &self.received[self.emitted..];
+        let release = if let Some((idx, _)) = find_first_tag(pending, &STREAM_TOOL_CALL_MARKERS) {
+            self.halted = true;
+            idx
+        } else {
+            let held = STREAM_TOOL_CALL_MARKERS
+                .iter()
+                .flat_map(|marker| (1..marker.len()).map(move |len| &marker[..len]))
+                .filter(|prefix| pending.ends_with(prefix))
+                .map(str::len)
+                .max()
+                .unwrap_or(0);
+            pending.len() - held
+        };
+
+        if release == 0 {
+            return None;
+        }
+        let text = pending[..release].to_string();
+        self.emitted += release;
+        Some(text)
+    }
+
+    /// Text forwarded so far.
+    fn emitted(&self) -> &str {
+        &self.received[..self.emitted]
+    }
+}
+
 fn matching_tool_call_close_tag(open_tag: &str) -> Option<&'static str> {
     match open_tag {
         "<tool_call>" => Some("</tool_call>"),
@@ -849,6 +1032,65 @@ pub(crate) async fn agent_turn(
     .await
 }
 
+/// Print streamed assistant text to stdout as it arrives. The returned task
+/// resolves to `true` once the sender is dropped if anything was printed.
+fn spawn_cli_stream_printer(
+    leading_newline: bool,
+) -> (
+    tokio::sync::mpsc::Sender<String>,
+    tokio::task::JoinHandle<bool>,
+) {
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<String>(64);
+    let printer = tokio::spawn(async move {
+        let mut printed = false;
+        while let Some(delta) = rx.recv().await {
+            if !printed && leading_newline {
+                println!();
+            }
+            print!("{delta}");
+            let _ = std::io::stdout().flush();
+            printed = true;
+        }
+        printed
+    });
+    (tx, printer)
+}
+
+/// Run one provider call with live token streaming. Deltas pass through a
+/// [`ToolCallStreamGate`] before reaching `on_delta`; returns the response
+/// together with the text that was forwarded.
+async fn stream_provider_turn(
+    provider: &dyn Provider,
+    request: ChatRequest<'_>,
+    model: &str,
+    temperature: f64,
+    on_delta: &tokio::sync::mpsc::Sender<String>,
+    after_earlier_text: bool,
+) -> (Result<providers::ChatResponse>, String) {
+    let (raw_tx, mut raw_rx) = tokio::sync::mpsc::channel::<String>(64);
+    let request_fut = async move {
+        // `raw_tx` is dropped when the call returns, which ends the forwarder.
+        provider
+            .stream_chat(request, model, temperature, &raw_tx)
+            .await
+    };
+    let forward_fut = async {
+        let mut gate = ToolCallStreamGate::default();
+        while let Some(delta) = raw_rx.recv().await {
+            let Some(text) = gate.push(&delta) else {
+                continue;
+            };
+            // Keep this round apart from text streamed before a tool call.
+            if after_earlier_text && gate.emitted().len() == text.len() {
+                let _ = on_delta.send("\n\n".to_string()).await;
+            }
+            let _ = on_delta.send(text).await;
+        }
+        gate.emitted().to_string()
+    };
+    tokio::join!(request_fut, forward_fut)
+}
+
 /// Execute a single turn of the agent loop: send messages, parse tool calls,
 /// execute tools, and loop until the LLM produces a final text response.
 #[allow(clippy::too_many_arguments)]
@@ -877,6 +1119,10 @@ pub(crate) async fn run_tool_call_loop(
     let tool_specs: Vec<crate::tools::ToolSpec> =
         tools_registry.iter().map(|tool| tool.spec()).collect();
     let use_native_tools = provider.supports_native_tools() && !tool_specs.is_empty();
+    let supports_vision = provider.supports_vision();
+    if !supports_vision {
+        strip_image_parts(history);
+    }
 
     // ── Three-layer guardrails: check input before first LLM call ──
     // Check the last user message for tripwire patterns
@@ -887,6 +1133,11 @@ pub(crate) async fn run_tool_call_loop(
         }
     }
 
+    // Stream tokens live only when nothing has to vet the complete text
+    // first: output tripwires cannot retract text that is already visible.
+    let live_stream = on_delta.is_some() && security.tripwire_patterns.is_empty();
+    let mut streamed_any = false;
+
     for _iteration in 0..max_iterations {
         observer.record_event(&ObserverEvent::LlmRequest {
             provider: provider_name.to_string(),
@@ -904,18 +1155,24 @@ pub(crate) async fn run_tool_call_loop(
             None
         };
 
+        let request = ChatRequest {
+            messages: history,
+            tools: request_tools,
+        };
+        let (chat_result, streamed_text) = match on_delta.as_ref().filter(|_| live_stream) {
+            Some(tx) => {
+                stream_provider_turn(provider, request, model, temperature, tx, streamed_any).await
+            }
+            None => (
+                provider.chat(request, model, temperature).await,
+                String::new(),
+            ),
+        };
+        let streamed_before = streamed_any;
+        streamed_any |= !streamed_text.is_empty();
+
         let (response_text, parsed_text, tool_calls, assistant_history_content, native_tool_calls) =
-            match provider
-                .chat(
-                    ChatRequest {
-                        messages: history,
-                        tools: request_tools,
-                    },
-                    model,
-                    temperature,
-                )
-                .await
-            {
+            match chat_result {
                 Ok(resp) => {
                     observer.record_event(&ObserverEvent::LlmResponse {
                         provider: provider_name.to_string(),
@@ -983,6 +1240,23 @@ pub(crate) async fn run_tool_call_loop(
             // If a streaming sender is provided, relay the text in small chunks
             // so the channel can progressively update the draft message.
             if let Some(ref tx) = on_delta {
+                if !streamed_text.is_empty() {
+                    // Tokens were streamed live; forward only what the gate
+                    // held back, or the whole answer if it diverged.
+                    let rest = match display_text.strip_prefix(streamed_text.trim_start()) {
+                        Some(rest) => rest.to_string(),
+                        None => format!("\n\n{display_text}"),
+                    };
+                    if !rest.is_empty() {
+                        let _ = tx.send(rest).await;
+                    }
+                    history.push(ChatMessage::assistant(response_text.clone()));
+                    return Ok(display_text);
+                }
+                if streamed_before && !display_text.is_empty() {
+                    let _ = tx.send("\n\n".to_string()).await;
+                }
+
                 // Split on whitespace boundaries, accumulating chunks of at least
                 // STREAM_CHUNK_MIN_CHARS characters for progressive draft updates.
                 let mut chunk = String::new();
@@ -1002,8 +1276,9 @@ pub(crate) async fn run_tool_call_loop(
             return Ok(display_text);
         }
 
-        // Print any text the LLM produced alongside tool calls (unless silent)
-        if !silent && !display_text.is_empty() {
+        // Print any text the LLM produced alongside tool calls (unless silent
+        // or already streamed)
+        if !silent && !display_text.is_empty() && streamed_text.is_empty() {
             print!("{display_text}");
             let _ = std::io::stdout().flush();
         }
@@ -1013,25 +1288,55 @@ pub(crate) async fn run_tool_call_loop(
         // can emit one `role: tool` message per tool call with the correct ID.
         let mut tool_results = String::new();
         let mut individual_results: Vec<String> = Vec::new();
+        let mut tool_images: Vec<ContentPart> = Vec::new();
         for call in &tool_calls {
+            let mut arguments = call.arguments.clone();
+            let mut approved = false;
+            // ── Policy rules on tool arguments ───────────────
+            let rule = security.check_tool_args(&call.name, &call.arguments, channel_name);
+            let rule_denial = match rule.action {
+                RuleAction::Deny => Some(rule.reason.clone()),
+                RuleAction::RequireApproval if approval.is_none() => Some(format!(
+                    "{} (requires approval, and no approver is available)",
+                    rule.reason
+                )),
+                _ => None,
+            };
+            if let Some(reason) = rule_denial {
+                tracing::warn!(tool = %call.name, reason = %reason, "Policy rule denied tool call");
+                individual_results.push(format!("DENIED: {reason}"));
+                let _ = writeln!(
+                    tool_results,
+                    "<tool_result name=\"{}\">\nDENIED: {reason}\n</tool_result>",
+                    call.name
+                );
+                continue;
+            }
+            let rule_requires_approval = rule.action == RuleAction::RequireApproval;
+
             // ── Approval hook ────────────────────────────────
             if let Some(mgr) = approval {
-                if mgr.needs_approval(&call.name) {
+                if rule_requires_approval || mgr.needs_approval(&call.name) {
                     let request = ApprovalRequest {
                         tool_name: call.name.clone(),
                         arguments: call.arguments.clone(),
                     };
 
-                    // Only prompt interactively on CLI; auto-approve on other channels.
-                    let decision = if channel_name == "cli" {
-                        mgr.prompt_cli(&request)
-                    } else {
-                        ApprovalResponse::Yes
-                    };
-
-                    mgr.record_decision(&call.name, &call.arguments, decision, channel_name);
+                    let mut decision = mgr.decide(&request, channel_name).await;
+                    // A policy rule asks for a person; automatic approval
+                    // does not satisfy it.
+                    if rule_requires_approval && decision.responder.is_none() {
+                        decision.response = ApprovalResponse::No;
+                    }
+                    mgr.record_decision_by(
+                        &call.name,
+                        &call.arguments,
+                        decision.response,
+                        channel_name,
+                        decision.responder.as_deref(),
+                    );
 
-                    if decision == ApprovalResponse::No {
+                    if decision.response == ApprovalResponse::No {
                         let denied = "Denied by user.".to_string();
                         individual_results.push(denied.clone());
                         let _ = writeln!(
@@ -1041,12 +1346,23 @@ pub(crate) async fn run_tool_call_loop(
                         );
                         continue;
                     }
+                    // A person approved this exact call, which covers the
+                    // tool's own `approved` flag (medium-risk shell commands).
+                    if decision.responder.is_some() {
+                        approved = true;
+                        mark_approved(tools_registry, &call.name, &mut arguments);
+                    }
                 }
             }
 
             // ── Three-layer guardrails: check tool ──
             if let Err(reason) = security.check_tool(&call.name) {
                 tracing::warn!(tool = %call.name, reason = %reason, "Tool guardrail triggered");
+                audit::record(
+                    &tool_call_audit(channel_name, call, approved, false)
+                        .with_policy_violation()
+                        .with_result(false, None, 0, Some(reason.clone())),
+                );
                 individual_results.push(format!("DENIED: {reason}"));
                 let _ = writeln!(
                     tool_results,
@@ -1074,6 +1390,11 @@ DENIED: {}
                     }
                     InterventionVerdict::Drop(reason) => {
                         tracing::warn!(tool = %call.name, reason = %reason, "InterventionChain dropped tool call");
+                        audit::record(
+                            &tool_call_audit(channel_name, call, approved, false)
+                                .with_policy_violation()
+                                .with_result(false, None, 0, Some(reason.clone())),
+                        );
                         individual_results.push(format!("DROPPED: {reason}"));
                         let _ = writeln!(
                             tool_results,
@@ -1086,6 +1407,11 @@ DROPPED by intervention handler: {}
                     }
                     InterventionVerdict::Halt(reason) => {
                         tracing::error!(tool = %call.name, reason = %reason, "InterventionChain HALT");
+                        audit::record(
+                            &tool_call_audit(channel_name, call, approved, false)
+                                .with_policy_violation()
+                                .with_result(false, None, 0, Some(reason.clone())),
+                        );
                         return Ok(format!("HALTED: {reason}"));
                     }
                 }
@@ -1096,14 +1422,26 @@ DROPPED by intervention handler: {}
             });
             let start = Instant::now();
             let result = if let Some(tool) = find_tool(tools_registry, &call.name) {
-                match tool.execute(call.arguments.clone()).await {
+                match tool.execute(arguments).await {
                     Ok(r) => {
                         observer.record_event(&ObserverEvent::ToolCall {
                             tool: call.name.clone(),
                             duration: start.elapsed(),
                             success: r.success,
                         });
-                        if r.success {
+                        audit::record(
+                            &tool_call_audit(channel_name, call, approved, true).with_result(
+                                r.success,
+                                None,
+                                elapsed_ms(start),
+                                r.error.clone(),
+                            ),
+                        );
+                        if r.success && supports_vision {
+                            let (text, images) = extract_inline_images(&r.output);
+                            tool_images.extend(images);
+                            scrub_credentials(&text)
+                        } else if r.success {
                             scrub_credentials(&r.output)
                         } else {
                             format!("Error: {}", r.error.unwrap_or_else(|| r.output))
@@ -1115,6 +1453,14 @@ DROPPED by intervention handler: {}
                             duration: start.elapsed(),
                             success: false,
                         });
+                        audit::record(
+                            &tool_call_audit(channel_name, call, approved, true).with_result(
+                                false,
+                                None,
+                                elapsed_ms(start),
+                                Some(e.to_string()),
+                            ),
+                        );
                         format!("Error executing {}: {e}", call.name)
                     }
                 }
@@ -1136,7 +1482,10 @@ DROPPED by intervention handler: {}
         // Prompt mode: use XML-based text format as before.
         history.push(ChatMessage::assistant(assistant_history_content));
         if native_tool_calls.is_empty() {
-            history.push(ChatMessage::user(format!("[Tool results]\n{tool_results}")));
+            history.push(
+                ChatMessage::user(format!("[Tool results]\n{tool_results}"))
+                    .with_parts(tool_images),
+            );
         } else {
             for (native_call, result) in native_tool_calls.iter().zip(individual_results.iter()) {
                 let tool_msg = serde_json::json!({
@@ -1145,12 +1494,35 @@ DROPPED by intervention handler: {}
                 });
                 history.push(ChatMessage::tool(tool_msg.to_string()));
             }
+            // Tool messages are text-only in every native API, so images
+            // produced by tools ride along in a follow-up user turn.
+            if !tool_images.is_empty() {
+                history
+                    .push(ChatMessage::user("[Images from tool results]").with_parts(tool_images));
+            }
         }
     }
 
     anyhow::bail!("Agent exceeded maximum tool iterations ({max_iterations})")
 }
 
+/// Build the per-turn behavioral guardrails (tripwires, depth, single-action,
+/// convergence) from autonomy config.
+pub(crate) fn build_intervention_chain(autonomy: &AutonomyConfig) -> InterventionChain {
+    let mut chain = InterventionChain::new();
+    chain.add(Box::new(TripwireHandler::from_strings(
+        &autonomy.tripwire_patterns,
+    )));
+    chain.add(Box::new(DepthGuardHandler));
+    chain.add(Box::new(SingleActionHandler::new(
+        autonomy.max_tools_per_turn,
+    )));
+    chain.add(Box::new(ConvergenceDetector::new(
+        autonomy.convergence_threshold,
+    )));
+    chain
+}
+
 /// Build the tool instruction block for the system prompt so the LLM knows
 /// how to invoke tools.
 pub(crate) fn build_tool_instructions(tools_registry: &[Box<dyn Tool>]) -> String {
@@ -1189,6 +1561,7 @@ pub async fn run(
     model_override: Option<String>,
     temperature: f64,
     peripheral_overrides: Vec<String>,
+    session_id: Option<String>,
 ) -> Result<String> {
     // ── Wire up agnostic subsystems ──────────────────────────────
     let base_observer = observability::create_observer(&config.observability);
@@ -1201,16 +1574,10 @@ pub async fn run(
     ));
 
     // ── InterventionChain (behavioral guardrails) ────────────────
-    let mut chain = InterventionChain::new();
-    chain.add(Box::new(
-        TripwireHandler::from_strings(&config.autonomy.tripwire_patterns),
-    ));
-    chain.add(Box::new(DepthGuardHandler));
-    chain.add(Box::new(SingleActionHandler::new(config.autonomy.max_tools_per_turn)));
-    chain.add(Box::new(ConvergenceDetector::new(config.autonomy.convergence_threshold)));
-    let chain = chain; // freeze
+    let chain = build_intervention_chain(&config.autonomy);
 
     // ── Memory (the brain) ────────────────────────────────────────
+    memory::embeddings::init(&config.memory).await;
     let mem: Arc<dyn Memory> = Arc::from(memory::create_memory_with_storage(
         &config.memory,
         Some(&config.storage.provider.config),
@@ -1236,6 +1603,7 @@ pub async fn run(
     } else {
         (None, None)
     };
+    crate::mcp::init(&config).await;
     let mut tools_registry = tools::all_tools_with_runtime(
         Arc::new(config.clone()),
         &security,
@@ -1330,6 +1698,10 @@ pub async fn run(
             "memory_forget",
             "Delete a memory entry. Use when: memory is incorrect/stale or explicitly requested for removal. Don't use when: impact is uncertain.",
         ),
+        (
+            "ingest_document",
+            "Index workspace documents for recall with path/line citations. Use when: the user points at docs, notes, or code to remember. Don't use when: a single fact fits memory_store.",
+        ),
     ];
     tool_descs.push((
         "cron_add",
@@ -1378,6 +1750,10 @@ pub async fn run(
             "delegate",
             "Delegate a sub-task to a specialized agent. Use when: task needs different model/capability, or to parallelize work.",
         ));
+        tool_descs.push((
+            "delegate_parallel",
+            "Send one task to several agents at once and collect their answers, optionally reconciled by a reviewer. Use when: independent opinions should be cross-checked.",
+        ));
     }
     if config.peripherals.enabled && !config.peripherals.boards.is_empty() {
         tool_descs.push((
@@ -1429,6 +1805,26 @@ pub async fn run(
     // ── Approval manager (supervised mode) ───────────────────────
     let approval_manager = ApprovalManager::from_config(&config.autonomy);
 
+    // ── Sessions (persistent transcripts) ────────────────────────
+    let session_store = match SessionStore::from_config(&config) {
+        Ok(store) => store,
+        Err(e) if session_id.is_none() => {
+            tracing::warn!("Session store unavailable, conversation will not be saved: {e}");
+            None
+        }
+        Err(e) => return Err(e),
+    };
+    let mut active_session: Option<String> = None;
+    let mut prior_turns: Vec<ChatMessage> = Vec::new();
+    if let Some(requested) = session_id.as_deref() {
+        let Some(store) = session_store.as_ref() else {
+            anyhow::bail!("Sessions are disabled. Set [sessions] enabled = true in config.toml");
+        };
+        let id = store.resolve_id(requested)?;
+        prior_turns = sessions::chat_from_transcript(store.load(&id)?);
+        active_session = Some(id);
+    }
+
     // ── Execute ──────────────────────────────────────────────────
     let start = Instant::now();
 
@@ -1458,11 +1854,11 @@ pub async fn run(
             format!("{context}{msg}")
         };
 
-        let mut history = vec![
-            ChatMessage::system(&system_prompt),
-            ChatMessage::user(&enriched),
-        ];
+        let mut history = vec![ChatMessage::system(&system_prompt)];
+        history.append(&mut prior_turns);
+        history.push(ChatMessage::user(&enriched));
 
+        let (delta_tx, printer) = spawn_cli_stream_printer(false);
         let response = run_tool_call_loop(
             provider.as_ref(),
             &mut history,
@@ -1475,15 +1871,25 @@ pub async fn run(
             Some(&approval_manager),
             "cli",
             config.agent.max_tool_iterations,
-            None,
+            Some(delta_tx),
             &security,
             &chain,
         )
         .await?;
         final_output = response.clone();
-        println!("{response}");
+        if printer.await.unwrap_or(false) {
+            println!();
+        } else {
+            println!("{response}");
+        }
         observer.record_event(&ObserverEvent::TurnComplete);
 
+        // Only an explicitly resumed session is saved in single-message mode,
+        // so cron and heartbeat runs don't leave a trail of one-shot sessions.
+        if let (Some(store), Some(_)) = (session_store.as_ref(), active_session.as_ref()) {
+            persist_cli_session(store, &mut active_session, &history);
+        }
+
         // Auto-save assistant response to daily log
         if config.memory.auto_save {
             let summary = truncate_with_ellipsis(&response, 100);
@@ -1499,6 +1905,10 @@ pub async fn run(
 
         // Persistent conversation history across turns
         let mut history = vec![ChatMessage::system(&system_prompt)];
+        if let Some(id) = active_session.as_deref() {
+            println!("Resumed session {id} ({} messages).\n", prior_turns.len());
+        }
+        history.append(&mut prior_turns);
 
         loop {
             print!("> ");
@@ -1546,6 +1956,8 @@ pub async fn run(
 
                     history.clear();
                     history.push(ChatMessage::system(&system_prompt));
+                    // The next turn starts a new session; the old one stays resumable.
+                    active_session = None;
                     // Clear conversation and daily memory
                     let mut cleared = 0;
                     for category in [MemoryCategory::Conversation, MemoryCategory::Daily] {
@@ -1591,7 +2003,8 @@ pub async fn run(
 
             history.push(ChatMessage::user(&enriched));
 
-            let response = match run_tool_call_loop(
+            let (delta_tx, printer) = spawn_cli_stream_printer(true);
+            let result = run_tool_call_loop(
                 provider.as_ref(),
                 &mut history,
                 &tools_registry,
@@ -1603,12 +2016,13 @@ pub async fn run(
                 Some(&approval_manager),
                 "cli",
                 config.agent.max_tool_iterations,
-                None,
+                Some(delta_tx),
                 &security,
                 &chain,
             )
-            .await
-            {
+            .await;
+            let streamed = printer.await.unwrap_or(false);
+            let response = match result {
                 Ok(resp) => resp,
                 Err(e) => {
                     eprintln!("\nError: {e}\n");
@@ -1618,7 +2032,9 @@ pub async fn run(
             // Reset per-turn handler state (SingleActionHandler counter, etc.)
             chain.reset_all();
             final_output = response.clone();
-            if let Err(e) = crate::channels::Channel::send(
+            if streamed {
+                println!("\n");
+            } else if let Err(e) = crate::channels::Channel::send(
                 &cli,
                 &crate::channels::traits::SendMessage::new(format!("\n{response}\n"), "user"),
             )
@@ -1645,6 +2061,10 @@ pub async fn run(
             // Hard cap as a safety net.
             trim_history(&mut history, config.agent.max_history_messages);
 
+            if let Some(store) = session_store.as_ref() {
+                persist_cli_session(store, &mut active_session, &history);
+            }
+
             if config.memory.auto_save {
                 let summary = truncate_with_ellipsis(&response, 100);
                 let response_key = autosave_memory_key("assistant_resp");
@@ -1667,6 +2087,35 @@ pub async fn run(
     Ok(final_output)
 }
 
+/// Save the CLI conversation, starting a new session on the first save.
+fn persist_cli_session(
+    store: &SessionStore,
+    active_session: &mut Option<String>,
+    history: &[ChatMessage],
+) {
+    let id = match active_session.as_ref() {
+        Some(id) => id.clone(),
+        None => match store.create(sessions::CLI_CHANNEL, sessions::CLI_SENDER) {
+            Ok(session) => {
+                println!(
+                    "💾 Session {} (resume with `zeroclaw agent --session {}`)\n",
+                    session.id,
+                    &session.id[..8]
+                );
+                active_session.insert(session.id).clone()
+            }
+            Err(e) => {
+                tracing::warn!("Failed to create session: {e}");
+                return;
+            }
+        },
+    };
+
+    if let Err(e) = store.save(&id, &sessions::transcript_from_chat(history)) {
+        tracing::warn!("Failed to save session {id}: {e}");
+    }
+}
+
 /// Process a single message through the full agent (with tools, peripherals, memory).
 /// Used by channels (Telegram, Discord, etc.) to enable hardware and tool use.
 pub async fn process_message(config: Config, message: &str) -> Result<String> {
@@ -1678,6 +2127,7 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         &config.autonomy,
         &config.workspace_dir,
     ));
+    memory::embeddings::init(&config.memory).await;
     let mem: Arc<dyn Memory> = Arc::from(memory::create_memory_with_storage(
         &config.memory,
         Some(&config.storage.provider.config),
@@ -1693,6 +2143,7 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
     } else {
         (None, None)
     };
+    crate::mcp::init(&config).await;
     let mut tools_registry = tools::all_tools_with_runtime(
         Arc::new(config.clone()),
         &security,
@@ -1748,6 +2199,7 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         ("memory_store", "Save to memory."),
         ("memory_recall", "Search memory."),
         ("memory_forget", "Delete a memory entry."),
+        ("ingest_document", "Index workspace documents into memory."),
         ("screenshot", "Capture a screenshot."),
         ("image_info", "Read image metadata."),
     ];
@@ -1818,13 +2270,7 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
     ];
 
     // ── InterventionChain for process_message ──
-    let mut chain = InterventionChain::new();
-    chain.add(Box::new(
-        TripwireHandler::from_strings(&config.autonomy.tripwire_patterns),
-    ));
-    chain.add(Box::new(DepthGuardHandler));
-    chain.add(Box::new(SingleActionHandler::new(config.autonomy.max_tools_per_turn)));
-    chain.add(Box::new(ConvergenceDetector::new(config.autonomy.convergence_threshold)));
+    let chain = build_intervention_chain(&config.autonomy);
 
     agent_turn(
         provider.as_ref(),
@@ -1857,6 +2303,51 @@ mod tests {
         assert!(!scrubbed.contains("secret123456"));
     }
 
+    #[test]
+    fn extract_inline_images_moves_data_uris_into_parts() {
+        let output = "File: cat.png\nFormat: png\ndata:image/png;base64,iVBORw0KGgo=";
+        let (text, images) = extract_inline_images(output);
+
+        assert_eq!(
+            text,
+            "File: cat.png\nFormat: png\n[image attached: image/png]"
+        );
+        assert_eq!(
+            images,
+            vec![ContentPart::image("image/png", "iVBORw0KGgo=")]
+        );
+    }
+
+    #[test]
+    fn extract_inline_images_leaves_unsupported_or_truncated_payloads() {
+        let bmp = "data:image/bmp;base64,Qk0=";
+        let truncated = "Base64 length: 5 (truncated)\ndata:image/png;base64,iVBOR";
+        for output in [bmp, truncated, "no images here"] {
+            let (text, images) = extract_inline_images(output);
+            assert_eq!(text, output);
+            assert!(images.is_empty());
+        }
+    }
+
+    #[test]
+    fn strip_image_parts_replaces_images_with_note() {
+        let mut history = vec![
+            ChatMessage::system("sys"),
+            ChatMessage::user("look").with_parts(vec![
+                ContentPart::image("image/png", "a"),
+                ContentPart::image("image/jpeg", "b"),
+            ]),
+        ];
+        strip_image_parts(&mut history);
+
+        assert_eq!(history[0].content, "sys");
+        assert!(history[1].parts.is_empty());
+        assert_eq!(
+            history[1].content,
+            "look\n[2 image attachment(s) omitted: the current model does not accept image input]"
+        );
+    }
+
     #[test]
     fn test_scrub_credentials_json() {
         let input = r#"{"api_key": "sk-1234567890", "other": "public"}"#;
@@ -2606,4 +3097,65 @@ browser_open/url>https://example.com"#;
         assert_eq!(calls[0].arguments["command"], "pwd");
         assert_eq!(text, "Done");
     }
+
+    fn feed(gate: &mut ToolCallStreamGate, deltas: &[&str]) -> String {
+        deltas.iter().filter_map(|delta| gate.push(delta)).collect()
+    }
+
+    #[test]
+    fn stream_gate_forwards_plain_text() {
+        let mut gate = ToolCallStreamGate::default();
+        let forwarded = feed(&mut gate, &["Hello", ", wor", "ld!"]);
+        assert_eq!(forwarded, "Hello, world!");
+        assert_eq!(gate.emitted(), "Hello, world!");
+    }
+
+    #[test]
+    fn stream_gate_halts_at_tool_call_split_across_deltas() {
+        let mut gate = ToolCallStreamGate::default();
+        let forwarded = feed(
+            &mut gate,
+            &[
+                "Let me check.\n<to",
+                "ol_ca",
+                "ll>{\"name\":\"shell\"}",
+                "</tool_call> more",
+            ],
+        );
+        assert_eq!(forwarded, "Let me check.\n");
+    }
+
+    #[test]
+    fn stream_gate_releases_false_marker_prefix() {
+        let mut gate = ToolCallStreamGate::default();
+        assert_eq!(gate.push("a <to").as_deref(), Some("a "));
+        assert_eq!(gate.push("day").as_deref(), Some("<today"));
+    }
+
+    #[test]
+    fn stream_gate_halts_at_markdown_tool_call_fence() {
+        let mut gate = ToolCallStreamGate::default();
+        let forwarded = feed(&mut gate, &["Sure.\n``", "`tool_call\n{}", "\n```"]);
+        assert_eq!(forwarded, "Sure.\n");
+    }
+
+    #[test]
+    fn stream_gate_keeps_regular_code_fences() {
+        let mut gate = ToolCallStreamGate::default();
+        let forwarded = feed(&mut gate, &["```", "rust\nfn main() {}\n```"]);
+        // The closing fence could still become "```tool_call", so it stays
+        // held until the response completes.
+        assert_eq!(forwarded, "```rust\nfn main() {}\n");
+    }
+
+    #[test]
+    fn stream_gate_never_streams_leading_json() {
+        let mut gate = ToolCallStreamGate::default();
+        let forwarded = feed(
+            &mut gate,
+            &["  ", "{\"tool_calls\": []", ", \"content\": \"hi\"}"],
+        );
+        assert!(forwarded.is_empty());
+        assert!(gate.emitted().is_empty());
+    }
 }
@@ -50,7 +50,12 @@ impl MemoryLoader for DefaultMemoryLoader {
                     continue;
                 }
             }
-            let _ = writeln!(context, "- {}: {}", entry.key, entry.content);
+            let _ = writeln!(
+                context,
+                "- {}: {}",
+                crate::memory::ingest::cite(&entry.key),
+                entry.content
+            );
         }
 
         // If all entries were below threshold, return empty
@@ -99,6 +104,11 @@ mod tests {
                 timestamp: "now".into(),
                 session_id: None,
                 score: None,
+                importance: None,
+                expires_at: None,
+                last_accessed: None,
+                access_count: 0,
+                namespace: None,
             }])
         }
 
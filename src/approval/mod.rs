@@ -1,15 +1,23 @@
 //! Interactive approval workflow for supervised mode.
 //!
 //! Provides a pre-execution hook that prompts the user before tool calls,
-//! with session-scoped "Always" allowlists and audit logging.
+//! with session-scoped "Always" allowlists and audit logging. Outside the
+//! CLI, requests wait in an [`queue::ApprovalQueue`] answered by an operator
+//! chat ([`remote::RemoteApprover`]) or the gateway approval API.
+
+pub mod queue;
+pub mod remote;
 
 use crate::config::AutonomyConfig;
+use crate::security::audit::{self, AuditEvent, AuditEventType};
 use crate::security::AutonomyLevel;
 use chrono::Utc;
 use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::io::{self, BufRead, Write};
+use std::sync::Arc;
+use std::time::Duration;
 
 // ── Types ────────────────────────────────────────────────────────
 
@@ -25,13 +33,33 @@ pub struct ApprovalRequest {
 #[serde(rename_all = "lowercase")]
 pub enum ApprovalResponse {
     /// Execute this one call.
+    #[serde(alias = "approve")]
     Yes,
     /// Deny this call.
+    #[serde(alias = "deny")]
     No,
     /// Execute and add tool to session-scoped allowlist.
     Always,
 }
 
+/// A response together with who gave it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApprovalDecision {
+    pub response: ApprovalResponse,
+    /// Who answered: `cli` for the terminal user, `channel:sender` or
+    /// `gateway:<name>` for remote operators, `None` for automatic decisions.
+    pub responder: Option<String>,
+}
+
+impl ApprovalDecision {
+    fn denied() -> Self {
+        Self {
+            response: ApprovalResponse::No,
+            responder: None,
+        }
+    }
+}
+
 /// A single audit log entry for an approval decision.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApprovalLogEntry {
@@ -40,6 +68,9 @@ pub struct ApprovalLogEntry {
     pub arguments_summary: String,
     pub decision: ApprovalResponse,
     pub channel: String,
+    /// Operator who answered a remote approval request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub responder: Option<String>,
 }
 
 // ── ApprovalManager ──────────────────────────────────────────────
@@ -60,6 +91,10 @@ pub struct ApprovalManager {
     session_allowlist: Mutex<HashSet<String>>,
     /// Audit trail of approval decisions.
     audit_log: Mutex<Vec<ApprovalLogEntry>>,
+    /// Where non-CLI requests wait for an answer, and for how long.
+    queue: Option<(Arc<queue::ApprovalQueue>, Duration)>,
+    /// Operator chat that queued requests are posted to.
+    remote: Option<Arc<remote::RemoteApprover>>,
 }
 
 impl ApprovalManager {
@@ -71,9 +106,36 @@ impl ApprovalManager {
             autonomy_level: config.level,
             session_allowlist: Mutex::new(HashSet::new()),
             audit_log: Mutex::new(Vec::new()),
+            queue: None,
+            remote: None,
         }
     }
 
+    /// Park requests from non-CLI channels in `queue`, denying them when
+    /// nobody answers within `timeout`.
+    #[must_use]
+    pub fn with_queue(mut self, queue: Arc<queue::ApprovalQueue>, timeout: Duration) -> Self {
+        self.queue = Some((queue, timeout));
+        self
+    }
+
+    /// Also post queued requests to an operator chat.
+    #[must_use]
+    pub fn with_remote(mut self, remote: Arc<remote::RemoteApprover>) -> Self {
+        self.remote = Some(remote);
+        self
+    }
+
+    /// The queue non-CLI requests wait in, if any.
+    pub fn queue(&self) -> Option<&Arc<queue::ApprovalQueue>> {
+        self.queue.as_ref().map(|(queue, _)| queue)
+    }
+
+    /// The operator chat approvals are routed to, if any.
+    pub fn remote(&self) -> Option<&Arc<remote::RemoteApprover>> {
+        self.remote.as_ref()
+    }
+
     /// Check whether a tool call requires interactive approval.
     ///
     /// Returns `true` if the call needs a prompt, `false` if it can proceed.
@@ -115,6 +177,18 @@ impl ApprovalManager {
         args: &serde_json::Value,
         decision: ApprovalResponse,
         channel: &str,
+    ) {
+        self.record_decision_by(tool_name, args, decision, channel, None);
+    }
+
+    /// Like [`record_decision`](Self::record_decision), noting who answered.
+    pub fn record_decision_by(
+        &self,
+        tool_name: &str,
+        args: &serde_json::Value,
+        decision: ApprovalResponse,
+        channel: &str,
+        responder: Option<&str>,
     ) {
         // If "Always", add to session allowlist.
         if decision == ApprovalResponse::Always {
@@ -122,6 +196,18 @@ impl ApprovalManager {
             allowlist.insert(tool_name.to_string());
         }
 
+        let approved = decision != ApprovalResponse::No;
+        audit::record(
+            &AuditEvent::new(AuditEventType::ApprovalDecision)
+                .with_actor(channel.to_string(), responder.map(str::to_string), None)
+                .with_tool(
+                    tool_name.to_string(),
+                    audit_summary(args),
+                    approved,
+                    approved,
+                ),
+        );
+
         // Append to audit log.
         let summary = summarize_args(args);
         let entry = ApprovalLogEntry {
@@ -130,6 +216,7 @@ impl ApprovalManager {
             arguments_summary: summary,
             decision,
             channel: channel.to_string(),
+            responder: responder.map(str::to_string),
         };
         let mut log = self.audit_log.lock();
         log.push(entry);
@@ -147,11 +234,65 @@ impl ApprovalManager {
 
     /// Prompt the user on the CLI and return their decision.
     ///
-    /// For non-CLI channels, returns `Yes` automatically (interactive
-    /// approval is only supported on CLI for now).
+    /// Use [`decide`](Self::decide) to also cover non-CLI channels.
     pub fn prompt_cli(&self, request: &ApprovalRequest) -> ApprovalResponse {
         prompt_cli_interactive(request)
     }
+
+    /// Ask whoever answers for `channel`: the terminal user on CLI, the
+    /// approval queue otherwise. Without a queue, non-CLI calls are
+    /// approved automatically.
+    pub async fn decide(&self, request: &ApprovalRequest, channel: &str) -> ApprovalDecision {
+        if channel == "cli" {
+            return ApprovalDecision {
+                response: self.prompt_cli(request),
+                responder: Some("cli".into()),
+            };
+        }
+        let Some((queue, timeout)) = &self.queue else {
+            return ApprovalDecision {
+                response: ApprovalResponse::Yes,
+                responder: None,
+            };
+        };
+
+        let (approval, mut decision) = queue.submit(request, channel);
+        // Outer timeouts or a dropped client can cancel this future mid-wait;
+        // the request must not outlive it in the queue.
+        let _expire = ExpireOnDrop {
+            queue,
+            id: &approval.id,
+        };
+        if let Some(remote) = &self.remote {
+            remote.announce(&approval, *timeout).await;
+        }
+        if let Ok(Ok(decision)) = tokio::time::timeout(*timeout, &mut decision).await {
+            return decision;
+        }
+        if !queue.expire(&approval.id) {
+            // Answered while the timeout fired.
+            if let Ok(decision) = decision.try_recv() {
+                return decision;
+            }
+        }
+        if let Some(remote) = &self.remote {
+            remote.announce_timeout(&approval).await;
+        }
+        ApprovalDecision::denied()
+    }
+}
+
+/// Expires a queued request when [`ApprovalManager::decide`] stops waiting.
+/// A no-op once the request has been answered or expired.
+struct ExpireOnDrop<'a> {
+    queue: &'a queue::ApprovalQueue,
+    id: &'a str,
+}
+
+impl Drop for ExpireOnDrop<'_> {
+    fn drop(&mut self) {
+        self.queue.expire(self.id);
+    }
 }
 
 // ── CLI prompt ───────────────────────────────────────────────────
@@ -178,6 +319,11 @@ fn prompt_cli_interactive(request: &ApprovalRequest) -> ApprovalResponse {
     }
 }
 
+/// Argument summary for the security audit log, credentials redacted.
+pub(crate) fn audit_summary(args: &serde_json::Value) -> String {
+    crate::agent::loop_::scrub_credentials(&summarize_args(args))
+}
+
 /// Produce a short human-readable summary of tool arguments.
 fn summarize_args(args: &serde_json::Value) -> String {
     match args {
@@ -366,6 +512,92 @@ mod tests {
         assert_eq!(log[0].channel, "telegram");
     }
 
+    // ── queued decisions ─────────────────────────────────────
+
+    #[tokio::test]
+    async fn non_cli_request_waits_in_queue_for_answer() {
+        let queue = Arc::new(queue::ApprovalQueue::new());
+        let mgr = ApprovalManager::from_config(&supervised_config())
+            .with_queue(Arc::clone(&queue), Duration::from_secs(5));
+
+        let mut events = queue.subscribe();
+        let answer = tokio::spawn(async move {
+            if let Ok(queue::ApprovalEvent::Requested(pending)) = events.recv().await {
+                queue.resolve(
+                    &pending.id,
+                    ApprovalDecision {
+                        response: ApprovalResponse::Yes,
+                        responder: Some("gateway:ops".into()),
+                    },
+                );
+            }
+        });
+
+        let request = ApprovalRequest {
+            tool_name: "shell".into(),
+            arguments: serde_json::json!({"command": "touch out.txt"}),
+        };
+        let decision = mgr.decide(&request, "slack").await;
+        answer.await.unwrap();
+        assert_eq!(decision.response, ApprovalResponse::Yes);
+        assert_eq!(decision.responder.as_deref(), Some("gateway:ops"));
+    }
+
+    #[tokio::test]
+    async fn unanswered_queued_request_is_denied() {
+        let queue = Arc::new(queue::ApprovalQueue::new());
+        let mgr = ApprovalManager::from_config(&supervised_config())
+            .with_queue(Arc::clone(&queue), Duration::from_millis(20));
+        let request = ApprovalRequest {
+            tool_name: "shell".into(),
+            arguments: serde_json::json!({"command": "ls"}),
+        };
+        let decision = mgr.decide(&request, "discord").await;
+        assert_eq!(decision, ApprovalDecision::denied());
+        assert!(queue.pending().is_empty());
+    }
+
+    #[tokio::test]
+    async fn cancelled_decide_removes_its_request_from_the_queue() {
+        let queue = Arc::new(queue::ApprovalQueue::new());
+        let mgr = ApprovalManager::from_config(&supervised_config())
+            .with_queue(Arc::clone(&queue), Duration::from_secs(60));
+        let request = ApprovalRequest {
+            tool_name: "shell".into(),
+            arguments: serde_json::json!({"command": "ls"}),
+        };
+        let mut events = queue.subscribe();
+
+        let waited =
+            tokio::time::timeout(Duration::from_millis(20), mgr.decide(&request, "slack")).await;
+        assert!(waited.is_err());
+        assert!(queue.pending().is_empty());
+        assert!(matches!(
+            events.recv().await.unwrap(),
+            queue::ApprovalEvent::Requested(_)
+        ));
+        assert!(matches!(
+            events.recv().await.unwrap(),
+            queue::ApprovalEvent::Closed {
+                decision: ApprovalResponse::No,
+                responder: None,
+                ..
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn non_cli_request_without_queue_is_approved() {
+        let mgr = ApprovalManager::from_config(&supervised_config());
+        let request = ApprovalRequest {
+            tool_name: "shell".into(),
+            arguments: serde_json::json!({"command": "ls"}),
+        };
+        let decision = mgr.decide(&request, "telegram").await;
+        assert_eq!(decision.response, ApprovalResponse::Yes);
+        assert!(decision.responder.is_none());
+    }
+
     // ── summarize_args ───────────────────────────────────────
 
     #[test]
@@ -409,6 +641,8 @@ mod tests {
         assert_eq!(json, "\"always\"");
         let parsed: ApprovalResponse = serde_json::from_str("\"no\"").unwrap();
         assert_eq!(parsed, ApprovalResponse::No);
+        let parsed: ApprovalResponse = serde_json::from_str("\"approve\"").unwrap();
+        assert_eq!(parsed, ApprovalResponse::Yes);
     }
 
     // ── ApprovalRequest ──────────────────────────────────────
@@ -0,0 +1,222 @@
+//! Queue of approval requests waiting for an answer.
+//!
+//! Agent turns outside the CLI park their approval requests here instead of
+//! blocking on stdin. Whoever can answer — the operator chat, the gateway
+//! approval API — lists, watches and resolves them through the same queue.
+//! Under the daemon, channels and gateway share [`ApprovalQueue::global`].
+
+use super::{audit_summary, ApprovalDecision, ApprovalRequest, ApprovalResponse};
+use chrono::Utc;
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+use tokio::sync::{broadcast, oneshot};
+
+static GLOBAL: OnceLock<Arc<ApprovalQueue>> = OnceLock::new();
+
+/// Buffered events per subscriber before slow readers start lagging.
+const EVENT_CAPACITY: usize = 64;
+
+/// A request waiting for a decision.
+#[derive(Debug, Clone, Serialize)]
+pub struct PendingApproval {
+    pub id: String,
+    pub tool_name: String,
+    /// Raw arguments. Never serialized: API and stream clients only see the
+    /// redacted summary.
+    #[serde(skip)]
+    pub arguments: serde_json::Value,
+    pub arguments_summary: String,
+    /// Channel of the conversation that made the call.
+    pub channel: String,
+    pub requested_at: String,
+}
+
+/// Queue changes broadcast to subscribers.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ApprovalEvent {
+    Requested(PendingApproval),
+    /// Answered, or denied after the timeout (`responder` is then `None`).
+    Closed {
+        id: String,
+        decision: ApprovalResponse,
+        responder: Option<String>,
+    },
+}
+
+struct Waiting {
+    approval: PendingApproval,
+    reply: oneshot::Sender<ApprovalDecision>,
+}
+
+pub struct ApprovalQueue {
+    pending: Mutex<HashMap<String, Waiting>>,
+    events: broadcast::Sender<ApprovalEvent>,
+}
+
+impl Default for ApprovalQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ApprovalQueue {
+    pub fn new() -> Self {
+        Self {
+            pending: Mutex::new(HashMap::new()),
+            events: broadcast::channel(EVENT_CAPACITY).0,
+        }
+    }
+
+    /// The process-wide queue.
+    pub fn global() -> Arc<Self> {
+        Arc::clone(GLOBAL.get_or_init(|| Arc::new(Self::new())))
+    }
+
+    /// Park a request; the receiver yields the decision once it is resolved.
+    pub fn submit(
+        &self,
+        request: &ApprovalRequest,
+        channel: &str,
+    ) -> (PendingApproval, oneshot::Receiver<ApprovalDecision>) {
+        let approval = PendingApproval {
+            id: uuid::Uuid::new_v4().simple().to_string()[..8].to_string(),
+            tool_name: request.tool_name.clone(),
+            arguments: request.arguments.clone(),
+            arguments_summary: audit_summary(&request.arguments),
+            channel: channel.to_string(),
+            requested_at: Utc::now().to_rfc3339(),
+        };
+        let (reply, decision) = oneshot::channel();
+        self.pending.lock().insert(
+            approval.id.clone(),
+            Waiting {
+                approval: approval.clone(),
+                reply,
+            },
+        );
+        let _ = self.events.send(ApprovalEvent::Requested(approval.clone()));
+        (approval, decision)
+    }
+
+    /// Requests still waiting, oldest first.
+    pub fn pending(&self) -> Vec<PendingApproval> {
+        let mut pending: Vec<PendingApproval> = self
+            .pending
+            .lock()
+            .values()
+            .map(|waiting| waiting.approval.clone())
+            .collect();
+        pending.sort_by(|a, b| a.requested_at.cmp(&b.requested_at));
+        pending
+    }
+
+    /// Answer a pending request. Returns it, or `None` if `id` is unknown or
+    /// was already answered.
+    pub fn resolve(&self, id: &str, decision: ApprovalDecision) -> Option<PendingApproval> {
+        let waiting = self.pending.lock().remove(id)?;
+        let _ = self.events.send(ApprovalEvent::Closed {
+            id: id.to_string(),
+            decision: decision.response,
+            responder: decision.responder.clone(),
+        });
+        // The requester may have given up in the meantime.
+        let _ = waiting.reply.send(decision);
+        Some(waiting.approval)
+    }
+
+    /// Drop a request nobody answered in time. Returns `false` if it was
+    /// resolved first.
+    pub fn expire(&self, id: &str) -> bool {
+        if self.pending.lock().remove(id).is_none() {
+            return false;
+        }
+        let _ = self.events.send(ApprovalEvent::Closed {
+            id: id.to_string(),
+            decision: ApprovalResponse::No,
+            responder: None,
+        });
+        true
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<ApprovalEvent> {
+        self.events.subscribe()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request() -> ApprovalRequest {
+        ApprovalRequest {
+            tool_name: "shell".into(),
+            arguments: serde_json::json!({"command": "touch build.log"}),
+        }
+    }
+
+    #[tokio::test]
+    async fn resolve_delivers_decision_and_events() {
+        let queue = ApprovalQueue::new();
+        let mut events = queue.subscribe();
+
+        let (approval, decision) = queue.submit(&request(), "gateway");
+        assert_eq!(queue.pending().len(), 1);
+        assert!(approval.arguments_summary.contains("touch build.log"));
+
+        let resolved = queue
+            .resolve(
+                &approval.id,
+                ApprovalDecision {
+                    response: ApprovalResponse::Yes,
+                    responder: Some("gateway:ops".into()),
+                },
+            )
+            .unwrap();
+        assert_eq!(resolved.tool_name, "shell");
+        assert!(queue.pending().is_empty());
+        assert_eq!(
+            decision.await.unwrap().responder.as_deref(),
+            Some("gateway:ops")
+        );
+
+        assert!(matches!(
+            events.recv().await.unwrap(),
+            ApprovalEvent::Requested(ref p) if p.id == approval.id
+        ));
+        assert!(matches!(
+            events.recv().await.unwrap(),
+            ApprovalEvent::Closed {
+                decision: ApprovalResponse::Yes,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn summary_redacts_credentials() {
+        let queue = ApprovalQueue::new();
+        let (approval, _decision) = queue.submit(
+            &ApprovalRequest {
+                tool_name: "http_request".into(),
+                arguments: serde_json::json!({"url": "https://api.example.com", "api_key": "sk-live-1234567890"}),
+            },
+            "telegram",
+        );
+        assert!(approval.arguments_summary.contains("api.example.com"));
+        assert!(!approval.arguments_summary.contains("1234567890"));
+    }
+
+    #[test]
+    fn requests_resolve_only_once() {
+        let queue = ApprovalQueue::new();
+        let (approval, _decision) = queue.submit(&request(), "telegram");
+        assert!(queue.expire(&approval.id));
+        assert!(!queue.expire(&approval.id));
+        assert!(queue
+            .resolve(&approval.id, ApprovalDecision::denied())
+            .is_none());
+    }
+}
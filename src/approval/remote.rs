@@ -0,0 +1,252 @@
+//! Remote approval through an operator chat.
+//!
+//! When the agent runs under channels there is no terminal to answer
+//! `prompt_cli`, so queued approval requests are also posted to a configured
+//! operator conversation. The operator replies `approve <id>`, `deny <id>` or
+//! `always <id>`; the id may be omitted while a single request is pending.
+
+use super::queue::{ApprovalQueue, PendingApproval};
+use super::{ApprovalDecision, ApprovalResponse};
+use crate::channels::traits::ChannelMessage;
+use crate::channels::{Channel, SendMessage};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Posts approval requests to an operator conversation and resolves them
+/// from the operator's replies.
+pub struct RemoteApprover {
+    channel: Arc<dyn Channel>,
+    recipient: String,
+    queue: Arc<ApprovalQueue>,
+}
+
+impl RemoteApprover {
+    pub fn new(
+        channel: Arc<dyn Channel>,
+        recipient: impl Into<String>,
+        queue: Arc<ApprovalQueue>,
+    ) -> Self {
+        Self {
+            channel,
+            recipient: recipient.into(),
+            queue,
+        }
+    }
+
+    /// Post a queued request to the operator.
+    pub async fn announce(&self, approval: &PendingApproval, timeout: Duration) {
+        let id = &approval.id;
+        let prompt = format!(
+            "🔧 Approval needed [{id}] from {}\n`{}` — {}\nReply `approve {id}`, `deny {id}` or `always {id}` within {}s (no answer denies).",
+            approval.channel,
+            approval.tool_name,
+            approval.arguments_summary,
+            timeout.as_secs()
+        );
+        if let Err(e) = self
+            .channel
+            .send(&SendMessage::new(prompt, &self.recipient))
+            .await
+        {
+            tracing::warn!("Failed to send approval request to operator: {e}");
+        }
+    }
+
+    /// Tell the operator a request expired unanswered.
+    pub async fn announce_timeout(&self, approval: &PendingApproval) {
+        let notice = format!(
+            "⌛ Approval [{}] for `{}` timed out and was denied.",
+            approval.id, approval.tool_name
+        );
+        let _ = self
+            .channel
+            .send(&SendMessage::new(notice, &self.recipient))
+            .await;
+    }
+
+    /// Resolve a pending request from an operator reply.
+    ///
+    /// Returns the acknowledgement to send back when `msg` was an approval
+    /// reply, or `None` when it should be handled as a normal message.
+    pub fn handle_reply(&self, msg: &ChannelMessage) -> Option<String> {
+        if msg.channel != self.channel.name() || msg.reply_target != self.recipient {
+            return None;
+        }
+        let (response, id) = parse_reply(&msg.content)?;
+
+        let id = match id {
+            Some(id) => id.to_string(),
+            None => {
+                let pending = self.queue.pending();
+                match pending.as_slice() {
+                    [] => return None,
+                    [only] => only.id.clone(),
+                    several => {
+                        let ids: Vec<&str> = several.iter().map(|p| p.id.as_str()).collect();
+                        return Some(format!(
+                            "Several approvals are pending; reply with an id: {}",
+                            ids.join(", ")
+                        ));
+                    }
+                }
+            }
+        };
+
+        let decision = ApprovalDecision {
+            response,
+            responder: Some(format!("{}:{}", msg.channel, msg.sender)),
+        };
+        let Some(approval) = self.queue.resolve(&id, decision) else {
+            return Some(format!("No pending approval [{id}]."));
+        };
+        let verdict = match response {
+            ApprovalResponse::Yes => "Approved",
+            ApprovalResponse::Always => "Approved for this session",
+            ApprovalResponse::No => "Denied",
+        };
+        Some(format!(
+            "{verdict}: `{}` [{id}] by {}",
+            approval.tool_name, msg.sender
+        ))
+    }
+}
+
+/// Parse `approve|deny|always [id]`.
+fn parse_reply(content: &str) -> Option<(ApprovalResponse, Option<&str>)> {
+    let mut words = content.split_whitespace();
+    let response = match words.next()?.to_ascii_lowercase().as_str() {
+        "approve" | "/approve" => ApprovalResponse::Yes,
+        "deny" | "/deny" => ApprovalResponse::No,
+        "always" | "/always" => ApprovalResponse::Always,
+        _ => return None,
+    };
+    let id = words.next();
+    if words.next().is_some() {
+        return None;
+    }
+    Some((
+        response,
+        id.map(|id| id.trim_matches(|c| c == '[' || c == ']')),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::approval::ApprovalRequest;
+    use async_trait::async_trait;
+    use parking_lot::Mutex;
+
+    #[derive(Default)]
+    struct OperatorChannel {
+        sent: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl Channel for OperatorChannel {
+        fn name(&self) -> &str {
+            "telegram"
+        }
+
+        async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+            self.sent.lock().push(message.content.clone());
+            Ok(())
+        }
+
+        async fn listen(
+            &self,
+            _tx: tokio::sync::mpsc::Sender<ChannelMessage>,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn reply(sender: &str, reply_target: &str, content: &str) -> ChannelMessage {
+        ChannelMessage {
+            id: "m".into(),
+            sender: sender.into(),
+            reply_target: reply_target.into(),
+            content: content.into(),
+            channel: "telegram".into(),
+            timestamp: 0,
+            attachments: vec![],
+        }
+    }
+
+    fn shell_request() -> ApprovalRequest {
+        ApprovalRequest {
+            tool_name: "shell".into(),
+            arguments: serde_json::json!({"command": "ls"}),
+        }
+    }
+
+    fn approver() -> (Arc<OperatorChannel>, Arc<ApprovalQueue>, RemoteApprover) {
+        let channel = Arc::new(OperatorChannel::default());
+        let queue = Arc::new(ApprovalQueue::new());
+        let approver = RemoteApprover::new(channel.clone(), "ops-chat", Arc::clone(&queue));
+        (channel, queue, approver)
+    }
+
+    #[test]
+    fn parse_reply_keywords() {
+        assert_eq!(
+            parse_reply("approve a1b2c3"),
+            Some((ApprovalResponse::Yes, Some("a1b2c3")))
+        );
+        assert_eq!(
+            parse_reply("Deny [a1b2c3]"),
+            Some((ApprovalResponse::No, Some("a1b2c3")))
+        );
+        assert_eq!(
+            parse_reply("always"),
+            Some((ApprovalResponse::Always, None))
+        );
+        assert_eq!(parse_reply("approve this plan please"), None);
+        assert_eq!(parse_reply("hello"), None);
+    }
+
+    #[tokio::test]
+    async fn operator_reply_resolves_request_with_identity() {
+        let (channel, queue, approver) = approver();
+        let (approval, decision) = queue.submit(&shell_request(), "slack");
+        approver.announce(&approval, Duration::from_secs(5)).await;
+        let id = &approval.id;
+        assert!(channel.sent.lock()[0].contains(&format!("approve {id}")));
+
+        // Replies from other conversations are ordinary messages.
+        assert!(approver
+            .handle_reply(&reply("mallory", "other-chat", &format!("approve {id}")))
+            .is_none());
+
+        let ack = approver
+            .handle_reply(&reply("alice", "ops-chat", &format!("always {id}")))
+            .unwrap();
+        assert!(ack.contains("Approved for this session"));
+
+        let decision = decision.await.unwrap();
+        assert_eq!(decision.response, ApprovalResponse::Always);
+        assert_eq!(decision.responder.as_deref(), Some("telegram:alice"));
+    }
+
+    #[tokio::test]
+    async fn id_is_optional_with_single_pending_request() {
+        let (_channel, queue, approver) = approver();
+        assert!(approver
+            .handle_reply(&reply("alice", "ops-chat", "deny"))
+            .is_none());
+
+        let (_first, decision) = queue.submit(&shell_request(), "discord");
+        approver
+            .handle_reply(&reply("alice", "ops-chat", "deny"))
+            .unwrap();
+        assert_eq!(decision.await.unwrap().response, ApprovalResponse::No);
+
+        queue.submit(&shell_request(), "discord");
+        queue.submit(&shell_request(), "matrix");
+        let ack = approver
+            .handle_reply(&reply("alice", "ops-chat", "approve"))
+            .unwrap();
+        assert!(ack.contains("Several approvals are pending"));
+        assert_eq!(queue.pending().len(), 2);
+    }
+}
@@ -47,6 +47,7 @@ impl Channel for CliChannel {
                     .duration_since(std::time::UNIX_EPOCH)
                     .unwrap_or_default()
                     .as_secs(),
+                attachments: vec![],
             };
 
             if tx.send(msg).await.is_err() {
@@ -74,6 +75,7 @@ mod tests {
                 content: "hello".into(),
                 recipient: "user".into(),
                 subject: None,
+                attachments: vec![],
             })
             .await;
         assert!(result.is_ok());
@@ -87,6 +89,7 @@ mod tests {
                 content: String::new(),
                 recipient: String::new(),
                 subject: None,
+                attachments: vec![],
             })
             .await;
         assert!(result.is_ok());
@@ -107,6 +110,7 @@ mod tests {
             content: "hello".into(),
             channel: "cli".into(),
             timestamp: 1_234_567_890,
+            attachments: vec![],
         };
         assert_eq!(msg.id, "test-id");
         assert_eq!(msg.sender, "user");
@@ -125,6 +129,7 @@ mod tests {
             content: "c".into(),
             channel: "ch".into(),
             timestamp: 0,
+            attachments: vec![],
         };
         let cloned = msg.clone();
         assert_eq!(cloned.id, msg.id);
@@ -274,6 +274,7 @@ impl Channel for DingTalkChannel {
                             .duration_since(std::time::UNIX_EPOCH)
                             .unwrap_or_default()
                             .as_secs(),
+                        attachments: vec![],
                     };
 
                     if tx.send(channel_msg).await.is_err() {
@@ -1,4 +1,5 @@
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::traits::{Channel, ChannelMessage, MediaAttachment, OutgoingAttachment, SendMessage};
+use crate::config::StreamMode;
 use async_trait::async_trait;
 use futures_util::{SinkExt, StreamExt};
 use parking_lot::Mutex;
@@ -14,6 +15,9 @@ pub struct DiscordChannel {
     listen_to_bots: bool,
     mention_only: bool,
     typing_handle: Mutex<Option<tokio::task::JoinHandle<()>>>,
+    stream_mode: StreamMode,
+    draft_update_interval_ms: u64,
+    last_draft_edit: Mutex<std::collections::HashMap<String, std::time::Instant>>,
 }
 
 impl DiscordChannel {
@@ -31,9 +35,51 @@ impl DiscordChannel {
             listen_to_bots,
             mention_only,
             typing_handle: Mutex::new(None),
+            stream_mode: StreamMode::Off,
+            draft_update_interval_ms: 1000,
+            last_draft_edit: Mutex::new(std::collections::HashMap::new()),
         }
     }
 
+    /// Configure streaming mode for progressive draft updates.
+    pub fn with_streaming(
+        mut self,
+        stream_mode: StreamMode,
+        draft_update_interval_ms: u64,
+    ) -> Self {
+        self.stream_mode = stream_mode;
+        self.draft_update_interval_ms = draft_update_interval_ms;
+        self
+    }
+
+    /// Replace the content of a previously sent message.
+    async fn edit_message(
+        &self,
+        channel_id: &str,
+        message_id: &str,
+        content: &str,
+    ) -> anyhow::Result<()> {
+        let url =
+            format!("https://discord.com/api/v10/channels/{channel_id}/messages/{message_id}");
+        let resp = self
+            .http_client()
+            .patch(&url)
+            .header("Authorization", format!("Bot {}", self.bot_token))
+            .json(&json!({ "content": content }))
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let err = resp
+                .text()
+                .await
+                .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
+            anyhow::bail!("Discord edit message failed ({status}): {err}");
+        }
+        Ok(())
+    }
+
     fn http_client(&self) -> reqwest::Client {
         crate::config::build_runtime_proxy_client("channel.discord")
     }
@@ -116,6 +162,27 @@ fn contains_bot_mention(content: &str, bot_user_id: &str) -> bool {
     content.contains(&tags[0]) || content.contains(&tags[1])
 }
 
+/// Image attachments on a MESSAGE_CREATE payload as `(url, content_type, filename)`.
+fn image_attachment_refs(d: &serde_json::Value) -> Vec<(&str, &str, Option<&str>)> {
+    d.get("attachments")
+        .and_then(serde_json::Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|a| {
+                    let content_type = a.get("content_type")?.as_str()?;
+                    if !content_type.starts_with("image/") {
+                        return None;
+                    }
+                    let url = a.get("url")?.as_str()?;
+                    let filename = a.get("filename").and_then(serde_json::Value::as_str);
+                    Some((url, content_type, filename))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 fn normalize_incoming_content(
     content: &str,
     mention_only: bool,
@@ -188,8 +255,125 @@ impl Channel for DiscordChannel {
         "discord"
     }
 
+    fn supports_draft_updates(&self) -> bool {
+        self.stream_mode != StreamMode::Off
+    }
+
+    async fn send_draft(&self, message: &SendMessage) -> anyhow::Result<Option<String>> {
+        if self.stream_mode == StreamMode::Off {
+            return Ok(None);
+        }
+
+        let url = format!(
+            "https://discord.com/api/v10/channels/{}/messages",
+            message.recipient
+        );
+        let initial_text = if message.content.is_empty() {
+            "..."
+        } else {
+            message.content.as_str()
+        };
+
+        let resp = self
+            .http_client()
+            .post(&url)
+            .header("Authorization", format!("Bot {}", self.bot_token))
+            .json(&json!({ "content": initial_text }))
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let err = resp.text().await.unwrap_or_default();
+            anyhow::bail!("Discord send message (draft) failed ({status}): {err}");
+        }
+
+        let resp_json: serde_json::Value = resp.json().await?;
+        let message_id = resp_json
+            .get("id")
+            .and_then(|id| id.as_str())
+            .map(ToString::to_string);
+
+        self.last_draft_edit
+            .lock()
+            .insert(message.recipient.clone(), std::time::Instant::now());
+
+        Ok(message_id)
+    }
+
+    async fn update_draft(
+        &self,
+        recipient: &str,
+        message_id: &str,
+        text: &str,
+    ) -> anyhow::Result<()> {
+        // Rate-limit edits per channel
+        {
+            let last_edits = self.last_draft_edit.lock();
+            if let Some(last_time) = last_edits.get(recipient) {
+                let elapsed = u64::try_from(last_time.elapsed().as_millis()).unwrap_or(u64::MAX);
+                if elapsed < self.draft_update_interval_ms {
+                    return Ok(());
+                }
+            }
+        }
+
+        // Mid-stream edits show the head of the answer; the overflow is
+        // delivered as follow-up messages when the draft is finalized.
+        let display_text = match text.char_indices().nth(DISCORD_MAX_MESSAGE_LENGTH) {
+            Some((idx, _)) => &text[..idx],
+            None => text,
+        };
+        if display_text.trim().is_empty() {
+            return Ok(());
+        }
+
+        match self.edit_message(recipient, message_id, display_text).await {
+            Ok(()) => {
+                self.last_draft_edit
+                    .lock()
+                    .insert(recipient.to_string(), std::time::Instant::now());
+            }
+            Err(e) => tracing::debug!("{e}"),
+        }
+
+        Ok(())
+    }
+
+    async fn finalize_draft(
+        &self,
+        recipient: &str,
+        message_id: &str,
+        text: &str,
+    ) -> anyhow::Result<()> {
+        // Clean up rate-limit tracking for this channel
+        self.last_draft_edit.lock().remove(recipient);
+
+        if text.trim().is_empty() {
+            return Ok(());
+        }
+
+        let chunks = split_message_for_discord(text);
+        if let Err(e) = self.edit_message(recipient, message_id, &chunks[0]).await {
+            tracing::warn!("Discord finalize_draft edit failed: {e}; falling back to send");
+            return self.send(&SendMessage::new(text, recipient)).await;
+        }
+
+        for chunk in &chunks[1..] {
+            tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+            self.send(&SendMessage::new(chunk.as_str(), recipient))
+                .await?;
+        }
+
+        Ok(())
+    }
+
     async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
-        let chunks = split_message_for_discord(&message.content);
+        let chunks = if message.has_text() {
+            split_message_for_discord(&message.content)
+        } else {
+            Vec::new()
+        };
 
         for (i, chunk) in chunks.iter().enumerate() {
             let url = format!(
@@ -222,6 +406,51 @@ impl Channel for DiscordChannel {
             }
         }
 
+        for attachment in &message.attachments {
+            self.send_file(&message.recipient, attachment, None).await?;
+        }
+
+        Ok(())
+    }
+
+    fn supports_files(&self) -> bool {
+        true
+    }
+
+    async fn send_file(
+        &self,
+        recipient: &str,
+        attachment: &OutgoingAttachment,
+        caption: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let file_bytes = tokio::fs::read(&attachment.path).await?;
+        let part = reqwest::multipart::Part::bytes(file_bytes)
+            .file_name(attachment.file_name().to_string())
+            .mime_str(&attachment.mime_type)?;
+        let payload = json!({ "content": caption.unwrap_or_default() });
+        let form = reqwest::multipart::Form::new()
+            .text("payload_json", payload.to_string())
+            .part("files[0]", part);
+
+        let resp = self
+            .http_client()
+            .post(format!(
+                "https://discord.com/api/v10/channels/{recipient}/messages"
+            ))
+            .header("Authorization", format!("Bot {}", self.bot_token))
+            .multipart(form)
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let err = resp
+                .text()
+                .await
+                .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
+            anyhow::bail!("Discord file upload failed ({status}): {err}");
+        }
+
         Ok(())
     }
 
@@ -386,11 +615,35 @@ impl Channel for DiscordChannel {
                     }
 
                     let content = d.get("content").and_then(|c| c.as_str()).unwrap_or("");
-                    let Some(clean_content) =
-                        normalize_incoming_content(content, self.mention_only, &bot_user_id)
-                    else {
+                    let image_refs = image_attachment_refs(d);
+                    let clean_content =
+                        match normalize_incoming_content(content, self.mention_only, &bot_user_id) {
+                            Some(clean) => clean,
+                            // Image-only posts have no text; still require a mention in mention-only mode
+                            None if content.is_empty()
+                                && !self.mention_only
+                                && !image_refs.is_empty() =>
+                            {
+                                String::new()
+                            }
+                            None => continue,
+                        };
+
+                    let mut attachments = Vec::with_capacity(image_refs.len());
+                    for (url, content_type, filename) in image_refs {
+                        let request = self.http_client().get(url);
+                        attachments.extend(
+                            MediaAttachment::download_image(
+                                request,
+                                Some(content_type),
+                                filename.map(str::to_string),
+                            )
+                            .await,
+                        );
+                    }
+                    if clean_content.is_empty() && attachments.is_empty() {
                         continue;
-                    };
+                    }
 
                     let message_id = d.get("id").and_then(|i| i.as_str()).unwrap_or("");
                     let channel_id = d.get("channel_id").and_then(|c| c.as_str()).unwrap_or("").to_string();
@@ -413,6 +666,7 @@ impl Channel for DiscordChannel {
                             .duration_since(std::time::UNIX_EPOCH)
                             .unwrap_or_default()
                             .as_secs(),
+                        attachments,
                     };
 
                     if tx.send(channel_msg).await.is_err() {
@@ -601,6 +855,28 @@ mod tests {
         assert!(cleaned.is_none());
     }
 
+    #[test]
+    fn image_attachment_refs_keeps_only_images() {
+        let d = json!({
+            "attachments": [
+                {"url": "https://cdn.discordapp.com/a.png", "content_type": "image/png", "filename": "a.png"},
+                {"url": "https://cdn.discordapp.com/b.pdf", "content_type": "application/pdf", "filename": "b.pdf"},
+                {"url": "https://cdn.discordapp.com/c", "filename": "c"}
+            ]
+        });
+
+        let refs = image_attachment_refs(&d);
+        assert_eq!(
+            refs,
+            vec![(
+                "https://cdn.discordapp.com/a.png",
+                "image/png",
+                Some("a.png")
+            )]
+        );
+        assert!(image_attachment_refs(&json!({})).is_empty());
+    }
+
     // Message splitting tests
 
     #[test]
@@ -750,6 +1026,38 @@ mod tests {
         assert_eq!(reconstructed, msg);
     }
 
+    #[test]
+    fn supports_draft_updates_respects_stream_mode() {
+        let off = DiscordChannel::new("fake".into(), None, vec![], false, false);
+        assert!(!off.supports_draft_updates());
+
+        let partial = DiscordChannel::new("fake".into(), None, vec![], false, false)
+            .with_streaming(StreamMode::Partial, 750);
+        assert!(partial.supports_draft_updates());
+        assert_eq!(partial.draft_update_interval_ms, 750);
+    }
+
+    #[tokio::test]
+    async fn send_draft_returns_none_when_stream_mode_off() {
+        let ch = DiscordChannel::new("fake".into(), None, vec![], false, false);
+        let id = ch
+            .send_draft(&SendMessage::new("draft", "123456"))
+            .await
+            .unwrap();
+        assert!(id.is_none());
+    }
+
+    #[tokio::test]
+    async fn update_draft_rate_limit_short_circuits_network() {
+        let ch = DiscordChannel::new("fake".into(), None, vec![], false, false)
+            .with_streaming(StreamMode::Partial, 60_000);
+        ch.last_draft_edit
+            .lock()
+            .insert("123456".to_string(), std::time::Instant::now());
+
+        assert!(ch.update_draft("123456", "42", "text").await.is_ok());
+    }
+
     #[test]
     fn typing_handle_starts_as_none() {
         let ch = DiscordChannel::new("fake".into(), None, vec![], false, false);
@@ -14,7 +14,7 @@ use async_imap::types::Fetch;
 use async_imap::Session;
 use async_trait::async_trait;
 use futures::TryStreamExt;
-use lettre::message::SinglePart;
+use lettre::message::{header::ContentType, Attachment, MultiPart, SinglePart};
 use lettre::transport::smtp::authentication::Credentials;
 use lettre::{Message, SmtpTransport, Transport};
 use mail_parser::{MessageParser, MimeHeaders};
@@ -32,7 +32,7 @@ use tokio_rustls::TlsConnector;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::traits::{Channel, ChannelMessage, OutgoingAttachment, SendMessage};
 
 /// Email channel configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -442,6 +442,7 @@ impl EmailChannel {
                 content: email.content,
                 channel: "email".to_string(),
                 timestamp: email.timestamp,
+                attachments: vec![],
             };
 
             if tx.send(msg).await.is_err() {
@@ -506,11 +507,26 @@ impl Channel for EmailChannel {
             ("ZeroClaw Message", message.content.as_str())
         };
 
-        let email = Message::builder()
+        let builder = Message::builder()
             .from(self.config.from_address.parse()?)
             .to(message.recipient.parse()?)
-            .subject(subject)
-            .singlepart(SinglePart::plain(body.to_string()))?;
+            .subject(subject);
+
+        let email = if message.attachments.is_empty() {
+            builder.singlepart(SinglePart::plain(body.to_string()))?
+        } else {
+            let mut parts = MultiPart::mixed().singlepart(SinglePart::plain(body.to_string()));
+            for attachment in &message.attachments {
+                let file_bytes = tokio::fs::read(&attachment.path).await?;
+                let content_type = ContentType::parse(&attachment.mime_type)
+                    .or_else(|_| ContentType::parse("application/octet-stream"))?;
+                parts = parts.singlepart(
+                    Attachment::new(attachment.file_name().to_string())
+                        .body(file_bytes, content_type),
+                );
+            }
+            builder.multipart(parts)?
+        };
 
         let transport = self.create_smtp_transport()?;
         transport.send(&email)?;
@@ -518,6 +534,25 @@ impl Channel for EmailChannel {
         Ok(())
     }
 
+    fn supports_files(&self) -> bool {
+        true
+    }
+
+    async fn send_file(
+        &self,
+        recipient: &str,
+        attachment: &OutgoingAttachment,
+        caption: Option<&str>,
+    ) -> Result<()> {
+        let message = SendMessage::with_subject(
+            caption.unwrap_or_default(),
+            recipient,
+            attachment.file_name(),
+        )
+        .with_attachments(vec![attachment.clone()]);
+        self.send(&message).await
+    }
+
     async fn listen(&self, tx: mpsc::Sender<ChannelMessage>) -> Result<()> {
         info!(
             "Starting email channel with IDLE support on {}",
@@ -231,6 +231,7 @@ end tell"#
                                 .duration_since(std::time::UNIX_EPOCH)
                                 .unwrap_or_default()
                                 .as_secs(),
+                            attachments: vec![],
                         };
 
                         if tx.send(msg).await.is_err() {
@@ -573,6 +573,7 @@ impl Channel for IrcChannel {
                             .duration_since(std::time::UNIX_EPOCH)
                             .unwrap_or_default()
                             .as_secs(),
+                        attachments: vec![],
                     };
 
                     if tx.send(channel_msg).await.is_err() {
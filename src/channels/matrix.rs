@@ -1,11 +1,16 @@
-use crate::channels::traits::{Channel, ChannelMessage, SendMessage};
+use crate::channels::traits::{
+    Channel, ChannelMessage, MediaAttachment, OutgoingAttachment, SendMessage, MAX_ATTACHMENT_BYTES,
+};
 use async_trait::async_trait;
 use matrix_sdk::{
+    attachment::AttachmentConfig,
     authentication::matrix::MatrixSession,
     config::SyncSettings,
+    media::{MediaFormat, MediaRequestParameters},
     ruma::{
         events::room::message::{
-            MessageType, OriginalSyncRoomMessageEvent, RoomMessageEventContent,
+            ImageMessageEventContent, MessageType, OriginalSyncRoomMessageEvent,
+            RoomMessageEventContent, TextMessageEventContent,
         },
         OwnedRoomId, OwnedUserId,
     },
@@ -178,6 +183,37 @@ impl MatrixChannel {
         !body.trim().is_empty()
     }
 
+    /// Download (and decrypt, for encrypted rooms) an `m.image` payload.
+    async fn download_image(
+        client: &MatrixSdkClient,
+        content: &ImageMessageEventContent,
+    ) -> Option<MediaAttachment> {
+        let info = content.info.as_deref();
+        if info
+            .and_then(|i| i.size)
+            .is_some_and(|size| u64::from(size) > MAX_ATTACHMENT_BYTES as u64)
+        {
+            tracing::warn!("Matrix: skipping image larger than {MAX_ATTACHMENT_BYTES} bytes");
+            return None;
+        }
+
+        let request = MediaRequestParameters {
+            source: content.source.clone(),
+            format: MediaFormat::File,
+        };
+        match client.media().get_media_content(&request, true).await {
+            Ok(bytes) => MediaAttachment::image_from_bytes(
+                &bytes,
+                info.and_then(|i| i.mimetype.as_deref()),
+                Some(content.filename().to_string()),
+            ),
+            Err(error) => {
+                tracing::warn!("Matrix: failed to download image: {error}");
+                None
+            }
+        }
+    }
+
     fn cache_event_id(
         event_id: &str,
         recent_order: &mut std::collections::VecDeque<String>,
@@ -202,6 +238,29 @@ impl MatrixChannel {
         false
     }
 
+    /// Resolve the configured room and make sure the bot has joined it.
+    async fn joined_target_room(&self) -> anyhow::Result<Room> {
+        let client = self.matrix_client().await?;
+        let target_room_id = self.target_room_id().await?;
+        let target_room: OwnedRoomId = target_room_id.parse()?;
+
+        let mut room = client.get_room(&target_room);
+        if room.is_none() {
+            let _ = client.sync_once(SyncSettings::new()).await;
+            room = client.get_room(&target_room);
+        }
+
+        let Some(room) = room else {
+            anyhow::bail!("Matrix room '{}' not found in joined rooms", target_room_id);
+        };
+
+        if room.state() != RoomState::Joined {
+            anyhow::bail!("Matrix room '{}' is not in joined state", target_room_id);
+        }
+
+        Ok(room)
+    }
+
     async fn target_room_id(&self) -> anyhow::Result<String> {
         if self.room_id.starts_with('!') {
             return Ok(self.room_id.clone());
@@ -481,25 +540,39 @@ impl Channel for MatrixChannel {
     }
 
     async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
-        let client = self.matrix_client().await?;
-        let target_room_id = self.target_room_id().await?;
-        let target_room: OwnedRoomId = target_room_id.parse()?;
+        let room = self.joined_target_room().await?;
 
-        let mut room = client.get_room(&target_room);
-        if room.is_none() {
-            let _ = client.sync_once(SyncSettings::new()).await;
-            room = client.get_room(&target_room);
+        if message.has_text() {
+            room.send(RoomMessageEventContent::text_markdown(&message.content))
+                .await?;
         }
 
-        let Some(room) = room else {
-            anyhow::bail!("Matrix room '{}' not found in joined rooms", target_room_id);
-        };
-
-        if room.state() != RoomState::Joined {
-            anyhow::bail!("Matrix room '{}' is not in joined state", target_room_id);
+        for attachment in &message.attachments {
+            self.send_file(&message.recipient, attachment, None).await?;
         }
 
-        room.send(RoomMessageEventContent::text_markdown(&message.content))
+        Ok(())
+    }
+
+    fn supports_files(&self) -> bool {
+        true
+    }
+
+    async fn send_file(
+        &self,
+        _recipient: &str,
+        attachment: &OutgoingAttachment,
+        caption: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let room = self.joined_target_room().await?;
+        let file_bytes = tokio::fs::read(&attachment.path).await?;
+        let content_type: mime::Mime = attachment
+            .mime_type
+            .parse()
+            .unwrap_or(mime::APPLICATION_OCTET_STREAM);
+        let config = AttachmentConfig::new().caption(caption.map(TextMessageEventContent::plain));
+
+        room.send_attachment(attachment.file_name(), &content_type, file_bytes, config)
             .await?;
 
         Ok(())
@@ -567,13 +640,17 @@ impl Channel for MatrixChannel {
                     return;
                 }
 
-                let body = match &event.content.msgtype {
-                    MessageType::Text(content) => content.body.clone(),
-                    MessageType::Notice(content) => content.body.clone(),
+                let (body, image) = match &event.content.msgtype {
+                    MessageType::Text(content) => (content.body.clone(), None),
+                    MessageType::Notice(content) => (content.body.clone(), None),
+                    MessageType::Image(content) => (
+                        content.caption().unwrap_or_default().to_string(),
+                        Some(content.clone()),
+                    ),
                     _ => return,
                 };
 
-                if !MatrixChannel::has_non_empty_body(&body) {
+                if image.is_none() && !MatrixChannel::has_non_empty_body(&body) {
                     return;
                 }
 
@@ -586,6 +663,15 @@ impl Channel for MatrixChannel {
                     }
                 }
 
+                let mut attachments = Vec::new();
+                if let Some(content) = image {
+                    attachments
+                        .extend(MatrixChannel::download_image(&room.client(), &content).await);
+                    if attachments.is_empty() && !MatrixChannel::has_non_empty_body(&body) {
+                        return;
+                    }
+                }
+
                 let msg = ChannelMessage {
                     id: event_id,
                     sender: sender.clone(),
@@ -596,6 +682,7 @@ impl Channel for MatrixChannel {
                         .duration_since(std::time::UNIX_EPOCH)
                         .unwrap_or_default()
                         .as_secs(),
+                    attachments,
                 };
 
                 let _ = tx.send(msg).await;
@@ -726,7 +813,7 @@ mod tests {
             "!r:m".to_string(),
             vec![],
             Some("   ".to_string()),
-            Some("".to_string()),
+            Some(String::new()),
         );
 
         assert!(ch.session_user_id_hint.is_none());
@@ -1,4 +1,4 @@
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::traits::{Channel, ChannelMessage, OutgoingAttachment, SendMessage};
 use anyhow::{bail, Result};
 use async_trait::async_trait;
 use parking_lot::Mutex;
@@ -81,28 +81,28 @@ impl MattermostChannel {
             .to_string();
         (id, username)
     }
-}
-
-#[async_trait]
-impl Channel for MattermostChannel {
-    fn name(&self) -> &str {
-        "mattermost"
-    }
 
-    async fn send(&self, message: &SendMessage) -> Result<()> {
-        // Mattermost supports threading via 'root_id'.
-        // We pack 'channel_id:root_id' into recipient if it's a thread.
-        let (channel_id, root_id) = if let Some((c, r)) = message.recipient.split_once(':') {
+    /// Create a post. Mattermost supports threading via 'root_id', so
+    /// 'channel_id:root_id' is packed into the recipient for thread replies.
+    async fn create_post(&self, recipient: &str, text: &str, file_ids: &[&str]) -> Result<()> {
+        let (channel_id, root_id) = if let Some((c, r)) = recipient.split_once(':') {
             (c, Some(r))
         } else {
-            (message.recipient.as_str(), None)
+            (recipient, None)
         };
 
         let mut body_map = serde_json::json!({
             "channel_id": channel_id,
-            "message": message.content
+            "message": text
         });
 
+        if !file_ids.is_empty() {
+            body_map
+                .as_object_mut()
+                .unwrap()
+                .insert("file_ids".to_string(), serde_json::json!(file_ids));
+        }
+
         if let Some(root) = root_id {
             body_map.as_object_mut().unwrap().insert(
                 "root_id".to_string(),
@@ -129,6 +129,74 @@ impl Channel for MattermostChannel {
 
         Ok(())
     }
+}
+
+#[async_trait]
+impl Channel for MattermostChannel {
+    fn name(&self) -> &str {
+        "mattermost"
+    }
+
+    async fn send(&self, message: &SendMessage) -> Result<()> {
+        if message.has_text() {
+            self.create_post(&message.recipient, &message.content, &[])
+                .await?;
+        }
+
+        for attachment in &message.attachments {
+            self.send_file(&message.recipient, attachment, None).await?;
+        }
+
+        Ok(())
+    }
+
+    fn supports_files(&self) -> bool {
+        true
+    }
+
+    async fn send_file(
+        &self,
+        recipient: &str,
+        attachment: &OutgoingAttachment,
+        caption: Option<&str>,
+    ) -> Result<()> {
+        let channel_id = recipient.split_once(':').map_or(recipient, |(c, _)| c);
+        let file_bytes = tokio::fs::read(&attachment.path).await?;
+        let part = reqwest::multipart::Part::bytes(file_bytes)
+            .file_name(attachment.file_name().to_string())
+            .mime_str(&attachment.mime_type)?;
+        let form = reqwest::multipart::Form::new()
+            .text("channel_id", channel_id.to_string())
+            .part("files", part);
+
+        let resp = self
+            .http_client()
+            .post(format!("{}/api/v4/files", self.base_url))
+            .bearer_auth(&self.bot_token)
+            .multipart(form)
+            .send()
+            .await?;
+
+        let status = resp.status();
+        if !status.is_success() {
+            let body = resp
+                .text()
+                .await
+                .unwrap_or_else(|e| format!("<failed to read response: {e}>"));
+            bail!("Mattermost file upload failed ({status}): {body}");
+        }
+
+        let uploaded: serde_json::Value = resp.json().await?;
+        let file_id = uploaded
+            .get("file_infos")
+            .and_then(|infos| infos.get(0))
+            .and_then(|info| info.get("id"))
+            .and_then(|id| id.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Mattermost upload response has no file id"))?;
+
+        self.create_post(recipient, caption.unwrap_or_default(), &[file_id])
+            .await
+    }
 
     async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> Result<()> {
         let channel_id = self
@@ -321,6 +389,7 @@ impl MattermostChannel {
             channel: "mattermost".to_string(),
             #[allow(clippy::cast_sign_loss)]
             timestamp: (create_at / 1000) as u64,
+            attachments: vec![],
         })
     }
 }
@@ -31,13 +31,17 @@ pub use traits::{Channel, SendMessage};
 pub use whatsapp::WhatsAppChannel;
 
 use crate::agent::loop_::{build_tool_instructions, run_tool_call_loop};
-use crate::config::Config;
+use crate::approval::queue::ApprovalQueue;
+use crate::approval::remote::RemoteApprover;
+use crate::approval::ApprovalManager;
+use crate::config::{Config, MemoryIsolation};
 use crate::identity;
-use crate::memory::{self, Memory};
+use crate::memory::{self, Memory, ResponseCache};
 use crate::observability::{self, Observer};
-use crate::providers::{self, ChatMessage, Provider};
+use crate::providers::{self, ChatMessage, ContentPart, Provider};
 use crate::runtime;
 use crate::security::SecurityPolicy;
+use crate::sessions::{self, SessionStore};
 use crate::observability::{InterventionChain, TripwireHandler, SingleActionHandler, DepthGuardHandler, ConvergenceDetector};
 use crate::tools::{self, Tool};
 use crate::util::truncate_with_ellipsis;
@@ -113,6 +117,9 @@ struct ChannelRuntimeContext {
     auto_save_memory: bool,
     max_tool_iterations: usize,
     min_relevance_score: f64,
+    memory_isolation: MemoryIsolation,
+    response_cache: Option<Arc<ResponseCache>>,
+    approval: Option<Arc<ApprovalManager>>,
     conversation_histories: ConversationHistoryMap,
     provider_cache: ProviderCacheMap,
     route_overrides: RouteSelectionMap,
@@ -123,6 +130,7 @@ struct ChannelRuntimeContext {
     workspace_dir: Arc<PathBuf>,
     security: Arc<SecurityPolicy>,
     chain: Arc<InterventionChain>,
+    sessions: Option<Arc<SessionStore>>,
 }
 
 fn conversation_memory_key(msg: &traits::ChannelMessage) -> String {
@@ -236,11 +244,71 @@ fn set_route_selection(ctx: &ChannelRuntimeContext, sender_key: &str, next: Chan
     }
 }
 
-fn clear_sender_history(ctx: &ChannelRuntimeContext, sender_key: &str) {
+fn clear_sender_history(ctx: &ChannelRuntimeContext, msg: &traits::ChannelMessage) {
     ctx.conversation_histories
         .lock()
         .unwrap_or_else(|e| e.into_inner())
-        .remove(sender_key);
+        .remove(&conversation_history_key(msg));
+
+    // Start a fresh stored session; the previous one stays resumable.
+    if let Some(store) = ctx.sessions.as_ref() {
+        if let Err(e) = store.create(&msg.channel, &msg.sender) {
+            tracing::warn!("Failed to start new session for {}: {e}", msg.sender);
+        }
+    }
+}
+
+/// Restore a sender's history from the session store (e.g. after a restart).
+fn hydrate_sender_history(
+    ctx: &ChannelRuntimeContext,
+    msg: &traits::ChannelMessage,
+    history_key: &str,
+) {
+    let Some(store) = ctx.sessions.as_ref() else {
+        return;
+    };
+    if ctx
+        .conversation_histories
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .contains_key(history_key)
+    {
+        return;
+    }
+
+    let restored = match store.latest_for(&msg.channel, &msg.sender) {
+        Ok(Some(session)) => store.load(&session.id).map(sessions::chat_from_transcript),
+        Ok(None) => Ok(Vec::new()),
+        Err(e) => Err(e),
+    };
+    match restored {
+        Ok(mut turns) => {
+            let excess = turns.len().saturating_sub(MAX_CHANNEL_HISTORY);
+            turns.drain(..excess);
+            ctx.conversation_histories
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .entry(history_key.to_string())
+                .or_insert(turns);
+        }
+        Err(e) => tracing::warn!("Failed to restore session for {}: {e}", msg.sender),
+    }
+}
+
+fn persist_sender_history(
+    ctx: &ChannelRuntimeContext,
+    msg: &traits::ChannelMessage,
+    turns: &[ChatMessage],
+) {
+    let Some(store) = ctx.sessions.as_ref() else {
+        return;
+    };
+    let saved = store
+        .current_for(&msg.channel, &msg.sender)
+        .and_then(|session| store.save(&session.id, &sessions::transcript_from_chat(turns)));
+    if let Err(e) = saved {
+        tracing::warn!("Failed to save session for {}: {e}", msg.sender);
+    }
 }
 
 fn load_cached_model_preview(workspace_dir: &Path, provider_name: &str) -> Vec<String> {
@@ -390,7 +458,7 @@ async fn handle_runtime_command_if_needed(
                         if provider_name != current.provider {
                             current.provider = provider_name.clone();
                             set_route_selection(ctx, &sender_key, current.clone());
-                            clear_sender_history(ctx, &sender_key);
+                            clear_sender_history(ctx, msg);
                         }
 
                         format!(
@@ -420,7 +488,7 @@ async fn handle_runtime_command_if_needed(
             } else {
                 current.model = model.clone();
                 set_route_selection(ctx, &sender_key, current.clone());
-                clear_sender_history(ctx, &sender_key);
+                clear_sender_history(ctx, msg);
 
                 format!(
                     "Model switched to `{model}` for provider `{}` in this sender session.",
@@ -462,7 +530,12 @@ async fn build_memory_context(
         if !relevant.is_empty() {
             context.push_str("[Memory context]\n");
             for entry in &relevant {
-                let _ = writeln!(context, "- {}: {}", entry.key, entry.content);
+                let _ = writeln!(
+                    context,
+                    "- {}: {}",
+                    memory::ingest::cite(&entry.key),
+                    entry.content
+                );
             }
             context.push('\n');
         }
@@ -556,6 +629,17 @@ fn spawn_scoped_typing_task(
     handle
 }
 
+/// System prompt argument for response cache lookups: the prompt plus the
+/// tenant namespace and the memory context injected into the message.
+fn response_cache_scope(
+    system_prompt: &str,
+    namespace: Option<&str>,
+    memory_context: &str,
+) -> String {
+    let scope = namespace.map_or_else(|| "[shared]".to_string(), |ns| format!("[namespace: {ns}]"));
+    format!("{system_prompt}\n{scope}\n{memory_context}")
+}
+
 async fn process_channel_message(ctx: Arc<ChannelRuntimeContext>, msg: traits::ChannelMessage) {
     println!(
         "  💬 [{}] from {}: {}",
@@ -588,13 +672,21 @@ async fn process_channel_message(ctx: Arc<ChannelRuntimeContext>, msg: traits::C
         }
     };
 
-    let memory_context =
-        build_memory_context(ctx.memory.as_ref(), &msg.content, ctx.min_relevance_score).await;
+    // Recall, auto-save and the memory tools stay inside the tenant
+    // namespace of this message (`memory.isolation`).
+    let memory_namespace =
+        memory::tenant::namespace_for(ctx.memory_isolation, &msg.channel, &msg.sender);
+    let tenant_memory = memory::tenant::scoped(&ctx.memory, memory_namespace.as_deref());
+    let memory_context = build_memory_context(
+        tenant_memory.as_ref(),
+        &msg.content,
+        ctx.min_relevance_score,
+    )
+    .await;
 
     if ctx.auto_save_memory {
         let autosave_key = conversation_memory_key(&msg);
-        let _ = ctx
-            .memory
+        let _ = tenant_memory
             .store(
                 &autosave_key,
                 &msg.content,
@@ -614,6 +706,7 @@ async fn process_channel_message(ctx: Arc<ChannelRuntimeContext>, msg: traits::C
     let started_at = Instant::now();
 
     // Build history from per-sender conversation cache
+    hydrate_sender_history(ctx.as_ref(), &msg, &history_key);
     let mut prior_turns = ctx
         .conversation_histories
         .lock()
@@ -622,18 +715,60 @@ async fn process_channel_message(ctx: Arc<ChannelRuntimeContext>, msg: traits::C
         .cloned()
         .unwrap_or_default();
 
+    let fresh_conversation = prior_turns.is_empty();
     let mut history = vec![ChatMessage::system(ctx.system_prompt.as_str())];
     history.append(&mut prior_turns);
-    history.push(ChatMessage::user(&enriched_message));
+    let image_parts: Vec<ContentPart> = msg
+        .attachments
+        .iter()
+        .map(|a| ContentPart::image(a.mime_type.as_str(), a.data.as_str()))
+        .collect();
+    history.push(ChatMessage::user(&enriched_message).with_parts(image_parts));
 
     if let Some(instructions) = channel_delivery_instructions(&msg.channel) {
         history.push(ChatMessage::system(instructions));
     }
 
-    // Determine if this channel supports streaming draft updates
-    let use_streaming = target_channel
+    // Only the opening message of a conversation is answered from the
+    // response cache; later turns depend on history the key does not cover.
+    // The reply was generated from this tenant's recalled memories, so the
+    // namespace and memory context are part of the key.
+    let cache_lookup = match ctx.response_cache.as_ref() {
+        Some(cache) if fresh_conversation && msg.attachments.is_empty() => {
+            let cache_scope = response_cache_scope(
+                ctx.system_prompt.as_str(),
+                memory_namespace.as_deref(),
+                &memory_context,
+            );
+            match cache
+                .lookup(route.model.as_str(), Some(&cache_scope), &msg.content)
+                .await
+            {
+                Ok(lookup) => {
+                    ctx.observer
+                        .record_event(&observability::ObserverEvent::CacheLookup {
+                            hit: lookup.response.is_some(),
+                            semantic: lookup.semantic,
+                        });
+                    Some(lookup)
+                }
+                Err(e) => {
+                    tracing::warn!("Response cache lookup failed: {e}");
+                    None
+                }
+            }
+        }
+        _ => None,
+    };
+    let cached_response = cache_lookup
         .as_ref()
-        .map_or(false, |ch| ch.supports_draft_updates());
+        .and_then(|lookup| lookup.response.clone());
+
+    // Determine if this channel supports streaming draft updates
+    let use_streaming = cached_response.is_none()
+        && target_channel
+            .as_ref()
+            .map_or(false, |ch| ch.supports_draft_updates());
 
     // Set up streaming channel if supported
     let (delta_tx, delta_rx) = if use_streaming {
@@ -676,6 +811,11 @@ async fn process_channel_message(ctx: Arc<ChannelRuntimeContext>, msg: traits::C
             let mut accumulated = String::new();
             while let Some(delta) = rx.recv().await {
                 accumulated.push_str(&delta);
+                // Coalesce tokens that queued up during the previous edit so
+                // a slow edit API never backs up the provider stream.
+                while let Ok(more) = rx.try_recv() {
+                    accumulated.push_str(&more);
+                }
                 if let Err(e) = channel
                     .update_draft(&reply_target, &draft_id, &accumulated)
                     .await
@@ -698,26 +838,46 @@ async fn process_channel_message(ctx: Arc<ChannelRuntimeContext>, msg: traits::C
         _ => None,
     };
 
-    let llm_result = tokio::time::timeout(
-        Duration::from_secs(CHANNEL_MESSAGE_TIMEOUT_SECS),
-        run_tool_call_loop(
-            active_provider.as_ref(),
-            &mut history,
-            ctx.tools_registry.as_ref(),
-            ctx.observer.as_ref(),
-            route.provider.as_str(),
-            route.model.as_str(),
-            ctx.temperature,
-            true,
-            None,
-            msg.channel.as_str(),
-            ctx.max_tool_iterations,
-            delta_tx,
-            &ctx.security,
-            &ctx.chain,
-        ),
-    )
-    .await;
+    let history_len = history.len();
+    let tool_loop = run_tool_call_loop(
+        active_provider.as_ref(),
+        &mut history,
+        ctx.tools_registry.as_ref(),
+        ctx.observer.as_ref(),
+        route.provider.as_str(),
+        route.model.as_str(),
+        ctx.temperature,
+        true,
+        ctx.approval.as_deref(),
+        msg.channel.as_str(),
+        ctx.max_tool_iterations,
+        delta_tx,
+        &ctx.security,
+        &ctx.chain,
+    );
+    let tool_loop = async {
+        // Let send_file deliver into the conversation being answered.
+        match target_channel.as_ref() {
+            Some(channel) => {
+                let target = tools::send_file::ConversationTarget {
+                    channel: Arc::clone(channel),
+                    recipient: msg.reply_target.clone(),
+                };
+                tools::send_file::with_conversation(target, tool_loop).await
+            }
+            None => tool_loop.await,
+        }
+    };
+    let tool_loop = memory::tenant::with_namespace(memory_namespace, tool_loop);
+    let llm_result = match cached_response {
+        Some(response) => {
+            drop(tool_loop);
+            Ok(Ok(response))
+        }
+        None => {
+            tokio::time::timeout(Duration::from_secs(CHANNEL_MESSAGE_TIMEOUT_SECS), tool_loop).await
+        }
+    };
 
     // Wait for draft updater to finish
     if let Some(handle) = draft_updater {
@@ -733,20 +893,40 @@ async fn process_channel_message(ctx: Arc<ChannelRuntimeContext>, msg: traits::C
 
     match llm_result {
         Ok(Ok(response)) => {
+            // Replies that ran tools are not cached: a hit would skip the tools.
+            if let (Some(cache), Some(lookup)) = (ctx.response_cache.as_ref(), &cache_lookup) {
+                if lookup.response.is_none() && history.len() == history_len + 1 {
+                    let tokens = u32::try_from(response.len() / 4).unwrap_or(u32::MAX);
+                    if let Err(e) = cache.store(lookup, route.model.as_str(), &response, tokens) {
+                        tracing::warn!("Response cache store failed: {e}");
+                    }
+                }
+            }
+
             // Save user + assistant turn to per-sender history
-            {
+            let saved_turns = {
                 let mut histories = ctx
                     .conversation_histories
                     .lock()
                     .unwrap_or_else(|e| e.into_inner());
                 let turns = histories.entry(history_key).or_default();
-                turns.push(ChatMessage::user(&enriched_message));
+                // Images are not replayed on later turns; keep a marker instead.
+                if msg.attachments.is_empty() {
+                    turns.push(ChatMessage::user(&enriched_message));
+                } else {
+                    turns.push(ChatMessage::user(format!(
+                        "{enriched_message}\n[{} image(s) attached]",
+                        msg.attachments.len()
+                    )));
+                }
                 turns.push(ChatMessage::assistant(&response));
                 // Trim to MAX_CHANNEL_HISTORY (keep recent turns)
                 while turns.len() > MAX_CHANNEL_HISTORY {
                     turns.remove(0);
                 }
-            }
+                turns.clone()
+            };
+            persist_sender_history(ctx.as_ref(), &msg, &saved_turns);
             println!(
                 "  🤖 Reply ({}ms): {}",
                 started_at.elapsed().as_millis(),
@@ -827,6 +1007,27 @@ async fn run_message_dispatch_loop(
     let mut workers = tokio::task::JoinSet::new();
 
     while let Some(msg) = rx.recv().await {
+        // Operator replies are resolved here, not in a worker: the workers
+        // may all be waiting on exactly these approvals.
+        if let Some(ack) = ctx
+            .approval
+            .as_ref()
+            .and_then(|mgr| mgr.remote())
+            .and_then(|remote| remote.handle_reply(&msg))
+        {
+            if let Some(channel) = ctx.channels_by_name.get(&msg.channel).cloned() {
+                workers.spawn(async move {
+                    if let Err(e) = channel
+                        .send(&SendMessage::new(ack, &msg.reply_target))
+                        .await
+                    {
+                        tracing::warn!("Failed to acknowledge approval reply: {e}");
+                    }
+                });
+            }
+            continue;
+        }
+
         let permit = match Arc::clone(&semaphore).acquire_owned().await {
             Ok(permit) => permit,
             Err(_) => break,
@@ -848,6 +1049,56 @@ async fn run_message_dispatch_loop(
     }
 }
 
+/// Approval manager for channel runs. Supervised tool calls wait in the
+/// shared approval queue when `[autonomy] approval_channel` names a running
+/// channel or `[gateway] approvals_enabled` is set.
+fn build_channel_approval(
+    config: &Config,
+    channels_by_name: &HashMap<String, Arc<dyn Channel>>,
+) -> Option<Arc<ApprovalManager>> {
+    let queue = ApprovalQueue::global();
+    let remote = match config.autonomy.approval_channel.as_deref() {
+        None => None,
+        Some(name) => match (
+            channels_by_name.get(name),
+            config.autonomy.approval_to.as_deref(),
+        ) {
+            (Some(channel), Some(to)) => {
+                println!("  🔐 Approvals: {name} → {to}");
+                Some(Arc::new(RemoteApprover::new(
+                    Arc::clone(channel),
+                    to,
+                    Arc::clone(&queue),
+                )))
+            }
+            (None, _) => {
+                tracing::warn!(
+                    "approval_channel `{name}` is not running; operator chat approvals are off"
+                );
+                None
+            }
+            (Some(_), None) => {
+                tracing::warn!(
+                    "approval_channel is set without approval_to; operator chat approvals are off"
+                );
+                None
+            }
+        },
+    };
+    if remote.is_none() && !config.gateway.approvals_enabled {
+        return None;
+    }
+
+    let mut manager = ApprovalManager::from_config(&config.autonomy).with_queue(
+        queue,
+        Duration::from_secs(config.autonomy.approval_timeout_secs),
+    );
+    if let Some(remote) = remote {
+        manager = manager.with_remote(remote);
+    }
+    Some(Arc::new(manager))
+}
+
 /// Load OpenClaw format bootstrap files into the prompt.
 fn load_openclaw_bootstrap_files(
     prompt: &mut String,
@@ -1325,13 +1576,16 @@ pub async fn doctor_channels(config: Config) -> Result<()> {
     if let Some(ref dc) = config.channels_config.discord {
         channels.push((
             "Discord",
-            Arc::new(DiscordChannel::new(
-                dc.bot_token.clone(),
-                dc.guild_id.clone(),
-                dc.allowed_users.clone(),
-                dc.listen_to_bots,
-                dc.mention_only,
-            )),
+            Arc::new(
+                DiscordChannel::new(
+                    dc.bot_token.clone(),
+                    dc.guild_id.clone(),
+                    dc.allowed_users.clone(),
+                    dc.listen_to_bots,
+                    dc.mention_only,
+                )
+                .with_streaming(dc.stream_mode, dc.draft_update_interval_ms),
+            ),
         ));
     }
 
@@ -1521,6 +1775,7 @@ pub async fn start_channels(config: Config) -> Result<()> {
         .clone()
         .unwrap_or_else(|| "anthropic/claude-sonnet-4-20250514".into());
     let temperature = config.default_temperature;
+    memory::embeddings::init(&config.memory).await;
     let mem: Arc<dyn Memory> = Arc::from(memory::create_memory_with_storage(
         &config.memory,
         Some(&config.storage.provider.config),
@@ -1537,6 +1792,7 @@ pub async fn start_channels(config: Config) -> Result<()> {
     };
     // Build system prompt from workspace identity files + skills
     let workspace = config.workspace_dir.clone();
+    crate::mcp::init(&config).await;
     let tools_registry = Arc::new(tools::all_tools_with_runtime(
         Arc::new(config.clone()),
         &security,
@@ -1568,6 +1824,10 @@ pub async fn start_channels(config: Config) -> Result<()> {
             "file_write",
             "Write file contents. Use when: applying focused edits, scaffolding files, updating docs/code. Don't use when: side effects are unclear or file ownership is uncertain.",
         ),
+        (
+            "send_file",
+            "Send a workspace file to the user in this conversation. Use when: sharing screenshots, generated reports, diffs, exports. Don't use when: a short text answer is enough.",
+        ),
         (
             "memory_store",
             "Save to memory. Use when: preserving durable preferences, decisions, key context. Don't use when: information is transient/noisy/sensitive without need.",
@@ -1580,6 +1840,10 @@ pub async fn start_channels(config: Config) -> Result<()> {
             "memory_forget",
             "Delete a memory entry. Use when: memory is incorrect/stale or explicitly requested for removal. Don't use when: impact is uncertain.",
         ),
+        (
+            "ingest_document",
+            "Index workspace documents for recall with path/line citations. Use when: the user points at docs, notes, or code to remember. Don't use when: a single fact fits memory_store.",
+        ),
     ];
 
     if config.browser.enabled {
@@ -1605,7 +1869,11 @@ pub async fn start_channels(config: Config) -> Result<()> {
     if !config.agents.is_empty() {
         tool_descs.push((
             "delegate",
-            "Delegate a subtask to a specialized agent. Use when: a task benefits from a different model (e.g. fast summarization, deep reasoning, code generation). The sub-agent runs a single prompt and returns its response.",
+            "Delegate a subtask to a specialized agent. Use when: a task benefits from a different model (e.g. fast summarization, deep reasoning, code generation). The sub-agent works the task with its own tools and returns its final answer.",
+        ));
+        tool_descs.push((
+            "delegate_parallel",
+            "Send the same task to several agents (or several copies of one agent) at once and collect every answer. Set reconcile='pick' or 'merge' to have a reviewer agent produce a final answer. Use when: a question benefits from independent opinions to cross-check.",
         ));
     }
 
@@ -1650,13 +1918,16 @@ pub async fn start_channels(config: Config) -> Result<()> {
     }
 
     if let Some(ref dc) = config.channels_config.discord {
-        channels.push(Arc::new(DiscordChannel::new(
-            dc.bot_token.clone(),
-            dc.guild_id.clone(),
-            dc.allowed_users.clone(),
-            dc.listen_to_bots,
-            dc.mention_only,
-        )));
+        channels.push(Arc::new(
+            DiscordChannel::new(
+                dc.bot_token.clone(),
+                dc.guild_id.clone(),
+                dc.allowed_users.clone(),
+                dc.listen_to_bots,
+                dc.mention_only,
+            )
+            .with_streaming(dc.stream_mode, dc.draft_update_interval_ms),
+        ));
     }
 
     if let Some(ref sl) = config.channels_config.slack {
@@ -1813,6 +2084,7 @@ pub async fn start_channels(config: Config) -> Result<()> {
             .collect::<HashMap<_, _>>(),
     );
     let max_in_flight_messages = compute_max_in_flight_messages(channels.len());
+    let approval = build_channel_approval(&config, &channels_by_name);
 
     println!("  🚦 In-flight message limit: {max_in_flight_messages}");
 
@@ -1832,6 +2104,14 @@ pub async fn start_channels(config: Config) -> Result<()> {
         auto_save_memory: config.memory.auto_save,
         max_tool_iterations: config.agent.max_tool_iterations,
         min_relevance_score: config.memory.min_relevance_score,
+        memory_isolation: config.memory.isolation,
+        response_cache: memory::create_response_cache(
+            &config.memory,
+            &config.workspace_dir,
+            config.api_key.as_deref(),
+        )
+        .map(Arc::new),
+        approval,
         conversation_histories: Arc::new(Mutex::new(HashMap::new())),
         provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
         route_overrides: Arc::new(Mutex::new(HashMap::new())),
@@ -1849,6 +2129,13 @@ pub async fn start_channels(config: Config) -> Result<()> {
             c.add(Box::new(ConvergenceDetector::new(config.autonomy.convergence_threshold)));
             Arc::new(c)
         },
+        sessions: match SessionStore::from_config(&config) {
+            Ok(store) => store.map(Arc::new),
+            Err(e) => {
+                tracing::warn!("Session store unavailable, channel history won't persist: {e}");
+                None
+            }
+        },
     });
 
     run_message_dispatch_loop(rx, runtime_ctx, max_in_flight_messages).await;
@@ -2231,6 +2518,9 @@ mod tests {
             auto_save_memory: false,
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
+            memory_isolation: MemoryIsolation::Shared,
+            response_cache: None,
+            approval: None,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
@@ -2241,6 +2531,7 @@ mod tests {
             workspace_dir: Arc::new(std::env::temp_dir()),
             security: Arc::new(SecurityPolicy::default()),
             chain: Arc::new(InterventionChain::new()),
+            sessions: None,
         });
 
         process_channel_message(
@@ -2252,6 +2543,7 @@ mod tests {
                 content: "What is the BTC price now?".to_string(),
                 channel: "test-channel".to_string(),
                 timestamp: 1,
+                attachments: vec![],
             },
         )
         .await;
@@ -2264,6 +2556,111 @@ mod tests {
         assert!(!sent_messages[0].contains("mock_price"));
     }
 
+    #[tokio::test]
+    async fn dispatch_loop_routes_tool_approval_to_operator_chat() {
+        let channel_impl = Arc::new(RecordingChannel::default());
+        let channel: Arc<dyn Channel> = channel_impl.clone();
+
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), Arc::clone(&channel));
+
+        let queue = Arc::new(ApprovalQueue::new());
+        let remote = RemoteApprover::new(channel, "ops-room", Arc::clone(&queue));
+        let approval = Arc::new(
+            ApprovalManager::from_config(&crate::config::AutonomyConfig::default())
+                .with_queue(queue, Duration::from_secs(5))
+                .with_remote(Arc::new(remote)),
+        );
+
+        let runtime_ctx = Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider: Arc::new(ToolCallingProvider),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![Box::new(MockPriceTool)]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("test-system-prompt".to_string()),
+            model: Arc::new("test-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            max_tool_iterations: 10,
+            min_relevance_score: 0.0,
+            memory_isolation: MemoryIsolation::Shared,
+            response_cache: None,
+            approval: Some(Arc::clone(&approval)),
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            security: Arc::new(SecurityPolicy::default()),
+            chain: Arc::new(InterventionChain::new()),
+            sessions: None,
+        });
+
+        let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(4);
+        // One in-flight slot: the operator reply must not need a worker.
+        let dispatch = tokio::spawn(run_message_dispatch_loop(rx, runtime_ctx, 1));
+        tx.send(traits::ChannelMessage {
+            id: "msg-1".to_string(),
+            sender: "alice".to_string(),
+            reply_target: "chat-42".to_string(),
+            content: "What is the BTC price now?".to_string(),
+            channel: "test-channel".to_string(),
+            timestamp: 1,
+            attachments: vec![],
+        })
+        .await
+        .unwrap();
+
+        let mut request_id = None;
+        for _ in 0..200 {
+            let sent = channel_impl.sent_messages.lock().await;
+            request_id = sent
+                .iter()
+                .find(|m| m.starts_with("ops-room:") && m.contains("mock_price"))
+                .and_then(|m| m.split_once('[')?.1.split_once(']'))
+                .map(|(id, _)| id.to_string());
+            drop(sent);
+            if request_id.is_some() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let request_id = request_id.expect("approval request posted to operator chat");
+
+        tx.send(traits::ChannelMessage {
+            id: "msg-2".to_string(),
+            sender: "root".to_string(),
+            reply_target: "ops-room".to_string(),
+            content: format!("approve {request_id}"),
+            channel: "test-channel".to_string(),
+            timestamp: 2,
+            attachments: vec![],
+        })
+        .await
+        .unwrap();
+        drop(tx);
+        dispatch.await.unwrap();
+
+        let sent = channel_impl.sent_messages.lock().await;
+        assert!(sent
+            .iter()
+            .any(|m| m.starts_with("ops-room:Approved: `mock_price`")));
+        assert!(sent
+            .iter()
+            .any(|m| m.starts_with("chat-42:") && m.contains("BTC is currently around")));
+
+        let log = approval.audit_log();
+        assert_eq!(log.len(), 1);
+        assert_eq!(log[0].decision, crate::approval::ApprovalResponse::Yes);
+        assert_eq!(log[0].channel, "test-channel");
+        assert_eq!(log[0].responder.as_deref(), Some("test-channel:root"));
+    }
+
     #[tokio::test]
     async fn process_channel_message_executes_tool_calls_with_alias_tags() {
         let channel_impl = Arc::new(RecordingChannel::default());
@@ -2285,6 +2682,9 @@ mod tests {
             auto_save_memory: false,
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
+            memory_isolation: MemoryIsolation::Shared,
+            response_cache: None,
+            approval: None,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
@@ -2295,6 +2695,7 @@ mod tests {
             workspace_dir: Arc::new(std::env::temp_dir()),
             security: Arc::new(SecurityPolicy::default()),
             chain: Arc::new(InterventionChain::new()),
+            sessions: None,
         });
 
         process_channel_message(
@@ -2306,6 +2707,7 @@ mod tests {
                 content: "What is the BTC price now?".to_string(),
                 channel: "test-channel".to_string(),
                 timestamp: 2,
+                attachments: vec![],
             },
         )
         .await;
@@ -2348,6 +2750,9 @@ mod tests {
             auto_save_memory: false,
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
+            memory_isolation: MemoryIsolation::Shared,
+            response_cache: None,
+            approval: None,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
@@ -2358,6 +2763,7 @@ mod tests {
             workspace_dir: Arc::new(std::env::temp_dir()),
             security: Arc::new(SecurityPolicy::default()),
             chain: Arc::new(InterventionChain::new()),
+            sessions: None,
         });
 
         process_channel_message(
@@ -2369,6 +2775,7 @@ mod tests {
                 content: "/models openrouter".to_string(),
                 channel: "telegram".to_string(),
                 timestamp: 1,
+                attachments: vec![],
             },
         )
         .await;
@@ -2432,6 +2839,9 @@ mod tests {
             auto_save_memory: false,
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
+            memory_isolation: MemoryIsolation::Shared,
+            response_cache: None,
+            approval: None,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
             route_overrides: Arc::new(Mutex::new(route_overrides)),
@@ -2442,6 +2852,7 @@ mod tests {
             workspace_dir: Arc::new(std::env::temp_dir()),
             security: Arc::new(SecurityPolicy::default()),
             chain: Arc::new(InterventionChain::new()),
+            sessions: None,
         });
 
         process_channel_message(
@@ -2453,6 +2864,7 @@ mod tests {
                 content: "hello routed provider".to_string(),
                 channel: "telegram".to_string(),
                 timestamp: 2,
+                attachments: vec![],
             },
         )
         .await;
@@ -2492,6 +2904,9 @@ mod tests {
             auto_save_memory: false,
             max_tool_iterations: 12,
             min_relevance_score: 0.0,
+            memory_isolation: MemoryIsolation::Shared,
+            response_cache: None,
+            approval: None,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
@@ -2502,6 +2917,7 @@ mod tests {
             workspace_dir: Arc::new(std::env::temp_dir()),
             security: Arc::new(SecurityPolicy::default()),
             chain: Arc::new(InterventionChain::new()),
+            sessions: None,
         });
 
         process_channel_message(
@@ -2513,6 +2929,7 @@ mod tests {
                 content: "Loop until done".to_string(),
                 channel: "test-channel".to_string(),
                 timestamp: 1,
+                attachments: vec![],
             },
         )
         .await;
@@ -2547,6 +2964,9 @@ mod tests {
             auto_save_memory: false,
             max_tool_iterations: 3,
             min_relevance_score: 0.0,
+            memory_isolation: MemoryIsolation::Shared,
+            response_cache: None,
+            approval: None,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
@@ -2557,6 +2977,7 @@ mod tests {
             workspace_dir: Arc::new(std::env::temp_dir()),
             security: Arc::new(SecurityPolicy::default()),
             chain: Arc::new(InterventionChain::new()),
+            sessions: None,
         });
 
         process_channel_message(
@@ -2568,6 +2989,7 @@ mod tests {
                 content: "Loop forever".to_string(),
                 channel: "test-channel".to_string(),
                 timestamp: 2,
+                attachments: vec![],
             },
         )
         .await;
@@ -2653,6 +3075,9 @@ mod tests {
             auto_save_memory: false,
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
+            memory_isolation: MemoryIsolation::Shared,
+            response_cache: None,
+            approval: None,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
@@ -2663,6 +3088,7 @@ mod tests {
             workspace_dir: Arc::new(std::env::temp_dir()),
             security: Arc::new(SecurityPolicy::default()),
             chain: Arc::new(InterventionChain::new()),
+            sessions: None,
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(4);
@@ -2673,6 +3099,7 @@ mod tests {
             content: "hello".to_string(),
             channel: "test-channel".to_string(),
             timestamp: 1,
+            attachments: vec![],
         })
         .await
         .unwrap();
@@ -2683,6 +3110,7 @@ mod tests {
             content: "world".to_string(),
             channel: "test-channel".to_string(),
             timestamp: 2,
+            attachments: vec![],
         })
         .await
         .unwrap();
@@ -2725,6 +3153,9 @@ mod tests {
             auto_save_memory: false,
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
+            memory_isolation: MemoryIsolation::Shared,
+            response_cache: None,
+            approval: None,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
@@ -2735,6 +3166,7 @@ mod tests {
             workspace_dir: Arc::new(std::env::temp_dir()),
             security: Arc::new(SecurityPolicy::default()),
             chain: Arc::new(InterventionChain::new()),
+            sessions: None,
         });
 
         process_channel_message(
@@ -2746,6 +3178,7 @@ mod tests {
                 content: "hello".to_string(),
                 channel: "test-channel".to_string(),
                 timestamp: 1,
+                attachments: vec![],
             },
         )
         .await;
@@ -3006,6 +3439,7 @@ mod tests {
             content: "hello".into(),
             channel: "slack".into(),
             timestamp: 1,
+            attachments: vec![],
         };
 
         assert_eq!(conversation_memory_key(&msg), "slack_U123_msg_abc123");
@@ -3020,6 +3454,7 @@ mod tests {
             content: "first".into(),
             channel: "slack".into(),
             timestamp: 1,
+            attachments: vec![],
         };
         let msg2 = traits::ChannelMessage {
             id: "msg_2".into(),
@@ -3028,6 +3463,7 @@ mod tests {
             content: "second".into(),
             channel: "slack".into(),
             timestamp: 2,
+            attachments: vec![],
         };
 
         assert_ne!(
@@ -3048,6 +3484,7 @@ mod tests {
             content: "I'm Paul".into(),
             channel: "slack".into(),
             timestamp: 1,
+            attachments: vec![],
         };
         let msg2 = traits::ChannelMessage {
             id: "msg_2".into(),
@@ -3056,6 +3493,7 @@ mod tests {
             content: "I'm 45".into(),
             channel: "slack".into(),
             timestamp: 2,
+            attachments: vec![],
         };
 
         mem.store(
@@ -3117,6 +3555,9 @@ mod tests {
             auto_save_memory: false,
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
+            memory_isolation: MemoryIsolation::Shared,
+            response_cache: None,
+            approval: None,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
             route_overrides: Arc::new(Mutex::new(HashMap::new())),
@@ -3127,6 +3568,7 @@ mod tests {
             workspace_dir: Arc::new(std::env::temp_dir()),
             security: Arc::new(SecurityPolicy::default()),
             chain: Arc::new(InterventionChain::new()),
+            sessions: None,
         });
 
         process_channel_message(
@@ -3138,6 +3580,7 @@ mod tests {
                 content: "hello".to_string(),
                 channel: "test-channel".to_string(),
                 timestamp: 1,
+                attachments: vec![],
             },
         )
         .await;
@@ -3151,6 +3594,7 @@ mod tests {
                 content: "follow up".to_string(),
                 channel: "test-channel".to_string(),
                 timestamp: 2,
+                attachments: vec![],
             },
         )
         .await;
@@ -3173,6 +3617,593 @@ mod tests {
         assert!(calls[1][3].1.contains("follow up"));
     }
 
+    #[tokio::test]
+    async fn process_channel_message_answers_repeat_openers_from_response_cache() {
+        let channel_impl = Arc::new(RecordingChannel::default());
+        let channel: Arc<dyn Channel> = channel_impl.clone();
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+
+        let provider_impl = Arc::new(HistoryCaptureProvider::default());
+        let tmp = TempDir::new().unwrap();
+        let cache = Arc::new(ResponseCache::new(tmp.path(), 60, 100).unwrap());
+        let runtime_ctx = Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider: provider_impl.clone(),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("test-system-prompt".to_string()),
+            model: Arc::new("test-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            max_tool_iterations: 5,
+            min_relevance_score: 0.0,
+            memory_isolation: MemoryIsolation::Shared,
+            response_cache: Some(cache.clone()),
+            approval: None,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            security: Arc::new(SecurityPolicy::default()),
+            chain: Arc::new(InterventionChain::new()),
+            sessions: None,
+        });
+
+        for (id, sender, content) in [
+            ("msg-1", "alice", "what are your opening hours"),
+            ("msg-2", "bob", "what are your opening hours"),
+            ("msg-3", "bob", "what are your opening hours"),
+        ] {
+            process_channel_message(
+                runtime_ctx.clone(),
+                traits::ChannelMessage {
+                    id: id.to_string(),
+                    sender: sender.to_string(),
+                    reply_target: format!("chat-{sender}"),
+                    content: content.to_string(),
+                    channel: "test-channel".to_string(),
+                    timestamp: 1,
+                    attachments: vec![],
+                },
+            )
+            .await;
+        }
+
+        // Bob's opener is served from cache; his follow-up carries history.
+        {
+            let calls = provider_impl
+                .calls
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            assert_eq!(calls.len(), 2);
+            assert_eq!(calls[1].len(), 4);
+        }
+
+        let sent = channel_impl.sent_messages.lock().await;
+        assert_eq!(sent.len(), 3);
+        assert!(sent[1].contains("response-1"));
+        assert!(sent[2].contains("response-2"));
+
+        let stats = cache.stats().unwrap();
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 1);
+    }
+
+    #[tokio::test]
+    async fn process_channel_message_does_not_share_cached_replies_across_namespaces() {
+        let channel: Arc<dyn Channel> = Arc::new(RecordingChannel::default());
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+
+        let provider_impl = Arc::new(HistoryCaptureProvider::default());
+        let tmp = TempDir::new().unwrap();
+        let cache = Arc::new(ResponseCache::new(tmp.path(), 60, 100).unwrap());
+        let runtime_ctx = Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider: provider_impl.clone(),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("test-system-prompt".to_string()),
+            model: Arc::new("test-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            max_tool_iterations: 5,
+            min_relevance_score: 0.0,
+            memory_isolation: MemoryIsolation::PerSender,
+            response_cache: Some(cache.clone()),
+            approval: None,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            security: Arc::new(SecurityPolicy::default()),
+            chain: Arc::new(InterventionChain::new()),
+            sessions: None,
+        });
+
+        for (id, sender) in [("msg-1", "alice"), ("msg-2", "bob")] {
+            process_channel_message(
+                runtime_ctx.clone(),
+                traits::ChannelMessage {
+                    id: id.to_string(),
+                    sender: sender.to_string(),
+                    reply_target: format!("chat-{sender}"),
+                    content: "what do you remember about me".to_string(),
+                    channel: "test-channel".to_string(),
+                    timestamp: 1,
+                    attachments: vec![],
+                },
+            )
+            .await;
+        }
+
+        // Each sender's opener goes to the provider.
+        let calls = provider_impl
+            .calls
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        assert_eq!(calls.len(), 2);
+        let stats = cache.stats().unwrap();
+        assert_eq!(stats.hits, 0);
+        assert_eq!(stats.misses, 2);
+    }
+
+    #[test]
+    fn response_cache_scope_covers_namespace_and_memory_context() {
+        let alice = response_cache_scope("sys", Some("telegram:alice"), "");
+        assert_ne!(alice, response_cache_scope("sys", Some("telegram:bob"), ""));
+        assert_ne!(alice, response_cache_scope("sys", None, ""));
+        assert_ne!(
+            alice,
+            response_cache_scope("sys", Some("telegram:alice"), "[Memory context]\n- a: b\n")
+        );
+    }
+
+    #[tokio::test]
+    async fn process_channel_message_keeps_memories_per_sender() {
+        let channel: Arc<dyn Channel> = Arc::new(RecordingChannel::default());
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+
+        let provider_impl = Arc::new(HistoryCaptureProvider::default());
+        let tmp = TempDir::new().unwrap();
+        let runtime_ctx = Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider: provider_impl.clone(),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(SqliteMemory::new(tmp.path()).unwrap()),
+            tools_registry: Arc::new(vec![]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("test-system-prompt".to_string()),
+            model: Arc::new("test-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: true,
+            max_tool_iterations: 5,
+            min_relevance_score: 0.0,
+            memory_isolation: MemoryIsolation::PerSender,
+            response_cache: None,
+            approval: None,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            security: Arc::new(SecurityPolicy::default()),
+            chain: Arc::new(InterventionChain::new()),
+            sessions: None,
+        });
+
+        for (id, sender, content) in [
+            ("msg-1", "alice", "my locker code is 4711"),
+            ("msg-2", "bob", "what is the locker code"),
+            ("msg-3", "alice", "remind me of my locker code"),
+        ] {
+            process_channel_message(
+                runtime_ctx.clone(),
+                traits::ChannelMessage {
+                    id: id.to_string(),
+                    sender: sender.to_string(),
+                    reply_target: format!("chat-{sender}"),
+                    content: content.to_string(),
+                    channel: "test-channel".to_string(),
+                    timestamp: 1,
+                    attachments: vec![],
+                },
+            )
+            .await;
+        }
+
+        let calls = provider_impl
+            .calls
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        assert_eq!(calls.len(), 3);
+        assert!(calls[1].iter().all(|(_, content)| !content.contains("4711")));
+        let alice_turn = &calls[2].last().unwrap().1;
+        assert!(alice_turn.contains("[Memory context]"));
+        assert!(alice_turn.contains("4711"));
+    }
+
+    struct StreamingToolProvider;
+
+    #[async_trait::async_trait]
+    impl Provider for StreamingToolProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            Ok(tool_call_payload())
+        }
+
+        async fn stream_chat(
+            &self,
+            request: providers::ChatRequest<'_>,
+            _model: &str,
+            _temperature: f64,
+            on_delta: &tokio::sync::mpsc::Sender<String>,
+        ) -> anyhow::Result<providers::ChatResponse> {
+            let has_tool_results = request
+                .messages
+                .iter()
+                .any(|msg| msg.role == "user" && msg.content.contains("[Tool results]"));
+            let deltas: Vec<String> = if has_tool_results {
+                vec!["BTC is ".into(), "around ".into(), "$65,000.".into()]
+            } else {
+                vec![
+                    "Checking prices.\n".into(),
+                    "<tool".into(),
+                    tool_call_payload()["<tool".len()..].to_string(),
+                ]
+            };
+            for delta in &deltas {
+                on_delta.send(delta.clone()).await?;
+            }
+            Ok(providers::ChatResponse {
+                text: Some(deltas.concat()),
+                tool_calls: vec![],
+            })
+        }
+    }
+
+    #[derive(Default)]
+    struct DraftRecordingChannel {
+        draft_updates: tokio::sync::Mutex<Vec<String>>,
+        finalized: tokio::sync::Mutex<Option<String>>,
+        sent_messages: tokio::sync::Mutex<Vec<String>>,
+    }
+
+    #[async_trait::async_trait]
+    impl Channel for DraftRecordingChannel {
+        fn name(&self) -> &str {
+            "test-channel"
+        }
+
+        async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+            self.sent_messages
+                .lock()
+                .await
+                .push(message.content.clone());
+            Ok(())
+        }
+
+        async fn listen(
+            &self,
+            _tx: tokio::sync::mpsc::Sender<traits::ChannelMessage>,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        fn supports_draft_updates(&self) -> bool {
+            true
+        }
+
+        async fn send_draft(&self, _message: &SendMessage) -> anyhow::Result<Option<String>> {
+            Ok(Some("draft-1".to_string()))
+        }
+
+        async fn update_draft(
+            &self,
+            _recipient: &str,
+            _message_id: &str,
+            text: &str,
+        ) -> anyhow::Result<()> {
+            self.draft_updates.lock().await.push(text.to_string());
+            Ok(())
+        }
+
+        async fn finalize_draft(
+            &self,
+            _recipient: &str,
+            _message_id: &str,
+            text: &str,
+        ) -> anyhow::Result<()> {
+            *self.finalized.lock().await = Some(text.to_string());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn process_channel_message_streams_tokens_to_draft_without_tool_call_markup() {
+        let channel_impl = Arc::new(DraftRecordingChannel::default());
+        let channel: Arc<dyn Channel> = channel_impl.clone();
+
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+
+        let runtime_ctx = Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider: Arc::new(StreamingToolProvider),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![Box::new(MockPriceTool)]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("test-system-prompt".to_string()),
+            model: Arc::new("test-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            max_tool_iterations: 10,
+            min_relevance_score: 0.0,
+            memory_isolation: MemoryIsolation::Shared,
+            response_cache: None,
+            approval: None,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            security: Arc::new(SecurityPolicy::default()),
+            chain: Arc::new(InterventionChain::new()),
+            sessions: None,
+        });
+
+        process_channel_message(
+            runtime_ctx,
+            traits::ChannelMessage {
+                id: "msg-1".to_string(),
+                sender: "alice".to_string(),
+                reply_target: "chat-42".to_string(),
+                content: "What is the BTC price now?".to_string(),
+                channel: "test-channel".to_string(),
+                timestamp: 1,
+                attachments: vec![],
+            },
+        )
+        .await;
+
+        let updates = channel_impl.draft_updates.lock().await;
+        assert!(!updates.is_empty());
+        assert!(updates
+            .iter()
+            .all(|update| !update.contains("tool_call") && !update.contains("mock_price")));
+        assert_eq!(
+            updates.last().map(String::as_str),
+            Some("Checking prices.\n\n\nBTC is around $65,000.")
+        );
+        assert_eq!(
+            channel_impl.finalized.lock().await.as_deref(),
+            Some("BTC is around $65,000.")
+        );
+        assert!(channel_impl.sent_messages.lock().await.is_empty());
+    }
+
+    struct VisionRecordingProvider {
+        vision: bool,
+        requests: Mutex<Vec<Vec<ChatMessage>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl Provider for VisionRecordingProvider {
+        fn capabilities(&self) -> providers::traits::ProviderCapabilities {
+            providers::traits::ProviderCapabilities {
+                native_tool_calling: false,
+                vision: self.vision,
+            }
+        }
+
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            Ok("A red square.".to_string())
+        }
+
+        async fn chat_with_history(
+            &self,
+            messages: &[ChatMessage],
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            self.requests
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push(messages.to_vec());
+            Ok("A red square.".to_string())
+        }
+    }
+
+    async fn run_image_message(vision: bool) -> Vec<ChatMessage> {
+        let channel: Arc<dyn Channel> = Arc::new(RecordingChannel::default());
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+
+        let provider = Arc::new(VisionRecordingProvider {
+            vision,
+            requests: Mutex::new(Vec::new()),
+        });
+        let runtime_ctx = Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider: provider.clone(),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("test-system-prompt".to_string()),
+            model: Arc::new("test-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            max_tool_iterations: 5,
+            min_relevance_score: 0.0,
+            memory_isolation: MemoryIsolation::Shared,
+            response_cache: None,
+            approval: None,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            security: Arc::new(SecurityPolicy::default()),
+            chain: Arc::new(InterventionChain::new()),
+            sessions: None,
+        });
+
+        process_channel_message(
+            runtime_ctx,
+            traits::ChannelMessage {
+                id: "msg-img".to_string(),
+                sender: "alice".to_string(),
+                reply_target: "chat-42".to_string(),
+                content: "What is in this picture?".to_string(),
+                channel: "test-channel".to_string(),
+                timestamp: 1,
+                attachments: vec![traits::MediaAttachment {
+                    mime_type: "image/png".to_string(),
+                    data: "iVBORw0KGgo=".to_string(),
+                    file_name: Some("square.png".to_string()),
+                }],
+            },
+        )
+        .await;
+
+        let mut requests = provider.requests.lock().unwrap_or_else(|e| e.into_inner());
+        assert_eq!(requests.len(), 1);
+        requests.remove(0)
+    }
+
+    #[tokio::test]
+    async fn process_channel_message_forwards_image_attachments_to_vision_provider() {
+        let messages = run_image_message(true).await;
+        let user = messages.iter().rfind(|m| m.role == "user").unwrap();
+
+        assert_eq!(user.content, "What is in this picture?");
+        assert_eq!(
+            user.images().collect::<Vec<_>>(),
+            vec![("image/png", "iVBORw0KGgo=")]
+        );
+    }
+
+    #[tokio::test]
+    async fn process_channel_message_replaces_images_with_note_without_vision() {
+        let messages = run_image_message(false).await;
+        let user = messages.iter().rfind(|m| m.role == "user").unwrap();
+
+        assert!(user.parts.is_empty());
+        assert!(user.content.starts_with("What is in this picture?\n"));
+        assert!(user.content.contains("1 image attachment(s) omitted"));
+    }
+
+    #[tokio::test]
+    async fn process_channel_message_restores_history_from_session_store_after_restart() {
+        let tmp = TempDir::new().unwrap();
+        let store = Arc::new(SessionStore::open(tmp.path(), 200).unwrap());
+        let provider_impl = Arc::new(HistoryCaptureProvider::default());
+
+        let runtime_ctx = |channel: Arc<dyn Channel>| {
+            let mut channels_by_name = HashMap::new();
+            channels_by_name.insert(channel.name().to_string(), channel);
+            Arc::new(ChannelRuntimeContext {
+                channels_by_name: Arc::new(channels_by_name),
+                provider: provider_impl.clone(),
+                default_provider: Arc::new("test-provider".to_string()),
+                memory: Arc::new(NoopMemory),
+                tools_registry: Arc::new(vec![]),
+                observer: Arc::new(NoopObserver),
+                system_prompt: Arc::new("test-system-prompt".to_string()),
+                model: Arc::new("test-model".to_string()),
+                temperature: 0.0,
+                auto_save_memory: false,
+                max_tool_iterations: 5,
+                min_relevance_score: 0.0,
+                memory_isolation: MemoryIsolation::Shared,
+                response_cache: None,
+                approval: None,
+                conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+                provider_cache: Arc::new(Mutex::new(HashMap::new())),
+                route_overrides: Arc::new(Mutex::new(HashMap::new())),
+                api_key: None,
+                api_url: None,
+                reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+                provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+                workspace_dir: Arc::new(std::env::temp_dir()),
+                security: Arc::new(SecurityPolicy::default()),
+                chain: Arc::new(InterventionChain::new()),
+                sessions: Some(Arc::clone(&store)),
+            })
+        };
+        let message = |id: &str, content: &str| traits::ChannelMessage {
+            id: id.to_string(),
+            sender: "alice".to_string(),
+            reply_target: "chat-1".to_string(),
+            content: content.to_string(),
+            channel: "test-channel".to_string(),
+            timestamp: 1,
+            attachments: vec![],
+        };
+
+        process_channel_message(
+            runtime_ctx(Arc::new(RecordingChannel::default())),
+            message("msg-a", "hello"),
+        )
+        .await;
+
+        // Fresh runtime context = empty in-memory history, as after a daemon restart.
+        process_channel_message(
+            runtime_ctx(Arc::new(RecordingChannel::default())),
+            message("msg-b", "follow up"),
+        )
+        .await;
+
+        let calls = provider_impl
+            .calls
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[1].len(), 4);
+        assert!(calls[1][1].1.contains("hello"));
+        assert!(calls[1][2].1.contains("response-1"));
+        assert!(calls[1][3].1.contains("follow up"));
+
+        let session = store.latest_for("test-channel", "alice").unwrap().unwrap();
+        assert_eq!(session.message_count, 4);
+    }
+
     // ── AIEOS Identity Tests (Issue #168) ─────────────────────────
 
     #[test]
@@ -366,6 +366,7 @@ impl Channel for QQChannel {
                                     .duration_since(std::time::UNIX_EPOCH)
                                     .unwrap_or_default()
                                     .as_secs(),
+                                attachments: vec![],
                             };
 
                             if tx.send(channel_msg).await.is_err() {
@@ -404,6 +405,7 @@ impl Channel for QQChannel {
                                     .duration_since(std::time::UNIX_EPOCH)
                                     .unwrap_or_default()
                                     .as_secs(),
+                                attachments: vec![],
                             };
 
                             if tx.send(channel_msg).await.is_err() {
@@ -259,6 +259,7 @@ impl SignalChannel {
             content: text.to_string(),
             channel: "signal".to_string(),
             timestamp: timestamp / 1000, // millis → secs
+            attachments: vec![],
         })
     }
 }
@@ -1,4 +1,4 @@
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::traits::{Channel, ChannelMessage, OutgoingAttachment, SendMessage};
 use async_trait::async_trait;
 
 /// Slack channel — polls conversations.history via Web API
@@ -45,6 +45,46 @@ impl SlackChannel {
             .and_then(|u| u.as_str())
             .map(String::from)
     }
+
+    /// Send a request to a Slack Web API method and return the parsed body.
+    async fn call_api(
+        &self,
+        method: &str,
+        request: reqwest::RequestBuilder,
+    ) -> anyhow::Result<serde_json::Value> {
+        let resp = request.bearer_auth(&self.bot_token).send().await?;
+        let status = resp.status();
+        let body = resp
+            .text()
+            .await
+            .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
+        parse_api_response(method, status, &body)
+    }
+}
+
+/// Validate a Slack Web API response.
+///
+/// Slack returns 200 for most app-level errors, so the JSON "ok" field is
+/// checked as well as the HTTP status.
+fn parse_api_response(
+    method: &str,
+    status: reqwest::StatusCode,
+    body: &str,
+) -> anyhow::Result<serde_json::Value> {
+    if !status.is_success() {
+        anyhow::bail!("Slack {method} failed ({status}): {body}");
+    }
+
+    let parsed: serde_json::Value = serde_json::from_str(body).unwrap_or_default();
+    if parsed.get("ok") == Some(&serde_json::Value::Bool(false)) {
+        let err = parsed
+            .get("error")
+            .and_then(|e| e.as_str())
+            .unwrap_or("unknown");
+        anyhow::bail!("Slack {method} failed: {err}");
+    }
+
+    Ok(parsed)
 }
 
 #[async_trait]
@@ -54,39 +94,91 @@ impl Channel for SlackChannel {
     }
 
     async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
-        let body = serde_json::json!({
-            "channel": message.recipient,
-            "text": message.content
-        });
+        if message.has_text() {
+            let body = serde_json::json!({
+                "channel": message.recipient,
+                "text": message.content
+            });
+
+            self.call_api(
+                "chat.postMessage",
+                self.http_client()
+                    .post("https://slack.com/api/chat.postMessage")
+                    .json(&body),
+            )
+            .await?;
+        }
+
+        for attachment in &message.attachments {
+            self.send_file(&message.recipient, attachment, None).await?;
+        }
 
-        let resp = self
+        Ok(())
+    }
+
+    fn supports_files(&self) -> bool {
+        true
+    }
+
+    async fn send_file(
+        &self,
+        recipient: &str,
+        attachment: &OutgoingAttachment,
+        caption: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let file_bytes = tokio::fs::read(&attachment.path).await?;
+        let file_name = attachment.file_name();
+
+        // Slack's external upload flow: reserve an upload URL, push the bytes,
+        // then share the finished file into the channel.
+        let reserved = self
+            .call_api(
+                "files.getUploadURLExternal",
+                self.http_client()
+                    .post("https://slack.com/api/files.getUploadURLExternal")
+                    .form(&[
+                        ("filename", file_name.to_string()),
+                        ("length", file_bytes.len().to_string()),
+                    ]),
+            )
+            .await?;
+        let upload_url = reserved
+            .get("upload_url")
+            .and_then(|u| u.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Slack upload URL missing from response"))?;
+        let file_id = reserved
+            .get("file_id")
+            .and_then(|f| f.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Slack file_id missing from response"))?;
+
+        let upload = self
             .http_client()
-            .post("https://slack.com/api/chat.postMessage")
-            .bearer_auth(&self.bot_token)
-            .json(&body)
+            .post(upload_url)
+            .header(reqwest::header::CONTENT_TYPE, attachment.mime_type.as_str())
+            .body(file_bytes)
             .send()
             .await?;
-
-        let status = resp.status();
-        let body = resp
-            .text()
-            .await
-            .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
-
-        if !status.is_success() {
-            anyhow::bail!("Slack chat.postMessage failed ({status}): {body}");
+        if !upload.status().is_success() {
+            let status = upload.status();
+            anyhow::bail!("Slack file upload failed ({status})");
         }
 
-        // Slack returns 200 for most app-level errors; check JSON "ok" field
-        let parsed: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
-        if parsed.get("ok") == Some(&serde_json::Value::Bool(false)) {
-            let err = parsed
-                .get("error")
-                .and_then(|e| e.as_str())
-                .unwrap_or("unknown");
-            anyhow::bail!("Slack chat.postMessage failed: {err}");
+        let mut complete = serde_json::json!({
+            "files": [{ "id": file_id, "title": file_name }],
+            "channel_id": recipient,
+        });
+        if let Some(caption) = caption {
+            complete["initial_comment"] = serde_json::Value::String(caption.to_string());
         }
 
+        self.call_api(
+            "files.completeUploadExternal",
+            self.http_client()
+                .post("https://slack.com/api/files.completeUploadExternal")
+                .json(&complete),
+        )
+        .await?;
+
         Ok(())
     }
 
@@ -170,6 +262,7 @@ impl Channel for SlackChannel {
                             .duration_since(std::time::UNIX_EPOCH)
                             .unwrap_or_default()
                             .as_secs(),
+                        attachments: vec![],
                     };
 
                     if tx.send(channel_msg).await.is_err() {
@@ -195,6 +288,30 @@ impl Channel for SlackChannel {
 mod tests {
     use super::*;
 
+    #[test]
+    fn parse_api_response_checks_status_and_ok_field() {
+        let ok = parse_api_response(
+            "files.getUploadURLExternal",
+            reqwest::StatusCode::OK,
+            r#"{"ok":true,"file_id":"F1"}"#,
+        )
+        .unwrap();
+        assert_eq!(ok["file_id"], "F1");
+
+        let app_error = parse_api_response(
+            "files.completeUploadExternal",
+            reqwest::StatusCode::OK,
+            r#"{"ok":false,"error":"not_in_channel"}"#,
+        )
+        .unwrap_err();
+        assert!(app_error.to_string().contains("not_in_channel"));
+
+        let http_error =
+            parse_api_response("chat.postMessage", reqwest::StatusCode::BAD_GATEWAY, "oops")
+                .unwrap_err();
+        assert!(http_error.to_string().contains("502"));
+    }
+
     #[test]
     fn slack_channel_name() {
         let ch = SlackChannel::new("xoxb-fake".into(), None, vec![]);
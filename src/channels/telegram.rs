@@ -1,4 +1,6 @@
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::traits::{
+    Channel, ChannelMessage, MediaAttachment, OutgoingAttachment, SendMessage, MAX_ATTACHMENT_BYTES,
+};
 use crate::config::{Config, StreamMode};
 use crate::security::pairing::PairingGuard;
 use anyhow::Context;
@@ -451,6 +453,60 @@ impl TelegramChannel {
         format!("https://api.telegram.org/bot{}/{method}", self.bot_token)
     }
 
+    fn file_url(&self, file_path: &str) -> String {
+        format!(
+            "https://api.telegram.org/file/bot{}/{file_path}",
+            self.bot_token
+        )
+    }
+
+    /// File id of the largest photo size, or of an image sent as a document.
+    fn image_file_id(message: &serde_json::Value) -> Option<&str> {
+        if let Some(sizes) = message.get("photo").and_then(serde_json::Value::as_array) {
+            return sizes.last()?.get("file_id")?.as_str();
+        }
+        let document = message.get("document")?;
+        let is_image = document
+            .get("mime_type")
+            .and_then(serde_json::Value::as_str)
+            .is_some_and(|mime| mime.starts_with("image/"));
+        if is_image {
+            document.get("file_id")?.as_str()
+        } else {
+            None
+        }
+    }
+
+    /// Resolve a file id via `getFile` and download it as an image attachment.
+    async fn download_image(&self, file_id: &str) -> Option<MediaAttachment> {
+        let resp = self
+            .http_client()
+            .get(self.api_url("getFile"))
+            .query(&[("file_id", file_id)])
+            .send()
+            .await
+            .ok()?;
+        let data: serde_json::Value = resp.json().await.ok()?;
+        let file = data.get("result")?;
+        if file
+            .get("file_size")
+            .and_then(serde_json::Value::as_u64)
+            .is_some_and(|size| size > MAX_ATTACHMENT_BYTES as u64)
+        {
+            tracing::warn!("Telegram: skipping image larger than {MAX_ATTACHMENT_BYTES} bytes");
+            return None;
+        }
+        let file_path = file.get("file_path")?.as_str()?;
+        let file_name = file_path.rsplit('/').next().map(str::to_string);
+
+        MediaAttachment::download_image(
+            self.http_client().get(self.file_url(file_path)),
+            None,
+            file_name,
+        )
+        .await
+    }
+
     async fn fetch_bot_username(&self) -> anyhow::Result<String> {
         let resp = self.http_client().get(self.api_url("getMe")).send().await?;
 
@@ -741,7 +797,12 @@ Allowlist Telegram username (without '@') or numeric user ID.",
     fn parse_update_message(&self, update: &serde_json::Value) -> Option<ChannelMessage> {
         let message = update.get("message")?;
 
-        let text = message.get("text").and_then(serde_json::Value::as_str)?;
+        // Photos carry their text in `caption`; an uncaptioned photo is still a message.
+        let text = message
+            .get("text")
+            .or_else(|| message.get("caption"))
+            .and_then(serde_json::Value::as_str)
+            .or_else(|| Self::image_file_id(message).map(|_| ""))?;
 
         let username = message
             .get("from")
@@ -825,6 +886,7 @@ Allowlist Telegram username (without '@') or numeric user ID.",
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap_or_default()
                 .as_secs(),
+            attachments: vec![],
         })
     }
 
@@ -993,14 +1055,34 @@ Allowlist Telegram username (without '@') or numeric user ID.",
             anyhow::bail!("Telegram attachment path not found: {target}");
         }
 
-        match attachment.kind {
-            TelegramAttachmentKind::Image => self.send_photo(chat_id, thread_id, path, None).await,
+        self.send_local_file(chat_id, thread_id, attachment.kind, path, None)
+            .await
+    }
+
+    async fn send_local_file(
+        &self,
+        chat_id: &str,
+        thread_id: Option<&str>,
+        kind: TelegramAttachmentKind,
+        path: &Path,
+        caption: Option<&str>,
+    ) -> anyhow::Result<()> {
+        match kind {
+            TelegramAttachmentKind::Image => {
+                self.send_photo(chat_id, thread_id, path, caption).await
+            }
             TelegramAttachmentKind::Document => {
-                self.send_document(chat_id, thread_id, path, None).await
+                self.send_document(chat_id, thread_id, path, caption).await
+            }
+            TelegramAttachmentKind::Video => {
+                self.send_video(chat_id, thread_id, path, caption).await
+            }
+            TelegramAttachmentKind::Audio => {
+                self.send_audio(chat_id, thread_id, path, caption).await
+            }
+            TelegramAttachmentKind::Voice => {
+                self.send_voice(chat_id, thread_id, path, caption).await
             }
-            TelegramAttachmentKind::Video => self.send_video(chat_id, thread_id, path, None).await,
-            TelegramAttachmentKind::Audio => self.send_audio(chat_id, thread_id, path, None).await,
-            TelegramAttachmentKind::Voice => self.send_voice(chat_id, thread_id, path, None).await,
         }
     }
 
@@ -1652,17 +1734,42 @@ impl Channel for TelegramChannel {
             for attachment in &attachments {
                 self.send_attachment(chat_id, thread_id, attachment).await?;
             }
-
-            return Ok(());
-        }
-
-        if let Some(attachment) = parse_path_only_attachment(&content) {
+        } else if let Some(attachment) = parse_path_only_attachment(&content) {
             self.send_attachment(chat_id, thread_id, &attachment)
                 .await?;
-            return Ok(());
+        } else if message.has_text() {
+            self.send_text_chunks(&content, chat_id, thread_id).await?;
         }
 
-        self.send_text_chunks(&content, chat_id, thread_id).await
+        for attachment in &message.attachments {
+            self.send_file(&message.recipient, attachment, None).await?;
+        }
+
+        Ok(())
+    }
+
+    fn supports_files(&self) -> bool {
+        true
+    }
+
+    async fn send_file(
+        &self,
+        recipient: &str,
+        attachment: &OutgoingAttachment,
+        caption: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let (chat_id, thread_id) = match recipient.split_once(':') {
+            Some((chat, thread)) => (chat, Some(thread)),
+            None => (recipient, None),
+        };
+        let kind = attachment
+            .path
+            .to_str()
+            .and_then(infer_attachment_kind_from_target)
+            .unwrap_or(TelegramAttachmentKind::Document);
+
+        self.send_local_file(chat_id, thread_id, kind, &attachment.path, caption)
+            .await
     }
 
     async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
@@ -1744,10 +1851,16 @@ Ensure only one `zeroclaw` process is using this bot token."
                         offset = uid + 1;
                     }
 
-                    let Some(msg) = self.parse_update_message(update) else {
+                    let Some(mut msg) = self.parse_update_message(update) else {
                         self.handle_unauthorized_message(update).await;
                         continue;
                     };
+                    if let Some(file_id) = update.get("message").and_then(Self::image_file_id) {
+                        msg.attachments.extend(self.download_image(file_id).await);
+                    }
+                    if msg.content.is_empty() && msg.attachments.is_empty() {
+                        continue;
+                    }
                     // Send "typing" indicator immediately when we receive a message
                     let typing_body = serde_json::json!({
                         "chat_id": &msg.reply_target,
@@ -2123,6 +2236,59 @@ mod tests {
         assert_eq!(msg.id, "telegram_-100200300_33");
     }
 
+    #[test]
+    fn parse_update_message_accepts_photo_with_caption() {
+        let ch = TelegramChannel::new("token".into(), vec!["*".into()], false);
+        let update = serde_json::json!({
+            "update_id": 3,
+            "message": {
+                "message_id": 34,
+                "caption": "what is this?",
+                "photo": [
+                    { "file_id": "small", "width": 90, "height": 90 },
+                    { "file_id": "large", "width": 1280, "height": 1280 }
+                ],
+                "from": { "id": 555, "username": "alice" },
+                "chat": { "id": 42 }
+            }
+        });
+
+        let msg = ch
+            .parse_update_message(&update)
+            .expect("photo message should parse");
+        assert_eq!(msg.content, "what is this?");
+        assert_eq!(
+            TelegramChannel::image_file_id(&update["message"]),
+            Some("large")
+        );
+    }
+
+    #[test]
+    fn parse_update_message_accepts_uncaptioned_image_document() {
+        let ch = TelegramChannel::new("token".into(), vec!["*".into()], false);
+        let update = serde_json::json!({
+            "update_id": 4,
+            "message": {
+                "message_id": 35,
+                "document": { "file_id": "doc-1", "mime_type": "image/png" },
+                "from": { "id": 555, "username": "alice" },
+                "chat": { "id": 42 }
+            }
+        });
+
+        let msg = ch
+            .parse_update_message(&update)
+            .expect("image document should parse");
+        assert!(msg.content.is_empty());
+        assert_eq!(
+            TelegramChannel::image_file_id(&update["message"]),
+            Some("doc-1")
+        );
+
+        let pdf = serde_json::json!({ "document": { "file_id": "doc-2", "mime_type": "application/pdf" } });
+        assert!(TelegramChannel::image_file_id(&pdf).is_none());
+    }
+
     #[test]
     fn parse_update_message_allows_numeric_id_without_username() {
         let ch = TelegramChannel::new("token".into(), vec!["555".into()], false);
@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use std::path::{Path, PathBuf};
 
 /// A message received from or sent to a channel
 #[derive(Debug, Clone)]
@@ -9,6 +10,158 @@ pub struct ChannelMessage {
     pub content: String,
     pub channel: String,
     pub timestamp: u64,
+    /// Inbound media (photos, image files) sent with the message
+    pub attachments: Vec<MediaAttachment>,
+}
+
+/// Upper bound on inbound media accepted from a channel (raw bytes)
+pub const MAX_ATTACHMENT_BYTES: usize = 5 * 1024 * 1024;
+
+/// Media received with a channel message, base64-encoded for provider payloads
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MediaAttachment {
+    pub mime_type: String,
+    /// Base64-encoded payload (no `data:` prefix)
+    pub data: String,
+    pub file_name: Option<String>,
+}
+
+impl MediaAttachment {
+    /// Build an image attachment from raw bytes.
+    ///
+    /// The MIME type is sniffed from the payload and falls back to `mime_hint`.
+    /// Returns `None` for empty, oversized, or non-image payloads.
+    pub fn image_from_bytes(
+        bytes: &[u8],
+        mime_hint: Option<&str>,
+        file_name: Option<String>,
+    ) -> Option<Self> {
+        use base64::Engine;
+
+        if bytes.is_empty() || bytes.len() > MAX_ATTACHMENT_BYTES {
+            return None;
+        }
+        let mime_type = sniff_image_mime(bytes)
+            .or_else(|| mime_hint.filter(|m| m.starts_with("image/")))?
+            .to_string();
+        Some(Self {
+            mime_type,
+            data: base64::engine::general_purpose::STANDARD.encode(bytes),
+            file_name,
+        })
+    }
+
+    /// Download an image with a prepared request (auth headers already set).
+    ///
+    /// Failures are logged and yield `None` so a broken attachment never drops
+    /// the text of the message it came with.
+    pub async fn download_image(
+        request: reqwest::RequestBuilder,
+        mime_hint: Option<&str>,
+        file_name: Option<String>,
+    ) -> Option<Self> {
+        let response = match request.send().await {
+            Ok(response) if response.status().is_success() => response,
+            Ok(response) => {
+                tracing::warn!("Attachment download failed: HTTP {}", response.status());
+                return None;
+            }
+            Err(e) => {
+                tracing::warn!("Attachment download failed: {e}");
+                return None;
+            }
+        };
+        if response
+            .content_length()
+            .is_some_and(|len| len > MAX_ATTACHMENT_BYTES as u64)
+        {
+            tracing::warn!("Attachment skipped: larger than {MAX_ATTACHMENT_BYTES} bytes");
+            return None;
+        }
+        let header_mime = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.split(';').next().unwrap_or(v).trim().to_string());
+        let bytes = match response.bytes().await {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                tracing::warn!("Attachment download failed: {e}");
+                return None;
+            }
+        };
+        Self::image_from_bytes(&bytes, mime_hint.or(header_mime.as_deref()), file_name)
+    }
+}
+
+fn sniff_image_mime(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(b"\x89PNG") {
+        Some("image/png")
+    } else if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("image/jpeg")
+    } else if bytes.starts_with(b"GIF8") {
+        Some("image/gif")
+    } else if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        Some("image/webp")
+    } else {
+        None
+    }
+}
+
+/// A local file delivered alongside an outgoing message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutgoingAttachment {
+    pub path: PathBuf,
+    pub mime_type: String,
+}
+
+impl OutgoingAttachment {
+    /// Build an attachment for `path`, guessing the MIME type from its extension
+    pub fn from_path(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let mime_type = mime_from_extension(&path).to_string();
+        Self { path, mime_type }
+    }
+
+    /// File name shown to the recipient
+    pub fn file_name(&self) -> &str {
+        self.path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("file")
+    }
+
+    /// Whether platforms should render this attachment inline as a picture
+    pub fn is_image(&self) -> bool {
+        self.mime_type.starts_with("image/")
+    }
+}
+
+fn mime_from_extension(path: &Path) -> &'static str {
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_default();
+
+    match extension.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "pdf" => "application/pdf",
+        "zip" => "application/zip",
+        "json" => "application/json",
+        "csv" => "text/csv",
+        "html" | "htm" => "text/html",
+        "md" => "text/markdown",
+        "txt" | "log" | "diff" | "patch" => "text/plain",
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "mp4" => "video/mp4",
+        _ => "application/octet-stream",
+    }
 }
 
 /// Message to send through a channel
@@ -17,6 +170,8 @@ pub struct SendMessage {
     pub content: String,
     pub recipient: String,
     pub subject: Option<String>,
+    /// Local files delivered after the text (channels without file support ignore them)
+    pub attachments: Vec<OutgoingAttachment>,
 }
 
 impl SendMessage {
@@ -26,6 +181,7 @@ impl SendMessage {
             content: content.into(),
             recipient: recipient.into(),
             subject: None,
+            attachments: Vec::new(),
         }
     }
 
@@ -39,8 +195,21 @@ impl SendMessage {
             content: content.into(),
             recipient: recipient.into(),
             subject: Some(subject.into()),
+            attachments: Vec::new(),
         }
     }
+
+    /// Attach local files to the message
+    #[must_use]
+    pub fn with_attachments(mut self, attachments: Vec<OutgoingAttachment>) -> Self {
+        self.attachments = attachments;
+        self
+    }
+
+    /// Whether the text part should be sent (false only for attachment-only messages)
+    pub fn has_text(&self) -> bool {
+        self.attachments.is_empty() || !self.content.trim().is_empty()
+    }
 }
 
 /// Core channel trait — implement for any messaging platform
@@ -100,6 +269,21 @@ pub trait Channel: Send + Sync {
     ) -> anyhow::Result<()> {
         Ok(())
     }
+
+    /// Whether this channel can deliver files via [`Channel::send_file`].
+    fn supports_files(&self) -> bool {
+        false
+    }
+
+    /// Upload a local file to the recipient, with an optional caption.
+    async fn send_file(
+        &self,
+        _recipient: &str,
+        _attachment: &OutgoingAttachment,
+        _caption: Option<&str>,
+    ) -> anyhow::Result<()> {
+        anyhow::bail!("{} channel does not support file attachments", self.name())
+    }
 }
 
 #[cfg(test)]
@@ -129,6 +313,7 @@ mod tests {
                 content: "hello".into(),
                 channel: "dummy".into(),
                 timestamp: 123,
+                attachments: vec![],
             })
             .await
             .map_err(|e| anyhow::anyhow!(e.to_string()))
@@ -144,6 +329,7 @@ mod tests {
             content: "ping".into(),
             channel: "dummy".into(),
             timestamp: 999,
+            attachments: vec![],
         };
 
         let cloned = message.clone();
@@ -185,6 +371,47 @@ mod tests {
             .is_ok());
     }
 
+    #[tokio::test]
+    async fn default_send_file_is_unsupported() {
+        let channel = DummyChannel;
+        let attachment = OutgoingAttachment::from_path("report.pdf");
+
+        assert!(!channel.supports_files());
+        let err = channel
+            .send_file("bob", &attachment, None)
+            .await
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("does not support file attachments"));
+    }
+
+    #[test]
+    fn outgoing_attachment_guesses_mime_from_extension() {
+        let image = OutgoingAttachment::from_path("/tmp/shots/Screen.PNG");
+        assert_eq!(image.mime_type, "image/png");
+        assert_eq!(image.file_name(), "Screen.PNG");
+        assert!(image.is_image());
+
+        let report = OutgoingAttachment::from_path("report.pdf");
+        assert_eq!(report.mime_type, "application/pdf");
+        assert!(!report.is_image());
+
+        let unknown = OutgoingAttachment::from_path("blob");
+        assert_eq!(unknown.mime_type, "application/octet-stream");
+    }
+
+    #[test]
+    fn send_message_skips_text_only_for_attachment_only_messages() {
+        assert!(SendMessage::new("", "bob").has_text());
+        assert!(SendMessage::new("hi", "bob")
+            .with_attachments(vec![OutgoingAttachment::from_path("a.txt")])
+            .has_text());
+        assert!(!SendMessage::new("  ", "bob")
+            .with_attachments(vec![OutgoingAttachment::from_path("a.txt")])
+            .has_text());
+    }
+
     #[tokio::test]
     async fn listen_sends_message_to_channel() {
         let channel = DummyChannel;
@@ -197,4 +424,30 @@ mod tests {
         assert_eq!(received.content, "hello");
         assert_eq!(received.channel, "dummy");
     }
+
+    #[test]
+    fn image_from_bytes_sniffs_mime_and_encodes_base64() {
+        let png = b"\x89PNG\r\n\x1a\nrest";
+        let attachment =
+            MediaAttachment::image_from_bytes(png, Some("application/octet-stream"), None)
+                .expect("png should be accepted");
+        assert_eq!(attachment.mime_type, "image/png");
+        assert_eq!(attachment.data, "iVBORw0KGgpyZXN0");
+
+        let hinted = MediaAttachment::image_from_bytes(b"raw", Some("image/heic"), None)
+            .expect("image hint should be trusted when sniffing fails");
+        assert_eq!(hinted.mime_type, "image/heic");
+    }
+
+    #[test]
+    fn image_from_bytes_rejects_non_images_and_oversized_payloads() {
+        assert!(
+            MediaAttachment::image_from_bytes(b"%PDF-1.7", Some("application/pdf"), None).is_none()
+        );
+        assert!(MediaAttachment::image_from_bytes(&[], Some("image/png"), None).is_none());
+
+        let mut oversized = vec![0u8; MAX_ATTACHMENT_BYTES + 1];
+        oversized[..3].copy_from_slice(&[0xFF, 0xD8, 0xFF]);
+        assert!(MediaAttachment::image_from_bytes(&oversized, None, None).is_none());
+    }
 }
@@ -1,4 +1,4 @@
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::traits::{Channel, ChannelMessage, MediaAttachment, SendMessage, MAX_ATTACHMENT_BYTES};
 use async_trait::async_trait;
 use uuid::Uuid;
 
@@ -44,8 +44,68 @@ impl WhatsAppChannel {
         &self.verify_token
     }
 
-    /// Parse an incoming webhook payload from Meta and extract messages
+    /// Parse an incoming webhook payload from Meta and extract text messages
     pub fn parse_webhook_payload(&self, payload: &serde_json::Value) -> Vec<ChannelMessage> {
+        self.parse_webhook_entries(payload)
+            .into_iter()
+            .filter(|(_, media_id)| media_id.is_none())
+            .map(|(msg, _)| msg)
+            .collect()
+    }
+
+    /// Like [`Self::parse_webhook_payload`], but also accepts image messages
+    /// and downloads their media from the Graph API as attachments.
+    pub async fn parse_webhook_payload_with_media(
+        &self,
+        payload: &serde_json::Value,
+    ) -> Vec<ChannelMessage> {
+        let mut messages = Vec::new();
+        for (mut msg, media_id) in self.parse_webhook_entries(payload) {
+            if let Some(media_id) = media_id {
+                msg.attachments.extend(self.download_media(&media_id).await);
+                if msg.content.is_empty() && msg.attachments.is_empty() {
+                    continue;
+                }
+            }
+            messages.push(msg);
+        }
+        messages
+    }
+
+    /// Resolve a media id to its short-lived URL and download the image.
+    async fn download_media(&self, media_id: &str) -> Option<MediaAttachment> {
+        let url = format!("https://graph.facebook.com/v18.0/{media_id}");
+        let resp = self
+            .http_client()
+            .get(&url)
+            .bearer_auth(&self.access_token)
+            .send()
+            .await
+            .ok()?;
+        let meta: serde_json::Value = resp.json().await.ok()?;
+        if meta
+            .get("file_size")
+            .and_then(serde_json::Value::as_u64)
+            .is_some_and(|size| size > MAX_ATTACHMENT_BYTES as u64)
+        {
+            tracing::warn!("WhatsApp: skipping image larger than {MAX_ATTACHMENT_BYTES} bytes");
+            return None;
+        }
+        let media_url = meta.get("url")?.as_str()?;
+        let mime_type = meta.get("mime_type").and_then(serde_json::Value::as_str);
+
+        let request = self
+            .http_client()
+            .get(media_url)
+            .bearer_auth(&self.access_token);
+        MediaAttachment::download_image(request, mime_type, None).await
+    }
+
+    /// Extract messages plus the media id of image messages (content is the caption).
+    fn parse_webhook_entries(
+        &self,
+        payload: &serde_json::Value,
+    ) -> Vec<(ChannelMessage, Option<String>)> {
         let mut messages = Vec::new();
 
         // WhatsApp Cloud API webhook structure:
@@ -90,20 +150,31 @@ impl WhatsAppChannel {
                         continue;
                     }
 
-                    // Extract text content (support text messages only for now)
-                    let content = if let Some(text_obj) = msg.get("text") {
-                        text_obj
+                    // Extract text content; images carry an optional caption
+                    let (content, media_id) = if let Some(text_obj) = msg.get("text") {
+                        let body = text_obj
                             .get("body")
                             .and_then(|b| b.as_str())
                             .unwrap_or("")
-                            .to_string()
+                            .to_string();
+                        (body, None)
+                    } else if let Some(image) = msg.get("image") {
+                        let Some(id) = image.get("id").and_then(|i| i.as_str()) else {
+                            continue;
+                        };
+                        let caption = image
+                            .get("caption")
+                            .and_then(|c| c.as_str())
+                            .unwrap_or("")
+                            .to_string();
+                        (caption, Some(id.to_string()))
                     } else {
-                        // Could be image, audio, etc. — skip for now
-                        tracing::debug!("WhatsApp: skipping non-text message from {from}");
+                        // Could be audio, video, etc. — skip for now
+                        tracing::debug!("WhatsApp: skipping unsupported message from {from}");
                         continue;
                     };
 
-                    if content.is_empty() {
+                    if content.is_empty() && media_id.is_none() {
                         continue;
                     }
 
@@ -119,14 +190,18 @@ impl WhatsAppChannel {
                                 .as_secs()
                         });
 
-                    messages.push(ChannelMessage {
-                        id: Uuid::new_v4().to_string(),
-                        reply_target: normalized_from.clone(),
-                        sender: normalized_from,
-                        content,
-                        channel: "whatsapp".to_string(),
-                        timestamp,
-                    });
+                    messages.push((
+                        ChannelMessage {
+                            id: Uuid::new_v4().to_string(),
+                            reply_target: normalized_from.clone(),
+                            sender: normalized_from,
+                            content,
+                            channel: "whatsapp".to_string(),
+                            timestamp,
+                            attachments: vec![],
+                        },
+                        media_id,
+                    ));
                 }
             }
         }
@@ -348,6 +423,30 @@ mod tests {
         assert!(msgs.is_empty(), "Non-text messages should be skipped");
     }
 
+    #[test]
+    fn whatsapp_parse_entries_keeps_image_media_id_and_caption() {
+        let ch = WhatsAppChannel::new("tok".into(), "123".into(), "ver".into(), vec!["*".into()]);
+        let payload = serde_json::json!({
+            "entry": [{
+                "changes": [{
+                    "value": {
+                        "messages": [{
+                            "from": "1234567890",
+                            "timestamp": "1699999999",
+                            "type": "image",
+                            "image": { "id": "img123", "caption": "what is this?" }
+                        }]
+                    }
+                }]
+            }]
+        });
+
+        let entries = ch.parse_webhook_entries(&payload);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].0.content, "what is this?");
+        assert_eq!(entries[0].1.as_deref(), Some("img123"));
+    }
+
     #[test]
     fn whatsapp_parse_multiple_messages() {
         let ch = WhatsAppChannel::new("tok".into(), "123".into(), "ver".into(), vec!["*".into()]);
@@ -31,6 +31,7 @@ const SUPPORTED_PROXY_SERVICE_KEYS: &[&str] = &[
     "tool.browser",
     "tool.composio",
     "tool.http_request",
+    "tool.mcp",
     "tool.pushover",
     "memory.embeddings",
     "tunnel.custom",
@@ -92,6 +93,12 @@ pub struct Config {
     #[serde(default)]
     pub cron: CronConfig,
 
+    #[serde(default)]
+    pub sessions: SessionsConfig,
+
+    #[serde(default)]
+    pub workflows: WorkflowConfig,
+
     #[serde(default)]
     pub channels_config: ChannelsConfig,
 
@@ -110,9 +117,16 @@ pub struct Config {
     #[serde(default)]
     pub composio: ComposioConfig,
 
+    #[serde(default)]
+    pub mcp: McpConfig,
+
     #[serde(default)]
     pub secrets: SecretsConfig,
 
+    /// Sandboxing, resource limits and audit logging (`[security]`)
+    #[serde(default)]
+    pub security: SecurityConfig,
+
     #[serde(default)]
     pub browser: BrowserConfig,
 
@@ -176,6 +190,13 @@ pub struct DelegateAgentConfig {
     /// Takes precedence over denied_tools.
     #[serde(default)]
     pub allowed_tools: Vec<String>,
+    /// Max tool-call iterations for the sub-agent loop (0 = use `agent.max_tool_iterations`)
+    #[serde(default)]
+    pub max_iterations: usize,
+    /// Memory namespace for the sub-agent (default: `agent:<name>`).
+    /// Agents sharing a namespace share memories; the parent's entries stay hidden.
+    #[serde(default)]
+    pub memory_namespace: Option<String>,
 }
 
 fn default_max_depth() -> u32 {
@@ -514,6 +535,7 @@ impl Default for PeripheralBoardConfig {
 // ── Gateway security ─────────────────────────────────────────────
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct GatewayConfig {
     /// Gateway port (default: 3000)
     #[serde(default = "default_gateway_port")]
@@ -555,6 +577,11 @@ pub struct GatewayConfig {
     /// Maximum distinct idempotency keys retained in memory.
     #[serde(default = "default_gateway_idempotency_max_keys")]
     pub idempotency_max_keys: usize,
+
+    /// Serve the approval API (`/approvals/...`) and hold supervised tool
+    /// calls from gateway and channel turns until they are answered.
+    #[serde(default)]
+    pub approvals_enabled: bool,
 }
 
 fn default_gateway_port() -> u16 {
@@ -603,6 +630,7 @@ impl Default for GatewayConfig {
             rate_limit_max_keys: default_gateway_rate_limit_max_keys(),
             idempotency_ttl_secs: default_idempotency_ttl_secs(),
             idempotency_max_keys: default_gateway_idempotency_max_keys(),
+            approvals_enabled: false,
         }
     }
 }
@@ -636,6 +664,93 @@ impl Default for ComposioConfig {
     }
 }
 
+// ── MCP (Model Context Protocol tool servers) ──────────────────
+
+/// External MCP servers whose tools are exposed to the agent.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct McpConfig {
+    /// Servers to connect at startup (`[[mcp.servers]]`)
+    #[serde(default)]
+    pub servers: Vec<McpServerConfig>,
+}
+
+/// How ZeroClaw talks to an MCP server.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum McpTransportKind {
+    /// Spawn `command` and speak newline-delimited JSON-RPC over stdin/stdout
+    #[default]
+    Stdio,
+    /// Streamable HTTP: POST JSON-RPC to `url`, reply as JSON or SSE
+    Http,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpServerConfig {
+    /// Server name; remote tools are registered as `<name>__<tool>`
+    pub name: String,
+    /// Transport: "stdio" (default) or "http"
+    #[serde(default)]
+    pub transport: McpTransportKind,
+    /// Executable to spawn (stdio transport)
+    #[serde(default)]
+    pub command: Option<String>,
+    /// Arguments passed to `command`
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Extra environment for the child; the parent environment is not inherited
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    /// Working directory, relative to the workspace (default: workspace)
+    #[serde(default)]
+    pub cwd: Option<String>,
+    /// Endpoint URL (http transport)
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Extra HTTP headers, e.g. `Authorization` (http transport)
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// Only expose these remote tools (empty = all)
+    #[serde(default)]
+    pub allowed_tools: Vec<String>,
+    /// Timeout for process start + `initialize` handshake
+    #[serde(default = "default_mcp_startup_timeout_secs")]
+    pub startup_timeout_secs: u64,
+    /// Timeout for each `tools/list` / `tools/call` request
+    #[serde(default = "default_mcp_call_timeout_secs")]
+    pub call_timeout_secs: u64,
+    /// Set to false to keep the entry but skip connecting
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+fn default_mcp_startup_timeout_secs() -> u64 {
+    30
+}
+
+fn default_mcp_call_timeout_secs() -> u64 {
+    60
+}
+
+impl Default for McpServerConfig {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            transport: McpTransportKind::default(),
+            command: None,
+            args: Vec::new(),
+            env: HashMap::new(),
+            cwd: None,
+            url: None,
+            headers: HashMap::new(),
+            allowed_tools: Vec::new(),
+            startup_timeout_secs: default_mcp_startup_timeout_secs(),
+            call_timeout_secs: default_mcp_call_timeout_secs(),
+            enabled: true,
+        }
+    }
+}
+
 // ── Secrets (encrypted credential store) ────────────────────────
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1344,6 +1459,19 @@ impl Default for StorageProviderConfig {
     }
 }
 
+/// How channel conversations are partitioned in memory.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MemoryIsolation {
+    /// One memory for every user and channel
+    #[default]
+    Shared,
+    /// Each sender on each channel gets a private namespace
+    PerSender,
+    /// Each channel (Telegram, Discord, ...) gets a private namespace
+    PerChannel,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(clippy::struct_excessive_bools)]
 pub struct MemoryConfig {
@@ -1365,7 +1493,17 @@ pub struct MemoryConfig {
     /// For sqlite backend: prune conversation rows older than this many days
     #[serde(default = "default_conversation_retention_days")]
     pub conversation_retention_days: u32,
-    /// Embedding provider: "none" | "openai" | "custom:URL"
+    /// Distil new daily/conversation memories into deduplicated core facts
+    /// with the default provider on the hygiene cadence (daemon only).
+    /// While enabled, conversation rows are never pruned before they have
+    /// been consolidated.
+    #[serde(default)]
+    pub consolidation_enabled: bool,
+    /// Model for consolidation (defaults to `default_model`)
+    #[serde(default)]
+    pub consolidation_model: Option<String>,
+    /// Embedding provider: "none" | "openai" | "custom:URL" | "ollama" | "ollama:URL".
+    /// Unknown names fail memory startup instead of disabling semantic recall.
     #[serde(default = "default_embedding_provider")]
     pub embedding_provider: String,
     /// Embedding model name (e.g. "text-embedding-3-small")
@@ -1402,6 +1540,13 @@ pub struct MemoryConfig {
     /// Max number of cached responses before LRU eviction (default: 5000)
     #[serde(default = "default_response_cache_max")]
     pub response_cache_max_entries: usize,
+    /// Also reuse responses to similar prompts, matched by embedding
+    /// (requires `embedding_provider`)
+    #[serde(default)]
+    pub response_cache_semantic: bool,
+    /// Minimum cosine similarity for a semantic cache hit (default: 0.95)
+    #[serde(default = "default_response_cache_similarity")]
+    pub response_cache_similarity_threshold: f64,
 
     // ── Memory Snapshot (soul backup to Markdown) ─────────────
     /// Enable periodic export of core memories to MEMORY_SNAPSHOT.md
@@ -1419,6 +1564,18 @@ pub struct MemoryConfig {
     /// None = wait indefinitely (default). Recommended max: 300.
     #[serde(default)]
     pub sqlite_open_timeout_secs: Option<u64>,
+    /// For sqlite backend: vector recall index. "hnsw" (default) keeps an
+    /// approximate-nearest-neighbour graph in `memory/brain.hnsw`; "flat"
+    /// scans every embedding exactly.
+    #[serde(default = "default_vector_index")]
+    pub vector_index: String,
+
+    // ── Multi-tenant isolation ─────────────────────────────────
+    /// Namespace channel memories by tenant: "shared" (default),
+    /// "per_sender" or "per_channel". Recall, auto-save and the memory
+    /// tools only see the namespace of the message being handled.
+    #[serde(default)]
+    pub isolation: MemoryIsolation,
 }
 
 fn default_embedding_provider() -> String {
@@ -1451,6 +1608,9 @@ fn default_keyword_weight() -> f64 {
 fn default_min_relevance_score() -> f64 {
     0.4
 }
+fn default_vector_index() -> String {
+    "hnsw".into()
+}
 fn default_cache_size() -> usize {
     10_000
 }
@@ -1463,6 +1623,9 @@ fn default_response_cache_ttl() -> u32 {
 fn default_response_cache_max() -> usize {
     5_000
 }
+fn default_response_cache_similarity() -> f64 {
+    0.95
+}
 
 impl Default for MemoryConfig {
     fn default() -> Self {
@@ -1473,6 +1636,8 @@ impl Default for MemoryConfig {
             archive_after_days: default_archive_after_days(),
             purge_after_days: default_purge_after_days(),
             conversation_retention_days: default_conversation_retention_days(),
+            consolidation_enabled: false,
+            consolidation_model: None,
             embedding_provider: default_embedding_provider(),
             embedding_model: default_embedding_model(),
             embedding_dimensions: default_embedding_dims(),
@@ -1484,10 +1649,14 @@ impl Default for MemoryConfig {
             response_cache_enabled: false,
             response_cache_ttl_minutes: default_response_cache_ttl(),
             response_cache_max_entries: default_response_cache_max(),
+            response_cache_semantic: false,
+            response_cache_similarity_threshold: default_response_cache_similarity(),
             snapshot_enabled: false,
             snapshot_on_hygiene: false,
             auto_hydrate: true,
             sqlite_open_timeout_secs: None,
+            vector_index: default_vector_index(),
+            isolation: MemoryIsolation::default(),
         }
     }
 }
@@ -1548,7 +1717,7 @@ pub struct AutonomyConfig {
     /// Regex patterns that trigger an immediate halt (tripwire).
     /// Checked against all input/output content. If any pattern matches,
     /// the agent loop is terminated and the operator is notified.
-    /// Example: ["(?i)rm\s+-rf\s+/", "(?i)DROP\s+TABLE"]
+    /// Example: `["(?i)rm\s+-rf\s+/", "(?i)DROP\s+TABLE"]`
     #[serde(default)]
     pub tripwire_patterns: Vec<String>,
 
@@ -1563,10 +1732,38 @@ pub struct AutonomyConfig {
     /// is likely looping. Range: 0.0-1.0. Default: 0.7.
     #[serde(default = "default_convergence_threshold")]
     pub convergence_threshold: f64,
+
+    /// Channel that receives approval requests from channel conversations
+    /// (e.g. `telegram`, `slack`, `discord`, `matrix`). Unset: tool calls
+    /// from channels are approved automatically.
+    #[serde(default)]
+    pub approval_channel: Option<String>,
+
+    /// Operator conversation on `approval_channel` (chat, channel or room id)
+    /// where requests are posted and replies are accepted.
+    #[serde(default)]
+    pub approval_to: Option<String>,
+
+    /// Seconds to wait for an operator reply before denying the call.
+    #[serde(default = "default_approval_timeout_secs")]
+    pub approval_timeout_secs: u64,
+
+    /// Rule file evaluated against every tool call's arguments
+    /// (allow / deny / require approval). Relative paths resolve against the
+    /// ZeroClaw directory, outside the agent's workspace.
+    #[serde(default)]
+    pub policy_file: Option<String>,
 }
 
-fn default_max_tools_per_turn() -> u32 { 1 }
-fn default_convergence_threshold() -> f64 { 0.7 }
+fn default_max_tools_per_turn() -> u32 {
+    1
+}
+fn default_approval_timeout_secs() -> u64 {
+    120
+}
+fn default_convergence_threshold() -> f64 {
+    0.7
+}
 
 fn default_auto_approve() -> Vec<String> {
     vec!["file_read".into(), "memory_recall".into()]
@@ -1624,6 +1821,10 @@ impl Default for AutonomyConfig {
             tripwire_patterns: Vec::new(),
             max_tools_per_turn: default_max_tools_per_turn(),
             convergence_threshold: default_convergence_threshold(),
+            approval_channel: None,
+            approval_to: None,
+            approval_timeout_secs: default_approval_timeout_secs(),
+            policy_file: None,
         }
     }
 }
@@ -1931,6 +2132,58 @@ impl Default for CronConfig {
     }
 }
 
+// ── Sessions ────────────────────────────────────────────────────
+
+/// Persistent conversation sessions (`workspace/memory/sessions.db`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionsConfig {
+    /// Persist CLI and channel transcripts so conversations survive restarts.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Maximum messages kept per session transcript (oldest are dropped first).
+    #[serde(default = "default_session_max_messages")]
+    pub max_messages: usize,
+}
+
+fn default_session_max_messages() -> usize {
+    200
+}
+
+impl Default for SessionsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_messages: default_session_max_messages(),
+        }
+    }
+}
+
+// ── Workflows ───────────────────────────────────────────────────
+
+/// Declarative workflows (`workspace/workflows/*.yaml|toml`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowConfig {
+    /// Maximum steps of one run executed at the same time.
+    #[serde(default = "default_workflow_max_parallel_steps")]
+    pub max_parallel_steps: usize,
+    /// Allow starting workflows with `POST /workflows/{name}/run` on the gateway.
+    #[serde(default)]
+    pub webhook_enabled: bool,
+}
+
+fn default_workflow_max_parallel_steps() -> usize {
+    4
+}
+
+impl Default for WorkflowConfig {
+    fn default() -> Self {
+        Self {
+            max_parallel_steps: default_workflow_max_parallel_steps(),
+            webhook_enabled: false,
+        }
+    }
+}
+
 // ── Tunnel ──────────────────────────────────────────────────────
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -2085,6 +2338,12 @@ pub struct DiscordConfig {
     /// Other messages in the guild are silently ignored.
     #[serde(default)]
     pub mention_only: bool,
+    /// Streaming mode for progressive response delivery via message edits.
+    #[serde(default)]
+    pub stream_mode: StreamMode,
+    /// Minimum interval (ms) between draft message edits to avoid rate limits.
+    #[serde(default = "default_draft_update_interval_ms")]
+    pub draft_update_interval_ms: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -2450,13 +2709,17 @@ impl Default for Config {
             model_routes: Vec::new(),
             heartbeat: HeartbeatConfig::default(),
             cron: CronConfig::default(),
+            sessions: SessionsConfig::default(),
+            workflows: WorkflowConfig::default(),
             channels_config: ChannelsConfig::default(),
             memory: MemoryConfig::default(),
             storage: StorageConfig::default(),
             tunnel: TunnelConfig::default(),
             gateway: GatewayConfig::default(),
             composio: ComposioConfig::default(),
+            mcp: McpConfig::default(),
             secrets: SecretsConfig::default(),
+            security: SecurityConfig::default(),
             browser: BrowserConfig::default(),
             http_request: HttpRequestConfig::default(),
             web_search: WebSearchConfig::default(),
@@ -3244,6 +3507,12 @@ default_temperature = 0.7
                 auto_approve: vec!["file_read".into()],
                 always_ask: vec![],
                 tripwire_patterns: vec![],
+                max_tools_per_turn: default_max_tools_per_turn(),
+                convergence_threshold: default_convergence_threshold(),
+                approval_channel: None,
+                approval_to: None,
+                approval_timeout_secs: default_approval_timeout_secs(),
+                policy_file: None,
             },
             runtime: RuntimeConfig {
                 kind: "docker".into(),
@@ -3258,6 +3527,8 @@ default_temperature = 0.7
                 interval_minutes: 15,
             },
             cron: CronConfig::default(),
+            sessions: SessionsConfig::default(),
+            workflows: WorkflowConfig::default(),
             channels_config: ChannelsConfig {
                 cli: true,
                 telegram: Some(TelegramConfig {
@@ -3286,7 +3557,9 @@ default_temperature = 0.7
             tunnel: TunnelConfig::default(),
             gateway: GatewayConfig::default(),
             composio: ComposioConfig::default(),
+            mcp: McpConfig::default(),
             secrets: SecretsConfig::default(),
+            security: SecurityConfig::default(),
             browser: BrowserConfig::default(),
             http_request: HttpRequestConfig::default(),
             web_search: WebSearchConfig::default(),
@@ -3420,13 +3693,17 @@ tool_dispatcher = "xml"
             query_classification: QueryClassificationConfig::default(),
             heartbeat: HeartbeatConfig::default(),
             cron: CronConfig::default(),
+            sessions: SessionsConfig::default(),
+            workflows: WorkflowConfig::default(),
             channels_config: ChannelsConfig::default(),
             memory: MemoryConfig::default(),
             storage: StorageConfig::default(),
             tunnel: TunnelConfig::default(),
             gateway: GatewayConfig::default(),
             composio: ComposioConfig::default(),
+            mcp: McpConfig::default(),
             secrets: SecretsConfig::default(),
+            security: SecurityConfig::default(),
             browser: BrowserConfig::default(),
             http_request: HttpRequestConfig::default(),
             web_search: WebSearchConfig::default(),
@@ -3486,6 +3763,8 @@ tool_dispatcher = "xml"
                 max_depth: 3,
                 denied_tools: Vec::new(),
                 allowed_tools: Vec::new(),
+                max_iterations: 0,
+                memory_namespace: None,
             },
         );
 
@@ -3607,6 +3886,8 @@ tool_dispatcher = "xml"
             allowed_users: vec![],
             listen_to_bots: false,
             mention_only: false,
+            stream_mode: StreamMode::default(),
+            draft_update_interval_ms: 1000,
         };
         let json = serde_json::to_string(&dc).unwrap();
         let parsed: DiscordConfig = serde_json::from_str(&json).unwrap();
@@ -3622,6 +3903,8 @@ tool_dispatcher = "xml"
             allowed_users: vec![],
             listen_to_bots: false,
             mention_only: false,
+            stream_mode: StreamMode::default(),
+            draft_update_interval_ms: 1000,
         };
         let json = serde_json::to_string(&dc).unwrap();
         let parsed: DiscordConfig = serde_json::from_str(&json).unwrap();
@@ -4028,6 +4311,7 @@ channel_id = "C123"
             rate_limit_max_keys: 2048,
             idempotency_ttl_secs: 600,
             idempotency_max_keys: 4096,
+            approvals_enabled: true,
         };
         let toml_str = toml::to_string(&g).unwrap();
         let parsed: GatewayConfig = toml::from_str(&toml_str).unwrap();
@@ -4040,6 +4324,7 @@ channel_id = "C123"
         assert_eq!(parsed.rate_limit_max_keys, 2048);
         assert_eq!(parsed.idempotency_ttl_secs, 600);
         assert_eq!(parsed.idempotency_max_keys, 4096);
+        assert!(parsed.approvals_enabled);
     }
 
     #[test]
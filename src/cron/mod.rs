@@ -13,8 +13,8 @@ pub use schedule::{
 };
 #[allow(unused_imports)]
 pub use store::{
-    add_agent_job, add_job, add_shell_job, due_jobs, get_job, list_jobs, list_runs,
-    record_last_run, record_run, remove_job, reschedule_after_run, update_job,
+    add_agent_job, add_job, add_shell_job, add_workflow_job, due_jobs, get_job, list_jobs,
+    list_runs, record_last_run, record_run, remove_job, reschedule_after_run, update_job,
 };
 pub use types::{CronJob, CronJobPatch, CronRun, DeliveryConfig, JobType, Schedule, SessionTarget};
 
@@ -44,7 +44,9 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
                     last_run,
                     last_status,
                 );
-                if !job.command.is_empty() {
+                if job.job_type == JobType::Workflow {
+                    println!("    workflow: {}", job.command);
+                } else if !job.command.is_empty() {
                     println!("    cmd: {}", job.command);
                 }
                 if let Some(prompt) = &job.prompt {
@@ -96,6 +98,23 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
             println!("  Cmd : {}", job.command);
             Ok(())
         }
+        crate::CronCommands::AddWorkflow {
+            expression,
+            tz,
+            workflow,
+        } => {
+            crate::workflow::find_workflow(&config.workspace_dir, &workflow)?;
+            let schedule = Schedule::Cron {
+                expr: expression,
+                tz,
+            };
+            let job = add_workflow_job(config, Some(workflow.clone()), schedule, &workflow)?;
+            println!("✅ Added workflow cron job {}", job.id);
+            println!("  Expr    : {}", job.expression);
+            println!("  Next    : {}", job.next_run.to_rfc3339());
+            println!("  Workflow: {workflow}");
+            Ok(())
+        }
         crate::CronCommands::Remove { id } => remove_job(config, &id),
         crate::CronCommands::Pause { id } => {
             pause_job(config, &id)?;
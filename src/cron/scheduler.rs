@@ -62,6 +62,7 @@ async fn execute_job_with_retry(
         let (success, output) = match job.job_type {
             JobType::Shell => run_job_command(config, security, job).await,
             JobType::Agent => run_agent_job(config, job).await,
+            JobType::Workflow => run_workflow_job(config, job).await,
         };
         last_output = output;
 
@@ -69,6 +70,11 @@ async fn execute_job_with_retry(
             return (true, last_output);
         }
 
+        if job.job_type == JobType::Workflow {
+            // A failed run keeps its state; resume it rather than starting over.
+            return (false, last_output);
+        }
+
         if last_output.starts_with("blocked by security policy:") {
             // Deterministic policy violations are not retryable.
             return (false, last_output);
@@ -131,6 +137,7 @@ async fn run_agent_job(config: &Config, job: &CronJob) -> (bool, String) {
                 model_override,
                 config.default_temperature,
                 vec![],
+                None,
             )
             .await
         }
@@ -149,6 +156,24 @@ async fn run_agent_job(config: &Config, job: &CronJob) -> (bool, String) {
     }
 }
 
+async fn run_workflow_job(config: &Config, job: &CronJob) -> (bool, String) {
+    match start_workflow(config, &job.command).await {
+        Ok(run) => (
+            run.status != crate::workflow::RunStatus::Failed,
+            crate::workflow::describe_run(&run),
+        ),
+        Err(e) => (false, format!("workflow job failed: {e:#}")),
+    }
+}
+
+async fn start_workflow(config: &Config, name: &str) -> Result<crate::workflow::WorkflowRun> {
+    let definition = crate::workflow::find_workflow(&config.workspace_dir, name)?;
+    let engine = crate::workflow::WorkflowEngine::from_config(config)?;
+    engine
+        .start(&definition, &std::collections::HashMap::new(), "cron")
+        .await
+}
+
 async fn persist_job_result(
     config: &Config,
     job: &CronJob,
@@ -102,6 +102,44 @@ pub fn add_agent_job(
     get_job(config, &id)
 }
 
+/// Schedule a workflow run; the workflow name is kept in `command`.
+pub fn add_workflow_job(
+    config: &Config,
+    name: Option<String>,
+    schedule: Schedule,
+    workflow: &str,
+) -> Result<CronJob> {
+    let now = Utc::now();
+    validate_schedule(&schedule, now)?;
+    let next_run = next_run_for_schedule(&schedule, now)?;
+    let id = Uuid::new_v4().to_string();
+    let expression = schedule_cron_expression(&schedule).unwrap_or_default();
+    let schedule_json = serde_json::to_string(&schedule)?;
+
+    with_connection(config, |conn| {
+        conn.execute(
+            "INSERT INTO cron_jobs (
+                id, expression, command, schedule, job_type, prompt, name, session_target, model,
+                enabled, delivery, delete_after_run, created_at, next_run
+             ) VALUES (?1, ?2, ?3, ?4, 'workflow', NULL, ?5, 'isolated', NULL, 1, ?6, 0, ?7, ?8)",
+            params![
+                id,
+                expression,
+                workflow,
+                schedule_json,
+                name,
+                serde_json::to_string(&DeliveryConfig::default())?,
+                now.to_rfc3339(),
+                next_run.to_rfc3339(),
+            ],
+        )
+        .context("Failed to insert cron workflow job")?;
+        Ok(())
+    })?;
+
+    get_job(config, &id)
+}
+
 pub fn list_jobs(config: &Config) -> Result<Vec<CronJob>> {
     with_connection(config, |conn| {
         let mut stmt = conn.prepare(
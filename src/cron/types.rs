@@ -7,6 +7,8 @@ pub enum JobType {
     #[default]
     Shell,
     Agent,
+    /// Run the workflow named in `command` from `workspace/workflows`.
+    Workflow,
 }
 
 impl JobType {
@@ -14,12 +16,15 @@ impl JobType {
         match self {
             Self::Shell => "shell",
             Self::Agent => "agent",
+            Self::Workflow => "workflow",
         }
     }
 
     pub(crate) fn parse(raw: &str) -> Self {
         if raw.eq_ignore_ascii_case("agent") {
             Self::Agent
+        } else if raw.eq_ignore_ascii_case("workflow") {
+            Self::Workflow
         } else {
             Self::Shell
         }
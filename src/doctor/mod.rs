@@ -942,6 +942,8 @@ mod tests {
                 max_depth: 3,
                 denied_tools: Vec::new(),
                 allowed_tools: Vec::new(),
+                max_iterations: 0,
+                memory_namespace: None,
             },
         );
         config.agents.insert(
@@ -956,6 +958,8 @@ mod tests {
                 max_depth: 3,
                 denied_tools: Vec::new(),
                 allowed_tools: Vec::new(),
+                max_iterations: 0,
+                memory_namespace: None,
             },
         );
 
@@ -0,0 +1,185 @@
+//! Approval API: list pending tool approvals (`GET /approvals/pending`),
+//! answer one (`POST /approvals/{id}`) and watch the queue
+//! (`GET /approvals/stream`, SSE).
+//!
+//! Disabled unless `[gateway] approvals_enabled = true`. Requests need the
+//! pairing bearer token.
+
+use super::{client_key_from_request, AppState, RATE_LIMIT_WINDOW_SECS};
+use crate::approval::queue::{ApprovalEvent, ApprovalQueue};
+use crate::approval::{ApprovalDecision, ApprovalResponse};
+use crate::security::pairing::token_id;
+use axum::{
+    extract::{ConnectInfo, Path, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Json, Response,
+    },
+};
+use futures::StreamExt;
+use serde::Deserialize;
+use serde_json::json;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::sync::broadcast::error::RecvError;
+
+/// Body for `POST /approvals/{id}`.
+#[derive(Debug, Deserialize)]
+pub struct DecisionBody {
+    /// `approve`/`yes`, `deny`/`no` or `always`.
+    pub decision: ApprovalResponse,
+    /// Who is answering. Self-reported, so it is recorded next to the
+    /// authenticated client as `gateway:<client> (<responder>)`.
+    #[serde(default)]
+    pub responder: Option<String>,
+}
+
+fn error(status: StatusCode, message: &str) -> Response {
+    (status, Json(json!({ "error": message }))).into_response()
+}
+
+/// Feature gate and pairing check shared by all routes. Returns the queue,
+/// or the rejection status and message.
+fn bearer_token(headers: &HeaderMap) -> &str {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|auth| auth.strip_prefix("Bearer "))
+        .unwrap_or("")
+}
+
+fn authorize(
+    state: &AppState,
+    headers: &HeaderMap,
+) -> Result<Arc<ApprovalQueue>, (StatusCode, &'static str)> {
+    let Some(queue) = state
+        .approval
+        .as_ref()
+        .and_then(|manager| manager.queue())
+        .cloned()
+    else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            "Approval API is disabled — set [gateway] approvals_enabled = true",
+        ));
+    };
+
+    if state.pairing.require_pairing() && !state.pairing.is_authenticated(bearer_token(headers)) {
+        tracing::warn!("Approvals: rejected — not paired / invalid bearer token");
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            "Unauthorized — pair first via POST /pair, then send Authorization: Bearer <token>",
+        ));
+    }
+
+    Ok(queue)
+}
+
+/// GET /approvals/pending — requests waiting for a decision, oldest first
+pub async fn handle_pending(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    match authorize(&state, &headers) {
+        Ok(queue) => Json(json!({ "pending": queue.pending() })).into_response(),
+        Err((status, message)) => error(status, message),
+    }
+}
+
+/// POST /approvals/{id} — approve or deny a pending request
+pub async fn handle_decide(
+    State(state): State<AppState>,
+    ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    body: Result<Json<DecisionBody>, axum::extract::rejection::JsonRejection>,
+) -> Response {
+    let client_key =
+        client_key_from_request(Some(peer_addr), &headers, state.trust_forwarded_headers);
+    if !state.rate_limiter.allow_webhook(&client_key) {
+        tracing::warn!("/approvals rate limit exceeded for key: {client_key}");
+        return error(
+            StatusCode::TOO_MANY_REQUESTS,
+            &format!("Too many requests. Retry in {RATE_LIMIT_WINDOW_SECS}s."),
+        );
+    }
+    let queue = match authorize(&state, &headers) {
+        Ok(queue) => queue,
+        Err((status, message)) => return error(status, message),
+    };
+    let Json(body) = match body {
+        Ok(body) => body,
+        Err(e) => return error(StatusCode::BAD_REQUEST, &format!("Invalid body: {e}")),
+    };
+
+    // The log names the authenticated client; a claimed name only annotates it.
+    let client = if state.pairing.require_pairing() {
+        format!("token-{}", token_id(bearer_token(&headers)))
+    } else {
+        client_key
+    };
+    let responder = match body.responder.as_deref().map(str::trim) {
+        Some(name) if !name.is_empty() => format!("gateway:{client} ({name})"),
+        _ => format!("gateway:{client}"),
+    };
+    let decision = ApprovalDecision {
+        response: body.decision,
+        responder: Some(responder),
+    };
+    match queue.resolve(&id, decision) {
+        Some(approval) => Json(json!({
+            "id": approval.id,
+            "tool_name": approval.tool_name,
+            "decision": body.decision,
+        }))
+        .into_response(),
+        None => error(
+            StatusCode::NOT_FOUND,
+            &format!("No pending approval with id {id}"),
+        ),
+    }
+}
+
+/// GET /approvals/stream — SSE: pending requests, then `requested` and
+/// `closed` events as the queue changes
+pub async fn handle_stream(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    let queue = match authorize(&state, &headers) {
+        Ok(queue) => queue,
+        Err((status, message)) => return error(status, message),
+    };
+
+    // Subscribe before the snapshot so nothing falls between the two.
+    let events = queue.subscribe();
+    let backlog: Vec<ApprovalEvent> = queue
+        .pending()
+        .into_iter()
+        .map(ApprovalEvent::Requested)
+        .collect();
+    let live = futures::stream::unfold(events, |mut rx| async move {
+        loop {
+            match rx.recv().await {
+                Ok(event) => return Some((event, rx)),
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!("Approval stream lagged, skipped {skipped} events");
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    });
+    let stream = futures::stream::iter(backlog)
+        .chain(live)
+        .map(|event| Ok::<_, Infallible>(sse_event(&event)));
+
+    Sse::new(stream)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+fn sse_event(event: &ApprovalEvent) -> Event {
+    let name = match event {
+        ApprovalEvent::Requested(_) => "requested",
+        ApprovalEvent::Closed { .. } => "closed",
+    };
+    Event::default()
+        .event(name)
+        .data(serde_json::to_string(event).unwrap_or_default())
+}
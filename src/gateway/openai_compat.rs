@@ -0,0 +1,407 @@
+//! OpenAI-compatible chat API (`/v1/chat/completions`, `/v1/models`).
+//!
+//! Each request runs a full agent turn — tools, memory context, guardrails —
+//! so any OpenAI SDK client can talk to the agent. The agent's own system
+//! prompt stays in charge: client `system`/`developer` messages reach the
+//! model as labeled user content. Authentication uses the same pairing
+//! bearer token as `/webhook`. Memory follows `memory.isolation` on channel
+//! `gateway`. The sender is the authenticated client — its bearer token, or
+//! its address when pairing is off — and the request's `user` only picks a
+//! sub-tenant within it, so a caller cannot reach another client's memory.
+
+use super::{client_key_from_request, AppState, RATE_LIMIT_WINDOW_SECS};
+use crate::agent::loop_::{build_context, build_intervention_chain, run_tool_call_loop};
+use crate::memory::{self, MemoryCategory};
+use crate::observability::ObserverEvent;
+use crate::providers::{self, ChatMessage};
+use crate::security::pairing::token_id;
+use axum::{
+    extract::{ConnectInfo, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Json, Response,
+    },
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::time::Instant;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+/// Chat histories are larger than webhook payloads (1MB max).
+pub const MAX_BODY_SIZE: usize = 1_048_576;
+/// Agent turns with tool calls outlive the webhook timeout (5 min max).
+pub const REQUEST_TIMEOUT_SECS: u64 = 300;
+
+/// Request body for `POST /v1/chat/completions` (fields we honour).
+#[derive(Debug, Deserialize)]
+pub struct ChatCompletionRequest {
+    /// Accepted for compatibility; the gateway always answers with its configured model.
+    #[serde(default)]
+    pub model: Option<String>,
+    pub messages: Vec<ChatCompletionMessage>,
+    #[serde(default)]
+    pub stream: bool,
+    #[serde(default)]
+    pub temperature: Option<f64>,
+    /// End-user id; a sub-tenant of the authenticated client for per-sender
+    /// memory isolation.
+    #[serde(default)]
+    pub user: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ChatCompletionMessage {
+    pub role: String,
+    /// Either a string or an array of content parts.
+    #[serde(default)]
+    pub content: Option<Value>,
+}
+
+/// Flatten OpenAI message content (string or `[{type: "text", text}]`) to text.
+fn content_text(content: Option<&Value>) -> String {
+    match content {
+        Some(Value::String(text)) => text.clone(),
+        Some(Value::Array(parts)) => parts
+            .iter()
+            .filter(|part| part.get("type").and_then(Value::as_str) == Some("text"))
+            .filter_map(|part| part.get("text").and_then(Value::as_str))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+}
+
+/// Our system prompt first, then the client's conversation. Client
+/// `system`/`developer` messages carry no more authority than the caller, so
+/// they are folded into the next user message under a label rather than
+/// sitting next to the agent's own instructions. Client-side tool messages
+/// are dropped: tools run server-side in the agent loop.
+pub(super) fn agent_history(
+    system_prompt: &str,
+    messages: &[ChatCompletionMessage],
+) -> Vec<ChatMessage> {
+    let mut history = vec![ChatMessage::system(system_prompt)];
+    let mut client_instructions: Vec<String> = Vec::new();
+    for message in messages {
+        let text = content_text(message.content.as_ref());
+        match message.role.as_str() {
+            "system" | "developer" => client_instructions.push(text),
+            "user" if client_instructions.is_empty() => history.push(ChatMessage::user(text)),
+            "user" => {
+                let instructions = client_instructions.join("\n\n");
+                client_instructions.clear();
+                history.push(ChatMessage::user(format!(
+                    "[Instructions from the API client]\n{instructions}\n[End of client instructions]\n\n{text}"
+                )));
+            }
+            "assistant" => history.push(ChatMessage::assistant(text)),
+            _ => {}
+        }
+    }
+    history
+}
+
+fn openai_error(status: StatusCode, kind: &str, message: &str) -> Response {
+    (
+        status,
+        Json(json!({
+            "error": {
+                "message": message,
+                "type": kind,
+            }
+        })),
+    )
+        .into_response()
+}
+
+fn bearer_token(headers: &HeaderMap) -> &str {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|auth| auth.strip_prefix("Bearer "))
+        .unwrap_or("")
+}
+
+fn is_authorized(state: &AppState, headers: &HeaderMap) -> bool {
+    !state.pairing.require_pairing() || state.pairing.is_authenticated(bearer_token(headers))
+}
+
+fn unauthorized() -> Response {
+    openai_error(
+        StatusCode::UNAUTHORIZED,
+        "invalid_request_error",
+        "Unauthorized — pair first via POST /pair, then send Authorization: Bearer <token>",
+    )
+}
+
+/// GET /v1/models — the model this gateway serves
+pub async fn handle_models(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    if !is_authorized(&state, &headers) {
+        return unauthorized();
+    }
+    Json(json!({
+        "object": "list",
+        "data": [{
+            "id": state.model,
+            "object": "model",
+            "created": 0,
+            "owned_by": "zeroclaw",
+        }]
+    }))
+    .into_response()
+}
+
+/// POST /v1/chat/completions — run an agent turn, optionally streamed as SSE
+pub async fn handle_chat_completions(
+    State(state): State<AppState>,
+    ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    body: Result<Json<ChatCompletionRequest>, axum::extract::rejection::JsonRejection>,
+) -> Response {
+    let client_key =
+        client_key_from_request(Some(peer_addr), &headers, state.trust_forwarded_headers);
+    if !state.rate_limiter.allow_webhook(&client_key) {
+        tracing::warn!("/v1/chat/completions rate limit exceeded for key: {client_key}");
+        return openai_error(
+            StatusCode::TOO_MANY_REQUESTS,
+            "rate_limit_error",
+            &format!("Too many requests. Retry in {RATE_LIMIT_WINDOW_SECS}s."),
+        );
+    }
+
+    if !is_authorized(&state, &headers) {
+        tracing::warn!("/v1/chat/completions: rejected — not paired / invalid bearer token");
+        return unauthorized();
+    }
+
+    let Json(request) = match body {
+        Ok(b) => b,
+        Err(e) => {
+            tracing::warn!("/v1/chat/completions JSON parse error: {e}");
+            return openai_error(
+                StatusCode::BAD_REQUEST,
+                "invalid_request_error",
+                "Invalid JSON body. Expected an OpenAI chat completion request.",
+            );
+        }
+    };
+
+    let Some(last) = request.messages.last().filter(|m| m.role == "user") else {
+        return openai_error(
+            StatusCode::BAD_REQUEST,
+            "invalid_request_error",
+            "The last message must have role \"user\".",
+        );
+    };
+    let user_message = content_text(last.content.as_ref());
+
+    let (provider_label, max_tool_iterations, min_relevance_score, isolation, chain) = {
+        let config = state.config.lock();
+        (
+            config
+                .default_provider
+                .clone()
+                .unwrap_or_else(|| "unknown".to_string()),
+            config.agent.max_tool_iterations,
+            config.memory.min_relevance_score,
+            config.memory.isolation,
+            build_intervention_chain(&config.autonomy),
+        )
+    };
+
+    // Auto-save, recall and the memory tools stay in the caller's tenant
+    // namespace, as on the channels. `user` is self-reported, so it only
+    // narrows the authenticated client's tenant.
+    let client = if state.pairing.require_pairing() {
+        format!("token-{}", token_id(bearer_token(&headers)))
+    } else {
+        client_key
+    };
+    let sender = match request.user.as_deref().map(str::trim) {
+        Some(user) if !user.is_empty() => format!("{client}:{user}"),
+        _ => client,
+    };
+    let namespace = memory::tenant::namespace_for(isolation, "gateway", &sender);
+    let tenant_memory = memory::tenant::scoped(&state.mem, namespace.as_deref());
+
+    if state.auto_save {
+        let key = format!("openai_msg_{}", Uuid::new_v4());
+        let _ = tenant_memory
+            .store(&key, &user_message, MemoryCategory::Conversation, None)
+            .await;
+    }
+
+    let mut history = agent_history(&state.system_prompt, &request.messages);
+    let context = build_context(tenant_memory.as_ref(), &user_message, min_relevance_score).await;
+    if !context.is_empty() {
+        if let Some(last) = history.last_mut() {
+            last.content = format!("{context}{}", last.content);
+        }
+    }
+
+    let temperature = request.temperature.unwrap_or(state.temperature);
+    let completion_id = format!("chatcmpl-{}", Uuid::new_v4().simple());
+    let created = chrono::Utc::now().timestamp();
+
+    state.observer.record_event(&ObserverEvent::AgentStart {
+        provider: provider_label.clone(),
+        model: state.model.clone(),
+    });
+    let started_at = Instant::now();
+
+    if !request.stream {
+        let turn = run_tool_call_loop(
+            state.provider.as_ref(),
+            &mut history,
+            &state.tools_registry,
+            state.observer.as_ref(),
+            &provider_label,
+            &state.model,
+            temperature,
+            true,
+            state.approval.as_deref(),
+            "gateway",
+            max_tool_iterations,
+            None,
+            &state.security,
+            &chain,
+        );
+        let result = memory::tenant::with_namespace(namespace, turn).await;
+        record_agent_end(&state, provider_label, started_at);
+
+        return match result {
+            Ok(reply) => Json(json!({
+                "id": completion_id,
+                "object": "chat.completion",
+                "created": created,
+                "model": state.model,
+                "choices": [{
+                    "index": 0,
+                    "message": { "role": "assistant", "content": reply },
+                    "finish_reason": "stop",
+                }],
+            }))
+            .into_response(),
+            Err(e) => {
+                let sanitized = providers::sanitize_api_error(&e.to_string());
+                tracing::error!("/v1/chat/completions agent error: {sanitized}");
+                openai_error(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "server_error",
+                    &sanitized,
+                )
+            }
+        };
+    }
+
+    // ── Streaming: forward the loop's deltas as chat.completion.chunk events ──
+    let (delta_tx, mut delta_rx) = mpsc::channel::<String>(64);
+    let (event_tx, event_rx) = mpsc::channel::<Event>(64);
+    let model = state.model.clone();
+    let turn_state = state.clone();
+    let turn = tokio::spawn(memory::tenant::with_namespace(namespace, async move {
+        run_tool_call_loop(
+            turn_state.provider.as_ref(),
+            &mut history,
+            &turn_state.tools_registry,
+            turn_state.observer.as_ref(),
+            &provider_label,
+            &turn_state.model,
+            temperature,
+            true,
+            turn_state.approval.as_deref(),
+            "gateway",
+            max_tool_iterations,
+            Some(delta_tx),
+            &turn_state.security,
+            &chain,
+        )
+        .await
+        .map(|reply| (reply, provider_label))
+    }));
+
+    tokio::spawn(async move {
+        let chunk = |delta: Value, finish_reason: Option<&str>| {
+            Event::default().data(
+                json!({
+                    "id": completion_id,
+                    "object": "chat.completion.chunk",
+                    "created": created,
+                    "model": model,
+                    "choices": [{
+                        "index": 0,
+                        "delta": delta,
+                        "finish_reason": finish_reason,
+                    }],
+                })
+                .to_string(),
+            )
+        };
+
+        let _ = event_tx
+            .send(chunk(json!({"role": "assistant"}), None))
+            .await;
+        let mut streamed_any = false;
+        while let Some(delta) = delta_rx.recv().await {
+            streamed_any = true;
+            if event_tx
+                .send(chunk(json!({ "content": delta }), None))
+                .await
+                .is_err()
+            {
+                break;
+            }
+        }
+
+        match turn.await {
+            Ok(Ok((reply, provider_label))) => {
+                // Early exits (guardrail denials) return text without streaming it.
+                if !streamed_any && !reply.is_empty() {
+                    let _ = event_tx
+                        .send(chunk(json!({ "content": reply }), None))
+                        .await;
+                }
+                record_agent_end(&state, provider_label, started_at);
+            }
+            Ok(Err(e)) => {
+                let sanitized = providers::sanitize_api_error(&e.to_string());
+                tracing::error!("/v1/chat/completions agent error: {sanitized}");
+                let error = json!({"error": {"message": sanitized, "type": "server_error"}});
+                let _ = event_tx
+                    .send(Event::default().data(error.to_string()))
+                    .await;
+            }
+            Err(e) => tracing::error!("/v1/chat/completions agent task failed: {e}"),
+        }
+
+        let _ = event_tx.send(chunk(json!({}), Some("stop"))).await;
+        let _ = event_tx.send(Event::default().data("[DONE]")).await;
+    });
+
+    let stream = futures::stream::unfold(event_rx, |mut rx| async move {
+        rx.recv()
+            .await
+            .map(|event| (Ok::<_, Infallible>(event), rx))
+    });
+    Sse::new(stream)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+fn record_agent_end(state: &AppState, provider: String, started_at: Instant) {
+    let duration = started_at.elapsed();
+    state
+        .observer
+        .record_metric(&crate::observability::traits::ObserverMetric::RequestLatency(duration));
+    state.observer.record_event(&ObserverEvent::AgentEnd {
+        provider,
+        model: state.model.clone(),
+        duration,
+        tokens_used: None,
+        cost_usd: None,
+    });
+}
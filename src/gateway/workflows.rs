@@ -0,0 +1,152 @@
+//! Workflow webhook trigger (`POST /workflows/{name}/run`) and run status
+//! (`GET /workflows/runs/{id}`).
+//!
+//! Disabled unless `[workflows] webhook_enabled = true`. Authentication is the
+//! same as `/webhook`: the pairing bearer token plus the optional
+//! `X-Webhook-Secret` header.
+
+use super::{client_key_from_request, hash_webhook_secret, AppState, RATE_LIMIT_WINDOW_SECS};
+use crate::security::pairing::constant_time_eq;
+use crate::workflow::{find_workflow, WorkflowEngine, WorkflowStore};
+use axum::{
+    extract::{ConnectInfo, Path, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+/// Optional body for `POST /workflows/{name}/run`.
+#[derive(Debug, Default, Deserialize)]
+pub struct WorkflowRunBody {
+    #[serde(default)]
+    pub inputs: HashMap<String, String>,
+}
+
+fn error(status: StatusCode, message: &str) -> Response {
+    (status, Json(json!({ "error": message }))).into_response()
+}
+
+/// Feature gate plus pairing and webhook-secret checks shared by both routes.
+/// Returns the rejection response when the request may not proceed.
+fn reject(state: &AppState, headers: &HeaderMap) -> Option<Response> {
+    if !state.config.lock().workflows.webhook_enabled {
+        return Some(error(
+            StatusCode::NOT_FOUND,
+            "Workflow triggers are disabled — set [workflows] webhook_enabled = true",
+        ));
+    }
+
+    if state.pairing.require_pairing() {
+        let token = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|auth| auth.strip_prefix("Bearer "))
+            .unwrap_or("");
+        if !state.pairing.is_authenticated(token) {
+            tracing::warn!("Workflow trigger: rejected — not paired / invalid bearer token");
+            return Some(error(
+                StatusCode::UNAUTHORIZED,
+                "Unauthorized — pair first via POST /pair, then send Authorization: Bearer <token>",
+            ));
+        }
+    }
+
+    if let Some(ref secret_hash) = state.webhook_secret_hash {
+        let header_hash = headers
+            .get("X-Webhook-Secret")
+            .and_then(|v| v.to_str().ok())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(hash_webhook_secret);
+        match header_hash {
+            Some(val) if constant_time_eq(&val, secret_hash.as_ref()) => {}
+            _ => {
+                tracing::warn!("Workflow trigger: rejected — invalid or missing X-Webhook-Secret");
+                return Some(error(
+                    StatusCode::UNAUTHORIZED,
+                    "Unauthorized — invalid or missing X-Webhook-Secret header",
+                ));
+            }
+        }
+    }
+
+    None
+}
+
+/// POST /workflows/{name}/run — start a run in the background
+pub async fn handle_run(
+    State(state): State<AppState>,
+    ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
+    Path(name): Path<String>,
+    headers: HeaderMap,
+    body: Option<Json<WorkflowRunBody>>,
+) -> Response {
+    let client_key =
+        client_key_from_request(Some(peer_addr), &headers, state.trust_forwarded_headers);
+    if !state.rate_limiter.allow_webhook(&client_key) {
+        tracing::warn!("/workflows rate limit exceeded for key: {client_key}");
+        return error(
+            StatusCode::TOO_MANY_REQUESTS,
+            &format!("Too many requests. Retry in {RATE_LIMIT_WINDOW_SECS}s."),
+        );
+    }
+    if let Some(response) = reject(&state, &headers) {
+        return response;
+    }
+
+    let config = state.config.lock().clone();
+    let definition = match find_workflow(&config.workspace_dir, &name) {
+        Ok(definition) => definition,
+        Err(e) => return error(StatusCode::NOT_FOUND, &format!("{e:#}")),
+    };
+    let engine = match WorkflowEngine::new(
+        Arc::new(config),
+        state.tools_registry.clone(),
+        state.security.clone(),
+    ) {
+        Ok(engine) => engine,
+        Err(e) => return error(StatusCode::INTERNAL_SERVER_ERROR, &format!("{e:#}")),
+    };
+    let inputs = body.map(|Json(body)| body.inputs).unwrap_or_default();
+    let run = match engine.create_run(&definition, &inputs, "webhook") {
+        Ok(run) => run,
+        Err(e) => return error(StatusCode::BAD_REQUEST, &format!("{e:#}")),
+    };
+
+    let run_id = run.id.clone();
+    tokio::spawn(async move {
+        if let Err(e) = engine.drive(&run.id).await {
+            tracing::warn!("Workflow run {} failed to drive: {e:#}", run.id);
+        }
+    });
+
+    (
+        StatusCode::ACCEPTED,
+        Json(json!({
+            "status": "accepted",
+            "workflow": definition.name,
+            "run_id": run_id,
+        })),
+    )
+        .into_response()
+}
+
+/// GET /workflows/runs/{id} — run and step status
+pub async fn handle_run_status(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    if let Some(response) = reject(&state, &headers) {
+        return response;
+    }
+    let workspace_dir = state.config.lock().workspace_dir.clone();
+    match WorkflowStore::open(&workspace_dir).and_then(|store| store.get_run(&id)) {
+        Ok(run) => Json(run).into_response(),
+        Err(e) => error(StatusCode::NOT_FOUND, &format!("{e:#}")),
+    }
+}
@@ -53,6 +53,7 @@ pub mod health;
 pub mod heartbeat;
 pub mod identity;
 pub mod integrations;
+pub mod mcp;
 pub mod memory;
 pub mod migration;
 pub mod observability;
@@ -64,10 +65,12 @@ pub mod rag;
 pub mod runtime;
 pub mod security;
 pub mod service;
+pub mod sessions;
 pub mod skills;
 pub mod tools;
 pub mod tunnel;
 pub mod util;
+pub mod workflow;
 
 pub use config::Config;
 
@@ -197,6 +200,16 @@ pub enum CronCommands {
         /// Task ID
         id: String,
     },
+    /// Schedule a workflow from workspace/workflows on a cron expression
+    AddWorkflow {
+        /// Cron expression
+        expression: String,
+        /// Optional IANA timezone (e.g. America/Los_Angeles)
+        #[arg(long)]
+        tz: Option<String>,
+        /// Workflow name
+        workflow: String,
+    },
 }
 
 /// Integration subcommands
@@ -227,6 +240,302 @@ pub enum HardwareCommands {
     },
 }
 
+/// MCP (Model Context Protocol) subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum McpCommands {
+    /// Serve ZeroClaw's tools to other agent hosts over MCP
+    Serve {
+        /// Transport: stdio (default) or http
+        #[arg(long, default_value = "stdio")]
+        transport: String,
+        /// Host to bind for the http transport
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+        /// Port for the http transport
+        #[arg(short, long, default_value = "3100")]
+        port: u16,
+    },
+    /// List configured MCP servers and the tools they expose
+    List,
+}
+
+/// Conversation session subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SessionCommands {
+    /// List stored sessions, most recent first
+    List {
+        /// Only show sessions from this channel (cli, telegram, discord, ...)
+        #[arg(long)]
+        channel: Option<String>,
+        /// Maximum number of sessions to show
+        #[arg(short, long, default_value = "20")]
+        limit: usize,
+    },
+    /// Print a session transcript
+    Show {
+        /// Session id (or a unique prefix)
+        id: String,
+    },
+    /// Continue a stored session in interactive mode
+    Resume {
+        /// Session id (or a unique prefix)
+        id: String,
+        /// Provider to use (defaults to config)
+        #[arg(short, long)]
+        provider: Option<String>,
+        /// Model to use (defaults to config)
+        #[arg(long)]
+        model: Option<String>,
+    },
+    /// Delete a session and its transcript
+    Delete {
+        /// Session id (or a unique prefix)
+        id: String,
+    },
+}
+
+/// Memory subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum MemoryCommands {
+    /// List memories, most recently updated first
+    List {
+        #[command(flatten)]
+        filter: MemoryFilterArgs,
+        /// Maximum number of entries to show
+        #[arg(short, long, default_value = "50")]
+        limit: usize,
+        /// Print JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+    /// Search memories with the backend's recall (keyword and/or vector)
+    Search {
+        /// Search query
+        query: String,
+        #[command(flatten)]
+        filter: MemoryFilterArgs,
+        /// Maximum number of results
+        #[arg(short, long, default_value = "10")]
+        limit: usize,
+        /// Print JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show a single memory by key
+    Get {
+        /// Memory key
+        key: String,
+        /// Print JSON instead of text
+        #[arg(long)]
+        json: bool,
+    },
+    /// Store or overwrite a memory
+    Put {
+        /// Memory key
+        key: String,
+        /// Memory content
+        content: String,
+        /// Category (core, daily, conversation, or a custom name)
+        #[arg(short, long, default_value = "core")]
+        category: String,
+        /// Session id to scope the memory to
+        #[arg(long)]
+        session: Option<String>,
+    },
+    /// Delete a memory by key
+    Forget {
+        /// Memory key
+        key: String,
+    },
+    /// Show entry counts per category and the covered time range
+    Stats {
+        /// Print JSON instead of text
+        #[arg(long)]
+        json: bool,
+    },
+    /// Rebuild search indexes and embed entries that have no embedding yet
+    Reindex,
+    /// Distil daily and conversation memories into core facts now
+    Consolidate,
+    /// Export every memory of the active backend as versioned JSONL
+    Export {
+        /// Output file (defaults to stdout)
+        #[arg(short, long)]
+        output: Option<std::path::PathBuf>,
+        /// Only export this category (core, daily, conversation, or a custom name)
+        #[arg(long)]
+        category: Option<String>,
+        /// Include stored embeddings, tagged with the configured embedding model
+        #[arg(long)]
+        embeddings: bool,
+    },
+    /// Restore a JSONL export into the active backend (existing keys are overwritten)
+    Import {
+        /// Export file to read
+        input: std::path::PathBuf,
+        /// Validate the file without writing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+/// Filters shared by `memory list` and `memory search`
+#[derive(clap::Args, Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MemoryFilterArgs {
+    /// Only entries in this category (core, daily, conversation, or a custom name)
+    #[arg(short, long)]
+    pub category: Option<String>,
+    /// Only entries scoped to this session id
+    #[arg(long)]
+    pub session: Option<String>,
+    /// Only entries from this date onwards (YYYY-MM-DD or RFC 3339)
+    #[arg(long)]
+    pub since: Option<String>,
+    /// Only entries up to and including this date (YYYY-MM-DD or RFC 3339)
+    #[arg(long)]
+    pub until: Option<String>,
+}
+
+/// Workflow subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum WorkflowCommands {
+    /// List workflow files in workspace/workflows
+    List,
+    /// Show a workflow's inputs and steps in execution order
+    Show {
+        /// Workflow name
+        name: String,
+    },
+    /// Run a workflow now
+    Run {
+        /// Workflow name
+        name: String,
+        /// Workflow input as key=value (repeatable)
+        #[arg(short, long)]
+        input: Vec<String>,
+    },
+    /// List recent workflow runs, most recent first
+    Runs {
+        /// Only show runs of this workflow
+        #[arg(long)]
+        workflow: Option<String>,
+        /// Maximum number of runs to show
+        #[arg(short, long, default_value = "20")]
+        limit: usize,
+    },
+    /// Show a run and the state of each step
+    Status {
+        /// Run id (or a unique prefix)
+        id: String,
+    },
+    /// Continue an interrupted or failed run
+    Resume {
+        /// Run id (or a unique prefix)
+        id: String,
+    },
+    /// Approve a step waiting for approval and continue the run
+    Approve {
+        /// Run id (or a unique prefix)
+        id: String,
+        /// Approval step id
+        step: String,
+        /// Note passed to later steps as the approval step's output
+        #[arg(long)]
+        note: Option<String>,
+    },
+    /// Reject a step waiting for approval, failing the run
+    Reject {
+        /// Run id (or a unique prefix)
+        id: String,
+        /// Approval step id
+        step: String,
+        /// Reason recorded on the step
+        #[arg(long)]
+        note: Option<String>,
+    },
+}
+
+/// Audit log subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum AuditCommands {
+    /// Check the audit log hash chain (and signatures) across rotated files
+    Verify,
+    /// Search events across current and rotated files, most recent first
+    Search {
+        #[command(flatten)]
+        filter: AuditFilterArgs,
+        /// Maximum number of events to show
+        #[arg(short, long, default_value = "50")]
+        limit: usize,
+        /// Print JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+    /// Export matching events, oldest first, as CSV or JSON
+    Export {
+        #[command(flatten)]
+        filter: AuditFilterArgs,
+        /// Output format: csv or json
+        #[arg(short, long, default_value = "json")]
+        format: String,
+        /// Output file (defaults to stdout)
+        #[arg(short, long)]
+        output: Option<std::path::PathBuf>,
+    },
+}
+
+/// Filters shared by `audit search` and `audit export`
+#[derive(clap::Args, Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AuditFilterArgs {
+    /// Only this event type (tool_call, approval_decision, command_execution, policy_violation, ...)
+    #[arg(long = "type")]
+    pub event_type: Option<String>,
+    /// Only events from this channel (cli, telegram, slack, gateway, ...)
+    #[arg(long)]
+    pub channel: Option<String>,
+    /// Only events by this user id or username
+    #[arg(long)]
+    pub user: Option<String>,
+    /// Only events for this tool
+    #[arg(long)]
+    pub tool: Option<String>,
+    /// Only this risk level (low, medium, high)
+    #[arg(long)]
+    pub risk: Option<String>,
+    /// Only actions that were allowed
+    #[arg(long, conflicts_with = "denied")]
+    pub allowed: bool,
+    /// Only actions that were denied, and policy violations
+    #[arg(long)]
+    pub denied: bool,
+    /// Only events from this date onwards (YYYY-MM-DD or RFC 3339)
+    #[arg(long)]
+    pub since: Option<String>,
+    /// Only events up to and including this date (YYYY-MM-DD or RFC 3339)
+    #[arg(long)]
+    pub until: Option<String>,
+}
+
+/// Tool argument policy subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum PolicyCommands {
+    /// Check the rule file and run its [[tests]], or dry-run one tool call
+    Test {
+        /// Rule file (defaults to [autonomy] policy_file)
+        #[arg(short, long)]
+        file: Option<std::path::PathBuf>,
+        /// Tool name of a call to dry-run
+        #[arg(long)]
+        tool: Option<String>,
+        /// Tool arguments of the dry-run call, as JSON
+        #[arg(long, requires = "tool", default_value = "{}")]
+        args: String,
+        /// Channel the dry-run call comes from
+        #[arg(long, default_value = "cli")]
+        channel: String,
+    },
+}
+
 /// Peripheral (hardware) management subcommands
 #[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum PeripheralCommands {
@@ -56,6 +56,7 @@ mod health;
 mod heartbeat;
 mod identity;
 mod integrations;
+mod mcp;
 mod memory;
 mod migration;
 mod observability;
@@ -66,16 +67,21 @@ mod providers;
 mod runtime;
 mod security;
 mod service;
+mod sessions;
 mod skillforge;
 mod skills;
 mod tools;
 mod tunnel;
 mod util;
+mod workflow;
 
 use config::Config;
 
 // Re-export so binary's hardware/peripherals modules can use crate::HardwareCommands etc.
-pub use zeroclaw::{HardwareCommands, PeripheralCommands};
+pub use zeroclaw::{
+    AuditCommands, AuditFilterArgs, HardwareCommands, McpCommands, MemoryCommands,
+    MemoryFilterArgs, PeripheralCommands, PolicyCommands, SessionCommands, WorkflowCommands,
+};
 
 /// `ZeroClaw` - Zero overhead. Zero compromise. 100% Rust.
 #[derive(Parser, Debug)]
@@ -148,6 +154,10 @@ enum Commands {
         /// Attach a peripheral (board:path, e.g. nucleo-f401re:/dev/ttyACM0)
         #[arg(long)]
         peripheral: Vec<String>,
+
+        /// Continue a stored session (id or unique prefix; see `zeroclaw sessions list`)
+        #[arg(long)]
+        session: Option<String>,
     },
 
     /// Start the gateway server (webhooks, websockets)
@@ -243,6 +253,48 @@ enum Commands {
         #[command(subcommand)]
         peripheral_command: zeroclaw::PeripheralCommands,
     },
+
+    /// Model Context Protocol: serve tools or inspect configured servers
+    Mcp {
+        #[command(subcommand)]
+        mcp_command: zeroclaw::McpCommands,
+    },
+
+    /// Manage persistent conversation sessions (list, show, resume, delete)
+    Sessions {
+        #[command(subcommand)]
+        session_command: zeroclaw::SessionCommands,
+    },
+
+    /// Inspect and edit agent memory (list, search, get, put, forget, stats, reindex, export, import)
+    Memory {
+        #[command(subcommand)]
+        memory_command: zeroclaw::MemoryCommands,
+    },
+
+    /// Ingest documents into memory for retrieval (Markdown, text, code; PDF with rag-pdf)
+    Ingest {
+        /// File or directory to ingest; unchanged files are skipped on re-runs
+        path: std::path::PathBuf,
+    },
+
+    /// Run and manage declarative workflows (list, run, status, resume, approve)
+    Workflow {
+        #[command(subcommand)]
+        workflow_command: zeroclaw::WorkflowCommands,
+    },
+
+    /// Inspect the security audit log (verify, search, export)
+    Audit {
+        #[command(subcommand)]
+        audit_command: zeroclaw::AuditCommands,
+    },
+
+    /// Check tool argument rules ([autonomy] policy_file) with dry runs
+    Policy {
+        #[command(subcommand)]
+        policy_command: zeroclaw::PolicyCommands,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -392,6 +444,16 @@ enum CronCommands {
         /// Task ID
         id: String,
     },
+    /// Schedule a workflow from workspace/workflows on a cron expression
+    AddWorkflow {
+        /// Cron expression
+        expression: String,
+        /// Optional IANA timezone (e.g. America/Los_Angeles)
+        #[arg(long)]
+        tz: Option<String>,
+        /// Workflow name
+        workflow: String,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -486,11 +548,21 @@ async fn main() -> Result<()> {
 
     let cli = Cli::parse();
 
-    // Initialize logging - respects RUST_LOG env var, defaults to INFO
+    // Initialize logging - respects RUST_LOG env var, defaults to INFO.
+    // `mcp serve` owns stdout for protocol frames and `memory export` may
+    // stream JSONL to it, so logs go to stderr there.
+    let log_to_stderr = matches!(cli.command, Commands::Mcp { .. } | Commands::Memory { .. });
     let subscriber = fmt::Subscriber::builder()
         .with_env_filter(
             EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
         )
+        .with_writer(move || -> Box<dyn std::io::Write> {
+            if log_to_stderr {
+                Box::new(std::io::stderr())
+            } else {
+                Box::new(std::io::stdout())
+            }
+        })
         .finish();
 
     tracing::subscriber::set_global_default(subscriber).expect("setting default subscriber failed");
@@ -540,6 +612,9 @@ async fn main() -> Result<()> {
     // All other commands need config loaded first
     let mut config = Config::load_or_init()?;
     config.apply_env_overrides();
+    if let Err(e) = security::audit::init(&config) {
+        tracing::warn!("Audit logging disabled: {e:#}");
+    }
 
     match cli.command {
         Commands::Onboard { .. } => unreachable!(),
@@ -550,9 +625,18 @@ async fn main() -> Result<()> {
             model,
             temperature,
             peripheral,
-        } => agent::run(config, message, provider, model, temperature, peripheral)
-            .await
-            .map(|_| ()),
+            session,
+        } => agent::run(
+            config,
+            message,
+            provider,
+            model,
+            temperature,
+            peripheral,
+            session,
+        )
+        .await
+        .map(|_| ()),
 
         Commands::Gateway { port, host } => {
             let port = port.unwrap_or(config.gateway.port);
@@ -750,6 +834,30 @@ async fn main() -> Result<()> {
         Commands::Peripheral { peripheral_command } => {
             peripherals::handle_command(peripheral_command.clone(), &config)
         }
+
+        Commands::Mcp { mcp_command } => mcp::handle_command(mcp_command, config).await,
+
+        Commands::Sessions { session_command } => {
+            sessions::handle_command(session_command, config).await
+        }
+
+        Commands::Memory { memory_command } => {
+            memory::handle_command(memory_command, &config).await
+        }
+
+        Commands::Ingest { path } => memory::handle_ingest(&path, &config).await,
+
+        Commands::Workflow { workflow_command } => {
+            workflow::handle_command(workflow_command, config).await
+        }
+
+        Commands::Audit { audit_command } => {
+            security::audit_cli::handle_command(audit_command, &config)
+        }
+
+        Commands::Policy { policy_command } => {
+            security::policy_cli::handle_command(policy_command, &config)
+        }
     }
 }
 
@@ -0,0 +1,277 @@
+//! MCP client session: `initialize` handshake, paginated `tools/list`, and
+//! `tools/call` over any [`McpTransport`].
+
+use super::protocol::{
+    CallToolResult, JsonRpcNotification, JsonRpcRequest, ListToolsResult, McpToolDef,
+    MCP_PROTOCOL_VERSION, SUPPORTED_PROTOCOL_VERSIONS,
+};
+use super::transport::{HttpTransport, McpTransport, StdioTransport};
+use crate::config::{McpServerConfig, McpTransportKind};
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Upper bound on `tools/list` pages, guarding against cursor loops.
+const MAX_LIST_PAGES: usize = 50;
+
+/// A connected, initialized MCP server.
+pub struct McpClient {
+    server_name: String,
+    transport: Box<dyn McpTransport>,
+    next_id: AtomicU64,
+    call_timeout: Duration,
+    protocol_version: String,
+    server_info: Option<Value>,
+}
+
+impl McpClient {
+    /// Open the configured transport and run the `initialize` handshake.
+    pub async fn connect(
+        server: &McpServerConfig,
+        workspace_dir: &std::path::Path,
+    ) -> Result<Self> {
+        let transport: Box<dyn McpTransport> = match server.transport {
+            McpTransportKind::Stdio => Box::new(StdioTransport::spawn(server, workspace_dir)?),
+            McpTransportKind::Http => Box::new(HttpTransport::new(server)?),
+        };
+        Self::initialize(
+            &server.name,
+            transport,
+            Duration::from_secs(server.startup_timeout_secs.max(1)),
+            Duration::from_secs(server.call_timeout_secs.max(1)),
+        )
+        .await
+    }
+
+    /// Run the handshake over an already-open transport.
+    pub async fn initialize(
+        server_name: &str,
+        transport: Box<dyn McpTransport>,
+        startup_timeout: Duration,
+        call_timeout: Duration,
+    ) -> Result<Self> {
+        let mut client = Self {
+            server_name: server_name.to_string(),
+            transport,
+            next_id: AtomicU64::new(1),
+            call_timeout,
+            protocol_version: MCP_PROTOCOL_VERSION.to_string(),
+            server_info: None,
+        };
+
+        let params = json!({
+            "protocolVersion": MCP_PROTOCOL_VERSION,
+            "capabilities": {},
+            "clientInfo": {
+                "name": "zeroclaw",
+                "version": env!("CARGO_PKG_VERSION"),
+            }
+        });
+        let result = client
+            .send("initialize", Some(params), startup_timeout)
+            .await
+            .with_context(|| format!("MCP server '{server_name}' failed to initialize"))?;
+
+        let negotiated = result
+            .get("protocolVersion")
+            .and_then(Value::as_str)
+            .unwrap_or(MCP_PROTOCOL_VERSION);
+        if !SUPPORTED_PROTOCOL_VERSIONS.contains(&negotiated) {
+            client.transport.close().await;
+            anyhow::bail!(
+                "MCP server '{server_name}' speaks unsupported protocol version {negotiated}"
+            );
+        }
+        client.protocol_version = negotiated.to_string();
+        client.server_info = result.get("serverInfo").cloned();
+
+        client
+            .transport
+            .notify(JsonRpcNotification::new("notifications/initialized", None))
+            .await?;
+
+        Ok(client)
+    }
+
+    pub fn server_name(&self) -> &str {
+        &self.server_name
+    }
+
+    pub fn protocol_version(&self) -> &str {
+        &self.protocol_version
+    }
+
+    pub fn server_info(&self) -> Option<&Value> {
+        self.server_info.as_ref()
+    }
+
+    async fn send(&self, method: &str, params: Option<Value>, timeout: Duration) -> Result<Value> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.transport
+            .request(JsonRpcRequest::new(id, method, params), timeout)
+            .await?
+            .into_result()
+    }
+
+    /// Fetch every tool the server exposes, following pagination cursors.
+    pub async fn list_tools(&self) -> Result<Vec<McpToolDef>> {
+        let mut tools = Vec::new();
+        let mut cursor: Option<String> = None;
+        for _ in 0..MAX_LIST_PAGES {
+            let params = cursor.as_ref().map(|c| json!({ "cursor": c }));
+            let page: ListToolsResult =
+                serde_json::from_value(self.send("tools/list", params, self.call_timeout).await?)
+                    .with_context(|| {
+                    format!(
+                        "MCP server '{}' sent a malformed tools/list",
+                        self.server_name
+                    )
+                })?;
+            tools.extend(page.tools);
+            match page.next_cursor.filter(|c| !c.is_empty()) {
+                Some(next) => cursor = Some(next),
+                None => return Ok(tools),
+            }
+        }
+        tracing::warn!(
+            server = %self.server_name,
+            "tools/list pagination exceeded {MAX_LIST_PAGES} pages; truncating"
+        );
+        Ok(tools)
+    }
+
+    /// Invoke a remote tool.
+    pub async fn call_tool(&self, name: &str, arguments: Value) -> Result<CallToolResult> {
+        let arguments = if arguments.is_null() {
+            json!({})
+        } else {
+            arguments
+        };
+        let result = self
+            .send(
+                "tools/call",
+                Some(json!({ "name": name, "arguments": arguments })),
+                self.call_timeout,
+            )
+            .await?;
+        serde_json::from_value(result).with_context(|| {
+            format!(
+                "MCP server '{}' sent a malformed tools/call result",
+                self.server_name
+            )
+        })
+    }
+
+    /// Shut down the underlying transport.
+    pub async fn close(&self) {
+        self.transport.close().await;
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+    /// Minimal in-process MCP server: answers initialize, a two-page
+    /// tools/list, and echoes tools/call arguments back as text.
+    pub(crate) async fn connect_fake_server(name: &str) -> McpClient {
+        let (client_io, server_io) = tokio::io::duplex(64 * 1024);
+        let (client_read, client_write) = tokio::io::split(client_io);
+        let (server_read, mut server_write) = tokio::io::split(server_io);
+
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(server_read).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                let msg: Value = serde_json::from_str(&line).unwrap();
+                let Some(id) = msg.get("id").cloned() else {
+                    continue;
+                };
+                let result = match msg["method"].as_str().unwrap_or_default() {
+                    "initialize" => json!({
+                        "protocolVersion": "2025-03-26",
+                        "capabilities": {"tools": {}},
+                        "serverInfo": {"name": "fake", "version": "0.0.1"}
+                    }),
+                    "tools/list" if msg["params"]["cursor"].is_null() => json!({
+                        "tools": [{
+                            "name": "echo",
+                            "description": "Echo text back",
+                            "inputSchema": {
+                                "type": "object",
+                                "properties": {"text": {"type": "string"}},
+                                "required": ["text"]
+                            }
+                        }],
+                        "nextCursor": "page-2"
+                    }),
+                    "tools/list" => json!({
+                        "tools": [{
+                            "name": "lookup",
+                            "inputSchema": {"type": "object", "properties": {}},
+                            "annotations": {"readOnlyHint": true}
+                        }]
+                    }),
+                    "tools/call" => {
+                        let args = &msg["params"]["arguments"];
+                        if msg["params"]["name"] == "fail" {
+                            json!({"content": [{"type": "text", "text": "boom"}], "isError": true})
+                        } else {
+                            json!({"content": [{"type": "text", "text": args["text"].as_str().unwrap_or("")}]})
+                        }
+                    }
+                    _ => json!({}),
+                };
+                let reply = json!({"jsonrpc": "2.0", "id": id, "result": result});
+                if server_write
+                    .write_all(format!("{reply}\n").as_bytes())
+                    .await
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        });
+
+        let transport = StdioTransport::from_streams(name, client_read, client_write);
+        McpClient::initialize(
+            name,
+            Box::new(transport),
+            Duration::from_secs(5),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn initialize_records_server_info() {
+        let client = connect_fake_server("fake").await;
+        assert_eq!(client.server_name(), "fake");
+        assert_eq!(client.protocol_version(), "2025-03-26");
+        assert_eq!(client.server_info().unwrap()["name"], "fake");
+    }
+
+    #[tokio::test]
+    async fn list_tools_follows_pagination() {
+        let client = connect_fake_server("fake").await;
+        let tools = client.list_tools().await.unwrap();
+        let names: Vec<&str> = tools.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["echo", "lookup"]);
+    }
+
+    #[tokio::test]
+    async fn call_tool_returns_content() {
+        let client = connect_fake_server("fake").await;
+        let result = client
+            .call_tool("echo", json!({"text": "hi there"}))
+            .await
+            .unwrap();
+        assert!(!result.is_error);
+        assert_eq!(result.render_text(), "hi there");
+
+        let failed = client.call_tool("fail", Value::Null).await.unwrap();
+        assert!(failed.is_error);
+    }
+}
@@ -0,0 +1,249 @@
+//! Model Context Protocol (MCP) support, in both directions.
+//!
+//! As a client, connects to the servers listed under `[[mcp.servers]]`,
+//! discovers their tools with `tools/list`, and exposes each one to the agent
+//! as a regular [`Tool`](crate::tools::Tool) (see [`crate::tools::McpTool`]).
+//! Remote calls pass through the same `check_tool` / approval gates as
+//! built-in tools. Servers are connected once per process by [`init`] and
+//! shared by every tool registry built afterwards (CLI agent, gateway,
+//! channels).
+//!
+//! As a server ([`server`]), `zeroclaw mcp serve` exposes ZeroClaw's own
+//! tool registry to other agent hosts.
+
+pub mod client;
+pub mod protocol;
+pub mod server;
+pub mod transport;
+
+pub use client::McpClient;
+
+use crate::config::{Config, McpServerConfig};
+use crate::security::SecurityPolicy;
+use crate::tools::{McpTool, Tool};
+use crate::McpCommands;
+use anyhow::Result;
+use protocol::McpToolDef;
+use std::sync::Arc;
+use tokio::sync::OnceCell;
+
+static REGISTRY: OnceCell<Arc<McpRegistry>> = OnceCell::const_new();
+
+/// A connected server together with the tools it advertised.
+pub struct McpServer {
+    pub client: Arc<McpClient>,
+    pub tools: Vec<McpToolDef>,
+}
+
+/// Every MCP server that connected successfully.
+#[derive(Default)]
+pub struct McpRegistry {
+    servers: Vec<McpServer>,
+}
+
+impl McpRegistry {
+    /// Connect all enabled servers concurrently. Servers that fail to start
+    /// are logged and skipped so one broken entry cannot block the agent.
+    pub async fn connect(config: &Config) -> Self {
+        let enabled: Vec<&McpServerConfig> =
+            config.mcp.servers.iter().filter(|s| s.enabled).collect();
+        if enabled.is_empty() {
+            return Self::default();
+        }
+
+        let results = futures::future::join_all(
+            enabled
+                .iter()
+                .map(|server| connect_server(server, &config.workspace_dir)),
+        )
+        .await;
+
+        let mut servers = Vec::new();
+        for (server, result) in enabled.into_iter().zip(results) {
+            let component = format!("mcp:{}", server.name);
+            match result {
+                Ok(connected) => {
+                    tracing::info!(
+                        server = %server.name,
+                        tools = connected.tools.len(),
+                        "MCP server connected"
+                    );
+                    crate::health::mark_component_ok(&component);
+                    servers.push(connected);
+                }
+                Err(e) => {
+                    tracing::warn!(server = %server.name, "MCP server unavailable: {e:#}");
+                    crate::health::mark_component_error(&component, format!("{e:#}"));
+                }
+            }
+        }
+
+        Self { servers }
+    }
+
+    pub fn servers(&self) -> &[McpServer] {
+        &self.servers
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.servers.is_empty()
+    }
+
+    /// Wrap every discovered remote tool for the agent tool registry.
+    pub fn tools(&self, security: &Arc<SecurityPolicy>) -> Vec<Box<dyn Tool>> {
+        self.servers
+            .iter()
+            .flat_map(|server| {
+                server.tools.iter().map(|def| -> Box<dyn Tool> {
+                    Box::new(McpTool::new(
+                        server.client.clone(),
+                        def.clone(),
+                        security.clone(),
+                    ))
+                })
+            })
+            .collect()
+    }
+}
+
+async fn connect_server(
+    server: &McpServerConfig,
+    workspace_dir: &std::path::Path,
+) -> anyhow::Result<McpServer> {
+    if server.name.trim().is_empty() {
+        anyhow::bail!("MCP server entry is missing a name");
+    }
+    let client = McpClient::connect(server, workspace_dir).await?;
+    let mut tools = client.list_tools().await?;
+    if !server.allowed_tools.is_empty() {
+        tools.retain(|t| {
+            server
+                .allowed_tools
+                .iter()
+                .any(|allowed| allowed == &t.name)
+        });
+    }
+    Ok(McpServer {
+        client: Arc::new(client),
+        tools,
+    })
+}
+
+/// Connect the configured MCP servers once for this process.
+///
+/// Safe to call from every entry point; only the first call does any work.
+pub async fn init(config: &Config) -> Arc<McpRegistry> {
+    REGISTRY
+        .get_or_init(|| async { Arc::new(McpRegistry::connect(config).await) })
+        .await
+        .clone()
+}
+
+/// The process-wide registry, if [`init`] has run.
+pub fn registry() -> Option<Arc<McpRegistry>> {
+    REGISTRY.get().cloned()
+}
+
+/// Handle `zeroclaw mcp ...` subcommands.
+pub async fn handle_command(command: McpCommands, config: Config) -> Result<()> {
+    match command {
+        McpCommands::Serve {
+            transport,
+            host,
+            port,
+        } => {
+            crate::memory::embeddings::init(&config.memory).await;
+            let server = Arc::new(server::McpToolServer::from_config(&config)?);
+            tracing::info!(tools = ?server.tool_names(), "Serving tools over MCP");
+            match transport.as_str() {
+                "stdio" => server::serve_stdio(server).await,
+                "http" => server::serve_http(server, &host, port, &config).await,
+                other => anyhow::bail!("Unknown MCP transport '{other}' (expected stdio or http)"),
+            }
+        }
+        McpCommands::List => {
+            if config.mcp.servers.is_empty() {
+                println!("No MCP servers configured. Add [[mcp.servers]] to config.toml.");
+                return Ok(());
+            }
+            let registry = McpRegistry::connect(&config).await;
+            for entry in &config.mcp.servers {
+                let connected = registry
+                    .servers()
+                    .iter()
+                    .find(|s| s.client.server_name() == entry.name);
+                match (entry.enabled, connected) {
+                    (false, _) => println!("○ {} (disabled)", entry.name),
+                    (true, None) => println!("✗ {} (failed to connect; see logs)", entry.name),
+                    (true, Some(server)) => {
+                        println!("✓ {} ({} tools)", entry.name, server.tools.len());
+                        for tool in &server.tools {
+                            println!(
+                                "    {}  {}",
+                                crate::tools::mcp_tool::qualified_tool_name(
+                                    &entry.name,
+                                    &tool.name
+                                ),
+                                tool.description.as_deref().unwrap_or("")
+                            );
+                        }
+                    }
+                }
+            }
+            for server in registry.servers() {
+                server.client.close().await;
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn registry_skips_disabled_and_broken_servers() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mut config = Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..Config::default()
+        };
+        config.mcp.servers = vec![
+            McpServerConfig {
+                name: "off".into(),
+                command: Some("definitely-not-a-real-binary".into()),
+                enabled: false,
+                ..McpServerConfig::default()
+            },
+            McpServerConfig {
+                name: "broken".into(),
+                command: Some("definitely-not-a-real-binary-zeroclaw".into()),
+                startup_timeout_secs: 2,
+                ..McpServerConfig::default()
+            },
+        ];
+
+        let registry = McpRegistry::connect(&config).await;
+        assert!(registry.is_empty());
+        assert!(registry
+            .tools(&Arc::new(SecurityPolicy::default()))
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn registry_wraps_each_remote_tool() {
+        let client = client::tests::connect_fake_server("fake").await;
+        let tools = client.list_tools().await.unwrap();
+        let registry = McpRegistry {
+            servers: vec![McpServer {
+                client: Arc::new(client),
+                tools,
+            }],
+        };
+
+        let wrapped = registry.tools(&Arc::new(SecurityPolicy::default()));
+        let names: Vec<&str> = wrapped.iter().map(|t| t.name()).collect();
+        assert_eq!(names, vec!["fake__echo", "fake__lookup"]);
+    }
+}
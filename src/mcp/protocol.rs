@@ -0,0 +1,333 @@
+//! JSON-RPC 2.0 envelopes and the subset of Model Context Protocol types
+//! ZeroClaw needs to list and call remote tools.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Protocol revision advertised during `initialize`.
+pub const MCP_PROTOCOL_VERSION: &str = "2025-03-26";
+
+/// Older revisions we still accept from servers.
+pub const SUPPORTED_PROTOCOL_VERSIONS: &[&str] = &["2025-06-18", "2025-03-26", "2024-11-05"];
+
+pub const JSONRPC_VERSION: &str = "2.0";
+
+/// Standard JSON-RPC error codes.
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+pub const INTERNAL_ERROR: i64 = -32603;
+
+/// Outgoing JSON-RPC request (has an id, expects a response).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonRpcRequest {
+    pub jsonrpc: String,
+    pub id: Value,
+    pub method: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub params: Option<Value>,
+}
+
+impl JsonRpcRequest {
+    pub fn new(id: u64, method: &str, params: Option<Value>) -> Self {
+        Self {
+            jsonrpc: JSONRPC_VERSION.into(),
+            id: Value::from(id),
+            method: method.into(),
+            params,
+        }
+    }
+}
+
+/// Outgoing JSON-RPC notification (no id, no response).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonRpcNotification {
+    pub jsonrpc: String,
+    pub method: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub params: Option<Value>,
+}
+
+impl JsonRpcNotification {
+    pub fn new(method: &str, params: Option<Value>) -> Self {
+        Self {
+            jsonrpc: JSONRPC_VERSION.into(),
+            method: method.into(),
+            params,
+        }
+    }
+}
+
+/// JSON-RPC error object.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonRpcError {
+    pub code: i64,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+/// JSON-RPC response carrying either `result` or `error`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonRpcResponse {
+    pub jsonrpc: String,
+    pub id: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<JsonRpcError>,
+}
+
+impl JsonRpcResponse {
+    pub fn success(id: Value, result: Value) -> Self {
+        Self {
+            jsonrpc: JSONRPC_VERSION.into(),
+            id,
+            result: Some(result),
+            error: None,
+        }
+    }
+
+    pub fn failure(id: Value, code: i64, message: impl Into<String>) -> Self {
+        Self {
+            jsonrpc: JSONRPC_VERSION.into(),
+            id,
+            result: None,
+            error: Some(JsonRpcError {
+                code,
+                message: message.into(),
+                data: None,
+            }),
+        }
+    }
+
+    /// Convert into the `result` payload, surfacing JSON-RPC errors.
+    pub fn into_result(self) -> anyhow::Result<Value> {
+        if let Some(error) = self.error {
+            anyhow::bail!("MCP error {}: {}", error.code, error.message);
+        }
+        Ok(self.result.unwrap_or(Value::Null))
+    }
+}
+
+/// Any message that can arrive on an MCP transport.
+#[derive(Debug, Clone)]
+pub enum IncomingMessage {
+    /// Response to one of our requests.
+    Response(JsonRpcResponse),
+    /// Request initiated by the peer (e.g. `ping`).
+    Request(JsonRpcRequest),
+    /// Notification from the peer (e.g. `notifications/tools/list_changed`).
+    Notification(JsonRpcNotification),
+}
+
+impl IncomingMessage {
+    /// Classify a raw JSON value by the presence of `id` and `method`.
+    pub fn from_value(value: Value) -> anyhow::Result<Self> {
+        let has_id = value.get("id").is_some_and(|id| !id.is_null());
+        let has_method = value.get("method").is_some();
+        match (has_id, has_method) {
+            (true, true) => Ok(Self::Request(serde_json::from_value(value)?)),
+            (true, false) => Ok(Self::Response(serde_json::from_value(value)?)),
+            (false, true) => Ok(Self::Notification(serde_json::from_value(value)?)),
+            (false, false) => anyhow::bail!("Message is neither a request nor a response"),
+        }
+    }
+}
+
+/// Tool description returned by `tools/list`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpToolDef {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default = "default_input_schema")]
+    pub input_schema: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub annotations: Option<McpToolAnnotations>,
+}
+
+fn default_input_schema() -> Value {
+    serde_json::json!({ "type": "object", "properties": {} })
+}
+
+/// Behavioural hints a server may attach to a tool.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpToolAnnotations {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read_only_hint: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub destructive_hint: Option<bool>,
+}
+
+/// Result page of `tools/list`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListToolsResult {
+    #[serde(default)]
+    pub tools: Vec<McpToolDef>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// One content block in a `tools/call` result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum McpContent {
+    Text {
+        text: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    Image {
+        data: String,
+        mime_type: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    Audio {
+        data: String,
+        mime_type: String,
+    },
+    Resource {
+        resource: Value,
+    },
+    #[serde(other)]
+    Unknown,
+}
+
+/// Result of `tools/call`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallToolResult {
+    #[serde(default)]
+    pub content: Vec<McpContent>,
+    #[serde(default)]
+    pub is_error: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub structured_content: Option<Value>,
+}
+
+impl CallToolResult {
+    /// Flatten content blocks into the plain text the agent loop consumes.
+    pub fn render_text(&self) -> String {
+        let mut parts: Vec<String> = self
+            .content
+            .iter()
+            .filter_map(|block| match block {
+                McpContent::Text { text } => Some(text.clone()),
+                McpContent::Image { mime_type, data } => {
+                    Some(format!("[image: {mime_type}, {} base64 bytes]", data.len()))
+                }
+                McpContent::Audio { mime_type, data } => {
+                    Some(format!("[audio: {mime_type}, {} base64 bytes]", data.len()))
+                }
+                McpContent::Resource { resource } => Some(
+                    resource
+                        .get("text")
+                        .and_then(Value::as_str)
+                        .map(str::to_string)
+                        .unwrap_or_else(|| resource.to_string()),
+                ),
+                McpContent::Unknown => None,
+            })
+            .collect();
+
+        if parts.is_empty() {
+            if let Some(structured) = &self.structured_content {
+                parts.push(structured.to_string());
+            }
+        }
+
+        parts.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn request_serializes_without_null_params() {
+        let req = JsonRpcRequest::new(7, "tools/list", None);
+        let json = serde_json::to_value(&req).unwrap();
+        assert_eq!(json["jsonrpc"], "2.0");
+        assert_eq!(json["id"], 7);
+        assert!(json.get("params").is_none());
+    }
+
+    #[test]
+    fn incoming_message_classification() {
+        let resp = IncomingMessage::from_value(json!({"jsonrpc": "2.0", "id": 1, "result": {}}));
+        assert!(matches!(resp, Ok(IncomingMessage::Response(_))));
+
+        let req = IncomingMessage::from_value(json!({"jsonrpc": "2.0", "id": 2, "method": "ping"}));
+        assert!(matches!(req, Ok(IncomingMessage::Request(_))));
+
+        let note = IncomingMessage::from_value(
+            json!({"jsonrpc": "2.0", "method": "notifications/tools/list_changed"}),
+        );
+        assert!(matches!(note, Ok(IncomingMessage::Notification(_))));
+
+        assert!(IncomingMessage::from_value(json!({"jsonrpc": "2.0"})).is_err());
+    }
+
+    #[test]
+    fn response_error_surfaces_code_and_message() {
+        let resp = JsonRpcResponse::failure(json!(1), METHOD_NOT_FOUND, "no such method");
+        let err = resp.into_result().unwrap_err().to_string();
+        assert!(err.contains("-32601"));
+        assert!(err.contains("no such method"));
+    }
+
+    #[test]
+    fn tool_def_defaults_schema_when_missing() {
+        let def: McpToolDef = serde_json::from_value(json!({"name": "echo"})).unwrap();
+        assert_eq!(def.input_schema["type"], "object");
+        assert!(def.description.is_none());
+    }
+
+    #[test]
+    fn tool_def_parses_annotations() {
+        let def: McpToolDef = serde_json::from_value(json!({
+            "name": "search",
+            "inputSchema": {"type": "object"},
+            "annotations": {"readOnlyHint": true}
+        }))
+        .unwrap();
+        assert_eq!(def.annotations.unwrap().read_only_hint, Some(true));
+    }
+
+    #[test]
+    fn call_result_renders_mixed_content() {
+        let result: CallToolResult = serde_json::from_value(json!({
+            "content": [
+                {"type": "text", "text": "hello"},
+                {"type": "image", "data": "aGVsbG8=", "mimeType": "image/png"},
+                {"type": "resource", "resource": {"uri": "file:///a", "text": "body"}},
+                {"type": "something_new"}
+            ],
+            "isError": false
+        }))
+        .unwrap();
+        let text = result.render_text();
+        assert!(text.contains("hello"));
+        assert!(text.contains("[image: image/png"));
+        assert!(text.contains("body"));
+        assert!(!result.is_error);
+    }
+
+    #[test]
+    fn call_result_falls_back_to_structured_content() {
+        let result: CallToolResult = serde_json::from_value(json!({
+            "content": [],
+            "structuredContent": {"temperature": 21}
+        }))
+        .unwrap();
+        assert_eq!(result.render_text(), r#"{"temperature":21}"#);
+    }
+}
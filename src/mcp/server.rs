@@ -0,0 +1,675 @@
+//! MCP server: exposes ZeroClaw's tool registry to other agent hosts over
+//! stdio or streamable HTTP (`zeroclaw mcp serve`).
+//!
+//! Every `tools/call` runs through the same gates as the agent loop:
+//! `check_tool`, tripwire `check_input` on the arguments, and the
+//! [`ApprovalManager`]. There is no operator to prompt on this transport,
+//! so tools that would need interactive approval are refused unless listed
+//! in `autonomy.auto_approve`. Calls are written to the audit log like the
+//! agent loop's, and tool output is scrubbed of credentials.
+
+use super::protocol::{
+    IncomingMessage, JsonRpcResponse, INVALID_PARAMS, INVALID_REQUEST, MCP_PROTOCOL_VERSION,
+    METHOD_NOT_FOUND, PARSE_ERROR, SUPPORTED_PROTOCOL_VERSIONS,
+};
+use crate::agent::loop_::{elapsed_ms, scrub_credentials, tool_audit_event};
+use crate::approval::{ApprovalManager, ApprovalResponse};
+use crate::config::Config;
+use crate::security::audit;
+use crate::security::pairing::{is_public_bind, PairingGuard};
+use crate::security::tool_rules::RuleAction;
+use crate::security::SecurityPolicy;
+use crate::tools::schema::{CleaningStrategy, SchemaCleanr};
+use crate::tools::{self, Tool};
+use anyhow::Result;
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::post,
+    Json, Router,
+};
+use serde_json::{json, Value};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::sync::mpsc;
+use tower_http::limit::RequestBodyLimitLayer;
+use tower_http::timeout::TimeoutLayer;
+
+/// Largest HTTP request body accepted (1MB), as for the gateway's agent routes.
+const MAX_HTTP_BODY_SIZE: usize = 1_048_576;
+/// Tool calls can run long, so HTTP requests get the gateway's agent-route
+/// timeout (5 min) rather than the webhook one.
+const HTTP_REQUEST_TIMEOUT_SECS: u64 = 300;
+
+/// Channel name recorded in the approval audit log.
+const APPROVAL_CHANNEL: &str = "mcp";
+
+/// Serves a fixed tool registry over MCP.
+pub struct McpToolServer {
+    tools: Vec<Box<dyn Tool>>,
+    security: Arc<SecurityPolicy>,
+    approval: ApprovalManager,
+}
+
+impl McpToolServer {
+    pub fn new(
+        tools: Vec<Box<dyn Tool>>,
+        security: Arc<SecurityPolicy>,
+        approval: ApprovalManager,
+    ) -> Self {
+        Self {
+            tools,
+            security,
+            approval,
+        }
+    }
+
+    /// Build the same registry the agent uses, minus tools proxied from
+    /// other MCP servers (this process never connects to any).
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let runtime: Arc<dyn crate::runtime::RuntimeAdapter> =
+            Arc::from(crate::runtime::create_runtime(&config.runtime)?);
+        let security = Arc::new(SecurityPolicy::from_config(
+            &config.autonomy,
+            &config.workspace_dir,
+        ));
+        let mem: Arc<dyn crate::memory::Memory> =
+            Arc::from(crate::memory::create_memory_with_storage(
+                &config.memory,
+                Some(&config.storage.provider.config),
+                &config.workspace_dir,
+                config.api_key.as_deref(),
+            )?);
+        let (composio_key, composio_entity_id) = if config.composio.enabled {
+            (
+                config.composio.api_key.as_deref(),
+                Some(config.composio.entity_id.as_str()),
+            )
+        } else {
+            (None, None)
+        };
+        let tools = tools::all_tools_with_runtime(
+            Arc::new(config.clone()),
+            &security,
+            runtime,
+            mem,
+            composio_key,
+            composio_entity_id,
+            &config.browser,
+            &config.http_request,
+            &config.workspace_dir,
+            &config.agents,
+            config.api_key.as_deref(),
+            config,
+        );
+        Ok(Self::new(
+            tools,
+            security,
+            ApprovalManager::from_config(&config.autonomy),
+        ))
+    }
+
+    pub fn tool_names(&self) -> Vec<&str> {
+        self.tools.iter().map(|t| t.name()).collect()
+    }
+
+    /// Handle one JSON-RPC message. Returns `None` for notifications.
+    pub async fn handle(&self, message: Value) -> Option<JsonRpcResponse> {
+        let request = match IncomingMessage::from_value(message) {
+            Ok(IncomingMessage::Request(req)) => req,
+            Ok(IncomingMessage::Notification(_) | IncomingMessage::Response(_)) => return None,
+            Err(e) => {
+                return Some(JsonRpcResponse::failure(
+                    Value::Null,
+                    INVALID_REQUEST,
+                    e.to_string(),
+                ))
+            }
+        };
+
+        let params = request.params.unwrap_or(Value::Null);
+        let result = match request.method.as_str() {
+            "initialize" => Ok(Self::initialize_result(&params)),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(self.list_tools()),
+            "tools/call" => self.call_tool(&params).await,
+            other => Err((METHOD_NOT_FOUND, format!("Method not found: {other}"))),
+        };
+
+        Some(match result {
+            Ok(value) => JsonRpcResponse::success(request.id, value),
+            Err((code, message)) => JsonRpcResponse::failure(request.id, code, message),
+        })
+    }
+
+    fn initialize_result(params: &Value) -> Value {
+        let requested = params
+            .get("protocolVersion")
+            .and_then(Value::as_str)
+            .unwrap_or(MCP_PROTOCOL_VERSION);
+        let version = if SUPPORTED_PROTOCOL_VERSIONS.contains(&requested) {
+            requested
+        } else {
+            MCP_PROTOCOL_VERSION
+        };
+        json!({
+            "protocolVersion": version,
+            "capabilities": { "tools": { "listChanged": false } },
+            "serverInfo": {
+                "name": "zeroclaw",
+                "version": env!("CARGO_PKG_VERSION"),
+            }
+        })
+    }
+
+    fn list_tools(&self) -> Value {
+        // Tools the policy always refuses would only fail when called.
+        let tools: Vec<Value> = self
+            .tools
+            .iter()
+            .filter(|tool| self.security.check_tool(tool.name()).is_ok())
+            .map(|tool| {
+                let spec = tool.spec();
+                json!({
+                    "name": spec.name,
+                    "description": spec.description,
+                    "inputSchema": SchemaCleanr::clean(spec.parameters, CleaningStrategy::Conservative),
+                })
+            })
+            .collect();
+        json!({ "tools": tools })
+    }
+
+    async fn call_tool(&self, params: &Value) -> Result<Value, (i64, String)> {
+        let name = params
+            .get("name")
+            .and_then(Value::as_str)
+            .ok_or((INVALID_PARAMS, "Missing 'name' parameter".to_string()))?;
+        let args = params
+            .get("arguments")
+            .cloned()
+            .unwrap_or_else(|| json!({}));
+
+        let Some(tool) = self.tools.iter().find(|t| t.name() == name) else {
+            return Err((INVALID_PARAMS, format!("Unknown tool: {name}")));
+        };
+
+        let guardrail = self
+            .security
+            .check_tool(name)
+            .and_then(|()| self.security.check_input(&args.to_string()));
+        if let Err(reason) = guardrail {
+            audit::record(
+                &tool_audit_event(APPROVAL_CHANNEL, name, &args, false, false)
+                    .with_policy_violation()
+                    .with_result(false, None, 0, Some(reason.clone())),
+            );
+            return Ok(tool_error(&reason));
+        }
+        let rule = self.security.check_tool_args(name, &args, APPROVAL_CHANNEL);
+        match rule.action {
+            RuleAction::Allow => {}
+            RuleAction::Deny => return Ok(tool_error(&rule.reason)),
+            RuleAction::RequireApproval => {
+                return Ok(tool_error(&format!(
+                    "{}; policy requires approval, which MCP clients cannot give",
+                    rule.reason
+                )));
+            }
+        }
+        if self.approval.needs_approval(name) {
+            self.approval
+                .record_decision(name, &args, ApprovalResponse::No, APPROVAL_CHANNEL);
+            return Ok(tool_error(&format!(
+                "Tool '{name}' requires interactive approval, which MCP clients cannot give; \
+                 add it to autonomy.auto_approve to expose it"
+            )));
+        }
+
+        let audit_event = tool_audit_event(APPROVAL_CHANNEL, name, &args, false, true);
+        let start = Instant::now();
+        match tool.execute(args).await {
+            Ok(result) => {
+                audit::record(&audit_event.with_result(
+                    result.success,
+                    None,
+                    elapsed_ms(start),
+                    result.error.clone(),
+                ));
+                if result.success {
+                    Ok(json!({
+                        "content": [{ "type": "text", "text": scrub_credentials(&result.output) }],
+                        "isError": false,
+                    }))
+                } else {
+                    Ok(tool_error(&scrub_credentials(
+                        result
+                            .error
+                            .as_deref()
+                            .filter(|e| !e.is_empty())
+                            .unwrap_or(&result.output),
+                    )))
+                }
+            }
+            Err(e) => {
+                audit::record(&audit_event.with_result(
+                    false,
+                    None,
+                    elapsed_ms(start),
+                    Some(e.to_string()),
+                ));
+                Ok(tool_error(&scrub_credentials(&format!("{e:#}"))))
+            }
+        }
+    }
+}
+
+fn tool_error(message: &str) -> Value {
+    json!({
+        "content": [{ "type": "text", "text": message }],
+        "isError": true,
+    })
+}
+
+/// Parse a raw frame and dispatch it, mapping JSON errors to `PARSE_ERROR`.
+async fn handle_frame(server: &McpToolServer, frame: &str) -> Option<JsonRpcResponse> {
+    match serde_json::from_str::<Value>(frame) {
+        Ok(message) => server.handle(message).await,
+        Err(e) => Some(JsonRpcResponse::failure(
+            Value::Null,
+            PARSE_ERROR,
+            format!("Parse error: {e}"),
+        )),
+    }
+}
+
+// ── stdio ────────────────────────────────────────────────────────
+
+/// Serve newline-delimited JSON-RPC on stdin/stdout until stdin closes.
+pub async fn serve_stdio(server: Arc<McpToolServer>) -> Result<()> {
+    serve_streams(server, tokio::io::stdin(), tokio::io::stdout()).await
+}
+
+/// Stdio loop over arbitrary streams. Requests are handled concurrently so a
+/// slow tool call does not block `ping`.
+pub async fn serve_streams<R, W>(server: Arc<McpToolServer>, reader: R, mut writer: W) -> Result<()>
+where
+    R: tokio::io::AsyncRead + Unpin,
+    W: tokio::io::AsyncWrite + Unpin + Send + 'static,
+{
+    let (tx, mut rx) = mpsc::channel::<JsonRpcResponse>(64);
+    let writer_task = tokio::spawn(async move {
+        while let Some(response) = rx.recv().await {
+            let Ok(mut line) = serde_json::to_string(&response) else {
+                continue;
+            };
+            line.push('\n');
+            if writer.write_all(line.as_bytes()).await.is_err() || writer.flush().await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut lines = BufReader::new(reader).lines();
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let server = server.clone();
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            if let Some(response) = handle_frame(&server, &line).await {
+                let _ = tx.send(response).await;
+            }
+        });
+    }
+
+    drop(tx);
+    let _ = writer_task.await;
+    Ok(())
+}
+
+// ── Streamable HTTP ─────────────────────────────────────────────
+
+#[derive(Clone)]
+struct HttpState {
+    server: Arc<McpToolServer>,
+    pairing: Arc<PairingGuard>,
+}
+
+/// Serve MCP over streamable HTTP at `POST /mcp`, authenticated with the
+/// gateway's paired bearer tokens.
+pub async fn serve_http(
+    server: Arc<McpToolServer>,
+    host: &str,
+    port: u16,
+    config: &Config,
+) -> Result<()> {
+    if is_public_bind(host) && !config.gateway.allow_public_bind {
+        anyhow::bail!(
+            "🛑 Refusing to bind MCP server to {host}.\n\
+             Fix: use --host 127.0.0.1 (default), or set\n\
+             [gateway] allow_public_bind = true in config.toml (NOT recommended)."
+        );
+    }
+
+    let pairing = Arc::new(PairingGuard::new(
+        config.gateway.require_pairing,
+        &config.gateway.paired_tokens,
+    ));
+    if pairing.require_pairing() && !pairing.is_paired() {
+        anyhow::bail!(
+            "No paired tokens yet. Pair once with `zeroclaw gateway` (POST /pair), \
+             then send Authorization: Bearer <token> to the MCP endpoint."
+        );
+    }
+
+    let addr: SocketAddr = format!("{host}:{port}").parse()?;
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    let actual_port = listener.local_addr()?.port();
+    eprintln!("🦀 ZeroClaw MCP server listening on http://{host}:{actual_port}/mcp");
+
+    axum::serve(listener, http_router(server, pairing)).await?;
+    Ok(())
+}
+
+fn http_router(server: Arc<McpToolServer>, pairing: Arc<PairingGuard>) -> Router {
+    Router::new()
+        .route(
+            "/mcp",
+            post(handle_http_post)
+                .get(|| async { StatusCode::METHOD_NOT_ALLOWED })
+                .delete(|| async { StatusCode::METHOD_NOT_ALLOWED }),
+        )
+        .layer(RequestBodyLimitLayer::new(MAX_HTTP_BODY_SIZE))
+        .layer(TimeoutLayer::with_status_code(
+            StatusCode::REQUEST_TIMEOUT,
+            Duration::from_secs(HTTP_REQUEST_TIMEOUT_SECS),
+        ))
+        .with_state(HttpState { server, pairing })
+}
+
+async fn handle_http_post(
+    State(state): State<HttpState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|auth| auth.strip_prefix("Bearer "))
+        .unwrap_or("");
+    if !state.pairing.is_authenticated(token) {
+        return (
+            StatusCode::UNAUTHORIZED,
+            Json(json!({ "error": "Unauthorized — send Authorization: Bearer <token>" })),
+        )
+            .into_response();
+    }
+
+    let frame = String::from_utf8_lossy(&body);
+    match handle_frame(&state.server, &frame).await {
+        Some(response) => Json(response).into_response(),
+        None => StatusCode::ACCEPTED.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AutonomyConfig;
+    use crate::mcp::protocol::McpToolDef;
+    use crate::mcp::transport::StdioTransport;
+    use crate::mcp::McpClient;
+    use crate::security::AutonomyLevel;
+    use crate::tools::ToolResult;
+    use async_trait::async_trait;
+
+    struct UpperTool;
+
+    #[async_trait]
+    impl Tool for UpperTool {
+        fn name(&self) -> &str {
+            "upper"
+        }
+
+        fn description(&self) -> &str {
+            "Uppercase text"
+        }
+
+        fn parameters_schema(&self) -> Value {
+            json!({
+                "type": "object",
+                "properties": { "text": { "type": "string" } },
+                "required": ["text"]
+            })
+        }
+
+        async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+            Ok(ToolResult {
+                success: true,
+                output: args["text"].as_str().unwrap_or_default().to_uppercase(),
+                error: None,
+            })
+        }
+    }
+
+    fn test_server(autonomy: &AutonomyConfig, security: SecurityPolicy) -> McpToolServer {
+        McpToolServer::new(
+            vec![Box::new(UpperTool)],
+            Arc::new(security),
+            ApprovalManager::from_config(autonomy),
+        )
+    }
+
+    fn full_autonomy() -> AutonomyConfig {
+        AutonomyConfig {
+            level: AutonomyLevel::Full,
+            ..AutonomyConfig::default()
+        }
+    }
+
+    async fn call(server: &McpToolServer, method: &str, params: Value) -> JsonRpcResponse {
+        server
+            .handle(json!({"jsonrpc": "2.0", "id": 1, "method": method, "params": params}))
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn initialize_negotiates_version() {
+        let server = test_server(&full_autonomy(), SecurityPolicy::default());
+        let result = call(
+            &server,
+            "initialize",
+            json!({"protocolVersion": "2024-11-05"}),
+        )
+        .await
+        .into_result()
+        .unwrap();
+        assert_eq!(result["protocolVersion"], "2024-11-05");
+        assert_eq!(result["serverInfo"]["name"], "zeroclaw");
+
+        let result = call(
+            &server,
+            "initialize",
+            json!({"protocolVersion": "1999-01-01"}),
+        )
+        .await
+        .into_result()
+        .unwrap();
+        assert_eq!(result["protocolVersion"], MCP_PROTOCOL_VERSION);
+    }
+
+    #[tokio::test]
+    async fn notifications_get_no_response() {
+        let server = test_server(&full_autonomy(), SecurityPolicy::default());
+        let reply = server
+            .handle(json!({"jsonrpc": "2.0", "method": "notifications/initialized"}))
+            .await;
+        assert!(reply.is_none());
+    }
+
+    #[tokio::test]
+    async fn unknown_method_and_tool_are_errors() {
+        let server = test_server(&full_autonomy(), SecurityPolicy::default());
+        let reply = call(&server, "resources/list", json!({})).await;
+        assert_eq!(reply.error.unwrap().code, METHOD_NOT_FOUND);
+
+        let reply = call(&server, "tools/call", json!({"name": "nope"})).await;
+        assert_eq!(reply.error.unwrap().code, INVALID_PARAMS);
+    }
+
+    #[tokio::test]
+    async fn call_output_is_scrubbed_of_credentials() {
+        let server = test_server(&full_autonomy(), SecurityPolicy::default());
+        let result = call(
+            &server,
+            "tools/call",
+            json!({"name": "upper", "arguments": {"text": "api_key=sk1234567890abcdef"}}),
+        )
+        .await
+        .into_result()
+        .unwrap();
+        assert_eq!(result["isError"], false);
+        let text = result["content"][0]["text"].as_str().unwrap();
+        assert!(text.contains("[REDACTED]"), "{text}");
+        assert!(!text.contains("SK1234567890ABCDEF"));
+    }
+
+    #[tokio::test]
+    async fn call_respects_agent_deny_list() {
+        let security = SecurityPolicy {
+            agent_denied_tools: vec!["upper".into()],
+            ..SecurityPolicy::default()
+        };
+        let server = test_server(&full_autonomy(), security);
+        let result = call(
+            &server,
+            "tools/call",
+            json!({"name": "upper", "arguments": {"text": "x"}}),
+        )
+        .await
+        .into_result()
+        .unwrap();
+        assert_eq!(result["isError"], true);
+    }
+
+    #[tokio::test]
+    async fn list_hides_tools_the_policy_denies() {
+        let security = SecurityPolicy {
+            agent_denied_tools: vec!["upper".into()],
+            ..SecurityPolicy::default()
+        };
+        let server = test_server(&full_autonomy(), security);
+        let result = call(&server, "tools/list", json!({}))
+            .await
+            .into_result()
+            .unwrap();
+        assert_eq!(result["tools"], json!([]));
+    }
+
+    #[tokio::test]
+    async fn call_refuses_tools_needing_interactive_approval() {
+        let supervised = AutonomyConfig {
+            level: AutonomyLevel::Supervised,
+            auto_approve: vec![],
+            ..AutonomyConfig::default()
+        };
+        let server = test_server(&supervised, SecurityPolicy::default());
+        let result = call(
+            &server,
+            "tools/call",
+            json!({"name": "upper", "arguments": {"text": "x"}}),
+        )
+        .await
+        .into_result()
+        .unwrap();
+        assert_eq!(result["isError"], true);
+        assert!(result["content"][0]["text"]
+            .as_str()
+            .unwrap()
+            .contains("auto_approve"));
+
+        let log = server.approval.audit_log();
+        assert_eq!(log.len(), 1);
+        assert_eq!(log[0].channel, "mcp");
+        assert_eq!(log[0].decision, ApprovalResponse::No);
+    }
+
+    #[tokio::test]
+    async fn round_trips_through_mcp_client() {
+        let server = Arc::new(test_server(&full_autonomy(), SecurityPolicy::default()));
+        let (client_io, server_io) = tokio::io::duplex(64 * 1024);
+        let (client_read, client_write) = tokio::io::split(client_io);
+        let (server_read, server_write) = tokio::io::split(server_io);
+        tokio::spawn(serve_streams(server, server_read, server_write));
+
+        let transport = StdioTransport::from_streams("self", client_read, client_write);
+        let client = McpClient::initialize(
+            "self",
+            Box::new(transport),
+            Duration::from_secs(5),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap();
+
+        let tools: Vec<McpToolDef> = client.list_tools().await.unwrap();
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].name, "upper");
+        assert_eq!(tools[0].input_schema["required"][0], "text");
+
+        let result = client
+            .call_tool("upper", json!({"text": "hello"}))
+            .await
+            .unwrap();
+        assert!(!result.is_error);
+        assert_eq!(result.render_text(), "HELLO");
+    }
+
+    #[tokio::test]
+    async fn http_requires_bearer_token() {
+        let server = Arc::new(test_server(&full_autonomy(), SecurityPolicy::default()));
+        let pairing = Arc::new(PairingGuard::new(true, &["zc_test_token".to_string()]));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, http_router(server, pairing))
+                .await
+                .unwrap();
+        });
+
+        let url = format!("http://{addr}/mcp");
+        let body = json!({"jsonrpc": "2.0", "id": 1, "method": "tools/list"});
+        let client = reqwest::Client::new();
+
+        let denied = client.post(&url).json(&body).send().await.unwrap();
+        assert_eq!(denied.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+        let ok: Value = client
+            .post(&url)
+            .bearer_auth("zc_test_token")
+            .json(&body)
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(ok["result"]["tools"][0]["name"], "upper");
+
+        let oversized = client
+            .post(&url)
+            .bearer_auth("zc_test_token")
+            .body(vec![b' '; MAX_HTTP_BODY_SIZE + 1])
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(oversized.status(), reqwest::StatusCode::PAYLOAD_TOO_LARGE);
+    }
+}
@@ -0,0 +1,630 @@
+//! MCP transports: newline-delimited JSON-RPC over a child process's stdio,
+//! and the streamable-HTTP transport (JSON or SSE responses over POST).
+
+use super::protocol::{
+    IncomingMessage, JsonRpcNotification, JsonRpcRequest, JsonRpcResponse, METHOD_NOT_FOUND,
+};
+use crate::config::McpServerConfig;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::sync::oneshot;
+
+/// Environment variables passed to stdio MCP servers in addition to the
+/// server's configured `env`. Mirrors the shell tool: never API keys.
+const SAFE_ENV_VARS: &[&str] = &[
+    "PATH", "HOME", "TERM", "LANG", "LC_ALL", "LC_CTYPE", "USER", "SHELL", "TMPDIR",
+];
+
+/// Maximum size of a single JSON-RPC line accepted from a stdio server (8MB).
+const MAX_LINE_BYTES: usize = 8 * 1024 * 1024;
+
+/// Session header used by the streamable-HTTP transport.
+const MCP_SESSION_HEADER: &str = "Mcp-Session-Id";
+
+/// Bidirectional JSON-RPC channel to one MCP peer.
+#[async_trait]
+pub trait McpTransport: Send + Sync {
+    /// Send a request and wait for the matching response.
+    async fn request(&self, request: JsonRpcRequest, timeout: Duration) -> Result<JsonRpcResponse>;
+
+    /// Send a notification (fire-and-forget).
+    async fn notify(&self, notification: JsonRpcNotification) -> Result<()>;
+
+    /// Tear down the connection (kills stdio children).
+    async fn close(&self);
+}
+
+type PendingMap = Arc<Mutex<HashMap<String, oneshot::Sender<JsonRpcResponse>>>>;
+type SharedWriter = Arc<tokio::sync::Mutex<Box<dyn AsyncWrite + Send + Unpin>>>;
+
+fn id_key(id: &Value) -> String {
+    match id {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Read one newline-terminated line into `buf`, buffering at most `limit`
+/// bytes. The rest of a longer line is consumed and discarded so a peer
+/// cannot grow the buffer without bound. Returns `None` at EOF, otherwise
+/// whether the line was over the limit (`buf` is then empty).
+async fn read_capped_line<R>(
+    reader: &mut R,
+    buf: &mut Vec<u8>,
+    limit: usize,
+) -> std::io::Result<Option<bool>>
+where
+    R: AsyncBufRead + Unpin,
+{
+    buf.clear();
+    let mut consumed = 0;
+    let mut overflowed = false;
+    loop {
+        let available = reader.fill_buf().await?;
+        if available.is_empty() {
+            return Ok((consumed > 0).then_some(overflowed));
+        }
+        let newline = available.iter().position(|&b| b == b'\n');
+        let chunk = newline.map_or(available, |i| &available[..=i]);
+        if !overflowed && buf.len() + chunk.len() <= limit {
+            buf.extend_from_slice(chunk);
+        } else {
+            overflowed = true;
+            buf.clear();
+        }
+        let used = chunk.len();
+        reader.consume(used);
+        consumed += used;
+        if newline.is_some() {
+            return Ok(Some(overflowed));
+        }
+    }
+}
+
+async fn write_line(writer: &SharedWriter, value: &impl serde::Serialize) -> Result<()> {
+    let mut line = serde_json::to_vec(value)?;
+    line.push(b'\n');
+    let mut guard = writer.lock().await;
+    guard.write_all(&line).await?;
+    guard.flush().await?;
+    Ok(())
+}
+
+// ── Stdio ────────────────────────────────────────────────────────
+
+/// Newline-delimited JSON-RPC over an async reader/writer pair.
+///
+/// Usually backed by a spawned child process; tests wire it to in-memory
+/// duplex streams instead.
+pub struct StdioTransport {
+    label: String,
+    writer: SharedWriter,
+    pending: PendingMap,
+    reader_task: tokio::task::JoinHandle<()>,
+    child: tokio::sync::Mutex<Option<tokio::process::Child>>,
+}
+
+impl StdioTransport {
+    /// Spawn the configured command and speak MCP over its stdin/stdout.
+    pub fn spawn(server: &McpServerConfig, workspace_dir: &std::path::Path) -> Result<Self> {
+        let command = server
+            .command
+            .as_deref()
+            .map(str::trim)
+            .filter(|c| !c.is_empty())
+            .with_context(|| format!("MCP server '{}' has no command configured", server.name))?;
+
+        let mut cmd = tokio::process::Command::new(command);
+        cmd.args(&server.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+
+        let cwd = server
+            .cwd
+            .as_deref()
+            .map(|dir| workspace_dir.join(shellexpand::tilde(dir).as_ref()))
+            .unwrap_or_else(|| workspace_dir.to_path_buf());
+        if cwd.is_dir() {
+            cmd.current_dir(cwd);
+        }
+
+        cmd.env_clear();
+        for var in SAFE_ENV_VARS {
+            if let Ok(val) = std::env::var(var) {
+                cmd.env(var, val);
+            }
+        }
+        cmd.envs(&server.env);
+
+        let mut child = cmd
+            .spawn()
+            .with_context(|| format!("Failed to spawn MCP server '{}' ({command})", server.name))?;
+
+        let stdout = child.stdout.take().context("MCP child has no stdout")?;
+        let stdin = child.stdin.take().context("MCP child has no stdin")?;
+        if let Some(stderr) = child.stderr.take() {
+            let label = server.name.clone();
+            tokio::spawn(async move {
+                let mut lines = BufReader::new(stderr).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    tracing::debug!(server = %label, "mcp stderr: {line}");
+                }
+            });
+        }
+
+        Ok(Self::build(&server.name, stdout, stdin, Some(child)))
+    }
+
+    /// Build a transport over arbitrary streams (used for tests and embedding).
+    pub fn from_streams<R, W>(label: &str, reader: R, writer: W) -> Self
+    where
+        R: AsyncRead + Send + Unpin + 'static,
+        W: AsyncWrite + Send + Unpin + 'static,
+    {
+        Self::build(label, reader, writer, None)
+    }
+
+    fn build<R, W>(label: &str, reader: R, writer: W, child: Option<tokio::process::Child>) -> Self
+    where
+        R: AsyncRead + Send + Unpin + 'static,
+        W: AsyncWrite + Send + Unpin + 'static,
+    {
+        let writer: SharedWriter = Arc::new(tokio::sync::Mutex::new(Box::new(writer)));
+        let pending: PendingMap = Arc::new(Mutex::new(HashMap::new()));
+
+        let reader_task = tokio::spawn(Self::read_loop(
+            label.to_string(),
+            reader,
+            Arc::clone(&writer),
+            Arc::clone(&pending),
+        ));
+
+        Self {
+            label: label.to_string(),
+            writer,
+            pending,
+            reader_task,
+            child: tokio::sync::Mutex::new(child),
+        }
+    }
+
+    async fn read_loop<R>(label: String, reader: R, writer: SharedWriter, pending: PendingMap)
+    where
+        R: AsyncRead + Send + Unpin + 'static,
+    {
+        let mut reader = BufReader::new(reader);
+        let mut line = Vec::new();
+        loop {
+            let overflowed = match read_capped_line(&mut reader, &mut line, MAX_LINE_BYTES).await {
+                Ok(None) => break,
+                Ok(Some(overflowed)) => overflowed,
+                Err(e) => {
+                    tracing::warn!(server = %label, "MCP stdio read failed: {e}");
+                    break;
+                }
+            };
+            if overflowed {
+                tracing::warn!(server = %label, limit = MAX_LINE_BYTES, "Dropping oversized MCP message");
+                continue;
+            }
+            let Ok(text) = std::str::from_utf8(&line) else {
+                tracing::debug!(server = %label, "Ignoring non-UTF-8 MCP output");
+                continue;
+            };
+            let trimmed = text.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+
+            let value: Value = match serde_json::from_str(trimmed) {
+                Ok(v) => v,
+                Err(e) => {
+                    tracing::debug!(server = %label, "Ignoring non-JSON MCP output: {e}");
+                    continue;
+                }
+            };
+
+            match IncomingMessage::from_value(value) {
+                Ok(IncomingMessage::Response(resp)) => {
+                    let sender = pending.lock().remove(&id_key(&resp.id));
+                    if let Some(sender) = sender {
+                        let _ = sender.send(resp);
+                    }
+                }
+                Ok(IncomingMessage::Request(req)) => {
+                    let reply = if req.method == "ping" {
+                        JsonRpcResponse::success(req.id, serde_json::json!({}))
+                    } else {
+                        JsonRpcResponse::failure(
+                            req.id,
+                            METHOD_NOT_FOUND,
+                            format!("Client does not support '{}'", req.method),
+                        )
+                    };
+                    if let Err(e) = write_line(&writer, &reply).await {
+                        tracing::warn!(server = %label, "Failed to answer MCP request: {e}");
+                    }
+                }
+                Ok(IncomingMessage::Notification(note)) => {
+                    tracing::debug!(server = %label, method = %note.method, "MCP notification");
+                }
+                Err(e) => {
+                    tracing::debug!(server = %label, "Ignoring malformed MCP message: {e}");
+                }
+            }
+        }
+
+        // Wake every waiter so callers fail fast instead of timing out.
+        pending.lock().clear();
+    }
+}
+
+#[async_trait]
+impl McpTransport for StdioTransport {
+    async fn request(&self, request: JsonRpcRequest, timeout: Duration) -> Result<JsonRpcResponse> {
+        if self.reader_task.is_finished() {
+            anyhow::bail!("MCP server '{}' connection is closed", self.label);
+        }
+
+        let key = id_key(&request.id);
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().insert(key.clone(), tx);
+
+        if let Err(e) = write_line(&self.writer, &request).await {
+            self.pending.lock().remove(&key);
+            return Err(e.context(format!("Failed to write to MCP server '{}'", self.label)));
+        }
+
+        match tokio::time::timeout(timeout, rx).await {
+            Ok(Ok(resp)) => Ok(resp),
+            Ok(Err(_)) => anyhow::bail!("MCP server '{}' closed the connection", self.label),
+            Err(_) => {
+                self.pending.lock().remove(&key);
+                anyhow::bail!(
+                    "MCP server '{}' timed out after {}s on '{}'",
+                    self.label,
+                    timeout.as_secs(),
+                    request.method
+                )
+            }
+        }
+    }
+
+    async fn notify(&self, notification: JsonRpcNotification) -> Result<()> {
+        write_line(&self.writer, &notification).await
+    }
+
+    async fn close(&self) {
+        self.reader_task.abort();
+        if let Some(mut child) = self.child.lock().await.take() {
+            let _ = child.kill().await;
+        }
+    }
+}
+
+impl Drop for StdioTransport {
+    fn drop(&mut self) {
+        self.reader_task.abort();
+    }
+}
+
+// ── Streamable HTTP ──────────────────────────────────────────────
+
+/// Streamable-HTTP transport: every message is a POST; responses come back
+/// either as a JSON body or as an SSE stream carrying the response.
+pub struct HttpTransport {
+    label: String,
+    url: String,
+    headers: HashMap<String, String>,
+    session_id: Mutex<Option<String>>,
+}
+
+impl HttpTransport {
+    pub fn new(server: &McpServerConfig) -> Result<Self> {
+        let url = server
+            .url
+            .as_deref()
+            .map(str::trim)
+            .filter(|u| !u.is_empty())
+            .with_context(|| format!("MCP server '{}' has no url configured", server.name))?;
+        let parsed = reqwest::Url::parse(url)
+            .with_context(|| format!("MCP server '{}' has an invalid url", server.name))?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            anyhow::bail!(
+                "MCP server '{}' url must use http or https (got {})",
+                server.name,
+                parsed.scheme()
+            );
+        }
+
+        Ok(Self {
+            label: server.name.clone(),
+            url: url.to_string(),
+            headers: server.headers.clone(),
+            session_id: Mutex::new(None),
+        })
+    }
+
+    fn client(timeout: Duration) -> reqwest::Client {
+        crate::config::build_runtime_proxy_client_with_timeouts(
+            "tool.mcp",
+            timeout.as_secs().max(1),
+            10,
+        )
+    }
+
+    fn build_post(
+        &self,
+        timeout: Duration,
+        body: &impl serde::Serialize,
+    ) -> reqwest::RequestBuilder {
+        let mut req = Self::client(timeout)
+            .post(&self.url)
+            .header(
+                reqwest::header::ACCEPT,
+                "application/json, text/event-stream",
+            )
+            .json(body);
+        for (name, value) in &self.headers {
+            req = req.header(name.as_str(), value.as_str());
+        }
+        if let Some(session) = self.session_id.lock().clone() {
+            req = req.header(MCP_SESSION_HEADER, session);
+        }
+        req
+    }
+
+    fn remember_session(&self, response: &reqwest::Response) {
+        if let Some(session) = response
+            .headers()
+            .get(MCP_SESSION_HEADER)
+            .and_then(|v| v.to_str().ok())
+        {
+            *self.session_id.lock() = Some(session.to_string());
+        }
+    }
+}
+
+/// Pull the response matching `id` out of an SSE body.
+pub(crate) fn find_response_in_sse(body: &str, id: &Value) -> Option<JsonRpcResponse> {
+    let wanted = id_key(id);
+    let mut data = String::new();
+    let mut events: Vec<String> = Vec::new();
+    for line in body.lines() {
+        if line.is_empty() {
+            if !data.is_empty() {
+                events.push(std::mem::take(&mut data));
+            }
+            continue;
+        }
+        if let Some(rest) = line.strip_prefix("data:") {
+            if !data.is_empty() {
+                data.push('\n');
+            }
+            data.push_str(rest.strip_prefix(' ').unwrap_or(rest));
+        }
+    }
+    if !data.is_empty() {
+        events.push(data);
+    }
+
+    events.into_iter().find_map(|event| {
+        let value: Value = serde_json::from_str(&event).ok()?;
+        match IncomingMessage::from_value(value).ok()? {
+            IncomingMessage::Response(resp) if id_key(&resp.id) == wanted => Some(resp),
+            _ => None,
+        }
+    })
+}
+
+#[async_trait]
+impl McpTransport for HttpTransport {
+    async fn request(&self, request: JsonRpcRequest, timeout: Duration) -> Result<JsonRpcResponse> {
+        let response = self
+            .build_post(timeout, &request)
+            .send()
+            .await
+            .with_context(|| format!("MCP server '{}' request failed", self.label))?;
+        self.remember_session(&response);
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!(
+                "MCP server '{}' returned HTTP {status}: {}",
+                self.label,
+                crate::util::truncate_with_ellipsis(&body, 300)
+            );
+        }
+
+        let is_sse = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|ct| ct.starts_with("text/event-stream"));
+        let body = response.text().await?;
+
+        if is_sse {
+            find_response_in_sse(&body, &request.id).with_context(|| {
+                format!(
+                    "MCP server '{}' SSE stream ended without a response to '{}'",
+                    self.label, request.method
+                )
+            })
+        } else {
+            serde_json::from_str(&body)
+                .with_context(|| format!("MCP server '{}' returned invalid JSON-RPC", self.label))
+        }
+    }
+
+    async fn notify(&self, notification: JsonRpcNotification) -> Result<()> {
+        let response = self
+            .build_post(Duration::from_secs(30), &notification)
+            .send()
+            .await?;
+        self.remember_session(&response);
+        if !response.status().is_success() {
+            anyhow::bail!(
+                "MCP server '{}' rejected notification: HTTP {}",
+                self.label,
+                response.status()
+            );
+        }
+        Ok(())
+    }
+
+    async fn close(&self) {
+        let session = self.session_id.lock().take();
+        if let Some(session) = session {
+            let mut req = Self::client(Duration::from_secs(5))
+                .delete(&self.url)
+                .header(MCP_SESSION_HEADER, session);
+            for (name, value) in &self.headers {
+                req = req.header(name.as_str(), value.as_str());
+            }
+            let _ = req.send().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn sse_body_yields_matching_response() {
+        let body = "event: message\n\
+                    data: {\"jsonrpc\":\"2.0\",\"method\":\"notifications/progress\"}\n\
+                    \n\
+                    event: message\n\
+                    data: {\"jsonrpc\":\"2.0\",\"id\":3,\"result\":{\"ok\":true}}\n\n";
+        let resp = find_response_in_sse(body, &json!(3)).unwrap();
+        assert_eq!(resp.result.unwrap()["ok"], true);
+        assert!(find_response_in_sse(body, &json!(4)).is_none());
+    }
+
+    #[tokio::test]
+    async fn capped_line_reader_discards_oversized_lines() {
+        let mut input: &[u8] = b"0123456789abcdef\n{\"ok\":1}\ntail";
+        let mut line = Vec::new();
+
+        assert_eq!(
+            read_capped_line(&mut input, &mut line, 10).await.unwrap(),
+            Some(true)
+        );
+        assert!(line.is_empty());
+        assert_eq!(
+            read_capped_line(&mut input, &mut line, 10).await.unwrap(),
+            Some(false)
+        );
+        assert_eq!(line, b"{\"ok\":1}\n");
+        assert_eq!(
+            read_capped_line(&mut input, &mut line, 10).await.unwrap(),
+            Some(false)
+        );
+        assert_eq!(line, b"tail");
+        assert_eq!(
+            read_capped_line(&mut input, &mut line, 10).await.unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn http_transport_rejects_non_http_urls() {
+        let server = McpServerConfig {
+            name: "bad".into(),
+            url: Some("file:///etc/passwd".into()),
+            ..McpServerConfig::default()
+        };
+        assert!(HttpTransport::new(&server).is_err());
+    }
+
+    #[test]
+    fn stdio_spawn_requires_command() {
+        let server = McpServerConfig {
+            name: "empty".into(),
+            ..McpServerConfig::default()
+        };
+        let err = StdioTransport::spawn(&server, std::path::Path::new("."))
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("no command"));
+    }
+
+    #[tokio::test]
+    async fn stdio_transport_routes_responses_and_answers_ping() {
+        let (client_io, server_io) = tokio::io::duplex(4096);
+        let (client_read, client_write) = tokio::io::split(client_io);
+        let (server_read, mut server_write) = tokio::io::split(server_io);
+        let transport = StdioTransport::from_streams("test", client_read, client_write);
+
+        let server = tokio::spawn(async move {
+            let mut lines = BufReader::new(server_read).lines();
+            // Ask the client for a ping before answering its request.
+            server_write
+                .write_all(b"{\"jsonrpc\":\"2.0\",\"id\":\"srv-1\",\"method\":\"ping\"}\n")
+                .await
+                .unwrap();
+            let mut saw_ping_reply = false;
+            let mut request_id = Value::Null;
+            while let Ok(Some(line)) = lines.next_line().await {
+                let msg: Value = serde_json::from_str(&line).unwrap();
+                if msg["id"] == "srv-1" {
+                    saw_ping_reply = true;
+                } else if msg["method"] == "tools/list" {
+                    request_id = msg["id"].clone();
+                }
+                if saw_ping_reply && !request_id.is_null() {
+                    break;
+                }
+            }
+            let reply = json!({"jsonrpc": "2.0", "id": request_id, "result": {"tools": []}});
+            server_write
+                .write_all(format!("{reply}\n").as_bytes())
+                .await
+                .unwrap();
+            saw_ping_reply
+        });
+
+        let resp = transport
+            .request(
+                JsonRpcRequest::new(1, "tools/list", None),
+                Duration::from_secs(5),
+            )
+            .await
+            .unwrap();
+        assert!(resp.result.unwrap()["tools"].is_array());
+        assert!(server.await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn stdio_transport_fails_fast_when_peer_closes() {
+        let (client_io, server_io) = tokio::io::duplex(1024);
+        let (client_read, client_write) = tokio::io::split(client_io);
+        let transport = StdioTransport::from_streams("closing", client_read, client_write);
+        drop(server_io);
+
+        let err = transport
+            .request(
+                JsonRpcRequest::new(1, "tools/list", None),
+                Duration::from_secs(5),
+            )
+            .await
+            .unwrap_err();
+        let msg = err.to_string();
+        assert!(
+            msg.contains("closed") || msg.contains("Failed to write"),
+            "{msg}"
+        );
+    }
+}
@@ -106,6 +106,68 @@ pub fn chunk_markdown(text: &str, max_tokens: usize) -> Vec<Chunk> {
     chunks
 }
 
+/// A chunk of whole source lines with its 1-based, inclusive line range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineChunk {
+    pub start_line: usize,
+    pub end_line: usize,
+    pub content: String,
+}
+
+/// Split text into chunks of whole lines under `max_tokens`, recording the
+/// line range each chunk covers so retrieval can cite it.
+///
+/// Breaks before markdown headings when `markdown` is set, and at blank lines
+/// once a chunk is half full. A single line longer than the limit is kept
+/// whole.
+pub fn chunk_lines(text: &str, max_tokens: usize, markdown: bool) -> Vec<LineChunk> {
+    let max_chars = max_tokens.max(1) * 4;
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    let mut start_line = 0;
+    let mut end_line = 0;
+
+    for (index, line) in text.lines().enumerate() {
+        let number = index + 1;
+        let blank = line.trim().is_empty();
+        if !current.is_empty() {
+            let heading = markdown
+                && (line.starts_with("# ") || line.starts_with("## ") || line.starts_with("### "));
+            let full = current.len() + line.len() + 1 > max_chars;
+            let paragraph_break = blank && current.len() >= max_chars / 2;
+            if heading || full || paragraph_break {
+                chunks.push(LineChunk {
+                    start_line,
+                    end_line,
+                    content: current.trim_end().to_string(),
+                });
+                current.clear();
+            }
+        }
+        if current.is_empty() {
+            if blank {
+                continue;
+            }
+            start_line = number;
+        }
+        current.push_str(line);
+        current.push('\n');
+        if !blank {
+            end_line = number;
+        }
+    }
+
+    if !current.trim().is_empty() {
+        chunks.push(LineChunk {
+            start_line,
+            end_line,
+            content: current.trim_end().to_string(),
+        });
+    }
+
+    chunks
+}
+
 /// Split text into `(heading, body)` sections.
 fn split_on_headings(text: &str) -> Vec<(Option<String>, String)> {
     let mut sections = Vec::new();
@@ -374,4 +436,32 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn line_chunks_record_line_ranges() {
+        let text = "\n# Title\nIntro line.\n\n## Usage\nRun it.\nThen stop.\n\n";
+        let chunks = chunk_lines(text, 512, true);
+        assert_eq!(chunks.len(), 2);
+        assert_eq!((chunks[0].start_line, chunks[0].end_line), (2, 3));
+        assert_eq!(chunks[0].content, "# Title\nIntro line.");
+        assert_eq!((chunks[1].start_line, chunks[1].end_line), (5, 7));
+        assert_eq!(chunks[1].content, "## Usage\nRun it.\nThen stop.");
+    }
+
+    #[test]
+    fn line_chunks_respect_size_and_ignore_headings_outside_markdown() {
+        let text = (1..=40)
+            .map(|i| format!("# comment {i}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let chunks = chunk_lines(&text, 20, false);
+        assert!(chunks.len() > 1);
+        assert_eq!(chunks[0].start_line, 1);
+        assert_eq!(chunks.last().unwrap().end_line, 40);
+        for pair in chunks.windows(2) {
+            assert_eq!(pair[0].end_line + 1, pair[1].start_line);
+        }
+        assert!(chunks.iter().all(|chunk| chunk.content.len() <= 80));
+        assert!(chunk_lines("  \n\n", 512, false).is_empty());
+    }
 }
@@ -0,0 +1,552 @@
+//! `zeroclaw memory` — inspect, edit, export and import the configured
+//! memory backend through the `Memory` trait.
+
+use super::traits::{Memory, MemoryCategory, MemoryEntry};
+use super::{
+    consolidation, create_memory_with_storage, ingest, portable, CacheStats, ResponseCache,
+};
+use crate::config::{Config, MemoryConfig};
+use crate::util::truncate_with_ellipsis;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, NaiveDate, NaiveTime, TimeZone, Utc};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::Path;
+
+/// `search` pulls this many times `--limit` candidates when category or
+/// date filters are applied after recall.
+const SEARCH_FILTER_OVERSAMPLE: usize = 4;
+
+/// Resolved `MemoryFilterArgs`.
+#[derive(Debug, Default)]
+struct EntryFilter {
+    category: Option<MemoryCategory>,
+    session: Option<String>,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+}
+
+impl EntryFilter {
+    fn from_args(args: crate::MemoryFilterArgs) -> Result<Self> {
+        Ok(Self {
+            category: args.category.as_deref().map(portable::parse_category),
+            session: args.session,
+            since: args
+                .since
+                .as_deref()
+                .map(|value| parse_date_bound(value, false))
+                .transpose()?,
+            until: args
+                .until
+                .as_deref()
+                .map(|value| parse_date_bound(value, true))
+                .transpose()?,
+        })
+    }
+
+    /// Whether filters beyond the session (which backends apply) are set.
+    fn filters_after_recall(&self) -> bool {
+        self.category.is_some() || self.since.is_some() || self.until.is_some()
+    }
+
+    fn matches(&self, entry: &MemoryEntry) -> bool {
+        if self
+            .category
+            .as_ref()
+            .is_some_and(|category| &entry.category != category)
+        {
+            return false;
+        }
+        // Backends without sessions (markdown) ignore the session argument.
+        if self
+            .session
+            .as_deref()
+            .is_some_and(|session| entry.session_id.as_deref() != Some(session))
+        {
+            return false;
+        }
+        if self.since.is_none() && self.until.is_none() {
+            return true;
+        }
+        let Some(time) = entry_time(&entry.timestamp) else {
+            return false;
+        };
+        self.since.map_or(true, |since| time >= since)
+            && self.until.map_or(true, |until| time <= until)
+    }
+}
+
+/// Local midnight (or the last second of the day for `end_of_day`) of a
+/// `YYYY-MM-DD` date, or an exact RFC 3339 timestamp.
+pub(crate) fn parse_date_bound(value: &str, end_of_day: bool) -> Result<DateTime<Utc>> {
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(value) {
+        return Ok(timestamp.with_timezone(&Utc));
+    }
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .with_context(|| format!("invalid date '{value}' (expected YYYY-MM-DD or RFC 3339)"))?;
+    let time = if end_of_day {
+        NaiveTime::from_hms_opt(23, 59, 59)
+    } else {
+        NaiveTime::from_hms_opt(0, 0, 0)
+    }
+    .expect("valid wall-clock time");
+    Local
+        .from_local_datetime(&date.and_time(time))
+        .earliest()
+        .map(|local| local.with_timezone(&Utc))
+        .with_context(|| format!("date '{value}' does not exist in the local timezone"))
+}
+
+/// Entry timestamps are RFC 3339 (sqlite, postgres) or a bare date (markdown).
+fn entry_time(timestamp: &str) -> Option<DateTime<Utc>> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(timestamp) {
+        return Some(time.with_timezone(&Utc));
+    }
+    parse_date_bound(timestamp, false).ok()
+}
+
+fn entry_json(entry: &MemoryEntry) -> serde_json::Value {
+    serde_json::json!({
+        "id": entry.id,
+        "key": entry.key,
+        "content": entry.content,
+        "category": entry.category.to_string(),
+        "timestamp": entry.timestamp,
+        "session_id": entry.session_id,
+        "score": entry.score,
+        "importance": entry.importance,
+        "expires_at": entry.expires_at,
+        "last_accessed": entry.last_accessed,
+        "access_count": entry.access_count,
+    })
+}
+
+fn print_entries(entries: &[MemoryEntry], json: bool) -> Result<()> {
+    if json {
+        let values: Vec<serde_json::Value> = entries.iter().map(entry_json).collect();
+        println!("{}", serde_json::to_string_pretty(&values)?);
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        println!("No memories found.");
+        return Ok(());
+    }
+    for entry in entries {
+        let mut meta = format!("{} | {}", entry.category, entry.timestamp);
+        if let Some(session) = &entry.session_id {
+            let _ = write!(meta, " | session {session}");
+        }
+        if let Some(score) = entry.score {
+            let _ = write!(meta, " | score {score:.3}");
+        }
+        println!("- {} ({meta})", entry.key);
+        println!(
+            "    {}",
+            truncate_with_ellipsis(&entry.content.replace('\n', " "), 160)
+        );
+    }
+    Ok(())
+}
+
+/// Response cache counters, or `None` when the cache is disabled.
+fn response_cache_stats(config: &Config) -> Result<Option<CacheStats>> {
+    if !config.memory.response_cache_enabled {
+        return Ok(None);
+    }
+    let cache = ResponseCache::new(
+        &config.workspace_dir,
+        config.memory.response_cache_ttl_minutes,
+        config.memory.response_cache_max_entries,
+    )?;
+    Ok(Some(cache.stats()?))
+}
+
+/// Entry counts per category, distinct sessions and the covered time range.
+fn collect_stats(entries: &[MemoryEntry]) -> serde_json::Value {
+    let mut categories: BTreeMap<String, usize> = BTreeMap::new();
+    let mut sessions = std::collections::HashSet::new();
+    let mut times = Vec::with_capacity(entries.len());
+    for entry in entries {
+        *categories.entry(entry.category.to_string()).or_default() += 1;
+        if let Some(session) = &entry.session_id {
+            sessions.insert(session.as_str());
+        }
+        if let Some(time) = entry_time(&entry.timestamp) {
+            times.push(time);
+        }
+    }
+    serde_json::json!({
+        "total": entries.len(),
+        "categories": categories,
+        "sessions": sessions.len(),
+        "oldest": times.iter().min().map(DateTime::to_rfc3339),
+        "newest": times.iter().max().map(DateTime::to_rfc3339),
+    })
+}
+
+/// Embedding model recorded in (and required to reuse vectors from) portable exports.
+fn export_embedding_model(config: &MemoryConfig) -> Option<portable::EmbeddingModel> {
+    let provider = config.embedding_provider.trim();
+    (!provider.is_empty() && provider != "none" && config.embedding_dimensions > 0).then(|| {
+        portable::EmbeddingModel {
+            provider: provider.to_string(),
+            model: config.embedding_model.clone(),
+            dimensions: config.embedding_dimensions,
+        }
+    })
+}
+
+pub async fn handle_command(command: crate::MemoryCommands, config: &Config) -> Result<()> {
+    let mem: Box<dyn Memory> = create_memory_with_storage(
+        &config.memory,
+        Some(&config.storage.provider.config),
+        &config.workspace_dir,
+        config.api_key.as_deref(),
+    )?;
+
+    match command {
+        crate::MemoryCommands::List {
+            filter,
+            limit,
+            json,
+        } => {
+            let filter = EntryFilter::from_args(filter)?;
+            let entries: Vec<MemoryEntry> = mem
+                .list(filter.category.as_ref(), filter.session.as_deref())
+                .await?
+                .into_iter()
+                .filter(|entry| filter.matches(entry))
+                .take(limit)
+                .collect();
+            print_entries(&entries, json)
+        }
+        crate::MemoryCommands::Search {
+            query,
+            filter,
+            limit,
+            json,
+        } => {
+            let filter = EntryFilter::from_args(filter)?;
+            let candidates = if filter.filters_after_recall() {
+                limit.saturating_mul(SEARCH_FILTER_OVERSAMPLE)
+            } else {
+                limit
+            };
+            let entries: Vec<MemoryEntry> = mem
+                .recall(&query, candidates, filter.session.as_deref())
+                .await?
+                .into_iter()
+                .filter(|entry| filter.matches(entry))
+                .take(limit)
+                .collect();
+            print_entries(&entries, json)
+        }
+        crate::MemoryCommands::Get { key, json } => {
+            let Some(entry) = mem.get(&key).await? else {
+                anyhow::bail!("Memory '{key}' not found");
+            };
+            if json {
+                println!("{}", serde_json::to_string_pretty(&entry_json(&entry))?);
+                return Ok(());
+            }
+            println!("🧠 {}", entry.key);
+            println!("  Id      : {}", entry.id);
+            println!("  Category: {}", entry.category);
+            println!("  Time    : {}", entry.timestamp);
+            if let Some(session) = &entry.session_id {
+                println!("  Session : {session}");
+            }
+            println!();
+            println!("{}", entry.content);
+            Ok(())
+        }
+        crate::MemoryCommands::Put {
+            key,
+            content,
+            category,
+            session,
+        } => {
+            let category = portable::parse_category(&category);
+            mem.store(&key, &content, category.clone(), session.as_deref())
+                .await?;
+            println!("✅ Stored '{key}' ({category}) in {}", mem.name());
+            Ok(())
+        }
+        crate::MemoryCommands::Forget { key } => {
+            if !mem.forget(&key).await? {
+                anyhow::bail!(
+                    "Memory '{key}' not found or {} cannot delete entries",
+                    mem.name()
+                );
+            }
+            println!("✅ Forgot '{key}'");
+            Ok(())
+        }
+        crate::MemoryCommands::Stats { json } => {
+            let entries: Vec<MemoryEntry> = mem
+                .export_entries(false)
+                .await?
+                .into_iter()
+                .map(|(entry, _)| entry)
+                .collect();
+            let mut stats = collect_stats(&entries);
+            stats["backend"] = mem.name().into();
+            stats["healthy"] = mem.health_check().await.into();
+            let cache = response_cache_stats(config)?;
+            if let Some(cache) = cache {
+                stats["response_cache"] = serde_json::to_value(cache)?;
+            }
+            if json {
+                println!("{}", serde_json::to_string_pretty(&stats)?);
+                return Ok(());
+            }
+
+            println!("🧠 Memory ({})", mem.name());
+            println!("  Healthy : {}", stats["healthy"]);
+            println!("  Entries : {}", stats["total"]);
+            if let Some(categories) = stats["categories"].as_object() {
+                for (category, count) in categories {
+                    println!("    {category:<14} {count}");
+                }
+            }
+            println!("  Sessions: {}", stats["sessions"]);
+            if let (Some(oldest), Some(newest)) =
+                (stats["oldest"].as_str(), stats["newest"].as_str())
+            {
+                println!("  Range   : {oldest} → {newest}");
+            }
+            if let Some(cache) = cache {
+                println!(
+                    "  Cache   : {} entries, {} hits ({} semantic), {} misses, ~{} tokens saved",
+                    cache.entries,
+                    cache.hits,
+                    cache.semantic_hits,
+                    cache.misses,
+                    cache.tokens_saved
+                );
+            }
+            Ok(())
+        }
+        crate::MemoryCommands::Reindex => {
+            let embedded = mem.reindex().await?;
+            println!("✅ Reindexed {} ({embedded} entries embedded)", mem.name());
+            Ok(())
+        }
+        crate::MemoryCommands::Consolidate => {
+            let (provider, model) = consolidation::build_provider(config)?;
+            let report = consolidation::consolidate(
+                mem.as_ref(),
+                provider.as_ref(),
+                &model,
+                &config.workspace_dir,
+            )
+            .await?;
+            println!(
+                "🧠 Consolidated {} entries in {} clusters into {} core facts ({} duplicates skipped)",
+                report.sources, report.clusters, report.facts_written, report.duplicates_skipped
+            );
+            Ok(())
+        }
+        crate::MemoryCommands::Export {
+            output,
+            category,
+            embeddings,
+        } => {
+            let category = category.as_deref().map(portable::parse_category);
+            let model = if embeddings {
+                let model = export_embedding_model(&config.memory);
+                if model.is_none() {
+                    tracing::warn!(
+                        "no embedding provider configured; exporting without embeddings"
+                    );
+                }
+                model
+            } else {
+                None
+            };
+
+            let count = match &output {
+                Some(path) => {
+                    let file = std::fs::File::create(path)
+                        .with_context(|| format!("failed to create {}", path.display()))?;
+                    portable::export_jsonl(
+                        mem.as_ref(),
+                        std::io::BufWriter::new(file),
+                        category.as_ref(),
+                        model,
+                    )
+                    .await?
+                }
+                None => {
+                    portable::export_jsonl(
+                        mem.as_ref(),
+                        std::io::stdout().lock(),
+                        category.as_ref(),
+                        model,
+                    )
+                    .await?
+                }
+            };
+            if let Some(path) = output {
+                println!(
+                    "🧠 Exported {count} memories from {} to {}",
+                    mem.name(),
+                    path.display()
+                );
+            }
+            Ok(())
+        }
+        crate::MemoryCommands::Import { input, dry_run } => {
+            let file = std::fs::File::open(&input)
+                .with_context(|| format!("failed to open {}", input.display()))?;
+            let reader = std::io::BufReader::new(file);
+
+            if dry_run {
+                let (header, entries) = portable::read_jsonl(reader)?;
+                println!(
+                    "🔎 {} is a valid v{} export of {} memories from {} ({})",
+                    input.display(),
+                    header.version,
+                    entries.len(),
+                    header.backend,
+                    header.exported_at
+                );
+                println!("   Dry run: nothing was written to {}", mem.name());
+                return Ok(());
+            }
+
+            let model = export_embedding_model(&config.memory);
+            let summary = portable::import_jsonl(mem.as_ref(), reader, model.as_ref()).await?;
+            println!(
+                "🧠 Imported {} memories into {} ({} with exported embeddings)",
+                summary.entries,
+                mem.name(),
+                summary.embeddings_reused
+            );
+            Ok(())
+        }
+    }
+}
+
+/// `zeroclaw ingest <path>` — chunk workspace documents into memory.
+pub async fn handle_ingest(path: &Path, config: &Config) -> Result<()> {
+    let mem: Box<dyn Memory> = create_memory_with_storage(
+        &config.memory,
+        Some(&config.storage.provider.config),
+        &config.workspace_dir,
+        config.api_key.as_deref(),
+    )?;
+    let report = ingest::ingest_path(mem.as_ref(), path, &config.workspace_dir).await?;
+    println!(
+        "📚 Ingested {} files into {} ({} chunks); {} unchanged, {} removed, {} skipped",
+        report.ingested,
+        mem.name(),
+        report.chunks,
+        report.unchanged,
+        report.removed,
+        report.skipped
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(key: &str, category: MemoryCategory, timestamp: &str) -> MemoryEntry {
+        MemoryEntry {
+            id: key.into(),
+            key: key.into(),
+            content: format!("content of {key}"),
+            category,
+            timestamp: timestamp.into(),
+            session_id: None,
+            score: None,
+            importance: None,
+            expires_at: None,
+            last_accessed: None,
+            access_count: 0,
+            namespace: None,
+        }
+    }
+
+    #[test]
+    fn date_bounds_cover_the_whole_day() {
+        let since = parse_date_bound("2026-02-16", false).unwrap();
+        let until = parse_date_bound("2026-02-16", true).unwrap();
+        assert_eq!((until - since).num_seconds(), 86_399);
+
+        let exact = parse_date_bound("2026-02-16T10:00:00Z", true).unwrap();
+        assert_eq!(exact.to_rfc3339(), "2026-02-16T10:00:00+00:00");
+
+        assert!(parse_date_bound("yesterday", false).is_err());
+    }
+
+    #[test]
+    fn filter_matches_category_session_and_date_range() {
+        let filter = EntryFilter::from_args(crate::MemoryFilterArgs {
+            category: Some("daily".into()),
+            session: None,
+            since: Some("2026-02-01T00:00:00Z".into()),
+            until: Some("2026-02-28T00:00:00Z".into()),
+        })
+        .unwrap();
+
+        assert!(filter.matches(&entry(
+            "a",
+            MemoryCategory::Daily,
+            "2026-02-16T08:00:00+00:00"
+        )));
+        assert!(!filter.matches(&entry(
+            "b",
+            MemoryCategory::Core,
+            "2026-02-16T08:00:00+00:00"
+        )));
+        assert!(!filter.matches(&entry(
+            "c",
+            MemoryCategory::Daily,
+            "2026-03-02T08:00:00+00:00"
+        )));
+        assert!(!filter.matches(&entry("d", MemoryCategory::Daily, "MEMORY")));
+
+        let session_filter = EntryFilter {
+            session: Some("s1".into()),
+            ..EntryFilter::default()
+        };
+        let mut scoped = entry("e", MemoryCategory::Conversation, "2026-02-16");
+        assert!(!session_filter.matches(&scoped));
+        scoped.session_id = Some("s1".into());
+        assert!(session_filter.matches(&scoped));
+    }
+
+    #[test]
+    fn stats_count_categories_sessions_and_range() {
+        let mut scoped = entry("c", MemoryCategory::Conversation, "2026-02-20T00:00:00Z");
+        scoped.session_id = Some("s1".into());
+        let entries = vec![
+            entry("a", MemoryCategory::Core, "2026-02-10T00:00:00Z"),
+            entry("b", MemoryCategory::Core, "2026-02-15T00:00:00Z"),
+            scoped,
+        ];
+
+        let stats = collect_stats(&entries);
+        assert_eq!(stats["total"], 3);
+        assert_eq!(stats["categories"]["core"], 2);
+        assert_eq!(stats["categories"]["conversation"], 1);
+        assert_eq!(stats["sessions"], 1);
+        assert_eq!(stats["oldest"], "2026-02-10T00:00:00+00:00");
+        assert_eq!(stats["newest"], "2026-02-20T00:00:00+00:00");
+    }
+
+    #[test]
+    fn entry_json_uses_plain_category_names() {
+        let value = entry_json(&entry(
+            "ticket",
+            MemoryCategory::Custom("work".into()),
+            "2026-02-16",
+        ));
+        assert_eq!(value["category"], "work");
+        assert_eq!(value["key"], "ticket");
+    }
+}
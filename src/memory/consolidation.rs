@@ -0,0 +1,631 @@
+//! LLM-driven memory consolidation.
+//!
+//! Daily and conversation memories are short-lived: hygiene archives and
+//! prunes them by age. Before that happens, this pass clusters the entries
+//! stored since the last run, asks the provider to distil each cluster into
+//! durable facts, and writes those as deduplicated `MemoryCategory::Core`
+//! entries whose content ends with a `Sources:` line naming the source keys.
+//! Each tenant namespace is consolidated on its own and keeps its facts.
+//!
+//! Progress is a timestamp watermark in `state/memory_consolidation_state.json`;
+//! hygiene never prunes conversation rows newer than it.
+
+use super::traits::{Memory, MemoryCategory, MemoryEntry};
+use super::vector;
+use crate::config::Config;
+use crate::providers::{self, Provider};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+const STATE_FILE: &str = "memory_consolidation_state.json";
+/// Key prefix of consolidated core facts.
+pub const CONSOLIDATED_KEY_PREFIX: &str = "consolidated/";
+/// Line that separates a consolidated fact from its provenance.
+const SOURCES_PREFIX: &str = "Sources: ";
+/// Oldest-first entries considered per run; the rest wait for the next run.
+const MAX_ENTRIES_PER_RUN: usize = 400;
+const MAX_CLUSTER_SIZE: usize = 12;
+/// Cosine similarity to a cluster's first entry needed to join it.
+const EMBEDDING_CLUSTER_THRESHOLD: f32 = 0.75;
+/// Word-overlap (Jaccard) similarity needed to join a cluster without embeddings.
+const KEYWORD_CLUSTER_THRESHOLD: f64 = 0.2;
+/// Word-overlap above which a new fact duplicates an existing core memory.
+const DUPLICATE_THRESHOLD: f64 = 0.8;
+const CONSOLIDATION_TEMPERATURE: f64 = 0.2;
+
+const SYSTEM_PROMPT: &str = "You maintain the long-term memory of an AI assistant. \
+You receive a group of related short-term notes (daily logs and conversation snippets). \
+Extract only durable facts worth remembering for weeks: user preferences, personal details, \
+decisions, commitments, recurring tasks and project facts. Ignore greetings, transient status \
+and anything speculative. Write each fact as one self-contained sentence in the third person. \
+Reply with a JSON array of strings and nothing else; reply [] when nothing is worth keeping.";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ConsolidationState {
+    /// Timestamp of the newest entry already consolidated.
+    watermark: Option<String>,
+    last_run_at: Option<String>,
+}
+
+/// Outcome of one consolidation pass.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConsolidationReport {
+    pub sources: usize,
+    pub clusters: usize,
+    pub facts_written: usize,
+    pub duplicates_skipped: usize,
+}
+
+/// Newest consolidated entry time, if any pass has completed.
+pub fn watermark(workspace_dir: &Path) -> Option<DateTime<Utc>> {
+    read_state(workspace_dir)
+        .watermark
+        .as_deref()
+        .and_then(parse_time)
+}
+
+/// Run `consolidate` when the hygiene cadence window has elapsed.
+pub async fn run_if_due(
+    memory: &dyn Memory,
+    provider: &dyn Provider,
+    model: &str,
+    workspace_dir: &Path,
+) -> Result<Option<ConsolidationReport>> {
+    let due = read_state(workspace_dir)
+        .last_run_at
+        .as_deref()
+        .and_then(parse_time)
+        .map_or(true, |last| {
+            Utc::now().signed_duration_since(last)
+                >= Duration::hours(super::hygiene::HYGIENE_INTERVAL_HOURS)
+        });
+    if !due {
+        return Ok(None);
+    }
+    consolidate(memory, provider, model, workspace_dir)
+        .await
+        .map(Some)
+}
+
+/// Distil daily/conversation entries stored since the last run into core facts.
+///
+/// A provider error aborts the pass without moving the watermark, so the
+/// same entries are retried next time.
+pub async fn consolidate(
+    memory: &dyn Memory,
+    provider: &dyn Provider,
+    model: &str,
+    workspace_dir: &Path,
+) -> Result<ConsolidationReport> {
+    let mut state = read_state(workspace_dir);
+    let since = state.watermark.as_deref().and_then(parse_time);
+
+    let mut pending: Vec<(DateTime<Utc>, MemoryEntry, Option<Vec<f32>>)> = memory
+        .export_entries(true)
+        .await?
+        .into_iter()
+        .filter(|(entry, _)| {
+            matches!(
+                entry.category,
+                MemoryCategory::Daily | MemoryCategory::Conversation
+            )
+        })
+        .filter_map(|(entry, embedding)| {
+            let time = parse_time(&entry.timestamp)?;
+            since
+                .map_or(true, |since| time > since)
+                .then_some((time, entry, embedding))
+        })
+        .collect();
+    pending.sort_by_key(|(time, _, _)| *time);
+    pending.truncate(MAX_ENTRIES_PER_RUN);
+
+    let mut report = ConsolidationReport {
+        sources: pending.len(),
+        ..ConsolidationReport::default()
+    };
+
+    if !pending.is_empty() {
+        // Facts are distilled per tenant namespace and stored back into it,
+        // so one tenant's conversations never become another's core facts.
+        let mut by_namespace: BTreeMap<Option<&str>, Vec<Item<'_>>> = BTreeMap::new();
+        for (_, entry, embedding) in &pending {
+            by_namespace
+                .entry(entry.namespace.as_deref())
+                .or_default()
+                .push((entry, embedding.as_deref()));
+        }
+        for (namespace, items) in by_namespace {
+            let scoped = match namespace {
+                Some(namespace) => {
+                    let Some(scoped) = memory.with_namespace(namespace) else {
+                        tracing::warn!(
+                            "memory consolidation: backend cannot scope namespace '{namespace}', skipped"
+                        );
+                        continue;
+                    };
+                    Some(scoped)
+                }
+                None => None,
+            };
+            let target = scoped.as_deref().unwrap_or(memory);
+            consolidate_items(target, &items, provider, model, &mut report).await?;
+        }
+
+        state.watermark = pending.last().map(|(time, _, _)| time.to_rfc3339());
+    }
+
+    state.last_run_at = Some(Utc::now().to_rfc3339());
+    write_state(workspace_dir, &state)?;
+
+    if report.facts_written > 0 || report.duplicates_skipped > 0 {
+        tracing::info!(
+            "memory consolidation complete: sources={} clusters={} facts={} duplicates={}",
+            report.sources,
+            report.clusters,
+            report.facts_written,
+            report.duplicates_skipped,
+        );
+    }
+    Ok(report)
+}
+
+/// A pending entry and its embedding, if the backend has one.
+type Item<'a> = (&'a MemoryEntry, Option<&'a [f32]>);
+
+/// Cluster `items` and store their distilled facts in `memory`.
+async fn consolidate_items(
+    memory: &dyn Memory,
+    items: &[Item<'_>],
+    provider: &dyn Provider,
+    model: &str,
+    report: &mut ConsolidationReport,
+) -> Result<()> {
+    let mut known: Vec<HashSet<String>> = memory
+        .list(Some(&MemoryCategory::Core), None)
+        .await?
+        .iter()
+        .map(|entry| word_set(fact_text(&entry.content)))
+        .collect();
+
+    for cluster in cluster_entries(items) {
+        let members: Vec<&MemoryEntry> = cluster.iter().map(|&i| items[i].0).collect();
+        report.clusters += 1;
+
+        let reply = provider
+            .chat_with_system(
+                Some(SYSTEM_PROMPT),
+                &cluster_prompt(&members),
+                model,
+                CONSOLIDATION_TEMPERATURE,
+            )
+            .await
+            .context("memory consolidation provider call failed")?;
+        let Some(facts) = parse_facts(&reply) else {
+            tracing::warn!("memory consolidation: unparseable reply for a cluster, skipped");
+            continue;
+        };
+
+        let sources: Vec<&str> = members.iter().map(|entry| entry.key.as_str()).collect();
+        for fact in facts {
+            let words = word_set(&fact);
+            if words.is_empty() {
+                continue;
+            }
+            if known
+                .iter()
+                .any(|existing| jaccard(existing, &words) >= DUPLICATE_THRESHOLD)
+            {
+                report.duplicates_skipped += 1;
+                continue;
+            }
+            let content = format!("{fact}\n\n{SOURCES_PREFIX}{}", sources.join(", "));
+            memory
+                .store(&fact_key(&words), &content, MemoryCategory::Core, None)
+                .await?;
+            known.push(words);
+            report.facts_written += 1;
+        }
+    }
+    Ok(())
+}
+
+/// Daemon worker: consolidate on the hygiene cadence with the default provider.
+pub async fn run_worker(config: Config) -> Result<()> {
+    let (provider, model) = build_provider(&config)?;
+    let memory: Arc<dyn Memory> = Arc::from(super::create_memory_with_storage(
+        &config.memory,
+        Some(&config.storage.provider.config),
+        &config.workspace_dir,
+        config.api_key.as_deref(),
+    )?);
+
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(60 * 60));
+    loop {
+        interval.tick().await;
+        if let Err(e) = run_if_due(
+            memory.as_ref(),
+            provider.as_ref(),
+            &model,
+            &config.workspace_dir,
+        )
+        .await
+        {
+            crate::health::mark_component_error("memory_consolidation", e.to_string());
+            tracing::warn!("memory consolidation failed: {e:#}");
+        } else {
+            crate::health::mark_component_ok("memory_consolidation");
+        }
+    }
+}
+
+/// Default provider and the consolidation model (falling back to `default_model`).
+pub fn build_provider(config: &Config) -> Result<(Box<dyn Provider>, String)> {
+    let provider = providers::create_resilient_provider_with_options(
+        config.default_provider.as_deref().unwrap_or("openrouter"),
+        config.api_key.as_deref(),
+        config.api_url.as_deref(),
+        &config.reliability,
+        &providers::ProviderRuntimeOptions {
+            auth_profile_override: None,
+            zeroclaw_dir: config.config_path.parent().map(PathBuf::from),
+            secrets_encrypt: config.secrets.encrypt,
+        },
+    )?;
+    let model = config
+        .memory
+        .consolidation_model
+        .clone()
+        .or_else(|| config.default_model.clone())
+        .unwrap_or_else(|| "anthropic/claude-sonnet-4".into());
+    Ok((provider, model))
+}
+
+/// Greedy single-pass clustering in time order: each entry joins the first
+/// open cluster whose seed is similar enough (cosine on embeddings when both
+/// have one, word overlap otherwise), or starts a new cluster.
+fn cluster_entries(items: &[Item<'_>]) -> Vec<Vec<usize>> {
+    let words: Vec<HashSet<String>> = items
+        .iter()
+        .map(|(entry, _)| word_set(&entry.content))
+        .collect();
+    let mut clusters: Vec<Vec<usize>> = Vec::new();
+
+    for (i, (_, embedding)) in items.iter().enumerate() {
+        let joined = clusters.iter_mut().find(|cluster| {
+            let seed = cluster[0];
+            if cluster.len() >= MAX_CLUSTER_SIZE {
+                return false;
+            }
+            match (embedding, items[seed].1) {
+                (Some(a), Some(b)) => {
+                    vector::cosine_similarity(a, b) >= EMBEDDING_CLUSTER_THRESHOLD
+                }
+                _ => jaccard(&words[i], &words[seed]) >= KEYWORD_CLUSTER_THRESHOLD,
+            }
+        });
+        match joined {
+            Some(cluster) => cluster.push(i),
+            None => clusters.push(vec![i]),
+        }
+    }
+    clusters
+}
+
+fn cluster_prompt(members: &[&MemoryEntry]) -> String {
+    let mut prompt = String::from("Short-term notes:\n");
+    for entry in members {
+        let _ = writeln!(
+            prompt,
+            "- [{} | {}] {}",
+            entry.category,
+            entry.timestamp,
+            entry.content.trim()
+        );
+    }
+    prompt
+}
+
+/// The JSON string array in a reply, tolerating code fences and prose around it.
+fn parse_facts(reply: &str) -> Option<Vec<String>> {
+    let start = reply.find('[')?;
+    let end = reply.rfind(']')?;
+    let facts: Vec<String> = serde_json::from_str(reply.get(start..=end)?).ok()?;
+    Some(
+        facts
+            .into_iter()
+            .map(|fact| fact.trim().to_string())
+            .filter(|fact| !fact.is_empty())
+            .collect(),
+    )
+}
+
+/// A consolidated fact without its provenance line.
+fn fact_text(content: &str) -> &str {
+    content
+        .rsplit_once(&format!("\n\n{SOURCES_PREFIX}"))
+        .map_or(content, |(fact, _)| fact)
+}
+
+/// Stable key so re-consolidating the same fact overwrites instead of piling up.
+fn fact_key(words: &HashSet<String>) -> String {
+    use sha2::{Digest, Sha256};
+    let mut sorted: Vec<&str> = words.iter().map(String::as_str).collect();
+    sorted.sort_unstable();
+    let hash = Sha256::digest(sorted.join(" ").as_bytes());
+    format!("{CONSOLIDATED_KEY_PREFIX}{}", hex::encode(&hash[..8]))
+}
+
+fn word_set(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.len() > 2)
+        .map(str::to_lowercase)
+        .collect()
+}
+
+fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let shared = a.intersection(b).count();
+    #[allow(clippy::cast_precision_loss)]
+    let score = shared as f64 / (a.len() + b.len() - shared) as f64;
+    score
+}
+
+/// RFC 3339, or a bare date (markdown entries) at midnight UTC.
+fn parse_time(value: &str) -> Option<DateTime<Utc>> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Some(time.with_timezone(&Utc));
+    }
+    chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()?
+        .and_hms_opt(0, 0, 0)
+        .map(|naive| naive.and_utc())
+}
+
+fn state_path(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("state").join(STATE_FILE)
+}
+
+fn read_state(workspace_dir: &Path) -> ConsolidationState {
+    fs::read_to_string(state_path(workspace_dir))
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn write_state(workspace_dir: &Path, state: &ConsolidationState) -> Result<()> {
+    let path = state_path(workspace_dir);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_vec_pretty(state)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::SqliteMemory;
+    use async_trait::async_trait;
+    use parking_lot::Mutex;
+    use tempfile::TempDir;
+
+    /// Replies with queued answers and records every prompt.
+    struct ScriptedProvider {
+        replies: Mutex<Vec<String>>,
+        prompts: Mutex<Vec<String>>,
+    }
+
+    impl ScriptedProvider {
+        fn new(replies: &[&str]) -> Self {
+            Self {
+                replies: Mutex::new(replies.iter().rev().map(|r| (*r).to_string()).collect()),
+                prompts: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Provider for ScriptedProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            self.prompts.lock().push(message.to_string());
+            Ok(self.replies.lock().pop().unwrap_or_else(|| "[]".into()))
+        }
+    }
+
+    struct FailingProvider;
+
+    #[async_trait]
+    impl Provider for FailingProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            anyhow::bail!("provider down")
+        }
+    }
+
+    fn entry(key: &str, content: &str) -> MemoryEntry {
+        MemoryEntry {
+            id: key.into(),
+            key: key.into(),
+            content: content.into(),
+            category: MemoryCategory::Daily,
+            timestamp: "2026-02-16T00:00:00Z".into(),
+            session_id: None,
+            score: None,
+            importance: None,
+            expires_at: None,
+            last_accessed: None,
+            access_count: 0,
+            namespace: None,
+        }
+    }
+
+    #[test]
+    fn clusters_by_word_overlap_without_embeddings() {
+        let a = entry("a", "User prefers dark mode in the editor");
+        let b = entry("b", "Switched editor theme to dark mode as user prefers");
+        let c = entry("c", "Deployed billing service to production");
+        let items = vec![(&a, None), (&b, None), (&c, None)];
+        assert_eq!(cluster_entries(&items), vec![vec![0, 1], vec![2]]);
+    }
+
+    #[test]
+    fn clusters_by_cosine_when_embeddings_exist() {
+        let a = entry("a", "alpha");
+        let b = entry("b", "beta");
+        let c = entry("c", "gamma");
+        let near: &[f32] = &[1.0, 0.0];
+        let close: &[f32] = &[0.9, 0.1];
+        let far: &[f32] = &[0.0, 1.0];
+        let items = vec![(&a, Some(near)), (&b, Some(close)), (&c, Some(far))];
+        assert_eq!(cluster_entries(&items), vec![vec![0, 1], vec![2]]);
+    }
+
+    #[test]
+    fn parse_facts_tolerates_fences_and_rejects_prose() {
+        let reply = "```json\n[\"User prefers tea\", \"  \"]\n```";
+        assert_eq!(parse_facts(reply).unwrap(), vec!["User prefers tea"]);
+        assert!(parse_facts("nothing to keep").is_none());
+    }
+
+    #[tokio::test]
+    async fn writes_deduplicated_core_facts_with_sources_and_advances_watermark() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        mem.store(
+            "pref",
+            "The user prefers tea over coffee",
+            MemoryCategory::Core,
+            None,
+        )
+        .await
+        .unwrap();
+        mem.store(
+            "daily_1",
+            "User said they prefer tea over coffee",
+            MemoryCategory::Daily,
+            None,
+        )
+        .await
+        .unwrap();
+        mem.store(
+            "conv_1",
+            "User mentioned their daughter Mia starts school in September",
+            MemoryCategory::Conversation,
+            Some("s1"),
+        )
+        .await
+        .unwrap();
+
+        let provider = ScriptedProvider::new(&[
+            r#"["The user prefers tea over coffee"]"#,
+            r#"["The user's daughter Mia starts school in September"]"#,
+        ]);
+        let report = consolidate(&mem, &provider, "test-model", tmp.path())
+            .await
+            .unwrap();
+        assert_eq!(report.sources, 2);
+        assert_eq!(report.clusters, 2);
+        assert_eq!(report.facts_written, 1);
+        assert_eq!(report.duplicates_skipped, 1);
+        assert!(provider.prompts.lock()[1].contains("Mia"));
+
+        let core = mem.list(Some(&MemoryCategory::Core), None).await.unwrap();
+        let fact = core
+            .iter()
+            .find(|entry| entry.key.starts_with(CONSOLIDATED_KEY_PREFIX))
+            .unwrap();
+        assert!(fact.content.starts_with("The user's daughter Mia"));
+        assert!(fact.content.ends_with("Sources: conv_1"));
+        assert!(watermark(tmp.path()).is_some());
+
+        // Nothing new since the watermark: no provider calls.
+        let provider = ScriptedProvider::new(&[]);
+        let report = consolidate(&mem, &provider, "test-model", tmp.path())
+            .await
+            .unwrap();
+        assert_eq!(report.sources, 0);
+        assert!(provider.prompts.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn provider_failure_keeps_watermark() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        mem.store("daily_1", "User likes hiking", MemoryCategory::Daily, None)
+            .await
+            .unwrap();
+
+        assert!(consolidate(&mem, &FailingProvider, "m", tmp.path())
+            .await
+            .is_err());
+        assert!(watermark(tmp.path()).is_none());
+    }
+
+    #[tokio::test]
+    async fn facts_stay_in_the_tenant_namespace_they_came_from() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        let alice = mem.with_namespace("telegram:alice").unwrap();
+        let bob = mem.with_namespace("telegram:bob").unwrap();
+        alice
+            .store(
+                "daily_1",
+                "User prefers green tea",
+                MemoryCategory::Daily,
+                None,
+            )
+            .await
+            .unwrap();
+        bob.store(
+            "daily_1",
+            "User prefers black tea",
+            MemoryCategory::Daily,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let provider = ScriptedProvider::new(&[
+            r#"["The user prefers green tea"]"#,
+            r#"["The user prefers black tea"]"#,
+        ]);
+        let report = consolidate(&mem, &provider, "test-model", tmp.path())
+            .await
+            .unwrap();
+        assert_eq!(report.clusters, 2);
+        assert_eq!(report.facts_written, 2);
+        assert!(!provider.prompts.lock()[0].contains("black"));
+
+        let shared = mem.list(Some(&MemoryCategory::Core), None).await.unwrap();
+        assert!(shared.is_empty());
+        let alice_core = alice.list(Some(&MemoryCategory::Core), None).await.unwrap();
+        assert_eq!(alice_core.len(), 1);
+        assert!(alice_core[0]
+            .content
+            .starts_with("The user prefers green tea"));
+        let bob_core = bob.list(Some(&MemoryCategory::Core), None).await.unwrap();
+        assert!(bob_core[0]
+            .content
+            .starts_with("The user prefers black tea"));
+    }
+}
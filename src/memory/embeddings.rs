@@ -1,4 +1,8 @@
+use crate::config::MemoryConfig;
 use async_trait::async_trait;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::LazyLock;
 
 /// Trait for embedding providers — convert text to vectors
 #[async_trait]
@@ -154,36 +158,254 @@ impl EmbeddingProvider for OpenAiEmbedding {
     }
 }
 
+// ── Ollama embedding provider ────────────────────────────────
+
+/// Default local Ollama endpoint.
+const OLLAMA_DEFAULT_URL: &str = "http://localhost:11434";
+/// Texts sent per `/api/embed` request.
+const OLLAMA_BATCH_SIZE: usize = 32;
+/// Timeout for the one-off dimension probe at startup.
+const OLLAMA_PROBE_TIMEOUT_SECS: u64 = 10;
+
+/// Dimensions found by [`init`], keyed by `(base_url, model)`.
+static OLLAMA_DIMENSIONS: LazyLock<Mutex<HashMap<(String, String), usize>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Native Ollama embeddings via `POST /api/embed`.
+pub struct OllamaEmbedding {
+    base_url: String,
+    model: String,
+    dims: usize,
+}
+
+impl OllamaEmbedding {
+    pub fn new(base_url: &str, model: &str, dims: usize) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            model: model.to_string(),
+            dims,
+        }
+    }
+
+    fn http_client(&self) -> reqwest::Client {
+        crate::config::build_runtime_proxy_client("memory.embeddings")
+    }
+
+    fn embed_url(&self) -> String {
+        format!("{}/api/embed", self.base_url)
+    }
+
+    fn parse_embeddings(
+        json: &serde_json::Value,
+        expected: usize,
+    ) -> anyhow::Result<Vec<Vec<f32>>> {
+        let data = json
+            .get("embeddings")
+            .and_then(|d| d.as_array())
+            .ok_or_else(|| {
+                anyhow::anyhow!("Invalid Ollama embed response: missing 'embeddings'")
+            })?;
+        if data.len() != expected {
+            anyhow::bail!(
+                "Ollama returned {} embeddings for {expected} inputs",
+                data.len()
+            );
+        }
+
+        data.iter()
+            .map(|item| {
+                let values = item
+                    .as_array()
+                    .ok_or_else(|| anyhow::anyhow!("Invalid Ollama embedding item"))?;
+                #[allow(clippy::cast_possible_truncation)]
+                Ok(values
+                    .iter()
+                    .filter_map(|v| v.as_f64().map(|f| f as f32))
+                    .collect())
+            })
+            .collect()
+    }
+
+    fn cache_key(&self) -> (String, String) {
+        (self.base_url.clone(), self.model.clone())
+    }
+
+    /// Ask the server for one embedding and adopt its length as the
+    /// dimensions, so `embedding_dimensions` doesn't have to match the model
+    /// by hand. Keeps the configured value if the server can't be reached.
+    pub async fn discover_dimensions(mut self) -> Self {
+        match self.probe_dimensions().await {
+            Ok(dims) if dims > 0 => {
+                if dims != self.dims {
+                    tracing::info!(
+                        model = self.model,
+                        "Ollama embeddings are {dims}-dimensional (configured {}); using {dims}",
+                        self.dims
+                    );
+                }
+                self.dims = dims;
+            }
+            Ok(_) => tracing::warn!(
+                model = self.model,
+                "Ollama returned an empty embedding; keeping embedding_dimensions = {}",
+                self.dims
+            ),
+            Err(e) => tracing::warn!(
+                model = self.model,
+                "Could not probe Ollama embedding dimensions at {}: {e:#}; keeping embedding_dimensions = {}",
+                self.base_url,
+                self.dims
+            ),
+        }
+        self
+    }
+
+    async fn probe_dimensions(&self) -> anyhow::Result<usize> {
+        let resp = self
+            .http_client()
+            .post(self.embed_url())
+            .timeout(std::time::Duration::from_secs(OLLAMA_PROBE_TIMEOUT_SECS))
+            .json(&serde_json::json!({ "model": self.model, "input": ["dimension probe"] }))
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let text = resp.text().await.unwrap_or_default();
+            anyhow::bail!("Ollama embed API error {status}: {text}");
+        }
+        let json: serde_json::Value = resp.json().await?;
+        let mut embeddings = Self::parse_embeddings(&json, 1)?;
+        Ok(embeddings.pop().map_or(0, |embedding| embedding.len()))
+    }
+
+    /// Adopt the dimensions [`init`] found for this server and model, if any.
+    /// Never touches the network, so providers can be built on any thread.
+    #[must_use]
+    fn with_discovered_dimensions(mut self) -> Self {
+        if let Some(&dims) = OLLAMA_DIMENSIONS.lock().get(&self.cache_key()) {
+            self.dims = dims;
+        }
+        self
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for OllamaEmbedding {
+    fn name(&self) -> &str {
+        "ollama"
+    }
+
+    fn dimensions(&self) -> usize {
+        self.dims
+    }
+
+    async fn embed(&self, texts: &[&str]) -> anyhow::Result<Vec<Vec<f32>>> {
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for batch in texts.chunks(OLLAMA_BATCH_SIZE) {
+            let body = serde_json::json!({
+                "model": self.model,
+                "input": batch,
+            });
+
+            let resp = self
+                .http_client()
+                .post(self.embed_url())
+                .json(&body)
+                .send()
+                .await?;
+
+            if !resp.status().is_success() {
+                let status = resp.status();
+                let text = resp.text().await.unwrap_or_default();
+                anyhow::bail!("Ollama embed API error {status}: {text}");
+            }
+
+            let json: serde_json::Value = resp.json().await?;
+            embeddings.extend(Self::parse_embeddings(&json, batch.len())?);
+        }
+
+        Ok(embeddings)
+    }
+}
+
 // ── Factory ──────────────────────────────────────────────────
 
+/// Probe the configured Ollama embedder's dimensions once for this process,
+/// before any memory backend is built. [`create_embedding_provider`] then
+/// picks the result up without blocking. Safe to call from every entry
+/// point; other providers need no probe.
+pub async fn init(config: &MemoryConfig) {
+    let provider = config.embedding_provider.trim();
+    let base_url = match provider {
+        "ollama" => OLLAMA_DEFAULT_URL,
+        name => match name.strip_prefix("ollama:") {
+            Some(base_url) => base_url,
+            None => return,
+        },
+    };
+    let embedder = OllamaEmbedding::new(
+        base_url,
+        &config.embedding_model,
+        config.embedding_dimensions,
+    );
+    let key = embedder.cache_key();
+    if OLLAMA_DIMENSIONS.lock().contains_key(&key) {
+        return;
+    }
+    let dims = embedder.discover_dimensions().await.dims;
+    OLLAMA_DIMENSIONS.lock().insert(key, dims);
+}
+
+/// Build the configured embedding provider.
+///
+/// Known names: `none` (or empty), `openai`, `custom:URL` (OpenAI-shaped),
+/// `ollama` and `ollama:URL`. Anything else is an error so a typo doesn't
+/// silently turn semantic recall off. Ollama uses the dimensions found by
+/// [`init`] when it has run, and `dims` otherwise.
 pub fn create_embedding_provider(
     provider: &str,
     api_key: Option<&str>,
     model: &str,
     dims: usize,
-) -> Box<dyn EmbeddingProvider> {
+) -> anyhow::Result<Box<dyn EmbeddingProvider>> {
+    let provider = provider.trim();
     match provider {
+        "" | "none" => Ok(Box::new(NoopEmbedding)),
         "openai" => {
             let key = api_key.unwrap_or("");
-            Box::new(OpenAiEmbedding::new(
+            Ok(Box::new(OpenAiEmbedding::new(
                 "https://api.openai.com",
                 key,
                 model,
                 dims,
-            ))
+            )))
         }
         name if name.starts_with("custom:") => {
             let base_url = name.strip_prefix("custom:").unwrap_or("");
             let key = api_key.unwrap_or("");
-            Box::new(OpenAiEmbedding::new(base_url, key, model, dims))
+            Ok(Box::new(OpenAiEmbedding::new(base_url, key, model, dims)))
         }
-        _ => Box::new(NoopEmbedding),
+        "ollama" => Ok(Box::new(
+            OllamaEmbedding::new(OLLAMA_DEFAULT_URL, model, dims).with_discovered_dimensions(),
+        )),
+        name if name.starts_with("ollama:") => {
+            let base_url = name.strip_prefix("ollama:").unwrap_or("");
+            Ok(Box::new(
+                OllamaEmbedding::new(base_url, model, dims).with_discovered_dimensions(),
+            ))
+        }
+        other => anyhow::bail!(
+            "Unknown embedding provider '{other}' — expected none, openai, custom:URL, ollama or ollama:URL"
+        ),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use axum::Json;
+    use serde_json::{json, Value};
+    use std::sync::Arc;
 
     #[test]
     fn noop_name() {
@@ -201,20 +423,22 @@ mod tests {
 
     #[test]
     fn factory_none() {
-        let p = create_embedding_provider("none", None, "model", 1536);
+        let p = create_embedding_provider("none", None, "model", 1536).unwrap();
         assert_eq!(p.name(), "none");
     }
 
     #[test]
     fn factory_openai() {
-        let p = create_embedding_provider("openai", Some("key"), "text-embedding-3-small", 1536);
+        let p = create_embedding_provider("openai", Some("key"), "text-embedding-3-small", 1536)
+            .unwrap();
         assert_eq!(p.name(), "openai");
         assert_eq!(p.dimensions(), 1536);
     }
 
     #[test]
     fn factory_custom_url() {
-        let p = create_embedding_provider("custom:http://localhost:1234", None, "model", 768);
+        let p =
+            create_embedding_provider("custom:http://localhost:1234", None, "model", 768).unwrap();
         assert_eq!(p.name(), "openai"); // uses OpenAiEmbedding internally
         assert_eq!(p.dimensions(), 768);
     }
@@ -245,26 +469,30 @@ mod tests {
 
     #[test]
     fn factory_empty_string_returns_noop() {
-        let p = create_embedding_provider("", None, "model", 1536);
+        let p = create_embedding_provider("", None, "model", 1536).unwrap();
         assert_eq!(p.name(), "none");
     }
 
     #[test]
-    fn factory_unknown_provider_returns_noop() {
-        let p = create_embedding_provider("cohere", None, "model", 1536);
-        assert_eq!(p.name(), "none");
+    fn factory_unknown_provider_is_error() {
+        let err = create_embedding_provider("cohere", None, "model", 1536)
+            .err()
+            .expect("unknown provider should fail");
+        assert!(err
+            .to_string()
+            .contains("Unknown embedding provider 'cohere'"));
     }
 
     #[test]
     fn factory_custom_empty_url() {
         // "custom:" with no URL — should still construct without panic
-        let p = create_embedding_provider("custom:", None, "model", 768);
+        let p = create_embedding_provider("custom:", None, "model", 768).unwrap();
         assert_eq!(p.name(), "openai");
     }
 
     #[test]
     fn factory_openai_no_api_key() {
-        let p = create_embedding_provider("openai", None, "text-embedding-3-small", 1536);
+        let p = create_embedding_provider("openai", None, "text-embedding-3-small", 1536).unwrap();
         assert_eq!(p.name(), "openai");
         assert_eq!(p.dimensions(), 1536);
     }
@@ -307,6 +535,89 @@ mod tests {
         );
     }
 
+    // ── Ollama ───────────────────────────────────────────────────
+
+    /// Fake `/api/embed`: each input becomes `[len, batch_size, 1]`, and every
+    /// request's batch size is recorded.
+    async fn spawn_fake_ollama() -> (String, Arc<Mutex<Vec<usize>>>) {
+        let batches = Arc::new(Mutex::new(Vec::new()));
+        let recorded = batches.clone();
+        let app = axum::Router::new().route(
+            "/api/embed",
+            axum::routing::post(move |Json(body): Json<Value>| {
+                let recorded = recorded.clone();
+                async move {
+                    let inputs = body["input"].as_array().cloned().unwrap_or_default();
+                    recorded.lock().push(inputs.len());
+                    let embeddings: Vec<Value> = inputs
+                        .iter()
+                        .map(|input| json!([input.as_str().unwrap_or("").len(), inputs.len(), 1]))
+                        .collect();
+                    Json(json!({ "model": body["model"], "embeddings": embeddings }))
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        (format!("http://{addr}"), batches)
+    }
+
+    #[tokio::test]
+    async fn ollama_embed_batches_requests() {
+        let (url, batches) = spawn_fake_ollama().await;
+        let p = OllamaEmbedding::new(&url, "nomic-embed-text", 3);
+        let texts: Vec<String> = (0..70).map(|i| format!("text {i}")).collect();
+        let refs: Vec<&str> = texts.iter().map(String::as_str).collect();
+
+        let embeddings = p.embed(&refs).await.unwrap();
+        assert_eq!(embeddings.len(), 70);
+        assert_eq!(embeddings[0], vec![6.0, 32.0, 1.0]);
+        assert_eq!(embeddings[69], vec![7.0, 6.0, 1.0]);
+        assert_eq!(*batches.lock(), vec![32, 32, 6]);
+    }
+
+    #[tokio::test]
+    async fn factory_ollama_uses_dimensions_from_init() {
+        let (url, batches) = spawn_fake_ollama().await;
+        let provider = format!("ollama:{url}/");
+        let before = create_embedding_provider(&provider, None, "nomic-embed-text", 1536).unwrap();
+        assert_eq!(before.dimensions(), 1536);
+        assert!(batches.lock().is_empty());
+
+        let config = MemoryConfig {
+            embedding_provider: provider.clone(),
+            embedding_model: "nomic-embed-text".into(),
+            embedding_dimensions: 1536,
+            ..MemoryConfig::default()
+        };
+        init(&config).await;
+        init(&config).await;
+        assert_eq!(*batches.lock(), vec![1]);
+
+        let p = create_embedding_provider(&provider, None, "nomic-embed-text", 1536).unwrap();
+        assert_eq!(p.name(), "ollama");
+        assert_eq!(p.dimensions(), 3);
+    }
+
+    #[tokio::test]
+    async fn ollama_probe_failure_keeps_configured_dimensions() {
+        // Port 9 (discard) is not an Ollama server.
+        let p = OllamaEmbedding::new("http://127.0.0.1:9", "m", 768)
+            .discover_dimensions()
+            .await;
+        assert_eq!(p.dimensions(), 768);
+    }
+
+    #[test]
+    fn ollama_rejects_mismatched_batch() {
+        let json = json!({ "embeddings": [[0.1, 0.2]] });
+        assert!(OllamaEmbedding::parse_embeddings(&json, 2).is_err());
+        assert!(OllamaEmbedding::parse_embeddings(&json!({}), 1).is_err());
+    }
+
     #[test]
     fn embeddings_url_custom_full_endpoint() {
         let p = OpenAiEmbedding::new(
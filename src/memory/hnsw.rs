@@ -0,0 +1,719 @@
+// HNSW (Hierarchical Navigable Small World) graph for approximate
+// nearest-neighbour search over memory embeddings.
+//
+// Vectors are L2-normalized on insert, so similarity is a dot product and
+// scores line up with `vector::cosine_similarity` (clamped to 0–1). Removed
+// entries stay in the graph as routing-only tombstones until `compact()`,
+// which keeps deletes cheap without cutting paths through the graph. Only the
+// graph is persisted — live vectors are re-attached from the database on load.
+
+use anyhow::Context;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::io::Write;
+use std::path::Path;
+
+const MAGIC: &[u8; 8] = b"ZCHNSW01";
+/// Sentinel for "no entry point" in the persisted header.
+const NO_ENTRY: u32 = u32::MAX;
+/// Hard cap on layer count; 16 layers covers far more than `u32::MAX` nodes.
+const MAX_LEVEL: usize = 16;
+
+/// Max neighbours per node on upper layers (layer 0 keeps twice as many).
+pub const DEFAULT_M: usize = 16;
+/// Candidate list size while inserting.
+pub const DEFAULT_EF_CONSTRUCTION: usize = 100;
+/// Candidate list size while searching.
+pub const DEFAULT_EF_SEARCH: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Candidate {
+    sim: f32,
+    node: u32,
+}
+
+impl Eq for Candidate {}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.sim
+            .total_cmp(&other.sim)
+            .then_with(|| self.node.cmp(&other.node))
+    }
+}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+struct Node {
+    id: String,
+    /// Normalized vector; empty until attached after `load_graph`.
+    vector: Vec<f32>,
+    /// Neighbour lists per layer; `neighbours.len() - 1` is the node's level.
+    neighbours: Vec<Vec<u32>>,
+    deleted: bool,
+}
+
+/// In-memory HNSW index keyed by memory id.
+pub struct HnswIndex {
+    dims: usize,
+    m: usize,
+    ef_construction: usize,
+    nodes: Vec<Node>,
+    live: HashMap<String, u32>,
+    entry: Option<u32>,
+    /// Opaque version stamp owned by the caller (e.g. a DB change counter).
+    generation: i64,
+}
+
+impl HnswIndex {
+    pub fn new(dims: usize) -> Self {
+        Self::with_params(dims, DEFAULT_M, DEFAULT_EF_CONSTRUCTION)
+    }
+
+    pub fn with_params(dims: usize, m: usize, ef_construction: usize) -> Self {
+        Self {
+            dims,
+            m: m.max(2),
+            ef_construction: ef_construction.max(1),
+            nodes: Vec::new(),
+            live: HashMap::new(),
+            entry: None,
+            generation: 0,
+        }
+    }
+
+    /// Build an index from `(id, vector)` pairs.
+    pub fn build<I>(dims: usize, entries: I) -> Self
+    where
+        I: IntoIterator<Item = (String, Vec<f32>)>,
+    {
+        let mut index = Self::new(dims);
+        for (id, vector) in entries {
+            index.insert(&id, &vector);
+        }
+        index
+    }
+
+    pub fn dims(&self) -> usize {
+        self.dims
+    }
+
+    /// Number of live (searchable) entries.
+    pub fn len(&self) -> usize {
+        self.live.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.live.is_empty()
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.live.contains_key(id)
+    }
+
+    pub fn generation(&self) -> i64 {
+        self.generation
+    }
+
+    pub fn set_generation(&mut self, generation: i64) {
+        self.generation = generation;
+    }
+
+    /// Removed entries still kept in the graph for routing.
+    pub fn tombstones(&self) -> usize {
+        self.nodes.len() - self.live.len()
+    }
+
+    /// True once tombstones outnumber live entries and a rebuild pays off.
+    pub fn needs_compaction(&self) -> bool {
+        let tombstones = self.tombstones();
+        tombstones >= 64 && tombstones > self.live.len()
+    }
+
+    /// Insert or replace `id`. Vectors with the wrong dimension are skipped
+    /// (and any previous vector for `id` is dropped).
+    pub fn insert(&mut self, id: &str, vector: &[f32]) {
+        self.remove(id);
+        if self.dims == 0 || vector.len() != self.dims {
+            return;
+        }
+
+        let node = u32::try_from(self.nodes.len()).expect("HNSW index exceeds u32::MAX nodes");
+        let level = self.random_level(id);
+        let query = normalize(vector);
+        self.nodes.push(Node {
+            id: id.to_string(),
+            vector: query.clone(),
+            neighbours: vec![Vec::new(); level + 1],
+            deleted: false,
+        });
+        self.live.insert(id.to_string(), node);
+
+        let Some(entry) = self.entry else {
+            self.entry = Some(node);
+            return;
+        };
+        let top = self.level_of(entry);
+
+        let mut current = entry;
+        for layer in (level + 1..=top).rev() {
+            current = self.greedy_closest(&query, current, layer);
+        }
+        for layer in (0..=level.min(top)).rev() {
+            let candidates = self.search_layer(&query, current, self.ef_construction, layer);
+            let selected = self.select_neighbours(&candidates, self.max_links(layer));
+            for &neighbour in &selected {
+                self.link(neighbour, node, layer);
+            }
+            self.nodes[node as usize].neighbours[layer] = selected;
+            if let Some(best) = candidates.first() {
+                current = best.node;
+            }
+        }
+
+        if level > top {
+            self.entry = Some(node);
+        }
+    }
+
+    /// Remove `id` from search results. Returns whether it was present.
+    pub fn remove(&mut self, id: &str) -> bool {
+        match self.live.remove(id) {
+            Some(node) => {
+                self.nodes[node as usize].deleted = true;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Top-`k` live entries by cosine similarity, best first.
+    pub fn search(&self, query: &[f32], k: usize, ef: usize) -> Vec<(String, f32)> {
+        let Some(entry) = self.entry else {
+            return Vec::new();
+        };
+        if k == 0 || query.len() != self.dims || self.live.is_empty() {
+            return Vec::new();
+        }
+
+        let query = normalize(query);
+        let mut current = entry;
+        for layer in (1..=self.level_of(entry)).rev() {
+            current = self.greedy_closest(&query, current, layer);
+        }
+
+        // Widen the beam by the tombstone ratio so deletes don't starve results.
+        let tombstone_slack = k * self.tombstones() / self.nodes.len().max(1);
+        let ef = ef.max(k) + tombstone_slack;
+        self.search_layer(&query, current, ef, 0)
+            .into_iter()
+            .filter(|c| !self.nodes[c.node as usize].deleted)
+            .take(k)
+            .map(|c| {
+                (
+                    self.nodes[c.node as usize].id.clone(),
+                    c.sim.clamp(0.0, 1.0),
+                )
+            })
+            .collect()
+    }
+
+    /// Rebuild the graph from live entries, dropping tombstones.
+    pub fn compact(&mut self) {
+        let mut fresh = Self::with_params(self.dims, self.m, self.ef_construction);
+        fresh.generation = self.generation;
+        for node in std::mem::take(&mut self.nodes) {
+            if !node.deleted {
+                fresh.insert(&node.id, &node.vector);
+            }
+        }
+        *self = fresh;
+    }
+
+    /// Persist the graph (not the live vectors) to `path` atomically.
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let mut buf = Vec::with_capacity(64 + self.nodes.len() * (self.m * 8 + 48));
+        buf.extend_from_slice(MAGIC);
+        put_u32(&mut buf, self.dims);
+        put_u32(&mut buf, self.m);
+        put_u32(&mut buf, self.ef_construction);
+        buf.extend_from_slice(&self.generation.to_le_bytes());
+        put_u32(&mut buf, self.nodes.len());
+        buf.extend_from_slice(&self.entry.unwrap_or(NO_ENTRY).to_le_bytes());
+
+        for node in &self.nodes {
+            buf.push(u8::from(node.deleted));
+            put_u32(&mut buf, node.id.len());
+            buf.extend_from_slice(node.id.as_bytes());
+            put_u32(&mut buf, node.neighbours.len());
+            for layer in &node.neighbours {
+                put_u32(&mut buf, layer.len());
+                for neighbour in layer {
+                    buf.extend_from_slice(&neighbour.to_le_bytes());
+                }
+            }
+            // Tombstones have no database row, so their routing vector is kept here.
+            // (Zero-filled if it was never attached after a load.)
+            if node.deleted {
+                for i in 0..self.dims {
+                    let value = node.vector.get(i).copied().unwrap_or(0.0);
+                    buf.extend_from_slice(&value.to_le_bytes());
+                }
+            }
+        }
+
+        let tmp = path.with_extension("hnsw.tmp");
+        let mut file = std::fs::File::create(&tmp)
+            .with_context(|| format!("failed to create {}", tmp.display()))?;
+        file.write_all(&buf)?;
+        file.sync_all()?;
+        std::fs::rename(&tmp, path)
+            .with_context(|| format!("failed to replace {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Load a graph written by [`HnswIndex::save`]. Live vectors must be
+    /// re-attached with [`HnswIndex::attach_vector`] before searching.
+    pub fn load_graph(path: &Path) -> anyhow::Result<Self> {
+        let bytes =
+            std::fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+        let mut reader = ByteReader::new(&bytes);
+        anyhow::ensure!(reader.take(MAGIC.len())? == MAGIC, "not an HNSW index file");
+
+        let dims = reader.u32()? as usize;
+        let m = reader.u32()? as usize;
+        let ef_construction = reader.u32()? as usize;
+        let generation = i64::from_le_bytes(reader.take(8)?.try_into()?);
+        let count = reader.u32()?;
+        let entry = reader.u32()?;
+
+        let mut index = Self::with_params(dims, m, ef_construction);
+        index.generation = generation;
+        index.entry = (entry != NO_ENTRY).then_some(entry);
+        index.nodes.reserve(count as usize);
+
+        for node in 0..count {
+            let deleted = reader.take(1)?[0] != 0;
+            let id_len = reader.u32()? as usize;
+            let id = std::str::from_utf8(reader.take(id_len)?)
+                .context("invalid node id")?
+                .to_string();
+            let layers = reader.u32()? as usize;
+            anyhow::ensure!((1..=MAX_LEVEL + 1).contains(&layers), "invalid node level");
+            let mut neighbours = Vec::with_capacity(layers);
+            for _ in 0..layers {
+                let len = reader.u32()? as usize;
+                let mut layer = Vec::with_capacity(len);
+                for _ in 0..len {
+                    let neighbour = reader.u32()?;
+                    anyhow::ensure!(neighbour < count, "neighbour out of range");
+                    layer.push(neighbour);
+                }
+                neighbours.push(layer);
+            }
+            let vector = if deleted {
+                (0..dims)
+                    .map(|_| {
+                        reader
+                            .take(4)
+                            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                    })
+                    .collect::<anyhow::Result<Vec<f32>>>()?
+            } else {
+                index.live.insert(id.clone(), node);
+                Vec::new()
+            };
+            index.nodes.push(Node {
+                id,
+                vector,
+                neighbours,
+                deleted,
+            });
+        }
+
+        anyhow::ensure!(reader.is_empty(), "trailing bytes in HNSW index file");
+        if let Some(entry) = index.entry {
+            anyhow::ensure!(entry < count, "entry point out of range");
+        }
+        Ok(index)
+    }
+
+    /// Attach the vector for a live entry after `load_graph`. Returns false
+    /// if the id is unknown or the dimension doesn't match.
+    pub fn attach_vector(&mut self, id: &str, vector: &[f32]) -> bool {
+        match self.live.get(id) {
+            Some(&node) if vector.len() == self.dims => {
+                self.nodes[node as usize].vector = normalize(vector);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// True when every live entry has its vector attached.
+    pub fn is_complete(&self) -> bool {
+        self.live
+            .values()
+            .all(|&node| self.nodes[node as usize].vector.len() == self.dims)
+    }
+
+    fn level_of(&self, node: u32) -> usize {
+        self.nodes[node as usize].neighbours.len() - 1
+    }
+
+    fn max_links(&self, layer: usize) -> usize {
+        if layer == 0 {
+            self.m * 2
+        } else {
+            self.m
+        }
+    }
+
+    fn similarity(&self, query: &[f32], node: u32) -> f32 {
+        dot(query, &self.nodes[node as usize].vector)
+    }
+
+    /// Deterministic level draw: `floor(-ln(u) / ln(M))` with `u` hashed
+    /// from the id and slot, so rebuilds produce the same graph shape.
+    fn random_level(&self, id: &str) -> usize {
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        for byte in id.bytes().chain(self.nodes.len().to_le_bytes()) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+        // Top 53 bits → uniform in (0, 1].
+        #[allow(clippy::cast_precision_loss)]
+        let uniform = ((hash >> 11) + 1) as f64 / (1_u64 << 53) as f64;
+        #[allow(clippy::cast_precision_loss)]
+        let level = -uniform.ln() / (self.m as f64).ln();
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let level = level.floor() as usize;
+        level.min(MAX_LEVEL)
+    }
+
+    fn greedy_closest(&self, query: &[f32], start: u32, layer: usize) -> u32 {
+        let mut best = start;
+        let mut best_sim = self.similarity(query, start);
+        loop {
+            let mut improved = false;
+            for &neighbour in self.nodes[best as usize]
+                .neighbours
+                .get(layer)
+                .map_or(&[][..], Vec::as_slice)
+            {
+                let sim = self.similarity(query, neighbour);
+                if sim > best_sim {
+                    best = neighbour;
+                    best_sim = sim;
+                    improved = true;
+                }
+            }
+            if !improved {
+                return best;
+            }
+        }
+    }
+
+    /// Beam search on one layer; returns up to `ef` candidates, best first.
+    fn search_layer(&self, query: &[f32], start: u32, ef: usize, layer: usize) -> Vec<Candidate> {
+        let first = Candidate {
+            sim: self.similarity(query, start),
+            node: start,
+        };
+        let mut visited = HashSet::from([start]);
+        let mut frontier = BinaryHeap::from([first]);
+        let mut best = BinaryHeap::from([Reverse(first)]);
+
+        while let Some(candidate) = frontier.pop() {
+            let worst = best.peek().map_or(f32::MIN, |Reverse(c)| c.sim);
+            if candidate.sim < worst && best.len() >= ef {
+                break;
+            }
+            let Some(links) = self.nodes[candidate.node as usize].neighbours.get(layer) else {
+                continue;
+            };
+            for &neighbour in links {
+                if !visited.insert(neighbour) {
+                    continue;
+                }
+                let sim = self.similarity(query, neighbour);
+                let worst = best.peek().map_or(f32::MIN, |Reverse(c)| c.sim);
+                if best.len() < ef || sim > worst {
+                    let next = Candidate {
+                        sim,
+                        node: neighbour,
+                    };
+                    frontier.push(next);
+                    best.push(Reverse(next));
+                    if best.len() > ef {
+                        best.pop();
+                    }
+                }
+            }
+        }
+
+        best.into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(c)| c)
+            .collect()
+    }
+
+    /// Neighbour selection heuristic from the HNSW paper: prefer candidates
+    /// that are closer to the new node than to any already-selected one, which
+    /// keeps links spread across clusters. Remaining slots are filled by rank.
+    fn select_neighbours(&self, candidates: &[Candidate], max: usize) -> Vec<u32> {
+        let mut selected: Vec<u32> = Vec::with_capacity(max);
+        for candidate in candidates {
+            if selected.len() >= max {
+                break;
+            }
+            let vector = &self.nodes[candidate.node as usize].vector;
+            let diverse = selected
+                .iter()
+                .all(|&chosen| self.similarity(vector, chosen) < candidate.sim);
+            if diverse {
+                selected.push(candidate.node);
+            }
+        }
+        for candidate in candidates {
+            if selected.len() >= max {
+                break;
+            }
+            if !selected.contains(&candidate.node) {
+                selected.push(candidate.node);
+            }
+        }
+        selected
+    }
+
+    /// Add a back-link `from → to`, pruning `from`'s list to its best links.
+    fn link(&mut self, from: u32, to: u32, layer: usize) {
+        let max = self.max_links(layer);
+        let links = &mut self.nodes[from as usize].neighbours[layer];
+        links.push(to);
+        if links.len() <= max {
+            return;
+        }
+
+        let origin = &self.nodes[from as usize].vector;
+        let mut ranked: Vec<Candidate> = self.nodes[from as usize].neighbours[layer]
+            .iter()
+            .map(|&node| Candidate {
+                sim: dot(origin, &self.nodes[node as usize].vector),
+                node,
+            })
+            .collect();
+        ranked.sort_by(|a, b| b.cmp(a));
+        let pruned = self.select_neighbours(&ranked, max);
+        self.nodes[from as usize].neighbours[layer] = pruned;
+    }
+}
+
+fn normalize(vector: &[f32]) -> Vec<f32> {
+    let norm = vector
+        .iter()
+        .map(|v| f64::from(*v) * f64::from(*v))
+        .sum::<f64>()
+        .sqrt();
+    if !norm.is_finite() || norm < f64::EPSILON {
+        return vec![0.0; vector.len()];
+    }
+    #[allow(clippy::cast_possible_truncation)]
+    vector
+        .iter()
+        .map(|v| (f64::from(*v) / norm) as f32)
+        .collect()
+}
+
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+fn put_u32(buf: &mut Vec<u8>, value: usize) {
+    let value = u32::try_from(value).expect("HNSW field exceeds u32::MAX");
+    buf.extend_from_slice(&value.to_le_bytes());
+}
+
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    fn take(&mut self, len: usize) -> anyhow::Result<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .context("HNSW index file is truncated")?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn u32(&mut self) -> anyhow::Result<u32> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pos == self.bytes.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::vector::cosine_similarity;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use tempfile::TempDir;
+
+    fn random_vectors(count: usize, dims: usize, seed: u64) -> Vec<(String, Vec<f32>)> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..count)
+            .map(|i| {
+                let vector = (0..dims).map(|_| rng.random_range(-1.0..1.0)).collect();
+                (format!("id-{i}"), vector)
+            })
+            .collect()
+    }
+
+    fn exact_top(entries: &[(String, Vec<f32>)], query: &[f32], k: usize) -> Vec<String> {
+        let mut scored: Vec<(String, f32)> = entries
+            .iter()
+            .map(|(id, v)| (id.clone(), cosine_similarity(query, v)))
+            .collect();
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scored.into_iter().take(k).map(|(id, _)| id).collect()
+    }
+
+    #[test]
+    fn search_recall_matches_brute_force() {
+        let entries = random_vectors(500, 16, 7);
+        let index = HnswIndex::build(16, entries.clone());
+        assert_eq!(index.len(), 500);
+
+        let queries = random_vectors(20, 16, 99);
+        let mut hits = 0;
+        for (_, query) in &queries {
+            let expected = exact_top(&entries, query, 10);
+            let found: Vec<String> = index
+                .search(query, 10, DEFAULT_EF_SEARCH)
+                .into_iter()
+                .map(|(id, _)| id)
+                .collect();
+            hits += expected.iter().filter(|id| found.contains(id)).count();
+        }
+        let recall = f64::from(u32::try_from(hits).unwrap()) / 200.0;
+        assert!(recall > 0.9, "recall@10 too low: {recall}");
+    }
+
+    #[test]
+    fn scores_match_cosine_similarity() {
+        let mut index = HnswIndex::new(3);
+        index.insert("a", &[1.0, 0.0, 0.0]);
+        index.insert("b", &[0.5, 0.5, 0.0]);
+        index.insert("c", &[-1.0, 0.0, 0.0]);
+        let results = index.search(&[2.0, 0.0, 0.0], 3, 16);
+        assert_eq!(results[0].0, "a");
+        assert!((results[0].1 - 1.0).abs() < 1e-6);
+        assert_eq!(results[1].0, "b");
+        let expected = cosine_similarity(&[2.0, 0.0, 0.0], &[0.5, 0.5, 0.0]);
+        assert!((results[1].1 - expected).abs() < 1e-5);
+        // Opposite vectors clamp to zero like cosine_similarity does.
+        assert!(results[2].1.abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn remove_and_reinsert_update_results() {
+        let mut index = HnswIndex::new(2);
+        index.insert("x", &[1.0, 0.0]);
+        index.insert("y", &[0.0, 1.0]);
+        assert!(index.remove("x"));
+        assert!(!index.remove("x"));
+        assert_eq!(index.len(), 1);
+        assert_eq!(index.tombstones(), 1);
+        let results = index.search(&[1.0, 0.0], 2, 16);
+        assert!(results.iter().all(|(id, _)| id != "x"));
+
+        index.insert("y", &[1.0, 0.1]);
+        assert_eq!(index.search(&[1.0, 0.0], 1, 16)[0].0, "y");
+        assert_eq!(index.len(), 1);
+    }
+
+    #[test]
+    fn wrong_dimension_is_skipped() {
+        let mut index = HnswIndex::new(3);
+        index.insert("a", &[1.0, 0.0, 0.0]);
+        index.insert("a", &[1.0, 0.0]);
+        assert!(index.is_empty());
+        assert!(index.search(&[1.0, 0.0], 1, 16).is_empty());
+    }
+
+    #[test]
+    fn compaction_drops_tombstones() {
+        let entries = random_vectors(200, 8, 3);
+        let mut index = HnswIndex::build(8, entries.clone());
+        index.set_generation(42);
+        for (id, _) in entries.iter().take(150) {
+            index.remove(id);
+        }
+        assert!(index.needs_compaction());
+        index.compact();
+        assert_eq!(index.tombstones(), 0);
+        assert_eq!(index.len(), 50);
+        assert_eq!(index.generation(), 42);
+        let (id, vector) = &entries[199];
+        assert_eq!(&index.search(vector, 1, 32)[0].0, id);
+    }
+
+    #[test]
+    fn save_and_load_graph_round_trip() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("brain.hnsw");
+        let entries = random_vectors(300, 16, 11);
+        let mut index = HnswIndex::build(16, entries.clone());
+        index.remove("id-0");
+        index.set_generation(7);
+        index.save(&path).unwrap();
+
+        let mut loaded = HnswIndex::load_graph(&path).unwrap();
+        assert_eq!(loaded.generation(), 7);
+        assert_eq!(loaded.len(), 299);
+        assert!(!loaded.is_complete());
+        for (id, vector) in entries.iter().skip(1) {
+            assert!(loaded.attach_vector(id, vector));
+        }
+        assert!(!loaded.attach_vector("id-0", &entries[0].1));
+        assert!(loaded.is_complete());
+
+        let query = &entries[42].1;
+        assert_eq!(
+            loaded.search(query, 5, DEFAULT_EF_SEARCH),
+            index.search(query, 5, DEFAULT_EF_SEARCH)
+        );
+    }
+
+    #[test]
+    fn load_graph_rejects_corrupt_files() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("brain.hnsw");
+        std::fs::write(&path, b"not an index").unwrap();
+        assert!(HnswIndex::load_graph(&path).is_err());
+
+        let index = HnswIndex::build(4, random_vectors(20, 4, 1));
+        index.save(&path).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::write(&path, &bytes[..bytes.len() - 3]).unwrap();
+        assert!(HnswIndex::load_graph(&path).is_err());
+    }
+}
@@ -1,13 +1,13 @@
 use crate::config::MemoryConfig;
 use anyhow::Result;
-use chrono::{DateTime, Duration, Local, NaiveDate, Utc};
+use chrono::{DateTime, Duration, Local, NaiveDate, SecondsFormat, Utc};
 use rusqlite::{params, Connection};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::time::{Duration as StdDuration, SystemTime};
 
-const HYGIENE_INTERVAL_HOURS: i64 = 12;
+pub(crate) const HYGIENE_INTERVAL_HOURS: i64 = 12;
 const STATE_FILE: &str = "memory_hygiene_state.json";
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -17,6 +17,8 @@ struct HygieneReport {
     purged_memory_archives: u64,
     purged_session_archives: u64,
     pruned_conversation_rows: u64,
+    #[serde(default)]
+    purged_expired_rows: u64,
 }
 
 impl HygieneReport {
@@ -26,6 +28,7 @@ impl HygieneReport {
             + self.purged_memory_archives
             + self.purged_session_archives
             + self.pruned_conversation_rows
+            + self.purged_expired_rows
     }
 }
 
@@ -37,14 +40,17 @@ struct HygieneState {
 
 /// Run memory/session hygiene if the cadence window has elapsed.
 ///
+/// Returns whether a pass ran, so callers can run backend-specific cleanup
+/// on the same cadence.
+///
 /// This function is intentionally best-effort: callers should log and continue on failure.
-pub fn run_if_due(config: &MemoryConfig, workspace_dir: &Path) -> Result<()> {
+pub fn run_if_due(config: &MemoryConfig, workspace_dir: &Path) -> Result<bool> {
     if !config.hygiene_enabled {
-        return Ok(());
+        return Ok(false);
     }
 
     if !should_run_now(workspace_dir)? {
-        return Ok(());
+        return Ok(false);
     }
 
     let report = HygieneReport {
@@ -58,23 +64,26 @@ pub fn run_if_due(config: &MemoryConfig, workspace_dir: &Path) -> Result<()> {
         pruned_conversation_rows: prune_conversation_rows(
             workspace_dir,
             config.conversation_retention_days,
+            config.consolidation_enabled,
         )?,
+        purged_expired_rows: purge_expired_rows(workspace_dir)?,
     };
 
     write_state(workspace_dir, &report)?;
 
     if report.total_actions() > 0 {
         tracing::info!(
-            "memory hygiene complete: archived_memory={} archived_sessions={} purged_memory={} purged_sessions={} pruned_conversation_rows={}",
+            "memory hygiene complete: archived_memory={} archived_sessions={} purged_memory={} purged_sessions={} pruned_conversation_rows={} purged_expired_rows={}",
             report.archived_memory_files,
             report.archived_session_files,
             report.purged_memory_archives,
             report.purged_session_archives,
             report.pruned_conversation_rows,
+            report.purged_expired_rows,
         );
     }
 
-    Ok(())
+    Ok(true)
 }
 
 fn should_run_now(workspace_dir: &Path) -> Result<bool> {
@@ -295,7 +304,11 @@ fn purge_session_archives(workspace_dir: &Path, purge_after_days: u32) -> Result
     Ok(removed)
 }
 
-fn prune_conversation_rows(workspace_dir: &Path, retention_days: u32) -> Result<u64> {
+fn prune_conversation_rows(
+    workspace_dir: &Path,
+    retention_days: u32,
+    consolidation_enabled: bool,
+) -> Result<u64> {
     if retention_days == 0 {
         return Ok(0);
     }
@@ -305,10 +318,19 @@ fn prune_conversation_rows(workspace_dir: &Path, retention_days: u32) -> Result<
         return Ok(0);
     }
 
+    let mut cutoff = Local::now() - Duration::days(i64::from(retention_days));
+    // Never prune rows the consolidation pass has not distilled yet.
+    if consolidation_enabled {
+        let Some(watermark) = super::consolidation::watermark(workspace_dir) else {
+            return Ok(0);
+        };
+        cutoff = cutoff.min(watermark.with_timezone(&Local));
+    }
+    let cutoff = cutoff.to_rfc3339();
+
     let conn = Connection::open(db_path)?;
     // Use WAL so hygiene pruning doesn't block agent reads
     conn.execute_batch("PRAGMA journal_mode = WAL; PRAGMA synchronous = NORMAL;")?;
-    let cutoff = (Local::now() - Duration::days(i64::from(retention_days))).to_rfc3339();
 
     let affected = conn.execute(
         "DELETE FROM memories WHERE category = 'conversation' AND updated_at < ?1",
@@ -318,6 +340,35 @@ fn prune_conversation_rows(workspace_dir: &Path, retention_days: u32) -> Result<
     Ok(u64::try_from(affected).unwrap_or(0))
 }
 
+fn purge_expired_rows(workspace_dir: &Path) -> Result<u64> {
+    let db_path = workspace_dir.join("memory").join("brain.db");
+    if !db_path.exists() {
+        return Ok(0);
+    }
+
+    let conn = Connection::open(db_path)?;
+    conn.execute_batch("PRAGMA journal_mode = WAL; PRAGMA synchronous = NORMAL;")?;
+
+    // Databases not yet opened by a metadata-aware SqliteMemory have no expiry column.
+    let has_expiry: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM pragma_table_info('memories') WHERE name = 'expires_at')",
+        [],
+        |row| row.get(0),
+    )?;
+    if !has_expiry {
+        return Ok(0);
+    }
+
+    // `expires_at` is stored normalized to UTC, so text comparison is exact.
+    let now = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+    let affected = conn.execute(
+        "DELETE FROM memories WHERE expires_at IS NOT NULL AND expires_at <= ?1",
+        params![now],
+    )?;
+
+    Ok(u64::try_from(affected).unwrap_or(0))
+}
+
 fn memory_date_from_filename(filename: &str) -> Option<NaiveDate> {
     let stem = filename.strip_suffix(".md")?;
     let date_part = stem.split('_').next().unwrap_or(stem);
@@ -379,7 +430,7 @@ fn split_name(filename: &str) -> (&str, &str) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::memory::{Memory, MemoryCategory, SqliteMemory};
+    use crate::memory::{Memory, MemoryCategory, MemoryMetadata, SqliteMemory};
     use tempfile::TempDir;
 
     fn default_cfg() -> MemoryConfig {
@@ -537,4 +588,78 @@ mod tests {
             "core memory should remain"
         );
     }
+
+    #[tokio::test]
+    async fn keeps_unconsolidated_conversation_rows_when_consolidation_enabled() {
+        let tmp = TempDir::new().unwrap();
+        let workspace = tmp.path();
+
+        let mem = SqliteMemory::new(workspace).unwrap();
+        mem.store("conv_old", "outdated", MemoryCategory::Conversation, None)
+            .await
+            .unwrap();
+        drop(mem);
+
+        let conn = Connection::open(workspace.join("memory").join("brain.db")).unwrap();
+        let old_cutoff = (Local::now() - Duration::days(60)).to_rfc3339();
+        conn.execute(
+            "UPDATE memories SET created_at = ?1, updated_at = ?1 WHERE key = 'conv_old'",
+            params![old_cutoff],
+        )
+        .unwrap();
+        drop(conn);
+
+        let mut cfg = default_cfg();
+        cfg.archive_after_days = 0;
+        cfg.purge_after_days = 0;
+        cfg.conversation_retention_days = 30;
+        cfg.consolidation_enabled = true;
+
+        run_if_due(&cfg, workspace).unwrap();
+
+        let mem2 = SqliteMemory::new(workspace).unwrap();
+        assert!(
+            mem2.get("conv_old").await.unwrap().is_some(),
+            "rows newer than the consolidation watermark must not be pruned"
+        );
+    }
+
+    #[tokio::test]
+    async fn purges_expired_rows_in_sqlite_backend() {
+        let tmp = TempDir::new().unwrap();
+        let workspace = tmp.path();
+
+        let mem = SqliteMemory::new(workspace).unwrap();
+        let expired = MemoryMetadata {
+            importance: None,
+            expires_at: Some((Utc::now() - Duration::hours(1)).to_rfc3339()),
+        };
+        let current = MemoryMetadata {
+            importance: None,
+            expires_at: Some((Utc::now() + Duration::days(1)).to_rfc3339()),
+        };
+        mem.store_with_metadata("stale", "old", MemoryCategory::Core, None, &expired)
+            .await
+            .unwrap();
+        mem.store_with_metadata("fresh", "new", MemoryCategory::Core, None, &current)
+            .await
+            .unwrap();
+        drop(mem);
+
+        let mut cfg = default_cfg();
+        cfg.archive_after_days = 0;
+        cfg.purge_after_days = 0;
+        cfg.conversation_retention_days = 0;
+        assert!(run_if_due(&cfg, workspace).unwrap());
+
+        let conn = Connection::open(workspace.join("memory").join("brain.db")).unwrap();
+        let keys: Vec<String> = conn
+            .prepare("SELECT key FROM memories")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<rusqlite::Result<_>>()
+            .unwrap();
+        assert_eq!(keys, vec!["fresh".to_string()]);
+    }
 }
@@ -0,0 +1,343 @@
+//! Workspace document ingestion for retrieval.
+//!
+//! `zeroclaw ingest <path>` and the `ingest_document` tool split Markdown,
+//! plain text and source files (and PDFs with the `rag-pdf` feature) into
+//! line chunks stored in the `document` category. Chunk keys carry the
+//! file's content hash, source path and line range, so unchanged files are
+//! skipped on re-ingestion and recall can cite where a chunk came from.
+
+use super::chunker;
+use super::traits::{Memory, MemoryCategory};
+use anyhow::{Context, Result};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Category ingested chunks are stored under.
+pub const DOCUMENT_CATEGORY: &str = "document";
+
+const KEY_PREFIX: &str = "doc:";
+const CHUNK_TOKENS: usize = 512;
+const MAX_FILE_BYTES: u64 = 10 * 1024 * 1024;
+
+const MARKDOWN_EXTENSIONS: &[&str] = &["md", "markdown", "mdx"];
+const TEXT_EXTENSIONS: &[&str] = &[
+    "txt", "rst", "adoc", "org", "rs", "py", "js", "jsx", "ts", "tsx", "go", "java", "kt", "c",
+    "h", "cc", "cpp", "hpp", "cs", "rb", "php", "swift", "scala", "lua", "sh", "bash", "zsh",
+    "sql", "toml", "yaml", "yml", "json", "html", "css", "proto",
+];
+/// Directories never walked: build output and dependencies.
+const SKIPPED_DIRS: &[&str] = &["target", "node_modules"];
+
+/// Outcome of one ingestion run.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct IngestReport {
+    /// Files chunked and stored because they were new or changed.
+    pub ingested: usize,
+    /// Files whose content hash matched what was already stored.
+    pub unchanged: usize,
+    /// Files that were empty or could not be read as text.
+    pub skipped: usize,
+    /// Chunks written.
+    pub chunks: usize,
+    /// Previously ingested files under the path that no longer exist.
+    pub removed: usize,
+}
+
+/// Chunks already stored for one source file.
+struct StoredDocument {
+    /// Content hash shared by every chunk; empty when the chunks disagree
+    /// (an interrupted run), which forces re-ingestion.
+    hash: String,
+    keys: Vec<String>,
+}
+
+pub fn document_category() -> MemoryCategory {
+    MemoryCategory::Custom(DOCUMENT_CATEGORY.into())
+}
+
+/// Ingest a file or every document under a directory into `memory`.
+/// Sources inside `workspace_dir` are named by their relative path.
+pub async fn ingest_path(
+    memory: &dyn Memory,
+    path: &Path,
+    workspace_dir: &Path,
+) -> Result<IngestReport> {
+    let root = std::fs::canonicalize(path)
+        .with_context(|| format!("cannot resolve {}", path.display()))?;
+    let workspace =
+        std::fs::canonicalize(workspace_dir).unwrap_or_else(|_| workspace_dir.to_path_buf());
+
+    let mut files = Vec::new();
+    if root.is_dir() {
+        collect_documents(&root, &workspace.join("memory"), &mut files);
+        files.sort();
+    } else {
+        files.push(root.clone());
+    }
+
+    let mut stored = stored_documents(memory).await?;
+    let mut report = IngestReport::default();
+
+    for file in &files {
+        let source = source_name(file, &workspace);
+        let text = match read_document(file).await {
+            Some(text) if !text.trim().is_empty() => text,
+            _ => {
+                report.skipped += 1;
+                continue;
+            }
+        };
+        let hash = content_hash(&text);
+        let previous = stored.remove(&source);
+        if previous.as_ref().is_some_and(|doc| doc.hash == hash) {
+            report.unchanged += 1;
+            continue;
+        }
+
+        // New chunks are written before the old ones are dropped, so an
+        // interrupted run leaves mixed hashes and is retried next time.
+        for chunk in chunker::chunk_lines(&text, CHUNK_TOKENS, is_markdown(file)) {
+            let key = format!(
+                "{KEY_PREFIX}{hash}:{source}:{}-{}",
+                chunk.start_line, chunk.end_line
+            );
+            memory
+                .store(&key, &chunk.content, document_category(), None)
+                .await?;
+            report.chunks += 1;
+        }
+        if let Some(previous) = previous {
+            forget_keys(memory, &previous.keys).await?;
+        }
+        report.ingested += 1;
+    }
+
+    if root.is_dir() {
+        for (source, doc) in stored {
+            let location = workspace.join(&source);
+            if location.starts_with(&root) && !location.exists() {
+                forget_keys(memory, &doc.keys).await?;
+                report.removed += 1;
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+/// Label for a recalled entry: `path:start-end` for ingested chunks, the key
+/// itself for everything else.
+pub fn cite(key: &str) -> Cow<'_, str> {
+    match parse_key(key) {
+        Some((_, source, start, end)) => Cow::Owned(format!("{source}:{start}-{end}")),
+        None => Cow::Borrowed(key),
+    }
+}
+
+/// Split a chunk key into `(hash, source, start_line, end_line)`.
+fn parse_key(key: &str) -> Option<(&str, &str, usize, usize)> {
+    let rest = key.strip_prefix(KEY_PREFIX)?;
+    let (hash, rest) = rest.split_once(':')?;
+    let (source, lines) = rest.rsplit_once(':')?;
+    let (start, end) = lines.split_once('-')?;
+    Some((hash, source, start.parse().ok()?, end.parse().ok()?))
+}
+
+async fn stored_documents(memory: &dyn Memory) -> Result<HashMap<String, StoredDocument>> {
+    let mut stored: HashMap<String, StoredDocument> = HashMap::new();
+    for entry in memory.list(Some(&document_category()), None).await? {
+        let Some((hash, source, _, _)) = parse_key(&entry.key) else {
+            continue;
+        };
+        let doc = stored
+            .entry(source.to_string())
+            .or_insert_with(|| StoredDocument {
+                hash: hash.to_string(),
+                keys: Vec::new(),
+            });
+        if doc.hash != hash {
+            doc.hash.clear();
+        }
+        doc.keys.push(entry.key);
+    }
+    Ok(stored)
+}
+
+async fn forget_keys(memory: &dyn Memory, keys: &[String]) -> Result<()> {
+    for key in keys {
+        memory.forget(key).await?;
+    }
+    Ok(())
+}
+
+/// Collect ingestible files under `dir`, skipping hidden entries, symlinks,
+/// build output and the memory store itself (`skip`).
+fn collect_documents(dir: &Path, skip: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if name.starts_with('.') || SKIPPED_DIRS.contains(&name.as_ref()) {
+            continue;
+        }
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        let path = entry.path();
+        if file_type.is_dir() && path != skip {
+            collect_documents(&path, skip, out);
+        } else if file_type.is_file() && is_document(&path) {
+            out.push(path);
+        }
+    }
+}
+
+fn extension(path: &Path) -> String {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+}
+
+fn is_markdown(path: &Path) -> bool {
+    MARKDOWN_EXTENSIONS.contains(&extension(path).as_str())
+}
+
+fn is_document(path: &Path) -> bool {
+    let ext = extension(path);
+    MARKDOWN_EXTENSIONS.contains(&ext.as_str())
+        || TEXT_EXTENSIONS.contains(&ext.as_str())
+        || (cfg!(feature = "rag-pdf") && ext == "pdf")
+}
+
+async fn read_document(path: &Path) -> Option<String> {
+    let metadata = tokio::fs::metadata(path).await.ok()?;
+    if metadata.len() > MAX_FILE_BYTES {
+        return None;
+    }
+    let bytes = tokio::fs::read(path).await.ok()?;
+    if extension(path) == "pdf" {
+        return extract_pdf_text(&bytes);
+    }
+    String::from_utf8(bytes).ok()
+}
+
+#[cfg(feature = "rag-pdf")]
+fn extract_pdf_text(bytes: &[u8]) -> Option<String> {
+    pdf_extract::extract_text_from_mem(bytes).ok()
+}
+
+#[cfg(not(feature = "rag-pdf"))]
+fn extract_pdf_text(_bytes: &[u8]) -> Option<String> {
+    None
+}
+
+fn content_hash(text: &str) -> String {
+    use sha2::{Digest, Sha256};
+    hex::encode(&Sha256::digest(text.as_bytes())[..8])
+}
+
+fn source_name(path: &Path, workspace: &Path) -> String {
+    match path.strip_prefix(workspace) {
+        Ok(relative) => relative.to_string_lossy().replace('\\', "/"),
+        Err(_) => path.display().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::SqliteMemory;
+    use tempfile::TempDir;
+
+    fn workspace() -> (TempDir, SqliteMemory) {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        let docs = tmp.path().join("docs");
+        std::fs::create_dir_all(docs.join("node_modules")).unwrap();
+        std::fs::write(
+            docs.join("setup.md"),
+            "# Setup\nInstall the zebra toolchain first.\n\n## Run\nStart the daemon.\n",
+        )
+        .unwrap();
+        std::fs::write(docs.join("main.py"), "print('zebra')\n").unwrap();
+        std::fs::write(docs.join("node_modules/dep.md"), "zebra dependency\n").unwrap();
+        std::fs::write(docs.join(".draft.md"), "zebra draft\n").unwrap();
+        std::fs::write(docs.join("logo.png"), [0u8, 159, 146, 150]).unwrap();
+        (tmp, mem)
+    }
+
+    #[tokio::test]
+    async fn ingests_documents_with_line_ranges() {
+        let (tmp, mem) = workspace();
+
+        let report = ingest_path(&mem, &tmp.path().join("docs"), tmp.path())
+            .await
+            .unwrap();
+        assert_eq!(report.ingested, 2);
+        assert_eq!(report.chunks, 3);
+
+        let mut cited: Vec<String> = mem
+            .list(Some(&document_category()), None)
+            .await
+            .unwrap()
+            .iter()
+            .map(|entry| cite(&entry.key).into_owned())
+            .collect();
+        cited.sort();
+        assert_eq!(
+            cited,
+            ["docs/main.py:1-1", "docs/setup.md:1-2", "docs/setup.md:4-5"]
+        );
+
+        let hits = mem.recall("zebra toolchain", 5, None).await.unwrap();
+        assert_eq!(cite(&hits[0].key), "docs/setup.md:1-2");
+        assert!(hits[0].content.contains("Install the zebra toolchain"));
+    }
+
+    #[tokio::test]
+    async fn reingestion_only_touches_changed_and_removed_files() {
+        let (tmp, mem) = workspace();
+        let docs = tmp.path().join("docs");
+        ingest_path(&mem, &docs, tmp.path()).await.unwrap();
+
+        let again = ingest_path(&mem, &docs, tmp.path()).await.unwrap();
+        assert_eq!(again.unchanged, 2);
+        assert_eq!(again.chunks, 0);
+
+        std::fs::write(docs.join("setup.md"), "# Setup\nUse the yak toolchain.\n").unwrap();
+        std::fs::remove_file(docs.join("main.py")).unwrap();
+        let changed = ingest_path(&mem, &docs, tmp.path()).await.unwrap();
+        assert_eq!(changed.ingested, 1);
+        assert_eq!(changed.removed, 1);
+
+        let entries = mem.list(Some(&document_category()), None).await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(cite(&entries[0].key), "docs/setup.md:1-2");
+        assert!(entries[0].content.contains("yak"));
+    }
+
+    #[tokio::test]
+    async fn single_files_outside_the_workspace_keep_absolute_sources() {
+        let (tmp, mem) = workspace();
+        let outside = TempDir::new().unwrap();
+        let notes = outside.path().join("notes.txt");
+        std::fs::write(&notes, "external zebra notes\n").unwrap();
+
+        let report = ingest_path(&mem, &notes, tmp.path()).await.unwrap();
+        assert_eq!(report.ingested, 1);
+        let entries = mem.list(Some(&document_category()), None).await.unwrap();
+        let expected = format!("{}:1-1", notes.canonicalize().unwrap().display());
+        assert_eq!(cite(&entries[0].key), expected);
+    }
+
+    #[test]
+    fn cite_leaves_other_keys_alone() {
+        assert_eq!(cite("user_lang"), "user_lang");
+        assert_eq!(cite("doc:not-a-chunk"), "doc:not-a-chunk");
+        assert_eq!(cite("doc:ab12:C:/notes/a:b.md:3-9"), "C:/notes/a:b.md:3-9");
+    }
+}
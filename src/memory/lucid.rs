@@ -1,10 +1,11 @@
 use super::sqlite::SqliteMemory;
-use super::traits::{Memory, MemoryCategory, MemoryEntry};
+use super::traits::{Memory, MemoryCategory, MemoryEntry, MemoryMetadata};
 use async_trait::async_trait;
 use chrono::Local;
 use parking_lot::Mutex;
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::process::Command;
 use tokio::time::timeout;
@@ -226,6 +227,11 @@ impl LucidMemory {
                 timestamp: now.clone(),
                 session_id: None,
                 score: Some((1.0 - rank as f64 * 0.05).max(0.1)),
+                importance: None,
+                expires_at: None,
+                last_accessed: None,
+                access_count: 0,
+                namespace: None,
             });
         }
 
@@ -320,6 +326,21 @@ impl Memory for LucidMemory {
         Ok(())
     }
 
+    async fn store_with_metadata(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+        metadata: &MemoryMetadata,
+    ) -> anyhow::Result<()> {
+        self.local
+            .store_with_metadata(key, content, category.clone(), session_id, metadata)
+            .await?;
+        self.sync_to_lucid_async(key, content, &category).await;
+        Ok(())
+    }
+
     async fn recall(
         &self,
         query: &str,
@@ -382,6 +403,30 @@ impl Memory for LucidMemory {
     async fn health_check(&self) -> bool {
         self.local.health_check().await
     }
+
+    /// Tenants stay on the local store; the shared Lucid index has no
+    /// notion of namespaces, so nothing tenant-scoped is sent to it.
+    fn with_namespace(&self, namespace: &str) -> Option<Arc<dyn Memory>> {
+        self.local.with_namespace(namespace)
+    }
+
+    async fn reindex(&self) -> anyhow::Result<usize> {
+        self.local.reindex().await
+    }
+
+    async fn export_entries(
+        &self,
+        with_embeddings: bool,
+    ) -> anyhow::Result<Vec<(MemoryEntry, Option<Vec<f32>>)>> {
+        self.local.export_entries(with_embeddings).await
+    }
+
+    async fn restore(&self, entry: &MemoryEntry, embedding: Option<&[f32]>) -> anyhow::Result<()> {
+        self.local.restore(entry, embedding).await?;
+        self.sync_to_lucid_async(&entry.key, &entry.content, &entry.category)
+            .await;
+        Ok(())
+    }
 }
 
 #[cfg(all(test, unix))]
@@ -1,7 +1,8 @@
 use super::traits::{Memory, MemoryCategory, MemoryEntry};
 use async_trait::async_trait;
-use chrono::Local;
+use chrono::{Local, NaiveDate};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use tokio::fs;
 
 /// Markdown-based memory — plain files as source of truth
@@ -30,9 +31,30 @@ impl MarkdownMemory {
 
     fn daily_path(&self) -> PathBuf {
         let date = Local::now().format("%Y-%m-%d").to_string();
+        self.daily_path_for(&date)
+    }
+
+    fn daily_path_for(&self, date: &str) -> PathBuf {
         self.memory_dir().join(format!("{date}.md"))
     }
 
+    /// Date (`YYYY-MM-DD`) at the start of an RFC 3339 or bare-date timestamp.
+    fn timestamp_date(timestamp: &str) -> Option<&str> {
+        let date = timestamp.get(..10)?;
+        NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+        Some(date)
+    }
+
+    /// Keys this backend generates itself (`MEMORY:3`, `2026-02-16:0`) carry
+    /// no information beyond the line position, so they are not re-rendered.
+    fn is_line_key(key: &str) -> bool {
+        key.rsplit_once(':').is_some_and(|(stem, index)| {
+            !index.is_empty()
+                && index.chars().all(|c| c.is_ascii_digit())
+                && (stem == "MEMORY" || Self::timestamp_date(stem) == Some(stem))
+        })
+    }
+
     async fn ensure_dirs(&self) -> anyhow::Result<()> {
         fs::create_dir_all(self.memory_dir()).await?;
         Ok(())
@@ -51,7 +73,10 @@ impl MarkdownMemory {
             let header = if path == self.core_path() {
                 "# Long-Term Memory\n\n"
             } else {
-                let date = Local::now().format("%Y-%m-%d").to_string();
+                let date = path
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or_default();
                 &format!("# Daily Log — {date}\n\n")
             };
             format!("{header}{content}\n")
@@ -91,6 +116,11 @@ impl MarkdownMemory {
                     timestamp: filename.to_string(),
                     session_id: None,
                     score: None,
+                    importance: None,
+                    expires_at: None,
+                    last_accessed: None,
+                    access_count: 0,
+                    namespace: None,
                 }
             })
             .collect()
@@ -224,6 +254,33 @@ impl Memory for MarkdownMemory {
     async fn health_check(&self) -> bool {
         self.workspace_dir.exists()
     }
+
+    /// Each tenant gets its own `MEMORY.md` and daily logs under
+    /// `memory/tenants/`, which the shared layout never reads.
+    fn with_namespace(&self, namespace: &str) -> Option<Arc<dyn Memory>> {
+        let dir = self
+            .memory_dir()
+            .join("tenants")
+            .join(super::tenant::directory_name(namespace));
+        Some(Arc::new(Self::new(&dir)))
+    }
+
+    /// Core entries go back to `MEMORY.md`; everything else lands in the
+    /// daily log for the entry's date. Sessions and custom categories have
+    /// no Markdown representation and are dropped.
+    async fn restore(&self, entry: &MemoryEntry, _embedding: Option<&[f32]>) -> anyhow::Result<()> {
+        let line = if Self::is_line_key(&entry.key) {
+            format!("- {}", entry.content)
+        } else {
+            format!("- **{}**: {}", entry.key, entry.content)
+        };
+        let path = match (&entry.category, Self::timestamp_date(&entry.timestamp)) {
+            (MemoryCategory::Core, _) => self.core_path(),
+            (_, Some(date)) => self.daily_path_for(date),
+            (_, None) => self.daily_path(),
+        };
+        self.append_to_file(&path, &line).await
+    }
 }
 
 #[cfg(test)]
@@ -348,6 +405,35 @@ mod tests {
         assert!(results.is_empty());
     }
 
+    #[tokio::test]
+    async fn markdown_restore_uses_entry_date_and_keeps_own_lines() {
+        let (_tmp, mem) = temp_workspace();
+        let entry = MemoryEntry {
+            id: "2026-01-02:0".into(),
+            key: "2026-01-02:0".into(),
+            content: "**standup**: shipped export".into(),
+            category: MemoryCategory::Daily,
+            timestamp: "2026-01-02".into(),
+            session_id: None,
+            score: None,
+            importance: None,
+            expires_at: None,
+            last_accessed: None,
+            access_count: 0,
+            namespace: None,
+        };
+        mem.restore(&entry, None).await.unwrap();
+
+        let content = sync_fs::read_to_string(mem.daily_path_for("2026-01-02")).unwrap();
+        assert!(content.starts_with("# Daily Log — 2026-01-02"));
+        assert!(content.contains("\n- **standup**: shipped export\n"));
+
+        let restored = mem.list(None, None).await.unwrap();
+        assert_eq!(restored.len(), 1);
+        assert_eq!(restored[0].content, entry.content);
+        assert_eq!(restored[0].timestamp, "2026-01-02");
+    }
+
     #[tokio::test]
     async fn markdown_empty_count() {
         let (_tmp, mem) = temp_workspace();
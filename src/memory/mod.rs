@@ -1,14 +1,21 @@
 pub mod backend;
 pub mod chunker;
+mod cli;
+pub mod consolidation;
 pub mod embeddings;
+pub mod hnsw;
 pub mod hygiene;
+pub mod ingest;
 pub mod lucid;
 pub mod markdown;
+pub mod namespaced;
 pub mod none;
+pub mod portable;
 pub mod postgres;
 pub mod response_cache;
 pub mod snapshot;
 pub mod sqlite;
+pub mod tenant;
 pub mod traits;
 pub mod vector;
 
@@ -17,15 +24,18 @@ pub use backend::{
     classify_memory_backend, default_memory_backend_key, memory_backend_profile,
     selectable_memory_backends, MemoryBackendKind, MemoryBackendProfile,
 };
+pub(crate) use cli::parse_date_bound;
+pub use cli::{handle_command, handle_ingest};
 pub use lucid::LucidMemory;
 pub use markdown::MarkdownMemory;
+pub use namespaced::NamespacedMemory;
 pub use none::NoneMemory;
 pub use postgres::PostgresMemory;
-pub use response_cache::ResponseCache;
+pub use response_cache::{CacheStats, ResponseCache};
 pub use sqlite::SqliteMemory;
 pub use traits::Memory;
 #[allow(unused_imports)]
-pub use traits::{MemoryCategory, MemoryEntry};
+pub use traits::{MemoryCategory, MemoryEntry, MemoryMetadata};
 
 use crate::config::{MemoryConfig, StorageProviderConfig};
 use anyhow::Context;
@@ -95,9 +105,10 @@ pub fn create_memory_with_storage(
     let backend_kind = classify_memory_backend(&backend_name);
 
     // Best-effort memory hygiene/retention pass (throttled by state file).
-    if let Err(e) = hygiene::run_if_due(config, workspace_dir) {
+    let hygiene_ran = hygiene::run_if_due(config, workspace_dir).unwrap_or_else(|e| {
         tracing::warn!("memory hygiene skipped: {e}");
-    }
+        false
+    });
 
     // If snapshot_on_hygiene is enabled, export core memories during hygiene.
     if config.snapshot_enabled
@@ -134,18 +145,24 @@ pub fn create_memory_with_storage(
         }
     }
 
+    fn build_embedder(
+        config: &MemoryConfig,
+        api_key: Option<&str>,
+    ) -> anyhow::Result<Arc<dyn embeddings::EmbeddingProvider>> {
+        Ok(Arc::from(embeddings::create_embedding_provider(
+            &config.embedding_provider,
+            api_key,
+            &config.embedding_model,
+            config.embedding_dimensions,
+        )?))
+    }
+
     fn build_sqlite_memory(
         config: &MemoryConfig,
         workspace_dir: &Path,
         api_key: Option<&str>,
     ) -> anyhow::Result<SqliteMemory> {
-        let embedder: Arc<dyn embeddings::EmbeddingProvider> =
-            Arc::from(embeddings::create_embedding_provider(
-                &config.embedding_provider,
-                api_key,
-                &config.embedding_model,
-                config.embedding_dimensions,
-            ));
+        let embedder = build_embedder(config, api_key)?;
 
         #[allow(clippy::cast_possible_truncation)]
         let mem = SqliteMemory::with_embedder(
@@ -155,12 +172,16 @@ pub fn create_memory_with_storage(
             config.keyword_weight as f32,
             config.embedding_cache_size,
             config.sqlite_open_timeout_secs,
-        )?;
+        )?
+        .with_vector_index(!config.vector_index.trim().eq_ignore_ascii_case("flat"));
         Ok(mem)
     }
 
     fn build_postgres_memory(
+        config: &MemoryConfig,
         storage_provider: Option<&StorageProviderConfig>,
+        api_key: Option<&str>,
+        hygiene_ran: bool,
     ) -> anyhow::Result<PostgresMemory> {
         let storage_provider = storage_provider
             .context("memory backend 'postgres' requires [storage.provider.config] settings")?;
@@ -173,19 +194,34 @@ pub fn create_memory_with_storage(
                 "memory backend 'postgres' requires [storage.provider.config].db_url (or dbURL)",
             )?;
 
-        PostgresMemory::new(
+        #[allow(clippy::cast_possible_truncation)]
+        let mem = PostgresMemory::new(
             db_url,
             &storage_provider.schema,
             &storage_provider.table,
             storage_provider.connect_timeout_secs,
-        )
+        )?
+        .with_embedder(
+            build_embedder(config, api_key)?,
+            config.vector_weight as f32,
+            config.keyword_weight as f32,
+        );
+        // File-based hygiene can't reach the database; purge expired rows on its cadence.
+        if hygiene_ran {
+            match mem.purge_expired() {
+                Ok(0) => {}
+                Ok(count) => tracing::info!("memory hygiene purged {count} expired postgres rows"),
+                Err(e) => tracing::warn!("postgres expired-memory purge skipped: {e}"),
+            }
+        }
+        Ok(mem)
     }
 
     create_memory_with_builders(
         &backend_name,
         workspace_dir,
         || build_sqlite_memory(config, workspace_dir, api_key),
-        || build_postgres_memory(storage_provider),
+        || build_postgres_memory(config, storage_provider, api_key, hygiene_ran),
         "",
     )
 }
@@ -219,7 +255,14 @@ pub fn create_memory_for_migration(
 }
 
 /// Factory: create an optional response cache from config.
-pub fn create_response_cache(config: &MemoryConfig, workspace_dir: &Path) -> Option<ResponseCache> {
+///
+/// Semantic mode embeds prompts with the memory embedding provider; without
+/// one the cache stays exact-match only.
+pub fn create_response_cache(
+    config: &MemoryConfig,
+    workspace_dir: &Path,
+    api_key: Option<&str>,
+) -> Option<ResponseCache> {
     if !config.response_cache_enabled {
         return None;
     }
@@ -235,7 +278,31 @@ pub fn create_response_cache(config: &MemoryConfig, workspace_dir: &Path) -> Opt
                 config.response_cache_ttl_minutes,
                 config.response_cache_max_entries
             );
-            Some(cache)
+            if !config.response_cache_semantic {
+                return Some(cache);
+            }
+            let embedder = match embeddings::create_embedding_provider(
+                &config.embedding_provider,
+                api_key,
+                &config.embedding_model,
+                config.embedding_dimensions,
+            ) {
+                Ok(embedder) if embedder.name() != "none" => embedder,
+                Ok(_) => {
+                    tracing::warn!(
+                        "response_cache_semantic needs an embedding_provider; using exact matches only"
+                    );
+                    return Some(cache);
+                }
+                Err(e) => {
+                    tracing::warn!("Semantic response cache disabled: {e}");
+                    return Some(cache);
+                }
+            };
+            Some(cache.with_semantic(
+                Arc::from(embedder),
+                config.response_cache_similarity_threshold,
+            ))
         }
         Err(e) => {
             tracing::warn!("Response cache disabled due to error: {e}");
@@ -0,0 +1,261 @@
+use super::traits::{Memory, MemoryCategory, MemoryEntry, MemoryMetadata};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// Memory view confined to a namespace of a shared backend.
+///
+/// Keys are stored as `{namespace}/{key}` and entries are tagged with the
+/// namespace as their session, so delegated agents can keep their own notes
+/// without reading or overwriting the parent agent's memories. `/` and `%`
+/// in the namespace are percent-encoded in the prefix, so a sender id
+/// containing `/` cannot reach into another tenant's keys.
+pub struct NamespacedMemory {
+    inner: Arc<dyn Memory>,
+    namespace: String,
+    key_prefix: String,
+}
+
+impl NamespacedMemory {
+    pub fn new(inner: Arc<dyn Memory>, namespace: impl Into<String>) -> Self {
+        let namespace = namespace.into();
+        let key_prefix = format!("{}/", escape_namespace(&namespace));
+        Self {
+            inner,
+            namespace,
+            key_prefix,
+        }
+    }
+
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    fn scoped_key(&self, key: &str) -> String {
+        format!("{}{key}", self.key_prefix)
+    }
+
+    fn scoped_session(&self, session_id: Option<&str>) -> String {
+        match session_id {
+            Some(session) => format!("{}:{session}", self.namespace),
+            None => self.namespace.clone(),
+        }
+    }
+
+    /// Drop entries outside the namespace (for backends that ignore the
+    /// session filter) and strip the key prefix from the rest.
+    fn unscope(&self, entries: Vec<MemoryEntry>) -> Vec<MemoryEntry> {
+        entries
+            .into_iter()
+            .filter_map(|entry| self.unscope_entry(entry))
+            .collect()
+    }
+
+    fn unscope_entry(&self, mut entry: MemoryEntry) -> Option<MemoryEntry> {
+        entry.key = entry.key.strip_prefix(&self.key_prefix)?.to_string();
+        Some(entry)
+    }
+}
+
+/// Make the namespace safe to end with the `/` key separator.
+fn escape_namespace(namespace: &str) -> String {
+    namespace.replace('%', "%25").replace('/', "%2F")
+}
+
+#[async_trait]
+impl Memory for NamespacedMemory {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    async fn store(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<()> {
+        self.inner
+            .store(
+                &self.scoped_key(key),
+                content,
+                category,
+                Some(&self.scoped_session(session_id)),
+            )
+            .await
+    }
+
+    async fn store_with_metadata(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+        metadata: &MemoryMetadata,
+    ) -> anyhow::Result<()> {
+        self.inner
+            .store_with_metadata(
+                &self.scoped_key(key),
+                content,
+                category,
+                Some(&self.scoped_session(session_id)),
+                metadata,
+            )
+            .await
+    }
+
+    async fn recall(
+        &self,
+        query: &str,
+        limit: usize,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        let entries = self
+            .inner
+            .recall(query, limit, Some(&self.scoped_session(session_id)))
+            .await?;
+        Ok(self.unscope(entries))
+    }
+
+    async fn get(&self, key: &str) -> anyhow::Result<Option<MemoryEntry>> {
+        let entry = self.inner.get(&self.scoped_key(key)).await?;
+        Ok(entry.and_then(|entry| self.unscope_entry(entry)))
+    }
+
+    async fn list(
+        &self,
+        category: Option<&MemoryCategory>,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        let entries = self
+            .inner
+            .list(category, Some(&self.scoped_session(session_id)))
+            .await?;
+        Ok(self.unscope(entries))
+    }
+
+    async fn forget(&self, key: &str) -> anyhow::Result<bool> {
+        self.inner.forget(&self.scoped_key(key)).await
+    }
+
+    async fn count(&self) -> anyhow::Result<usize> {
+        Ok(self.list(None, None).await?.len())
+    }
+
+    async fn health_check(&self) -> bool {
+        self.inner.health_check().await
+    }
+
+    /// Keep this view's prefix inside the tenant namespace of the backend.
+    fn with_namespace(&self, namespace: &str) -> Option<Arc<dyn Memory>> {
+        let inner = self.inner.with_namespace(namespace)?;
+        Some(Arc::new(Self::new(inner, self.namespace.clone())))
+    }
+
+    async fn reindex(&self) -> anyhow::Result<usize> {
+        self.inner.reindex().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::SqliteMemory;
+    use tempfile::TempDir;
+
+    fn shared_backend() -> (TempDir, Arc<dyn Memory>) {
+        let tmp = TempDir::new().unwrap();
+        let memory = SqliteMemory::new(tmp.path()).unwrap();
+        (tmp, Arc::new(memory))
+    }
+
+    #[tokio::test]
+    async fn namespaced_entries_are_invisible_to_parent_and_siblings() {
+        let (_tmp, shared) = shared_backend();
+        shared
+            .store(
+                "project",
+                "parent notes about rust",
+                MemoryCategory::Core,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let researcher = NamespacedMemory::new(shared.clone(), "agent:researcher");
+        let coder = NamespacedMemory::new(shared.clone(), "agent:coder");
+        researcher
+            .store(
+                "project",
+                "researcher notes about rust",
+                MemoryCategory::Core,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let own = researcher.get("project").await.unwrap().unwrap();
+        assert_eq!(own.key, "project");
+        assert_eq!(own.content, "researcher notes about rust");
+
+        let recalled = researcher.recall("rust", 10, None).await.unwrap();
+        assert_eq!(recalled.len(), 1);
+        assert_eq!(recalled[0].content, "researcher notes about rust");
+
+        assert!(coder.get("project").await.unwrap().is_none());
+        assert!(coder.recall("rust", 10, None).await.unwrap().is_empty());
+
+        let parent = shared.get("project").await.unwrap().unwrap();
+        assert_eq!(parent.content, "parent notes about rust");
+    }
+
+    #[tokio::test]
+    async fn namespaced_forget_and_count_stay_in_namespace() {
+        let (_tmp, shared) = shared_backend();
+        shared
+            .store("note", "parent", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+
+        let scoped = NamespacedMemory::new(shared.clone(), "agent:researcher");
+        scoped
+            .store("note", "scoped", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        scoped
+            .store("other", "scoped too", MemoryCategory::Daily, None)
+            .await
+            .unwrap();
+
+        assert_eq!(scoped.count().await.unwrap(), 2);
+        assert!(scoped.forget("note").await.unwrap());
+        assert_eq!(scoped.count().await.unwrap(), 1);
+        assert!(shared.get("note").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn sender_ids_with_slashes_stay_in_their_own_tenant() {
+        let (_tmp, shared) = shared_backend();
+        let outer = NamespacedMemory::new(shared.clone(), "telegram:a");
+        let inner = NamespacedMemory::new(shared.clone(), "telegram:a/b");
+        inner
+            .store("k", "inner secret", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        outer
+            .store("b/k", "outer note", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            inner.get("k").await.unwrap().unwrap().content,
+            "inner secret"
+        );
+        assert_eq!(
+            outer.get("b/k").await.unwrap().unwrap().content,
+            "outer note"
+        );
+        assert!(outer.forget("b/k").await.unwrap());
+        assert!(inner.get("k").await.unwrap().is_some());
+        assert!(shared.get("telegram:a%2Fb/k").await.unwrap().is_some());
+    }
+}
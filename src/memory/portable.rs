@@ -0,0 +1,391 @@
+//! Portable memory export/import.
+//!
+//! `zeroclaw memory export` writes every entry of the active backend as
+//! versioned JSONL: one header line, then one entry per line with its
+//! category, session, timestamp and importance/expiry/access metadata
+//! (and optionally its embedding).
+//! `zeroclaw memory import` restores such a file into any backend, so an
+//! agent's memory can move between machines and between sqlite, postgres,
+//! markdown and lucid.
+
+use super::traits::{Memory, MemoryCategory, MemoryEntry};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, Write};
+
+/// Value of the header's `format` field.
+pub const EXPORT_FORMAT: &str = "zeroclaw-memory";
+/// Current (and newest readable) export format version.
+pub const EXPORT_VERSION: u32 = 1;
+
+/// First line of an export file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportHeader {
+    pub format: String,
+    pub version: u32,
+    pub exported_at: String,
+    /// Backend the entries were read from.
+    pub backend: String,
+    pub entries: usize,
+    /// Model that produced the embeddings, when they were exported.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding: Option<EmbeddingModel>,
+}
+
+/// Identifies an embedding space. Exported vectors are only reused on
+/// import when the target is configured with the same model.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmbeddingModel {
+    pub provider: String,
+    pub model: String,
+    pub dimensions: usize,
+}
+
+/// One exported memory entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortableEntry {
+    pub id: String,
+    pub key: String,
+    pub content: String,
+    /// `core`, `daily`, `conversation`, or a custom category name.
+    pub category: String,
+    pub timestamp: String,
+    #[serde(default)]
+    pub session_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub importance: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_accessed: Option<String>,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub access_count: u64,
+    /// Tenant namespace; absent for shared entries.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding: Option<Vec<f32>>,
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn is_zero(value: &u64) -> bool {
+    *value == 0
+}
+
+impl PortableEntry {
+    fn from_entry(entry: MemoryEntry, embedding: Option<Vec<f32>>) -> Self {
+        Self {
+            id: entry.id,
+            key: entry.key,
+            content: entry.content,
+            category: entry.category.to_string(),
+            timestamp: entry.timestamp,
+            session_id: entry.session_id,
+            importance: entry.importance,
+            expires_at: entry.expires_at,
+            last_accessed: entry.last_accessed,
+            access_count: entry.access_count,
+            namespace: entry.namespace,
+            embedding,
+        }
+    }
+
+    fn to_entry(&self) -> MemoryEntry {
+        MemoryEntry {
+            id: self.id.clone(),
+            key: self.key.clone(),
+            content: self.content.clone(),
+            category: parse_category(&self.category),
+            timestamp: self.timestamp.clone(),
+            session_id: self.session_id.clone(),
+            score: None,
+            importance: self.importance,
+            expires_at: self.expires_at.clone(),
+            last_accessed: self.last_accessed.clone(),
+            access_count: self.access_count,
+            namespace: self.namespace.clone(),
+        }
+    }
+}
+
+/// Outcome of an import.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub entries: usize,
+    /// Entries restored with their exported embedding instead of re-embedding.
+    pub embeddings_reused: usize,
+}
+
+pub fn parse_category(value: &str) -> MemoryCategory {
+    match value {
+        "core" => MemoryCategory::Core,
+        "daily" => MemoryCategory::Daily,
+        "conversation" => MemoryCategory::Conversation,
+        other => MemoryCategory::Custom(other.to_string()),
+    }
+}
+
+/// Write every entry of `memory` (optionally one category) as JSONL.
+///
+/// Pass `embedding` to include stored vectors tagged with the model that
+/// produced them. Returns the number of entries written.
+pub async fn export_jsonl<W: Write>(
+    memory: &dyn Memory,
+    mut writer: W,
+    category: Option<&MemoryCategory>,
+    embedding: Option<EmbeddingModel>,
+) -> Result<usize> {
+    let entries: Vec<PortableEntry> = memory
+        .export_entries(embedding.is_some())
+        .await?
+        .into_iter()
+        .filter(|(entry, _)| category.map_or(true, |category| &entry.category == category))
+        .map(|(entry, vector)| PortableEntry::from_entry(entry, vector))
+        .collect();
+
+    let header = ExportHeader {
+        format: EXPORT_FORMAT.into(),
+        version: EXPORT_VERSION,
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        backend: memory.name().into(),
+        entries: entries.len(),
+        embedding,
+    };
+    serde_json::to_writer(&mut writer, &header)?;
+    writer.write_all(b"\n")?;
+    for entry in &entries {
+        serde_json::to_writer(&mut writer, entry)?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()?;
+    Ok(entries.len())
+}
+
+/// Parse and validate an export file without touching any backend.
+pub fn read_jsonl<R: BufRead>(reader: R) -> Result<(ExportHeader, Vec<PortableEntry>)> {
+    let mut lines = reader
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| line.as_ref().map_or(true, |line| !line.trim().is_empty()));
+
+    let (_, first) = lines.next().context("memory export is empty")?;
+    let header: ExportHeader =
+        serde_json::from_str(&first?).context("line 1: invalid memory export header")?;
+    if header.format != EXPORT_FORMAT {
+        anyhow::bail!(
+            "not a ZeroClaw memory export (format '{}', expected '{EXPORT_FORMAT}')",
+            header.format
+        );
+    }
+    if header.version == 0 || header.version > EXPORT_VERSION {
+        anyhow::bail!(
+            "memory export version {} is not supported (this build reads up to {EXPORT_VERSION})",
+            header.version
+        );
+    }
+
+    let mut entries = Vec::with_capacity(header.entries);
+    for (index, line) in lines {
+        let entry: PortableEntry = serde_json::from_str(&line?)
+            .with_context(|| format!("line {}: invalid memory entry", index + 1))?;
+        entries.push(entry);
+    }
+    if entries.len() != header.entries {
+        anyhow::bail!(
+            "memory export is truncated: header lists {} entries, found {}",
+            header.entries,
+            entries.len()
+        );
+    }
+    Ok((header, entries))
+}
+
+/// Restore an export file into `memory`. Existing keys are overwritten.
+///
+/// Embeddings are passed through only when `embedding` (the target's model)
+/// matches the one recorded in the header; otherwise the backend embeds the
+/// content itself. The whole file is validated before anything is written.
+pub async fn import_jsonl<R: BufRead>(
+    memory: &dyn Memory,
+    reader: R,
+    embedding: Option<&EmbeddingModel>,
+) -> Result<ImportSummary> {
+    let (header, entries) = read_jsonl(reader)?;
+    let reuse_embeddings = embedding.is_some() && header.embedding.as_ref() == embedding;
+
+    let mut summary = ImportSummary::default();
+    for entry in &entries {
+        let vector = entry.embedding.as_deref().filter(|_| reuse_embeddings);
+        memory
+            .restore(&entry.to_entry(), vector)
+            .await
+            .with_context(|| format!("failed to restore memory '{}'", entry.key))?;
+        summary.entries += 1;
+        if vector.is_some() {
+            summary.embeddings_reused += 1;
+        }
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::{MarkdownMemory, SqliteMemory};
+    use tempfile::TempDir;
+
+    async fn seeded_sqlite(dir: &TempDir) -> SqliteMemory {
+        let mem = SqliteMemory::new(dir.path()).unwrap();
+        mem.store("pref", "User likes Rust", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        mem.store("standup", "Shipped export", MemoryCategory::Daily, None)
+            .await
+            .unwrap();
+        mem.store(
+            "turn",
+            "Asked about tokio",
+            MemoryCategory::Conversation,
+            Some("session-1"),
+        )
+        .await
+        .unwrap();
+        mem.store(
+            "ticket",
+            "ZC-42 open",
+            MemoryCategory::Custom("work".into()),
+            None,
+        )
+        .await
+        .unwrap();
+        mem
+    }
+
+    async fn export_to_vec(mem: &dyn Memory, category: Option<&MemoryCategory>) -> Vec<u8> {
+        let mut buf = Vec::new();
+        export_jsonl(mem, &mut buf, category, None).await.unwrap();
+        buf
+    }
+
+    #[tokio::test]
+    async fn sqlite_roundtrip_preserves_every_field() {
+        let src_dir = TempDir::new().unwrap();
+        let src = seeded_sqlite(&src_dir).await;
+        let buf = export_to_vec(&src, None).await;
+
+        let dst_dir = TempDir::new().unwrap();
+        let dst = SqliteMemory::new(dst_dir.path()).unwrap();
+        let summary = import_jsonl(&dst, buf.as_slice(), None).await.unwrap();
+        assert_eq!(summary.entries, 4);
+        assert_eq!(dst.count().await.unwrap(), 4);
+
+        for key in ["pref", "standup", "turn", "ticket"] {
+            let before = src.get(key).await.unwrap().unwrap();
+            let after = dst.get(key).await.unwrap().unwrap();
+            assert_eq!(after.id, before.id);
+            assert_eq!(after.content, before.content);
+            assert_eq!(after.category, before.category);
+            assert_eq!(after.timestamp, before.timestamp);
+            assert_eq!(after.session_id, before.session_id);
+        }
+    }
+
+    #[tokio::test]
+    async fn export_filters_by_category_and_writes_header() {
+        let dir = TempDir::new().unwrap();
+        let mem = seeded_sqlite(&dir).await;
+        let buf = export_to_vec(&mem, Some(&MemoryCategory::Core)).await;
+
+        let (header, entries) = read_jsonl(buf.as_slice()).unwrap();
+        assert_eq!(header.format, EXPORT_FORMAT);
+        assert_eq!(header.version, EXPORT_VERSION);
+        assert_eq!(header.backend, "sqlite");
+        assert_eq!(header.entries, 1);
+        assert_eq!(entries[0].key, "pref");
+        assert_eq!(entries[0].category, "core");
+    }
+
+    #[tokio::test]
+    async fn sqlite_export_restores_into_markdown() {
+        let src_dir = TempDir::new().unwrap();
+        let src = seeded_sqlite(&src_dir).await;
+        let buf = export_to_vec(&src, None).await;
+
+        let dst_dir = TempDir::new().unwrap();
+        let dst = MarkdownMemory::new(dst_dir.path());
+        import_jsonl(&dst, buf.as_slice(), None).await.unwrap();
+
+        let core = dst.list(Some(&MemoryCategory::Core), None).await.unwrap();
+        assert_eq!(core.len(), 1);
+        assert!(core[0].content.contains("User likes Rust"));
+        assert_eq!(dst.count().await.unwrap(), 4);
+    }
+
+    #[tokio::test]
+    async fn embeddings_are_reused_only_for_the_same_model() {
+        let model = EmbeddingModel {
+            provider: "openai".into(),
+            model: "text-embedding-3-small".into(),
+            dimensions: 3,
+        };
+        let header = ExportHeader {
+            format: EXPORT_FORMAT.into(),
+            version: EXPORT_VERSION,
+            exported_at: "2026-02-16T00:00:00Z".into(),
+            backend: "sqlite".into(),
+            entries: 1,
+            embedding: Some(model.clone()),
+        };
+        let entry = PortableEntry {
+            id: "id-1".into(),
+            key: "pref".into(),
+            content: "User likes Rust".into(),
+            category: "core".into(),
+            timestamp: "2026-02-16T00:00:00Z".into(),
+            session_id: None,
+            importance: None,
+            expires_at: None,
+            last_accessed: None,
+            access_count: 0,
+            namespace: None,
+            embedding: Some(vec![0.1, 0.2, 0.3]),
+        };
+        let file = format!(
+            "{}\n{}\n",
+            serde_json::to_string(&header).unwrap(),
+            serde_json::to_string(&entry).unwrap()
+        );
+
+        let dir = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(dir.path()).unwrap();
+        let same = import_jsonl(&mem, file.as_bytes(), Some(&model))
+            .await
+            .unwrap();
+        assert_eq!(same.embeddings_reused, 1);
+
+        let other = EmbeddingModel {
+            model: "nomic-embed-text".into(),
+            ..model
+        };
+        let different = import_jsonl(&mem, file.as_bytes(), Some(&other))
+            .await
+            .unwrap();
+        assert_eq!(different.embeddings_reused, 0);
+    }
+
+    #[test]
+    fn read_rejects_foreign_newer_and_truncated_files() {
+        let foreign =
+            r#"{"format":"other","version":1,"exported_at":"","backend":"sqlite","entries":0}"#;
+        let err = read_jsonl(foreign.as_bytes()).unwrap_err();
+        assert!(err.to_string().contains("not a ZeroClaw memory export"));
+
+        let newer = r#"{"format":"zeroclaw-memory","version":99,"exported_at":"","backend":"sqlite","entries":0}"#;
+        let err = read_jsonl(newer.as_bytes()).unwrap_err();
+        assert!(err.to_string().contains("not supported"));
+
+        let truncated = r#"{"format":"zeroclaw-memory","version":1,"exported_at":"","backend":"sqlite","entries":2}"#;
+        let err = read_jsonl(truncated.as_bytes()).unwrap_err();
+        assert!(err.to_string().contains("truncated"));
+    }
+}
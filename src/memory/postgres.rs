@@ -1,23 +1,44 @@
-use super::traits::{Memory, MemoryCategory, MemoryEntry};
+use super::embeddings::{EmbeddingProvider, NoopEmbedding};
+use super::traits::{Memory, MemoryCategory, MemoryEntry, MemoryMetadata};
+use super::vector;
 use anyhow::{Context, Result};
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, SecondsFormat, Utc};
 use parking_lot::Mutex;
 use postgres::{Client, NoTls, Row};
+use std::collections::HashMap;
+use std::fmt::Write as _;
 use std::sync::Arc;
 use std::time::Duration;
 use uuid::Uuid;
 
 /// Maximum allowed connect timeout (seconds) to avoid unreasonable waits.
 const POSTGRES_CONNECT_TIMEOUT_CAP_SECS: u64 = 300;
+/// Rows embedded per provider call during `reindex`.
+const REINDEX_BATCH_SIZE: usize = 32;
+/// Columns read by `PostgresMemory::row_to_entry`, in order.
+const ENTRY_COLUMNS: &str = "id, key, content, category, created_at, session_id, \
+     importance, expires_at, last_accessed, access_count, namespace";
+/// Rows hidden from reads until hygiene deletes them.
+const NOT_EXPIRED: &str = "(expires_at IS NULL OR expires_at > NOW())";
 
 /// PostgreSQL-backed persistent memory.
 ///
-/// This backend focuses on reliable CRUD and keyword recall using SQL, without
-/// requiring extension setup (for example pgvector).
+/// Keyword recall works on any PostgreSQL. With an embedding provider and the
+/// pgvector extension available, rows also get an `embedding vector(N)`
+/// column and `recall` fuses full-text (`ts_rank_cd`) and cosine scores with
+/// the same `hybrid_merge` weighting as `SqliteMemory`. Without pgvector the
+/// backend stays keyword-only.
 pub struct PostgresMemory {
     client: Arc<Mutex<Client>>,
     qualified_table: String,
+    embedder: Arc<dyn EmbeddingProvider>,
+    /// True once pgvector is installed and the column matches the embedder.
+    vector_enabled: bool,
+    vector_weight: f32,
+    keyword_weight: f32,
+    /// Tenant namespace this handle reads and writes; empty is shared.
+    namespace: String,
 }
 
 impl PostgresMemory {
@@ -47,22 +68,346 @@ impl PostgresMemory {
         let table_ident = quote_identifier(table);
         let qualified_table = format!("{schema_ident}.{table_ident}");
 
-        Self::init_schema(&mut client, &schema_ident, &qualified_table)?;
+        // Name Postgres gave the `key UNIQUE` constraint of older tables.
+        let legacy_key_constraint = quote_identifier(&format!("{table}_key_key"));
+        Self::init_schema(
+            &mut client,
+            &schema_ident,
+            &qualified_table,
+            &legacy_key_constraint,
+        )?;
 
         Ok(Self {
             client: Arc::new(Mutex::new(client)),
             qualified_table,
+            embedder: Arc::new(NoopEmbedding),
+            vector_enabled: false,
+            vector_weight: 0.7,
+            keyword_weight: 0.3,
+            namespace: String::new(),
         })
     }
 
-    fn init_schema(client: &mut Client, schema_ident: &str, qualified_table: &str) -> Result<()> {
+    /// Enable semantic recall through pgvector. If the extension can't be
+    /// used (not installed, no privilege to create it, or the existing
+    /// column has other dimensions) the backend logs why and stays
+    /// keyword-only.
+    #[must_use]
+    pub fn with_embedder(
+        mut self,
+        embedder: Arc<dyn EmbeddingProvider>,
+        vector_weight: f32,
+        keyword_weight: f32,
+    ) -> Self {
+        let dims = embedder.dimensions();
+        self.embedder = embedder;
+        self.vector_weight = vector_weight;
+        self.keyword_weight = keyword_weight;
+        if dims == 0 {
+            return self;
+        }
+
+        let result = Self::init_vector_column(&mut self.client.lock(), &self.qualified_table, dims);
+        match result {
+            Ok(true) => self.vector_enabled = true,
+            Ok(false) => tracing::info!(
+                "pgvector extension is not available; postgres memory recall stays keyword-only"
+            ),
+            Err(e) => tracing::warn!(
+                "pgvector setup failed; postgres memory recall stays keyword-only: {e:#}"
+            ),
+        }
+        self
+    }
+
+    /// Detect pgvector, then add the embedding column plus the ANN and
+    /// full-text indexes. Returns `false` when the extension is unavailable.
+    fn init_vector_column(client: &mut Client, qualified_table: &str, dims: usize) -> Result<bool> {
+        let available: bool = client
+            .query_one(
+                "SELECT EXISTS (SELECT 1 FROM pg_available_extensions WHERE name = 'vector')",
+                &[],
+            )?
+            .get(0);
+        if !available {
+            return Ok(false);
+        }
+
+        // Creating an extension needs elevated privileges; an administrator may
+        // have installed it already, so only the final check is authoritative.
+        if let Err(e) = client.batch_execute("CREATE EXTENSION IF NOT EXISTS vector") {
+            tracing::debug!("CREATE EXTENSION vector failed: {e}");
+        }
+        let installed: bool = client
+            .query_one(
+                "SELECT EXISTS (SELECT 1 FROM pg_extension WHERE extname = 'vector')",
+                &[],
+            )?
+            .get(0);
+        if !installed {
+            return Ok(false);
+        }
+
+        client.batch_execute(&format!(
+            "ALTER TABLE {qualified_table} ADD COLUMN IF NOT EXISTS embedding vector({dims})"
+        ))?;
+        let column_type: Option<String> = client
+            .query_opt(
+                "SELECT format_type(atttypid, atttypmod) FROM pg_attribute
+                 WHERE attrelid = $1::TEXT::regclass AND attname = 'embedding' AND NOT attisdropped",
+                &[&qualified_table],
+            )?
+            .map(|row| row.get(0));
+        let expected = format!("vector({dims})");
+        if column_type.as_deref() != Some(expected.as_str()) {
+            anyhow::bail!(
+                "embedding column is {}, expected {expected}; drop the column to re-embed with the new model",
+                column_type.as_deref().unwrap_or("missing")
+            );
+        }
+
+        client.batch_execute(&format!(
+            "CREATE INDEX IF NOT EXISTS idx_memories_fts ON {qualified_table}
+                USING GIN (to_tsvector('simple', key || ' ' || content));"
+        ))?;
+        // HNSW indexes need pgvector >= 0.5; older versions still work with a
+        // sequential scan.
+        if let Err(e) = client.batch_execute(&format!(
+            "CREATE INDEX IF NOT EXISTS idx_memories_embedding ON {qualified_table}
+                USING hnsw (embedding vector_cosine_ops);"
+        )) {
+            tracing::debug!("pgvector HNSW index not created: {e}");
+        }
+
+        Ok(true)
+    }
+
+    /// Embed rows that have no embedding yet (e.g. stored before pgvector
+    /// was enabled). Returns how many rows were updated.
+    pub async fn reindex(&self) -> Result<usize> {
+        if !self.vector_enabled {
+            return Ok(0);
+        }
+
+        let client = self.client.clone();
+        let qualified_table = self.qualified_table.clone();
+        let pending: Vec<(String, String)> = tokio::task::spawn_blocking(move || -> Result<_> {
+            let stmt = format!("SELECT id, content FROM {qualified_table} WHERE embedding IS NULL");
+            let rows = client.lock().query(&stmt, &[])?;
+            Ok(rows.iter().map(|row| (row.get(0), row.get(1))).collect())
+        })
+        .await??;
+
+        let mut count = 0;
+        for batch in pending.chunks(REINDEX_BATCH_SIZE) {
+            let texts: Vec<&str> = batch.iter().map(|(_, content)| content.as_str()).collect();
+            let embeddings = self.embedder.embed(&texts).await?;
+            let updates: Vec<(String, String)> = batch
+                .iter()
+                .zip(embeddings)
+                .map(|((id, _), embedding)| (id.clone(), vector_literal(&embedding)))
+                .collect();
+
+            let client = self.client.clone();
+            let qualified_table = self.qualified_table.clone();
+            count += tokio::task::spawn_blocking(move || -> Result<usize> {
+                let stmt = format!(
+                    "UPDATE {qualified_table} SET embedding = $1::TEXT::vector WHERE id = $2"
+                );
+                let mut client = client.lock();
+                for (id, embedding) in &updates {
+                    client.execute(&stmt, &[embedding, id])?;
+                }
+                Ok(updates.len())
+            })
+            .await??;
+        }
+
+        Ok(count)
+    }
+
+    /// Delete rows whose `expires_at` has passed. Run on the hygiene cadence.
+    pub fn purge_expired(&self) -> Result<u64> {
+        let stmt = format!(
+            "DELETE FROM {} WHERE expires_at IS NOT NULL AND expires_at <= NOW()",
+            self.qualified_table
+        );
+        Ok(self.client.lock().execute(&stmt, &[])?)
+    }
+
+    /// Bump access stats of entries returned by `recall`.
+    fn record_access(
+        client: &mut Client,
+        qualified_table: &str,
+        entries: &[MemoryEntry],
+    ) -> Result<()> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+        let ids: Vec<&str> = entries.iter().map(|entry| entry.id.as_str()).collect();
+        let stmt = format!(
+            "UPDATE {qualified_table}
+             SET access_count = access_count + 1, last_accessed = NOW()
+             WHERE id = ANY($1)"
+        );
+        client.execute(&stmt, &[&ids])?;
+        Ok(())
+    }
+
+    /// Full-text ranking over key + content; any query word may match.
+    fn fts_search(
+        client: &mut Client,
+        qualified_table: &str,
+        query: &str,
+        namespace: &str,
+        session_id: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<(String, f32)>> {
+        let Some(tsquery) = tsquery_any(query) else {
+            return Ok(Vec::new());
+        };
+        let stmt = format!(
+            "
+            SELECT id,
+                   ts_rank_cd(to_tsvector('simple', key || ' ' || content),
+                              to_tsquery('simple', $1)) AS score
+            FROM {qualified_table}
+            WHERE to_tsvector('simple', key || ' ' || content) @@ to_tsquery('simple', $1)
+              AND ($2::TEXT IS NULL OR session_id = $2)
+              AND namespace = $4
+            ORDER BY score DESC
+            LIMIT $3
+            "
+        );
+        let rows = client.query(&stmt, &[&tsquery, &session_id, &limit, &namespace])?;
+        Ok(rows.iter().map(|row| (row.get(0), row.get(1))).collect())
+    }
+
+    /// Cosine similarity search through pgvector (`<=>` is cosine distance).
+    fn vector_search(
+        client: &mut Client,
+        qualified_table: &str,
+        query_embedding: &str,
+        namespace: &str,
+        session_id: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<(String, f32)>> {
+        let stmt = format!(
+            "
+            SELECT id, (1 - (embedding <=> $1::TEXT::vector))::REAL AS similarity
+            FROM {qualified_table}
+            WHERE embedding IS NOT NULL
+              AND ($2::TEXT IS NULL OR session_id = $2)
+              AND namespace = $4
+            ORDER BY embedding <=> $1::TEXT::vector
+            LIMIT $3
+            "
+        );
+        let rows = client.query(&stmt, &[&query_embedding, &session_id, &limit, &namespace])?;
+        Ok(rows
+            .iter()
+            .map(|row| {
+                (
+                    row.get::<_, String>(0),
+                    row.get::<_, f32>(1).clamp(0.0, 1.0),
+                )
+            })
+            .filter(|(_, similarity)| *similarity > 0.0)
+            .collect())
+    }
+
+    /// Hybrid FTS + vector recall; empty when neither side matches.
+    fn hybrid_recall(
+        client: &mut Client,
+        qualified_table: &str,
+        query: &str,
+        query_embedding: &str,
+        namespace: &str,
+        session_id: Option<&str>,
+        limit: usize,
+        vector_weight: f32,
+        keyword_weight: f32,
+    ) -> Result<Vec<MemoryEntry>> {
+        let candidates = i64::try_from(limit * 2).unwrap_or(i64::MAX);
+        let keyword_results = Self::fts_search(
+            client,
+            qualified_table,
+            query,
+            namespace,
+            session_id,
+            candidates,
+        )?;
+        let vector_results = Self::vector_search(
+            client,
+            qualified_table,
+            query_embedding,
+            namespace,
+            session_id,
+            candidates,
+        )?;
+
+        let merged = if vector_results.is_empty() {
+            keyword_results
+                .iter()
+                .take(limit)
+                .map(|(id, score)| vector::ScoredResult {
+                    id: id.clone(),
+                    vector_score: None,
+                    keyword_score: Some(*score),
+                    final_score: *score,
+                })
+                .collect::<Vec<_>>()
+        } else {
+            vector::hybrid_merge(
+                &vector_results,
+                &keyword_results,
+                vector_weight,
+                keyword_weight,
+                limit,
+            )
+        };
+        if merged.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let ids: Vec<String> = merged.iter().map(|scored| scored.id.clone()).collect();
+        let stmt = format!(
+            "
+            SELECT {ENTRY_COLUMNS}
+            FROM {qualified_table}
+            WHERE id = ANY($1) AND namespace = $2
+            "
+        );
+        let mut entries: HashMap<String, MemoryEntry> = HashMap::new();
+        for row in client.query(&stmt, &[&ids, &namespace])? {
+            let entry = Self::row_to_entry(&row)?;
+            entries.insert(entry.id.clone(), entry);
+        }
+
+        Ok(merged
+            .iter()
+            .filter_map(|scored| {
+                entries.remove(&scored.id).map(|mut entry| {
+                    entry.score = Some(f64::from(scored.final_score));
+                    entry
+                })
+            })
+            .collect())
+    }
+
+    fn init_schema(
+        client: &mut Client,
+        schema_ident: &str,
+        qualified_table: &str,
+        legacy_key_constraint: &str,
+    ) -> Result<()> {
         client.batch_execute(&format!(
             "
             CREATE SCHEMA IF NOT EXISTS {schema_ident};
 
             CREATE TABLE IF NOT EXISTS {qualified_table} (
                 id TEXT PRIMARY KEY,
-                key TEXT UNIQUE NOT NULL,
+                key TEXT NOT NULL,
                 content TEXT NOT NULL,
                 category TEXT NOT NULL,
                 created_at TIMESTAMPTZ NOT NULL,
@@ -73,6 +418,20 @@ impl PostgresMemory {
             CREATE INDEX IF NOT EXISTS idx_memories_category ON {qualified_table}(category);
             CREATE INDEX IF NOT EXISTS idx_memories_session_id ON {qualified_table}(session_id);
             CREATE INDEX IF NOT EXISTS idx_memories_updated_at ON {qualified_table}(updated_at DESC);
+
+            ALTER TABLE {qualified_table}
+                ADD COLUMN IF NOT EXISTS importance DOUBLE PRECISION,
+                ADD COLUMN IF NOT EXISTS expires_at TIMESTAMPTZ,
+                ADD COLUMN IF NOT EXISTS last_accessed TIMESTAMPTZ,
+                ADD COLUMN IF NOT EXISTS access_count BIGINT NOT NULL DEFAULT 0;
+            CREATE INDEX IF NOT EXISTS idx_memories_expires_at ON {qualified_table}(expires_at);
+
+            -- Keys are unique per tenant namespace ('' is shared)
+            ALTER TABLE {qualified_table}
+                ADD COLUMN IF NOT EXISTS namespace TEXT NOT NULL DEFAULT '';
+            ALTER TABLE {qualified_table} DROP CONSTRAINT IF EXISTS {legacy_key_constraint};
+            CREATE UNIQUE INDEX IF NOT EXISTS idx_memories_namespace_key
+                ON {qualified_table}(namespace, key);
             "
         ))?;
 
@@ -97,8 +456,12 @@ impl PostgresMemory {
         }
     }
 
+    /// Map a row selected with `ENTRY_COLUMNS` plus an optional `score` column.
     fn row_to_entry(row: &Row) -> Result<MemoryEntry> {
         let timestamp: DateTime<Utc> = row.get(4);
+        let format_time = |time: Option<DateTime<Utc>>| {
+            time.map(|t| t.to_rfc3339_opts(SecondsFormat::Secs, true))
+        };
 
         Ok(MemoryEntry {
             id: row.get(0),
@@ -107,7 +470,12 @@ impl PostgresMemory {
             category: Self::parse_category(&row.get::<_, String>(3)),
             timestamp: timestamp.to_rfc3339(),
             session_id: row.get(5),
-            score: row.try_get(6).ok(),
+            score: row.try_get("score").ok(),
+            importance: row.get(6),
+            expires_at: format_time(row.get(7)),
+            last_accessed: format_time(row.get(8)),
+            access_count: u64::try_from(row.get::<_, i64>(9)).unwrap_or(0),
+            namespace: Some(row.get::<_, String>(10)).filter(|namespace| !namespace.is_empty()),
         })
     }
 }
@@ -139,6 +507,75 @@ fn quote_identifier(value: &str) -> String {
     format!("\"{value}\"")
 }
 
+/// pgvector text literal (`[0.1,0.2,...]`), bound as TEXT and cast in SQL.
+fn vector_literal(values: &[f32]) -> String {
+    let mut literal = String::with_capacity(values.len() * 10 + 2);
+    literal.push('[');
+    for (i, value) in values.iter().enumerate() {
+        if i > 0 {
+            literal.push(',');
+        }
+        let _ = write!(literal, "{value}");
+    }
+    literal.push(']');
+    literal
+}
+
+/// Parse the pgvector text form (`[0.1,0.2,...]`) back into floats.
+fn parse_vector_literal(literal: &str) -> Result<Vec<f32>> {
+    let inner = literal
+        .trim()
+        .strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+        .with_context(|| format!("invalid pgvector literal: {literal}"))?;
+    if inner.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    inner
+        .split(',')
+        .map(|value| {
+            value
+                .trim()
+                .parse::<f32>()
+                .with_context(|| format!("invalid pgvector component: {value}"))
+        })
+        .collect()
+}
+
+/// Exported timestamps are RFC 3339, or a bare date for Markdown entries.
+/// Anything else is treated as "now".
+fn parse_timestamp(value: &str) -> DateTime<Utc> {
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(value) {
+        return timestamp.with_timezone(&Utc);
+    }
+    chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|naive| naive.and_utc())
+        .unwrap_or_else(Utc::now)
+}
+
+/// Optional RFC 3339 time as a timestamp parameter.
+fn parse_optional_time(value: Option<&str>) -> Result<Option<DateTime<Utc>>> {
+    value
+        .map(|value| {
+            DateTime::parse_from_rfc3339(value)
+                .map(|time| time.with_timezone(&Utc))
+                .with_context(|| format!("invalid RFC 3339 time: {value}"))
+        })
+        .transpose()
+}
+
+/// OR-query of the alphanumeric words in `query`, safe for `to_tsquery`.
+fn tsquery_any(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split(|ch: char| !ch.is_alphanumeric())
+        .filter(|term| !term.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" | "))
+}
+
 #[async_trait]
 impl Memory for PostgresMemory {
     fn name(&self) -> &str {
@@ -152,34 +589,116 @@ impl Memory for PostgresMemory {
         category: MemoryCategory,
         session_id: Option<&str>,
     ) -> Result<()> {
+        self.store_with_metadata(
+            key,
+            content,
+            category,
+            session_id,
+            &MemoryMetadata::default(),
+        )
+        .await
+    }
+
+    async fn store_with_metadata(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+        metadata: &MemoryMetadata,
+    ) -> Result<()> {
+        let metadata = metadata.normalized()?;
+        let importance = metadata.importance;
+        let expires_at = parse_optional_time(metadata.expires_at.as_deref())?;
+
+        // Compute embedding (async, before blocking work)
+        let embedding = if self.vector_enabled {
+            Some(vector_literal(&self.embedder.embed_one(content).await?))
+        } else {
+            None
+        };
+
         let client = self.client.clone();
         let qualified_table = self.qualified_table.clone();
         let key = key.to_string();
         let content = content.to_string();
         let category = Self::category_to_str(&category);
         let session_id = session_id.map(str::to_string);
+        let namespace = self.namespace.clone();
 
         tokio::task::spawn_blocking(move || -> Result<()> {
             let now = Utc::now();
             let mut client = client.lock();
+            let id = Uuid::new_v4().to_string();
+
+            // Access stats belong to the key and survive content updates.
+            if let Some(embedding) = embedding {
+                let stmt = format!(
+                    "
+                    INSERT INTO {qualified_table}
+                        (id, key, content, category, created_at, updated_at, session_id,
+                         importance, expires_at, embedding, namespace)
+                    VALUES
+                        ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10::TEXT::vector, $11)
+                    ON CONFLICT (namespace, key) DO UPDATE SET
+                        content = EXCLUDED.content,
+                        category = EXCLUDED.category,
+                        updated_at = EXCLUDED.updated_at,
+                        session_id = EXCLUDED.session_id,
+                        importance = EXCLUDED.importance,
+                        expires_at = EXCLUDED.expires_at,
+                        embedding = EXCLUDED.embedding
+                    "
+                );
+                client.execute(
+                    &stmt,
+                    &[
+                        &id,
+                        &key,
+                        &content,
+                        &category,
+                        &now,
+                        &now,
+                        &session_id,
+                        &importance,
+                        &expires_at,
+                        &embedding,
+                        &namespace,
+                    ],
+                )?;
+                return Ok(());
+            }
+
             let stmt = format!(
                 "
                 INSERT INTO {qualified_table}
-                    (id, key, content, category, created_at, updated_at, session_id)
+                    (id, key, content, category, created_at, updated_at, session_id,
+                     importance, expires_at, namespace)
                 VALUES
-                    ($1, $2, $3, $4, $5, $6, $7)
-                ON CONFLICT (key) DO UPDATE SET
+                    ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                ON CONFLICT (namespace, key) DO UPDATE SET
                     content = EXCLUDED.content,
                     category = EXCLUDED.category,
                     updated_at = EXCLUDED.updated_at,
-                    session_id = EXCLUDED.session_id
+                    session_id = EXCLUDED.session_id,
+                    importance = EXCLUDED.importance,
+                    expires_at = EXCLUDED.expires_at
                 "
             );
-
-            let id = Uuid::new_v4().to_string();
             client.execute(
                 &stmt,
-                &[&id, &key, &content, &category, &now, &now, &session_id],
+                &[
+                    &id,
+                    &key,
+                    &content,
+                    &category,
+                    &now,
+                    &now,
+                    &session_id,
+                    &importance,
+                    &expires_at,
+                    &namespace,
+                ],
             )?;
             Ok(())
         })
@@ -192,35 +711,81 @@ impl Memory for PostgresMemory {
         limit: usize,
         session_id: Option<&str>,
     ) -> Result<Vec<MemoryEntry>> {
+        // Compute query embedding (async, before blocking work)
+        let query_embedding = if self.vector_enabled && !query.trim().is_empty() {
+            Some(vector_literal(&self.embedder.embed_one(query).await?))
+        } else {
+            None
+        };
+
         let client = self.client.clone();
         let qualified_table = self.qualified_table.clone();
         let query = query.trim().to_string();
         let session_id = session_id.map(str::to_string);
+        let namespace = self.namespace.clone();
+        let vector_weight = self.vector_weight;
+        let keyword_weight = self.keyword_weight;
 
         tokio::task::spawn_blocking(move || -> Result<Vec<MemoryEntry>> {
             let mut client = client.lock();
-            let stmt = format!(
-                "
-                SELECT id, key, content, category, created_at, session_id,
-                       (
-                         CASE WHEN key ILIKE '%' || $1 || '%' THEN 2.0 ELSE 0.0 END +
-                         CASE WHEN content ILIKE '%' || $1 || '%' THEN 1.0 ELSE 0.0 END
-                       ) AS score
-                FROM {qualified_table}
-                WHERE ($2::TEXT IS NULL OR session_id = $2)
-                  AND ($1 = '' OR key ILIKE '%' || $1 || '%' OR content ILIKE '%' || $1 || '%')
-                ORDER BY score DESC, updated_at DESC
-                LIMIT $3
-                "
-            );
 
-            #[allow(clippy::cast_possible_wrap)]
-            let limit_i64 = limit as i64;
+            let mut results = match query_embedding {
+                Some(query_embedding) => Self::hybrid_recall(
+                    &mut client,
+                    &qualified_table,
+                    &query,
+                    &query_embedding,
+                    &namespace,
+                    session_id.as_deref(),
+                    limit,
+                    vector_weight,
+                    keyword_weight,
+                )?,
+                None => Vec::new(),
+            };
 
-            let rows = client.query(&stmt, &[&query, &session_id, &limit_i64])?;
-            rows.iter()
-                .map(Self::row_to_entry)
-                .collect::<Result<Vec<MemoryEntry>>>()
+            // Keyword-only recall (no pgvector, or hybrid found nothing)
+            if results.is_empty() {
+                let stmt = format!(
+                    "
+                    SELECT {ENTRY_COLUMNS},
+                           (
+                             CASE WHEN key ILIKE '%' || $1 || '%' THEN 2.0 ELSE 0.0 END +
+                             CASE WHEN content ILIKE '%' || $1 || '%' THEN 1.0 ELSE 0.0 END
+                           )::DOUBLE PRECISION AS score
+                    FROM {qualified_table}
+                    WHERE ($2::TEXT IS NULL OR session_id = $2)
+                      AND namespace = $4
+                      AND ($1 = '' OR key ILIKE '%' || $1 || '%' OR content ILIKE '%' || $1 || '%')
+                      AND {NOT_EXPIRED}
+                    ORDER BY score DESC, updated_at DESC
+                    LIMIT $3
+                    "
+                );
+
+                #[allow(clippy::cast_possible_wrap)]
+                let limit_i64 = limit as i64;
+
+                let rows = client.query(&stmt, &[&query, &session_id, &limit_i64, &namespace])?;
+                results = rows
+                    .iter()
+                    .map(Self::row_to_entry)
+                    .collect::<Result<Vec<MemoryEntry>>>()?;
+            }
+
+            // Drop expired entries, then rank by relevance weighted with
+            // importance, access frequency and recency.
+            let now = Utc::now();
+            results.retain(|entry| !entry.is_expired(now));
+            for entry in &mut results {
+                let weight = entry.recall_weight(now);
+                entry.score = entry.score.map(|score| score * weight);
+            }
+            results.sort_by(|a, b| b.score.unwrap_or(0.0).total_cmp(&a.score.unwrap_or(0.0)));
+            results.truncate(limit);
+
+            Self::record_access(&mut client, &qualified_table, &results)?;
+            Ok(results)
         })
         .await?
     }
@@ -229,19 +794,20 @@ impl Memory for PostgresMemory {
         let client = self.client.clone();
         let qualified_table = self.qualified_table.clone();
         let key = key.to_string();
+        let namespace = self.namespace.clone();
 
         tokio::task::spawn_blocking(move || -> Result<Option<MemoryEntry>> {
             let mut client = client.lock();
             let stmt = format!(
                 "
-                SELECT id, key, content, category, created_at, session_id
+                SELECT {ENTRY_COLUMNS}
                 FROM {qualified_table}
-                WHERE key = $1
+                WHERE key = $1 AND namespace = $2 AND {NOT_EXPIRED}
                 LIMIT 1
                 "
             );
 
-            let row = client.query_opt(&stmt, &[&key])?;
+            let row = client.query_opt(&stmt, &[&key, &namespace])?;
             row.as_ref().map(Self::row_to_entry).transpose()
         })
         .await?
@@ -256,22 +822,25 @@ impl Memory for PostgresMemory {
         let qualified_table = self.qualified_table.clone();
         let category = category.map(Self::category_to_str);
         let session_id = session_id.map(str::to_string);
+        let namespace = self.namespace.clone();
 
         tokio::task::spawn_blocking(move || -> Result<Vec<MemoryEntry>> {
             let mut client = client.lock();
             let stmt = format!(
                 "
-                SELECT id, key, content, category, created_at, session_id
+                SELECT {ENTRY_COLUMNS}
                 FROM {qualified_table}
                 WHERE ($1::TEXT IS NULL OR category = $1)
                   AND ($2::TEXT IS NULL OR session_id = $2)
+                  AND namespace = $3
+                  AND {NOT_EXPIRED}
                 ORDER BY updated_at DESC
                 "
             );
 
             let category_ref = category.as_deref();
             let session_ref = session_id.as_deref();
-            let rows = client.query(&stmt, &[&category_ref, &session_ref])?;
+            let rows = client.query(&stmt, &[&category_ref, &session_ref, &namespace])?;
             rows.iter()
                 .map(Self::row_to_entry)
                 .collect::<Result<Vec<MemoryEntry>>>()
@@ -283,11 +852,12 @@ impl Memory for PostgresMemory {
         let client = self.client.clone();
         let qualified_table = self.qualified_table.clone();
         let key = key.to_string();
+        let namespace = self.namespace.clone();
 
         tokio::task::spawn_blocking(move || -> Result<bool> {
             let mut client = client.lock();
-            let stmt = format!("DELETE FROM {qualified_table} WHERE key = $1");
-            let deleted = client.execute(&stmt, &[&key])?;
+            let stmt = format!("DELETE FROM {qualified_table} WHERE key = $1 AND namespace = $2");
+            let deleted = client.execute(&stmt, &[&key, &namespace])?;
             Ok(deleted > 0)
         })
         .await?
@@ -296,11 +866,12 @@ impl Memory for PostgresMemory {
     async fn count(&self) -> Result<usize> {
         let client = self.client.clone();
         let qualified_table = self.qualified_table.clone();
+        let namespace = self.namespace.clone();
 
         tokio::task::spawn_blocking(move || -> Result<usize> {
             let mut client = client.lock();
-            let stmt = format!("SELECT COUNT(*) FROM {qualified_table}");
-            let count: i64 = client.query_one(&stmt, &[])?.get(0);
+            let stmt = format!("SELECT COUNT(*) FROM {qualified_table} WHERE namespace = $1");
+            let count: i64 = client.query_one(&stmt, &[&namespace])?.get(0);
             let count =
                 usize::try_from(count).context("PostgreSQL returned a negative memory count")?;
             Ok(count)
@@ -314,6 +885,199 @@ impl Memory for PostgresMemory {
             .await
             .unwrap_or(false)
     }
+
+    fn with_namespace(&self, namespace: &str) -> Option<Arc<dyn Memory>> {
+        Some(Arc::new(Self {
+            client: Arc::clone(&self.client),
+            qualified_table: self.qualified_table.clone(),
+            embedder: Arc::clone(&self.embedder),
+            vector_enabled: self.vector_enabled,
+            vector_weight: self.vector_weight,
+            keyword_weight: self.keyword_weight,
+            namespace: namespace.to_string(),
+        }))
+    }
+
+    async fn reindex(&self) -> Result<usize> {
+        PostgresMemory::reindex(self).await
+    }
+
+    /// The shared handle exports every namespace (tagged on each entry) so
+    /// backups are complete; a namespace handle exports only its own.
+    async fn export_entries(
+        &self,
+        with_embeddings: bool,
+    ) -> Result<Vec<(MemoryEntry, Option<Vec<f32>>)>> {
+        let client = self.client.clone();
+        let qualified_table = self.qualified_table.clone();
+        let namespace = self.namespace.clone();
+        let embedding_column = if with_embeddings && self.vector_enabled {
+            "embedding::TEXT"
+        } else {
+            "NULL::TEXT"
+        };
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<(MemoryEntry, Option<Vec<f32>>)>> {
+            let mut client = client.lock();
+            let stmt = format!(
+                "
+                SELECT {ENTRY_COLUMNS}, {embedding_column}
+                FROM {qualified_table}
+                WHERE $1 = '' OR namespace = $1
+                ORDER BY created_at ASC
+                "
+            );
+
+            let rows = client.query(&stmt, &[&namespace])?;
+            rows.iter()
+                .map(|row| {
+                    let embedding: Option<String> = row.get(11);
+                    let embedding = embedding.as_deref().map(parse_vector_literal).transpose()?;
+                    let mut entry = Self::row_to_entry(row)?;
+                    entry.score = None;
+                    Ok((entry, embedding))
+                })
+                .collect()
+        })
+        .await?
+    }
+
+    async fn restore(&self, entry: &MemoryEntry, embedding: Option<&[f32]>) -> Result<()> {
+        let embedding = if self.vector_enabled {
+            match embedding {
+                Some(embedding) if embedding.len() == self.embedder.dimensions() => {
+                    Some(vector_literal(embedding))
+                }
+                _ => Some(vector_literal(
+                    &self.embedder.embed_one(&entry.content).await?,
+                )),
+            }
+        } else {
+            None
+        };
+
+        let client = self.client.clone();
+        let qualified_table = self.qualified_table.clone();
+        let id = if entry.id.is_empty() {
+            Uuid::new_v4().to_string()
+        } else {
+            entry.id.clone()
+        };
+        let key = entry.key.clone();
+        let content = entry.content.clone();
+        let category = Self::category_to_str(&entry.category);
+        let timestamp = parse_timestamp(&entry.timestamp);
+        let session_id = entry.session_id.clone();
+        let importance = entry.importance;
+        let expires_at = parse_optional_time(entry.expires_at.as_deref())?;
+        let last_accessed = parse_optional_time(entry.last_accessed.as_deref())?;
+        let access_count = i64::try_from(entry.access_count).unwrap_or(i64::MAX);
+        // Entries keep their exported namespace unless restored through a
+        // namespace handle, which owns everything written through it.
+        let namespace = if self.namespace.is_empty() {
+            entry.namespace.clone().unwrap_or_default()
+        } else {
+            self.namespace.clone()
+        };
+
+        tokio::task::spawn_blocking(move || -> Result<()> {
+            let mut client = client.lock();
+            // Keep the exported id unless another key already owns it.
+            let id_taken = client
+                .query_opt(
+                    &format!(
+                        "SELECT 1 FROM {qualified_table}
+                         WHERE id = $1 AND NOT (key = $2 AND namespace = $3)"
+                    ),
+                    &[&id, &key, &namespace],
+                )?
+                .is_some();
+            let id = if id_taken {
+                Uuid::new_v4().to_string()
+            } else {
+                id
+            };
+
+            if let Some(embedding) = embedding {
+                let stmt = format!(
+                    "
+                    INSERT INTO {qualified_table}
+                        (id, key, content, category, created_at, updated_at, session_id,
+                         importance, expires_at, last_accessed, access_count, embedding, namespace)
+                    VALUES
+                        ($1, $2, $3, $4, $5, $5, $6, $7, $8, $9, $10, $11::TEXT::vector, $12)
+                    ON CONFLICT (namespace, key) DO UPDATE SET
+                        content = EXCLUDED.content,
+                        category = EXCLUDED.category,
+                        created_at = EXCLUDED.created_at,
+                        updated_at = EXCLUDED.updated_at,
+                        session_id = EXCLUDED.session_id,
+                        importance = EXCLUDED.importance,
+                        expires_at = EXCLUDED.expires_at,
+                        last_accessed = EXCLUDED.last_accessed,
+                        access_count = EXCLUDED.access_count,
+                        embedding = EXCLUDED.embedding
+                    "
+                );
+                client.execute(
+                    &stmt,
+                    &[
+                        &id,
+                        &key,
+                        &content,
+                        &category,
+                        &timestamp,
+                        &session_id,
+                        &importance,
+                        &expires_at,
+                        &last_accessed,
+                        &access_count,
+                        &embedding,
+                        &namespace,
+                    ],
+                )?;
+                return Ok(());
+            }
+
+            let stmt = format!(
+                "
+                INSERT INTO {qualified_table}
+                    (id, key, content, category, created_at, updated_at, session_id,
+                     importance, expires_at, last_accessed, access_count, namespace)
+                VALUES
+                    ($1, $2, $3, $4, $5, $5, $6, $7, $8, $9, $10, $11)
+                ON CONFLICT (namespace, key) DO UPDATE SET
+                    content = EXCLUDED.content,
+                    category = EXCLUDED.category,
+                    created_at = EXCLUDED.created_at,
+                    updated_at = EXCLUDED.updated_at,
+                    session_id = EXCLUDED.session_id,
+                    importance = EXCLUDED.importance,
+                    expires_at = EXCLUDED.expires_at,
+                    last_accessed = EXCLUDED.last_accessed,
+                    access_count = EXCLUDED.access_count
+                "
+            );
+            client.execute(
+                &stmt,
+                &[
+                    &id,
+                    &key,
+                    &content,
+                    &category,
+                    &timestamp,
+                    &session_id,
+                    &importance,
+                    &expires_at,
+                    &last_accessed,
+                    &access_count,
+                    &namespace,
+                ],
+            )?;
+            Ok(())
+        })
+        .await?
+    }
 }
 
 #[cfg(test)]
@@ -349,4 +1113,36 @@ mod tests {
             MemoryCategory::Custom("custom_notes".into())
         );
     }
+
+    #[test]
+    fn vector_literal_uses_pgvector_text_format() {
+        assert_eq!(vector_literal(&[0.5, -1.0, 0.25]), "[0.5,-1,0.25]");
+        assert_eq!(vector_literal(&[]), "[]");
+    }
+
+    #[test]
+    fn parse_vector_literal_roundtrips_vector_literal() {
+        let values = [0.5, -1.0, 0.25];
+        let parsed = parse_vector_literal(&vector_literal(&values)).unwrap();
+        assert_eq!(parsed, values);
+        assert!(parse_vector_literal("[]").unwrap().is_empty());
+        assert!(parse_vector_literal("0.5,1").is_err());
+    }
+
+    #[test]
+    fn parse_timestamp_accepts_rfc3339_and_bare_dates() {
+        let parsed = parse_timestamp("2026-02-16T10:30:00+02:00");
+        assert_eq!(parsed.to_rfc3339(), "2026-02-16T08:30:00+00:00");
+        let parsed = parse_timestamp("2026-02-16");
+        assert_eq!(parsed.to_rfc3339(), "2026-02-16T00:00:00+00:00");
+    }
+
+    #[test]
+    fn tsquery_any_ors_words_and_drops_operators() {
+        assert_eq!(
+            tsquery_any("Rust & borrow-checker!").as_deref(),
+            Some("rust | borrow | checker")
+        );
+        assert_eq!(tsquery_any("  !:*() ").as_deref(), None);
+    }
 }
@@ -4,13 +4,21 @@
 //! `(model, system_prompt_hash, user_prompt)`. Entries expire after a
 //! configurable TTL (default: 1 hour). The cache is optional and disabled by
 //! default — users opt in via `[memory] response_cache_enabled = true`.
+//!
+//! In semantic mode (`response_cache_semantic = true`) each entry also keeps
+//! the prompt embedding, and a lookup that misses the exact key returns the
+//! most similar cached prompt for the same model and system prompt when its
+//! cosine similarity reaches `response_cache_similarity_threshold`.
 
+use super::embeddings::EmbeddingProvider;
+use super::vector;
 use anyhow::Result;
 use chrono::{Duration, Local};
 use parking_lot::Mutex;
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, OptionalExtension};
 use sha2::{Digest, Sha256};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 /// Response cache backed by a dedicated SQLite database.
 ///
@@ -22,6 +30,41 @@ pub struct ResponseCache {
     db_path: PathBuf,
     ttl_minutes: i64,
     max_entries: usize,
+    semantic: Option<SemanticMatch>,
+}
+
+/// Embedding model and similarity threshold for semantic lookups.
+struct SemanticMatch {
+    embedder: Arc<dyn EmbeddingProvider>,
+    threshold: f64,
+}
+
+/// Cache statistics returned by [`ResponseCache::stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize)]
+pub struct CacheStats {
+    /// Responses currently cached.
+    pub entries: usize,
+    /// Hits served by the cached responses (exact and semantic).
+    pub hits: u64,
+    /// The part of `hits` matched by prompt similarity.
+    pub semantic_hits: u64,
+    /// Lookups that found no usable response.
+    pub misses: u64,
+    /// Tokens not spent thanks to hits.
+    pub tokens_saved: u64,
+}
+
+/// Outcome of [`ResponseCache::lookup`]. Hand misses back to
+/// [`ResponseCache::store`] so the prompt is not embedded twice.
+#[derive(Debug, Clone)]
+pub struct CacheLookup {
+    /// The cached response, if any.
+    pub response: Option<String>,
+    /// Whether `response` was cached for a similar rather than identical prompt.
+    pub semantic: bool,
+    key: String,
+    system_hash: String,
+    embedding: Option<Vec<f32>>,
 }
 
 impl ResponseCache {
@@ -50,7 +93,29 @@ impl ResponseCache {
                 hit_count   INTEGER NOT NULL DEFAULT 0
             );
             CREATE INDEX IF NOT EXISTS idx_rc_accessed ON response_cache(accessed_at);
-            CREATE INDEX IF NOT EXISTS idx_rc_created ON response_cache(created_at);",
+            CREATE INDEX IF NOT EXISTS idx_rc_created ON response_cache(created_at);
+            CREATE TABLE IF NOT EXISTS response_cache_counters (
+                name  TEXT PRIMARY KEY,
+                value INTEGER NOT NULL DEFAULT 0
+            );",
+        )?;
+
+        // Semantic columns were added later; older databases gain them here.
+        let has_embedding: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM pragma_table_info('response_cache') WHERE name = 'embedding')",
+            [],
+            |row| row.get(0),
+        )?;
+        if !has_embedding {
+            conn.execute_batch(
+                "ALTER TABLE response_cache ADD COLUMN system_hash TEXT NOT NULL DEFAULT '';
+                 ALTER TABLE response_cache ADD COLUMN embedding BLOB;
+                 ALTER TABLE response_cache ADD COLUMN semantic_hit_count INTEGER NOT NULL DEFAULT 0;",
+            )?;
+        }
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_rc_semantic ON response_cache(model, system_hash)",
+            [],
         )?;
 
         Ok(Self {
@@ -58,9 +123,21 @@ impl ResponseCache {
             db_path,
             ttl_minutes: i64::from(ttl_minutes),
             max_entries,
+            semantic: None,
         })
     }
 
+    /// Also answer lookups from cached prompts whose embedding has at least
+    /// `threshold` cosine similarity to the new prompt.
+    #[must_use]
+    pub fn with_semantic(mut self, embedder: Arc<dyn EmbeddingProvider>, threshold: f64) -> Self {
+        self.semantic = Some(SemanticMatch {
+            embedder,
+            threshold,
+        });
+        self
+    }
+
     /// Build a deterministic cache key from model + system prompt + user prompt.
     pub fn cache_key(model: &str, system_prompt: Option<&str>, user_prompt: &str) -> String {
         let mut hasher = Sha256::new();
@@ -75,8 +152,66 @@ impl ResponseCache {
         format!("{:064x}", hash)
     }
 
+    fn system_hash(system_prompt: Option<&str>) -> String {
+        let hash = Sha256::digest(system_prompt.unwrap_or_default().as_bytes());
+        format!("{:064x}", hash)
+    }
+
     /// Look up a cached response. Returns `None` on miss or expired entry.
     pub fn get(&self, key: &str) -> Result<Option<String>> {
+        let result = self.fetch_exact(key)?;
+        if result.is_none() {
+            self.count_miss()?;
+        }
+        Ok(result)
+    }
+
+    /// Look up the response for a prompt: the exact key first, then (in
+    /// semantic mode) the most similar prompt cached for the same model and
+    /// system prompt.
+    pub async fn lookup(
+        &self,
+        model: &str,
+        system_prompt: Option<&str>,
+        user_prompt: &str,
+    ) -> Result<CacheLookup> {
+        let mut lookup = CacheLookup {
+            response: None,
+            semantic: false,
+            key: Self::cache_key(model, system_prompt, user_prompt),
+            system_hash: Self::system_hash(system_prompt),
+            embedding: None,
+        };
+
+        lookup.response = self.fetch_exact(&lookup.key)?;
+        if lookup.response.is_some() {
+            return Ok(lookup);
+        }
+
+        if let Some(semantic) = &self.semantic {
+            match semantic.embedder.embed_one(user_prompt).await {
+                Ok(embedding) if !embedding.is_empty() => {
+                    lookup.response = self.fetch_similar(
+                        model,
+                        &lookup.system_hash,
+                        &embedding,
+                        semantic.threshold,
+                    )?;
+                    lookup.semantic = lookup.response.is_some();
+                    lookup.embedding = Some(embedding);
+                }
+                Ok(_) => {}
+                Err(e) => tracing::warn!("response cache: prompt embedding failed: {e}"),
+            }
+        }
+
+        if lookup.response.is_none() {
+            self.count_miss()?;
+        }
+        Ok(lookup)
+    }
+
+    fn fetch_exact(&self, key: &str) -> Result<Option<String>> {
         let conn = self.conn.lock();
 
         let now = Local::now();
@@ -103,17 +238,118 @@ impl ResponseCache {
         Ok(result)
     }
 
+    fn fetch_similar(
+        &self,
+        model: &str,
+        system_hash: &str,
+        embedding: &[f32],
+        threshold: f64,
+    ) -> Result<Option<String>> {
+        let conn = self.conn.lock();
+
+        let now = Local::now();
+        let cutoff = (now - Duration::minutes(self.ttl_minutes)).to_rfc3339();
+
+        let mut stmt = conn.prepare(
+            "SELECT prompt_hash, embedding FROM response_cache
+             WHERE model = ?1 AND system_hash = ?2 AND embedding IS NOT NULL
+               AND created_at > ?3",
+        )?;
+        let mut best: Option<(String, f32)> = None;
+        let rows = stmt.query_map(params![model, system_hash, cutoff], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, Vec<u8>>(1)?))
+        })?;
+        for row in rows {
+            let (key, bytes) = row?;
+            let similarity = vector::cosine_similarity(embedding, &vector::bytes_to_vec(&bytes));
+            if f64::from(similarity) >= threshold
+                && best.as_ref().map_or(true, |(_, top)| similarity > *top)
+            {
+                best = Some((key, similarity));
+            }
+        }
+        let Some((key, _)) = best else {
+            return Ok(None);
+        };
+
+        conn.execute(
+            "UPDATE response_cache
+             SET accessed_at = ?1, hit_count = hit_count + 1,
+                 semantic_hit_count = semantic_hit_count + 1
+             WHERE prompt_hash = ?2",
+            params![now.to_rfc3339(), key],
+        )?;
+        let response = conn
+            .query_row(
+                "SELECT response FROM response_cache WHERE prompt_hash = ?1",
+                params![key],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(response)
+    }
+
+    fn count_miss(&self) -> Result<()> {
+        self.conn.lock().execute(
+            "INSERT INTO response_cache_counters (name, value) VALUES ('misses', 1)
+             ON CONFLICT(name) DO UPDATE SET value = value + 1",
+            [],
+        )?;
+        Ok(())
+    }
+
     /// Store a response in the cache.
     pub fn put(&self, key: &str, model: &str, response: &str, token_count: u32) -> Result<()> {
+        self.insert(key, model, "", None, response, token_count)
+    }
+
+    /// Store the response for a missed [`lookup`](Self::lookup), keeping the
+    /// prompt embedding for later semantic matches.
+    pub fn store(
+        &self,
+        lookup: &CacheLookup,
+        model: &str,
+        response: &str,
+        token_count: u32,
+    ) -> Result<()> {
+        self.insert(
+            &lookup.key,
+            model,
+            &lookup.system_hash,
+            lookup.embedding.as_deref(),
+            response,
+            token_count,
+        )
+    }
+
+    fn insert(
+        &self,
+        key: &str,
+        model: &str,
+        system_hash: &str,
+        embedding: Option<&[f32]>,
+        response: &str,
+        token_count: u32,
+    ) -> Result<()> {
         let conn = self.conn.lock();
 
         let now = Local::now().to_rfc3339();
 
         conn.execute(
             "INSERT OR REPLACE INTO response_cache
-             (prompt_hash, model, response, token_count, created_at, accessed_at, hit_count)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, 0)",
-            params![key, model, response, token_count, now, now],
+             (prompt_hash, model, response, token_count, created_at, accessed_at, hit_count,
+              system_hash, embedding, semantic_hit_count)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, 0, ?7, ?8, 0)",
+            params![
+                key,
+                model,
+                response,
+                token_count,
+                now,
+                now,
+                system_hash,
+                embedding.map(vector::vec_to_bytes)
+            ],
         )?;
 
         // Evict expired entries
@@ -138,27 +374,36 @@ impl ResponseCache {
         Ok(())
     }
 
-    /// Return cache statistics: (total_entries, total_hits, total_tokens_saved).
-    pub fn stats(&self) -> Result<(usize, u64, u64)> {
+    /// Return cache statistics. Hits and tokens saved cover the entries
+    /// still cached; misses accumulate until [`clear`](Self::clear).
+    pub fn stats(&self) -> Result<CacheStats> {
         let conn = self.conn.lock();
 
-        let count: i64 =
-            conn.query_row("SELECT COUNT(*) FROM response_cache", [], |row| row.get(0))?;
-
-        let hits: i64 = conn.query_row(
-            "SELECT COALESCE(SUM(hit_count), 0) FROM response_cache",
+        let (count, hits, semantic_hits, tokens_saved): (i64, i64, i64, i64) = conn.query_row(
+            "SELECT COUNT(*), COALESCE(SUM(hit_count), 0), COALESCE(SUM(semantic_hit_count), 0),
+                    COALESCE(SUM(token_count * hit_count), 0)
+             FROM response_cache",
             [],
-            |row| row.get(0),
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
         )?;
 
-        let tokens_saved: i64 = conn.query_row(
-            "SELECT COALESCE(SUM(token_count * hit_count), 0) FROM response_cache",
-            [],
-            |row| row.get(0),
-        )?;
+        let misses: i64 = conn
+            .query_row(
+                "SELECT value FROM response_cache_counters WHERE name = 'misses'",
+                [],
+                |row| row.get(0),
+            )
+            .optional()?
+            .unwrap_or(0);
 
         #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
-        Ok((count as usize, hits as u64, tokens_saved as u64))
+        Ok(CacheStats {
+            entries: count as usize,
+            hits: hits as u64,
+            semantic_hits: semantic_hits as u64,
+            misses: misses as u64,
+            tokens_saved: tokens_saved as u64,
+        })
     }
 
     /// Wipe the entire cache (useful for `zeroclaw cache clear`).
@@ -166,6 +411,7 @@ impl ResponseCache {
         let conn = self.conn.lock();
 
         let affected = conn.execute("DELETE FROM response_cache", [])?;
+        conn.execute("DELETE FROM response_cache_counters", [])?;
         Ok(affected)
     }
 }
@@ -258,8 +504,8 @@ mod tests {
             let _ = cache.get(&key).unwrap();
         }
 
-        let (_, total_hits, _) = cache.stats().unwrap();
-        assert_eq!(total_hits, 3);
+        let stats = cache.stats().unwrap();
+        assert_eq!(stats.hits, 3);
     }
 
     #[test]
@@ -274,8 +520,8 @@ mod tests {
             let _ = cache.get(&key).unwrap();
         }
 
-        let (_, _, tokens_saved) = cache.stats().unwrap();
-        assert_eq!(tokens_saved, 500);
+        let stats = cache.stats().unwrap();
+        assert_eq!(stats.tokens_saved, 500);
     }
 
     #[test]
@@ -290,7 +536,7 @@ mod tests {
                 .unwrap();
         }
 
-        let (count, _, _) = cache.stats().unwrap();
+        let count = cache.stats().unwrap().entries;
         assert!(count <= 3, "Should have at most 3 entries after eviction");
     }
 
@@ -308,17 +554,14 @@ mod tests {
         let cleared = cache.clear().unwrap();
         assert_eq!(cleared, 10);
 
-        let (count, _, _) = cache.stats().unwrap();
+        let count = cache.stats().unwrap().entries;
         assert_eq!(count, 0);
     }
 
     #[test]
     fn stats_empty_cache() {
         let (_tmp, cache) = temp_cache(60);
-        let (count, hits, tokens) = cache.stats().unwrap();
-        assert_eq!(count, 0);
-        assert_eq!(hits, 0);
-        assert_eq!(tokens, 0);
+        assert_eq!(cache.stats().unwrap(), CacheStats::default());
     }
 
     #[test]
@@ -332,7 +575,7 @@ mod tests {
         let result = cache.get(&key).unwrap();
         assert_eq!(result.as_deref(), Some("answer v2"));
 
-        let (count, _, _) = cache.stats().unwrap();
+        let count = cache.stats().unwrap().entries;
         assert_eq!(count, 1);
     }
 
@@ -372,7 +615,7 @@ mod tests {
         let key3 = ResponseCache::cache_key("gpt-4", None, "prompt 3");
         cache.put(&key3, "gpt-4", "response 3", 10).unwrap();
 
-        let (count, _, _) = cache.stats().unwrap();
+        let count = cache.stats().unwrap().entries;
         assert!(count <= 3, "cache must not exceed max_entries");
 
         // Entry 0 was recently accessed and should survive
@@ -392,7 +635,7 @@ mod tests {
         // Should not panic even with max_entries=0
         cache.put(&key, "gpt-4", "response", 10).unwrap();
 
-        let (count, _, _) = cache.stats().unwrap();
+        let count = cache.stats().unwrap().entries;
         assert_eq!(count, 0, "cache with max_entries=0 should evict everything");
     }
 
@@ -417,7 +660,141 @@ mod tests {
             handle.join().unwrap();
         }
 
-        let (_, hits, _) = cache.stats().unwrap();
+        let hits = cache.stats().unwrap().hits;
         assert_eq!(hits, 10, "all concurrent reads should register as hits");
     }
+
+    // ── Semantic mode ────────────────────────────────────────
+
+    /// Embeds text as keyword presence, so paraphrases share a vector.
+    struct KeywordEmbedding;
+
+    #[async_trait::async_trait]
+    impl EmbeddingProvider for KeywordEmbedding {
+        fn name(&self) -> &str {
+            "keywords"
+        }
+
+        fn dimensions(&self) -> usize {
+            3
+        }
+
+        async fn embed(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+            Ok(texts
+                .iter()
+                .map(|text| {
+                    let text = text.to_lowercase();
+                    ["rust", "python", "weather"]
+                        .iter()
+                        .map(|word| if text.contains(word) { 1.0 } else { 0.0 })
+                        .collect()
+                })
+                .collect())
+        }
+    }
+
+    fn semantic_cache() -> (TempDir, ResponseCache) {
+        let (tmp, cache) = temp_cache(60);
+        (tmp, cache.with_semantic(Arc::new(KeywordEmbedding), 0.9))
+    }
+
+    #[tokio::test]
+    async fn semantic_lookup_matches_paraphrased_prompt() {
+        let (_tmp, cache) = semantic_cache();
+
+        let first = cache
+            .lookup("gpt-4", Some("sys"), "What is Rust?")
+            .await
+            .unwrap();
+        assert!(first.response.is_none());
+        cache
+            .store(&first, "gpt-4", "A systems language.", 40)
+            .unwrap();
+
+        let paraphrase = cache
+            .lookup("gpt-4", Some("sys"), "could you explain rust to me")
+            .await
+            .unwrap();
+        assert_eq!(paraphrase.response.as_deref(), Some("A systems language."));
+        assert!(paraphrase.semantic);
+
+        let exact = cache
+            .lookup("gpt-4", Some("sys"), "What is Rust?")
+            .await
+            .unwrap();
+        assert!(exact.response.is_some());
+        assert!(!exact.semantic);
+
+        let stats = cache.stats().unwrap();
+        assert_eq!(stats.hits, 2);
+        assert_eq!(stats.semantic_hits, 1);
+        assert_eq!(stats.misses, 1);
+        assert_eq!(stats.tokens_saved, 80);
+    }
+
+    #[tokio::test]
+    async fn semantic_lookup_requires_same_model_system_prompt_and_topic() {
+        let (_tmp, cache) = semantic_cache();
+        let first = cache
+            .lookup("gpt-4", Some("sys"), "What is Rust?")
+            .await
+            .unwrap();
+        cache
+            .store(&first, "gpt-4", "A systems language.", 40)
+            .unwrap();
+
+        for (model, system, prompt) in [
+            ("claude-3", Some("sys"), "explain rust"),
+            ("gpt-4", Some("other"), "explain rust"),
+            ("gpt-4", Some("sys"), "what is python"),
+        ] {
+            let lookup = cache.lookup(model, system, prompt).await.unwrap();
+            assert!(lookup.response.is_none(), "{model} {system:?} {prompt}");
+        }
+        assert_eq!(cache.stats().unwrap().misses, 4);
+    }
+
+    #[tokio::test]
+    async fn exact_mode_ignores_paraphrases() {
+        let (_tmp, cache) = temp_cache(60);
+        let first = cache.lookup("gpt-4", None, "What is Rust?").await.unwrap();
+        cache
+            .store(&first, "gpt-4", "A systems language.", 40)
+            .unwrap();
+
+        let paraphrase = cache.lookup("gpt-4", None, "explain rust").await.unwrap();
+        assert!(paraphrase.response.is_none());
+        assert_eq!(cache.get("missing").unwrap(), None);
+        assert_eq!(cache.stats().unwrap().misses, 3);
+    }
+
+    #[test]
+    fn legacy_database_gains_semantic_columns() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::create_dir_all(tmp.path().join("memory")).unwrap();
+        let conn = Connection::open(tmp.path().join("memory/response_cache.db")).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE response_cache (
+                prompt_hash TEXT PRIMARY KEY,
+                model       TEXT NOT NULL,
+                response    TEXT NOT NULL,
+                token_count INTEGER NOT NULL DEFAULT 0,
+                created_at  TEXT NOT NULL,
+                accessed_at TEXT NOT NULL,
+                hit_count   INTEGER NOT NULL DEFAULT 0
+            );",
+        )
+        .unwrap();
+        let now = Local::now().to_rfc3339();
+        conn.execute(
+            "INSERT INTO response_cache VALUES ('k', 'gpt-4', 'old answer', 5, ?1, ?1, 2)",
+            params![now],
+        )
+        .unwrap();
+        drop(conn);
+
+        let cache = ResponseCache::new(tmp.path(), 60, 100).unwrap();
+        assert_eq!(cache.get("k").unwrap().as_deref(), Some("old answer"));
+        assert_eq!(cache.stats().unwrap().hits, 3);
+    }
 }
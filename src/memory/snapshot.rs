@@ -35,12 +35,24 @@ pub fn export_snapshot(workspace_dir: &Path) -> Result<usize> {
     let conn = Connection::open(&db_path)?;
     conn.execute_batch("PRAGMA journal_mode = WAL; PRAGMA synchronous = NORMAL;")?;
 
-    let mut stmt = conn.prepare(
+    // Tenant memories stay out of the snapshot, which hydrates the shared
+    // namespace.
+    let has_namespace: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM pragma_table_info('memories') WHERE name = 'namespace')",
+        [],
+        |row| row.get(0),
+    )?;
+    let namespace_filter = if has_namespace {
+        " AND namespace = ''"
+    } else {
+        ""
+    };
+    let mut stmt = conn.prepare(&format!(
         "SELECT key, content, category, created_at, updated_at
          FROM memories
-         WHERE category = 'core'
-         ORDER BY updated_at DESC",
-    )?;
+         WHERE category = 'core'{namespace_filter}
+         ORDER BY updated_at DESC"
+    ))?;
 
     let rows: Vec<(String, String, String, String, String)> = stmt
         .query_map([], |row| {
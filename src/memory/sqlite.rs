@@ -1,9 +1,10 @@
 use super::embeddings::EmbeddingProvider;
-use super::traits::{Memory, MemoryCategory, MemoryEntry};
+use super::hnsw::{HnswIndex, DEFAULT_EF_SEARCH};
+use super::traits::{self, Memory, MemoryCategory, MemoryEntry, MemoryMetadata};
 use super::vector;
 use anyhow::Context;
 use async_trait::async_trait;
-use chrono::Local;
+use chrono::{Local, SecondsFormat, Utc};
 use parking_lot::Mutex;
 use rusqlite::{params, Connection};
 use std::fmt::Write as _;
@@ -17,10 +18,175 @@ use uuid::Uuid;
 /// Maximum allowed open timeout (seconds) to avoid unreasonable waits.
 const SQLITE_OPEN_TIMEOUT_CAP_SECS: u64 = 300;
 
+/// HNSW graph persisted next to brain.db.
+const VECTOR_INDEX_FILE: &str = "brain.hnsw";
+/// Persist the graph after this many incremental updates (and on drop).
+const VECTOR_INDEX_PERSIST_EVERY: usize = 512;
+/// Change-log rows kept for indexes (other processes) that are behind;
+/// anything further behind rebuilds from scratch.
+const VECTOR_LOG_RETAIN: i64 = 10_000;
+/// Session-scoped recall pulls `limit * N` index candidates before filtering;
+/// if that comes back short it falls back to the exact (filtered) scan.
+const VECTOR_INDEX_FILTER_OVERSAMPLE: usize = 8;
+/// Texts per embedding-provider call when `reindex` backfills embeddings.
+const REINDEX_BATCH_SIZE: usize = 64;
+/// Columns read by `SqliteMemory::row_to_entry`, in order.
+const ENTRY_COLUMNS: &str = "id, key, content, category, created_at, session_id, \
+     importance, expires_at, last_accessed, access_count, namespace";
+
+/// ANN index over `memories.embedding`. Triggers append every changed row id
+/// to `memory_vector_log`, and the graph's generation is the last log
+/// sequence it has applied, so writes from any connection are picked up.
+struct VectorIndex {
+    path: PathBuf,
+    dims: usize,
+    /// Lazily loaded from `brain.hnsw` (or rebuilt) on first search.
+    graph: Option<HnswIndex>,
+    unsaved: usize,
+}
+
+impl VectorIndex {
+    fn new(path: PathBuf, dims: usize) -> Self {
+        Self {
+            path,
+            dims,
+            graph: None,
+            unsaved: 0,
+        }
+    }
+
+    /// Return a graph matching the table: load `brain.hnsw` on first use,
+    /// apply logged changes, and rebuild if the log no longer covers the gap.
+    fn current(&mut self, conn: &Connection) -> anyhow::Result<&HnswIndex> {
+        let generation = SqliteMemory::vector_generation(conn)?;
+        let loaded = self.graph.is_none();
+        if loaded {
+            self.graph = self.load(conn);
+        }
+
+        let synced = match self.graph.as_mut() {
+            Some(graph) => {
+                let applied = Self::catch_up(graph, conn, generation)?;
+                if let Some(applied) = applied {
+                    self.unsaved += applied;
+                }
+                applied.is_some()
+            }
+            None => false,
+        };
+        let consistent = synced
+            && (!loaded
+                || self.graph.as_ref().is_some_and(|graph| {
+                    graph.is_complete()
+                        && SqliteMemory::embedding_count(conn, self.dims).ok() == Some(graph.len())
+                }));
+
+        if !consistent {
+            self.rebuild(conn)?;
+        } else if self.unsaved >= VECTOR_INDEX_PERSIST_EVERY {
+            self.persist();
+        }
+        Ok(self.graph.get_or_insert_with(|| HnswIndex::new(self.dims)))
+    }
+
+    /// Bring a loaded graph up to date after a write; no-op until first search.
+    fn sync(&mut self, conn: &Connection) -> anyhow::Result<()> {
+        if self.graph.is_some() {
+            self.current(conn)?;
+        }
+        Ok(())
+    }
+
+    fn load(&self, conn: &Connection) -> Option<HnswIndex> {
+        let mut graph = HnswIndex::load_graph(&self.path).ok()?;
+        if graph.dims() != self.dims {
+            return None;
+        }
+        SqliteMemory::for_each_embedding(conn, |id, embedding| {
+            graph.attach_vector(id, embedding);
+        })
+        .ok()?;
+        Some(graph)
+    }
+
+    /// Apply log entries after the graph's generation. Returns how many rows
+    /// were applied, or `None` if the log was pruned past the graph (or the
+    /// database was recreated) and a rebuild is needed.
+    fn catch_up(
+        graph: &mut HnswIndex,
+        conn: &Connection,
+        generation: i64,
+    ) -> anyhow::Result<Option<usize>> {
+        let from = graph.generation();
+        if from == generation {
+            return Ok(Some(0));
+        }
+        let oldest: Option<i64> =
+            conn.query_row("SELECT MIN(seq) FROM memory_vector_log", [], |row| {
+                row.get(0)
+            })?;
+        if from > generation || oldest.map_or(true, |oldest| oldest > from + 1) {
+            return Ok(None);
+        }
+
+        let mut stmt = conn.prepare(
+            "SELECT l.memory_id, m.embedding FROM memory_vector_log l
+             LEFT JOIN memories m ON m.id = l.memory_id
+             WHERE l.seq > ?1 AND l.seq <= ?2
+             GROUP BY l.memory_id",
+        )?;
+        let rows = stmt.query_map(params![from, generation], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, Option<Vec<u8>>>(1)?))
+        })?;
+        let mut applied = 0;
+        for row in rows {
+            match row? {
+                (id, Some(blob)) => graph.insert(&id, &vector::bytes_to_vec(&blob)),
+                (id, None) => {
+                    graph.remove(&id);
+                }
+            }
+            applied += 1;
+        }
+
+        if graph.needs_compaction() {
+            graph.compact();
+        }
+        graph.set_generation(generation);
+        Ok(Some(applied))
+    }
+
+    /// Rebuild the graph from every stored embedding and persist it.
+    fn rebuild(&mut self, conn: &Connection) -> anyhow::Result<()> {
+        let generation = SqliteMemory::vector_generation(conn)?;
+        let mut graph = HnswIndex::new(self.dims);
+        SqliteMemory::for_each_embedding(conn, |id, embedding| graph.insert(id, embedding))?;
+        graph.set_generation(generation);
+        self.graph = Some(graph);
+        self.unsaved = 1;
+        self.persist();
+        Ok(())
+    }
+
+    fn persist(&mut self) {
+        if self.unsaved == 0 {
+            return;
+        }
+        if let Some(graph) = &self.graph {
+            if let Err(e) = graph.save(&self.path) {
+                tracing::warn!("failed to persist vector index: {e:#}");
+                return;
+            }
+        }
+        self.unsaved = 0;
+    }
+}
+
 /// SQLite-backed persistent memory — the brain
 ///
 /// Full-stack search engine:
-/// - **Vector DB**: embeddings stored as BLOB, cosine similarity search
+/// - **Vector DB**: embeddings stored as BLOB, HNSW index (`brain.hnsw`) for
+///   approximate cosine search, exact scan as fallback
 /// - **Keyword Search**: FTS5 virtual table with BM25 scoring
 /// - **Hybrid Merge**: weighted fusion of vector + keyword results
 /// - **Embedding Cache**: LRU-evicted cache to avoid redundant API calls
@@ -32,6 +198,9 @@ pub struct SqliteMemory {
     vector_weight: f32,
     keyword_weight: f32,
     cache_max: usize,
+    vector_index: Option<Arc<Mutex<VectorIndex>>>,
+    /// Tenant namespace this handle reads and writes; empty is shared.
+    namespace: String,
 }
 
 impl SqliteMemory {
@@ -83,14 +252,29 @@ impl SqliteMemory {
 
         Self::init_schema(&conn)?;
 
-        Ok(Self {
+        let memory = Self {
             conn: Arc::new(Mutex::new(conn)),
             db_path,
             embedder,
             vector_weight,
             keyword_weight,
             cache_max,
-        })
+            vector_index: None,
+            namespace: String::new(),
+        };
+        Ok(memory.with_vector_index(true))
+    }
+
+    /// Enable (default) or disable the HNSW index used for vector recall.
+    /// Without an embedder there is nothing to index, so this is a no-op.
+    #[must_use]
+    pub fn with_vector_index(mut self, enabled: bool) -> Self {
+        let dims = self.embedder.dimensions();
+        self.vector_index = (enabled && dims > 0).then(|| {
+            let path = self.db_path.with_file_name(VECTOR_INDEX_FILE);
+            Arc::new(Mutex::new(VectorIndex::new(path, dims)))
+        });
+        self
     }
 
     /// Open SQLite connection, optionally with a timeout (for locked/slow storage).
@@ -126,16 +310,17 @@ impl SqliteMemory {
 
     /// Initialize all tables: memories, FTS5, `embedding_cache`
     fn init_schema(conn: &Connection) -> anyhow::Result<()> {
-        conn.execute_batch(
-            "-- Core memories table
+        let schema = "-- Core memories table
             CREATE TABLE IF NOT EXISTS memories (
                 id          TEXT PRIMARY KEY,
-                key         TEXT NOT NULL UNIQUE,
+                key         TEXT NOT NULL,
                 content     TEXT NOT NULL,
                 category    TEXT NOT NULL DEFAULT 'core',
                 embedding   BLOB,
                 created_at  TEXT NOT NULL,
-                updated_at  TEXT NOT NULL
+                updated_at  TEXT NOT NULL,
+                namespace   TEXT NOT NULL DEFAULT '',
+                UNIQUE(namespace, key)
             );
             CREATE INDEX IF NOT EXISTS idx_memories_category ON memories(category);
             CREATE INDEX IF NOT EXISTS idx_memories_key ON memories(key);
@@ -168,8 +353,25 @@ impl SqliteMemory {
                 created_at   TEXT NOT NULL,
                 accessed_at  TEXT NOT NULL
             );
-            CREATE INDEX IF NOT EXISTS idx_cache_accessed ON embedding_cache(accessed_at);",
-        )?;
+            CREATE INDEX IF NOT EXISTS idx_cache_accessed ON embedding_cache(accessed_at);
+
+            -- Vector change log: every writer (other processes, hygiene,
+            -- snapshot hydration) records touched rows, so the HNSW index
+            -- in brain.hnsw can catch up instead of going stale.
+            CREATE TABLE IF NOT EXISTS memory_vector_log (
+                seq       INTEGER PRIMARY KEY AUTOINCREMENT,
+                memory_id TEXT NOT NULL
+            );
+            CREATE TRIGGER IF NOT EXISTS memories_vlog_ai AFTER INSERT ON memories BEGIN
+                INSERT INTO memory_vector_log (memory_id) VALUES (new.id);
+            END;
+            CREATE TRIGGER IF NOT EXISTS memories_vlog_ad AFTER DELETE ON memories BEGIN
+                INSERT INTO memory_vector_log (memory_id) VALUES (old.id);
+            END;
+            CREATE TRIGGER IF NOT EXISTS memories_vlog_au AFTER UPDATE OF embedding ON memories BEGIN
+                INSERT INTO memory_vector_log (memory_id) VALUES (new.id);
+            END;";
+        conn.execute_batch(schema)?;
 
         // Migration: add session_id column if not present (safe to run repeatedly)
         let has_session_id: bool = conn
@@ -183,6 +385,157 @@ impl SqliteMemory {
             )?;
         }
 
+        // Migration: importance, expiry and access-stat columns
+        for (column, ddl) in [
+            (
+                "importance",
+                "ALTER TABLE memories ADD COLUMN importance REAL;",
+            ),
+            (
+                "expires_at",
+                "ALTER TABLE memories ADD COLUMN expires_at TEXT;
+                 CREATE INDEX IF NOT EXISTS idx_memories_expires_at ON memories(expires_at);",
+            ),
+            (
+                "last_accessed",
+                "ALTER TABLE memories ADD COLUMN last_accessed TEXT;",
+            ),
+            (
+                "access_count",
+                "ALTER TABLE memories ADD COLUMN access_count INTEGER NOT NULL DEFAULT 0;",
+            ),
+        ] {
+            let exists: bool = conn.query_row(
+                "SELECT EXISTS(SELECT 1 FROM pragma_table_info('memories') WHERE name = ?1)",
+                params![column],
+                |row| row.get(0),
+            )?;
+            if !exists {
+                conn.execute_batch(ddl)?;
+            }
+        }
+
+        // Migration: keys became unique per namespace rather than globally.
+        // SQLite can't alter a UNIQUE constraint, so rebuild the table with
+        // rowids kept (the FTS index refers to them), then recreate the
+        // indexes and triggers dropped along with the old table.
+        let has_namespace: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM pragma_table_info('memories') WHERE name = 'namespace')",
+            [],
+            |row| row.get(0),
+        )?;
+        if !has_namespace {
+            conn.execute_batch(
+                "BEGIN;
+                 CREATE TABLE memories_migrated (
+                     id            TEXT PRIMARY KEY,
+                     key           TEXT NOT NULL,
+                     content       TEXT NOT NULL,
+                     category      TEXT NOT NULL DEFAULT 'core',
+                     embedding     BLOB,
+                     created_at    TEXT NOT NULL,
+                     updated_at    TEXT NOT NULL,
+                     session_id    TEXT,
+                     importance    REAL,
+                     expires_at    TEXT,
+                     last_accessed TEXT,
+                     access_count  INTEGER NOT NULL DEFAULT 0,
+                     namespace     TEXT NOT NULL DEFAULT '',
+                     UNIQUE(namespace, key)
+                 );
+                 INSERT INTO memories_migrated (rowid, id, key, content, category, embedding,
+                     created_at, updated_at, session_id, importance, expires_at, last_accessed,
+                     access_count)
+                 SELECT rowid, id, key, content, category, embedding, created_at, updated_at,
+                     session_id, importance, expires_at, last_accessed, access_count
+                 FROM memories;
+                 DROP TABLE memories;
+                 ALTER TABLE memories_migrated RENAME TO memories;
+                 COMMIT;",
+            )?;
+            conn.execute_batch(schema)?;
+            conn.execute_batch(
+                "CREATE INDEX IF NOT EXISTS idx_memories_session ON memories(session_id);
+                 CREATE INDEX IF NOT EXISTS idx_memories_expires_at ON memories(expires_at);",
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Map a row selected with `ENTRY_COLUMNS` (score is left unset).
+    fn row_to_entry(row: &rusqlite::Row) -> rusqlite::Result<MemoryEntry> {
+        Ok(MemoryEntry {
+            id: row.get(0)?,
+            key: row.get(1)?,
+            content: row.get(2)?,
+            category: Self::str_to_category(&row.get::<_, String>(3)?),
+            timestamp: row.get(4)?,
+            session_id: row.get(5)?,
+            score: None,
+            importance: row.get(6)?,
+            expires_at: row.get(7)?,
+            last_accessed: row.get(8)?,
+            access_count: row.get::<_, i64>(9)?.try_into().unwrap_or(0),
+            namespace: Some(row.get::<_, String>(10)?).filter(|namespace| !namespace.is_empty()),
+        })
+    }
+
+    /// Bump access stats of entries returned by `recall`.
+    fn record_access(conn: &Connection, entries: &[MemoryEntry], now: &str) -> anyhow::Result<()> {
+        let mut stmt = conn.prepare_cached(
+            "UPDATE memories SET access_count = access_count + 1, last_accessed = ?1 WHERE id = ?2",
+        )?;
+        for entry in entries {
+            stmt.execute(params![now, entry.id])?;
+        }
+        Ok(())
+    }
+
+    /// Last `memory_vector_log` sequence (monotonic, survives pruning).
+    fn vector_generation(conn: &Connection) -> anyhow::Result<i64> {
+        Ok(conn.query_row(
+            "SELECT COALESCE(
+                (SELECT seq FROM sqlite_sequence WHERE name = 'memory_vector_log'), 0)",
+            [],
+            |row| row.get(0),
+        )?)
+    }
+
+    /// Trim the change log to the last `VECTOR_LOG_RETAIN` entries.
+    fn prune_vector_log(conn: &Connection) -> anyhow::Result<()> {
+        conn.execute(
+            "DELETE FROM memory_vector_log WHERE seq <= (
+                SELECT seq FROM sqlite_sequence WHERE name = 'memory_vector_log') - ?1",
+            params![VECTOR_LOG_RETAIN],
+        )?;
+        Ok(())
+    }
+
+    /// Rows whose embedding has `dims` dimensions (i.e. what the index holds).
+    fn embedding_count(conn: &Connection, dims: usize) -> anyhow::Result<usize> {
+        let bytes = i64::try_from(dims * 4)?;
+        let count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM memories WHERE embedding IS NOT NULL AND length(embedding) = ?1",
+            params![bytes],
+            |row| row.get(0),
+        )?;
+        Ok(usize::try_from(count)?)
+    }
+
+    /// Stream every stored embedding without collecting the table.
+    fn for_each_embedding(
+        conn: &Connection,
+        mut f: impl FnMut(&str, &[f32]),
+    ) -> anyhow::Result<()> {
+        let mut stmt =
+            conn.prepare("SELECT id, embedding FROM memories WHERE embedding IS NOT NULL")?;
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let id: String = row.get(0)?;
+            let blob: Vec<u8> = row.get(1)?;
+            f(&id, &vector::bytes_to_vec(&blob));
+        }
         Ok(())
     }
 
@@ -223,40 +576,76 @@ impl SqliteMemory {
 
     /// Get embedding from cache, or compute + cache it
     async fn get_or_compute_embedding(&self, text: &str) -> anyhow::Result<Option<Vec<f32>>> {
+        Ok(self
+            .get_or_compute_embeddings(&[text])
+            .await?
+            .and_then(|mut embeddings| embeddings.pop()))
+    }
+
+    /// Batch form of `get_or_compute_embedding`: cache hits are served from
+    /// `embedding_cache` and all misses go to the provider in one `embed` call.
+    async fn get_or_compute_embeddings(
+        &self,
+        texts: &[&str],
+    ) -> anyhow::Result<Option<Vec<Vec<f32>>>> {
         if self.embedder.dimensions() == 0 {
             return Ok(None); // Noop embedder
         }
 
-        let hash = Self::content_hash(text);
+        let hashes: Vec<String> = texts.iter().map(|text| Self::content_hash(text)).collect();
         let now = Local::now().to_rfc3339();
 
         // Check cache (offloaded to blocking thread)
         let conn = self.conn.clone();
-        let hash_c = hash.clone();
+        let hashes_c = hashes.clone();
         let now_c = now.clone();
-        let cached = tokio::task::spawn_blocking(move || -> anyhow::Result<Option<Vec<f32>>> {
-            let conn = conn.lock();
-            let mut stmt =
-                conn.prepare("SELECT embedding FROM embedding_cache WHERE content_hash = ?1")?;
-            let blob: Option<Vec<u8>> = stmt.query_row(params![hash_c], |row| row.get(0)).ok();
-            if let Some(bytes) = blob {
-                conn.execute(
+        let mut results =
+            tokio::task::spawn_blocking(move || -> anyhow::Result<Vec<Option<Vec<f32>>>> {
+                let conn = conn.lock();
+                let mut stmt = conn.prepare_cached(
+                    "SELECT embedding FROM embedding_cache WHERE content_hash = ?1",
+                )?;
+                let mut touch = conn.prepare_cached(
                     "UPDATE embedding_cache SET accessed_at = ?1 WHERE content_hash = ?2",
-                    params![now_c, hash_c],
                 )?;
-                return Ok(Some(vector::bytes_to_vec(&bytes)));
-            }
-            Ok(None)
-        })
-        .await??;
+                let mut cached = Vec::with_capacity(hashes_c.len());
+                for hash in &hashes_c {
+                    let blob: Option<Vec<u8>> =
+                        stmt.query_row(params![hash], |row| row.get(0)).ok();
+                    if let Some(bytes) = blob {
+                        touch.execute(params![now_c, hash])?;
+                        cached.push(Some(vector::bytes_to_vec(&bytes)));
+                    } else {
+                        cached.push(None);
+                    }
+                }
+                Ok(cached)
+            })
+            .await??;
 
-        if cached.is_some() {
-            return Ok(cached);
+        let missing: Vec<usize> = (0..texts.len()).filter(|&i| results[i].is_none()).collect();
+        if missing.is_empty() {
+            return Ok(Some(results.into_iter().flatten().collect()));
         }
 
-        // Compute embedding (async I/O)
-        let embedding = self.embedder.embed_one(text).await?;
-        let bytes = vector::vec_to_bytes(&embedding);
+        // Compute embeddings (async I/O)
+        let inputs: Vec<&str> = missing.iter().map(|&i| texts[i]).collect();
+        let computed = self.embedder.embed(&inputs).await?;
+        if computed.len() != inputs.len() {
+            anyhow::bail!(
+                "Embedding provider returned {} vectors for {} inputs",
+                computed.len(),
+                inputs.len()
+            );
+        }
+        let rows: Vec<(String, Vec<u8>)> = missing
+            .iter()
+            .zip(&computed)
+            .map(|(&i, embedding)| (hashes[i].clone(), vector::vec_to_bytes(embedding)))
+            .collect();
+        for (&i, embedding) in missing.iter().zip(computed) {
+            results[i] = Some(embedding);
+        }
 
         // Store in cache + LRU eviction (offloaded to blocking thread)
         let conn = self.conn.clone();
@@ -264,11 +653,13 @@ impl SqliteMemory {
         let cache_max = self.cache_max as i64;
         tokio::task::spawn_blocking(move || -> anyhow::Result<()> {
             let conn = conn.lock();
-            conn.execute(
+            let mut insert = conn.prepare_cached(
                 "INSERT OR REPLACE INTO embedding_cache (content_hash, embedding, created_at, accessed_at)
                  VALUES (?1, ?2, ?3, ?4)",
-                params![hash, bytes, now, now],
             )?;
+            for (hash, bytes) in &rows {
+                insert.execute(params![hash, bytes, now, now])?;
+            }
             conn.execute(
                 "DELETE FROM embedding_cache WHERE content_hash IN (
                     SELECT content_hash FROM embedding_cache
@@ -281,14 +672,15 @@ impl SqliteMemory {
         })
         .await??;
 
-        Ok(Some(embedding))
+        Ok(Some(results.into_iter().flatten().collect()))
     }
 
-    /// FTS5 BM25 keyword search
+    /// FTS5 BM25 keyword search within a namespace
     fn fts5_search(
         conn: &Connection,
         query: &str,
         limit: usize,
+        namespace: &str,
     ) -> anyhow::Result<Vec<(String, f32)>> {
         // Escape FTS5 special chars and build query
         let fts_query: String = query
@@ -304,7 +696,7 @@ impl SqliteMemory {
         let sql = "SELECT m.id, bm25(memories_fts) as score
                    FROM memories_fts f
                    JOIN memories m ON m.rowid = f.rowid
-                   WHERE memories_fts MATCH ?1
+                   WHERE memories_fts MATCH ?1 AND m.namespace = ?3
                    ORDER BY score
                    LIMIT ?2";
 
@@ -312,7 +704,7 @@ impl SqliteMemory {
         #[allow(clippy::cast_possible_wrap)]
         let limit_i64 = limit as i64;
 
-        let rows = stmt.query_map(params![fts_query, limit_i64], |row| {
+        let rows = stmt.query_map(params![fts_query, limit_i64, namespace], |row| {
             let id: String = row.get(0)?;
             let score: f64 = row.get(1)?;
             // BM25 returns negative scores (lower = better), negate for ranking
@@ -327,7 +719,8 @@ impl SqliteMemory {
         Ok(results)
     }
 
-    /// Vector similarity search: scan embeddings and compute cosine similarity.
+    /// Vector similarity search: scan a namespace's embeddings and compute
+    /// cosine similarity.
     ///
     /// Optional `category` and `session_id` filters reduce full-table scans
     /// when the caller already knows the scope of relevant memories.
@@ -335,12 +728,16 @@ impl SqliteMemory {
         conn: &Connection,
         query_embedding: &[f32],
         limit: usize,
+        namespace: &str,
         category: Option<&str>,
         session_id: Option<&str>,
     ) -> anyhow::Result<Vec<(String, f32)>> {
-        let mut sql = "SELECT id, embedding FROM memories WHERE embedding IS NOT NULL".to_string();
-        let mut param_values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
-        let mut idx = 1;
+        let mut sql =
+            "SELECT id, embedding FROM memories WHERE embedding IS NOT NULL AND namespace = ?1"
+                .to_string();
+        let mut param_values: Vec<Box<dyn rusqlite::types::ToSql>> =
+            vec![Box::new(namespace.to_string())];
+        let mut idx = 2;
 
         if let Some(cat) = category {
             let _ = write!(sql, " AND category = ?{idx}");
@@ -376,8 +773,84 @@ impl SqliteMemory {
         Ok(scored)
     }
 
-    /// Safe reindex: rebuild FTS5 + embeddings with rollback on failure
-    #[allow(dead_code)]
+    /// Vector search through the HNSW index, falling back to the exact scan
+    /// when no index is configured or a namespace/session-scoped search comes
+    /// back short. The index spans every namespace, so candidates are only
+    /// left unfiltered while the table holds no tenant rows at all.
+    fn indexed_vector_search(
+        conn: &Connection,
+        index: Option<&Mutex<VectorIndex>>,
+        query_embedding: &[f32],
+        limit: usize,
+        namespace: &str,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<Vec<(String, f32)>> {
+        let Some(index) = index else {
+            return Self::vector_search(conn, query_embedding, limit, namespace, None, session_id);
+        };
+
+        let filtered = session_id.is_some() || !namespace.is_empty() || Self::has_namespaces(conn)?;
+        let want = if filtered {
+            limit * VECTOR_INDEX_FILTER_OVERSAMPLE
+        } else {
+            limit
+        };
+        let candidates: Vec<(String, f32)> = index
+            .lock()
+            .current(conn)?
+            .search(query_embedding, want, DEFAULT_EF_SEARCH.max(want))
+            .into_iter()
+            .filter(|(_, sim)| *sim > 0.0)
+            .collect();
+
+        if !filtered {
+            return Ok(candidates);
+        }
+        let mut in_scope = std::collections::HashSet::new();
+        if !candidates.is_empty() {
+            let placeholders: String = (3..=candidates.len() + 2)
+                .map(|i| format!("?{i}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let sql = format!(
+                "SELECT id FROM memories
+                 WHERE namespace = ?1 AND (?2 IS NULL OR session_id = ?2) AND id IN ({placeholders})"
+            );
+            let mut stmt = conn.prepare(&sql)?;
+            let mut param_values: Vec<&dyn rusqlite::types::ToSql> = vec![&namespace, &session_id];
+            param_values.extend(
+                candidates
+                    .iter()
+                    .map(|(id, _)| id as &dyn rusqlite::types::ToSql),
+            );
+            let rows = stmt.query_map(param_values.as_slice(), |row| row.get::<_, String>(0))?;
+            for row in rows {
+                in_scope.insert(row?);
+            }
+        }
+
+        let scoped: Vec<(String, f32)> = candidates
+            .into_iter()
+            .filter(|(id, _)| in_scope.contains(id))
+            .take(limit)
+            .collect();
+        if scoped.len() < limit {
+            return Self::vector_search(conn, query_embedding, limit, namespace, None, session_id);
+        }
+        Ok(scoped)
+    }
+
+    /// Whether any row belongs to a tenant namespace.
+    fn has_namespaces(conn: &Connection) -> anyhow::Result<bool> {
+        Ok(conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM memories WHERE namespace > '')",
+            [],
+            |row| row.get(0),
+        )?)
+    }
+
+    /// Safe reindex: rebuild FTS5, embed rows missing embeddings, then
+    /// rebuild the HNSW index from scratch.
     pub async fn reindex(&self) -> anyhow::Result<usize> {
         // Step 1: Rebuild FTS5
         {
@@ -408,28 +881,57 @@ impl SqliteMemory {
         .await??;
 
         let mut count = 0;
-        for (id, content) in &entries {
-            if let Ok(Some(emb)) = self.get_or_compute_embedding(content).await {
-                let bytes = vector::vec_to_bytes(&emb);
-                let conn = self.conn.clone();
-                let id = id.clone();
-                tokio::task::spawn_blocking(move || -> anyhow::Result<()> {
-                    let conn = conn.lock();
-                    conn.execute(
-                        "UPDATE memories SET embedding = ?1 WHERE id = ?2",
-                        params![bytes, id],
-                    )?;
-                    Ok(())
-                })
-                .await??;
-                count += 1;
-            }
+        for batch in entries.chunks(REINDEX_BATCH_SIZE) {
+            let texts: Vec<&str> = batch.iter().map(|(_, content)| content.as_str()).collect();
+            let embeddings = match self.get_or_compute_embeddings(&texts).await {
+                Ok(Some(embeddings)) => embeddings,
+                Ok(None) => break,
+                Err(e) => {
+                    tracing::warn!("reindex: embedding batch failed: {e:#}");
+                    continue;
+                }
+            };
+            let updates: Vec<(String, Vec<u8>)> = batch
+                .iter()
+                .zip(&embeddings)
+                .map(|((id, _), emb)| (id.clone(), vector::vec_to_bytes(emb)))
+                .collect();
+            let conn = self.conn.clone();
+            count += tokio::task::spawn_blocking(move || -> anyhow::Result<usize> {
+                let conn = conn.lock();
+                let mut stmt =
+                    conn.prepare_cached("UPDATE memories SET embedding = ?1 WHERE id = ?2")?;
+                for (id, bytes) in &updates {
+                    stmt.execute(params![bytes, id])?;
+                }
+                Ok(updates.len())
+            })
+            .await??;
+        }
+
+        // Step 3: Rebuild the ANN index over the final set of embeddings
+        if let Some(index) = self.vector_index.clone() {
+            let conn = self.conn.clone();
+            tokio::task::spawn_blocking(move || index.lock().rebuild(&conn.lock())).await??;
         }
 
         Ok(count)
     }
 }
 
+impl Drop for SqliteMemory {
+    fn drop(&mut self) {
+        // Namespace handles share the index; the last one out persists it.
+        if let Some(index) = self
+            .vector_index
+            .as_ref()
+            .filter(|i| Arc::strong_count(i) == 1)
+        {
+            index.lock().persist();
+        }
+    }
+}
+
 #[async_trait]
 impl Memory for SqliteMemory {
     fn name(&self) -> &str {
@@ -443,6 +945,26 @@ impl Memory for SqliteMemory {
         category: MemoryCategory,
         session_id: Option<&str>,
     ) -> anyhow::Result<()> {
+        self.store_with_metadata(
+            key,
+            content,
+            category,
+            session_id,
+            &MemoryMetadata::default(),
+        )
+        .await
+    }
+
+    async fn store_with_metadata(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+        metadata: &MemoryMetadata,
+    ) -> anyhow::Result<()> {
+        let metadata = metadata.normalized()?;
+
         // Compute embedding (async, before blocking work)
         let embedding_bytes = self
             .get_or_compute_embedding(content)
@@ -450,9 +972,11 @@ impl Memory for SqliteMemory {
             .map(|emb| vector::vec_to_bytes(&emb));
 
         let conn = self.conn.clone();
+        let vector_index = self.vector_index.clone();
         let key = key.to_string();
         let content = content.to_string();
         let session_id = session_id.map(String::from);
+        let namespace = self.namespace.clone();
 
         tokio::task::spawn_blocking(move || -> anyhow::Result<()> {
             let conn = conn.lock();
@@ -460,17 +984,39 @@ impl Memory for SqliteMemory {
             let cat = Self::category_to_str(&category);
             let id = Uuid::new_v4().to_string();
 
+            // Access stats belong to the key and survive content updates.
             conn.execute(
-                "INSERT INTO memories (id, key, content, category, embedding, created_at, updated_at, session_id)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
-                 ON CONFLICT(key) DO UPDATE SET
+                "INSERT INTO memories (id, key, content, category, embedding, created_at, updated_at, session_id, importance, expires_at, namespace)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+                 ON CONFLICT(namespace, key) DO UPDATE SET
                     content = excluded.content,
                     category = excluded.category,
                     embedding = excluded.embedding,
                     updated_at = excluded.updated_at,
-                    session_id = excluded.session_id",
-                params![id, key, content, cat, embedding_bytes, now, now, session_id],
+                    session_id = excluded.session_id,
+                    importance = excluded.importance,
+                    expires_at = excluded.expires_at",
+                params![
+                    id,
+                    key,
+                    content,
+                    cat,
+                    embedding_bytes,
+                    now,
+                    now,
+                    session_id,
+                    metadata.importance,
+                    metadata.expires_at,
+                    namespace
+                ],
             )?;
+            Self::prune_vector_log(&conn)?;
+
+            if let Some(index) = vector_index {
+                if let Err(e) = index.lock().sync(&conn) {
+                    tracing::warn!("vector index sync failed: {e:#}");
+                }
+            }
             Ok(())
         })
         .await?
@@ -490,8 +1036,10 @@ impl Memory for SqliteMemory {
         let query_embedding = self.get_or_compute_embedding(query).await?;
 
         let conn = self.conn.clone();
+        let vector_index = self.vector_index.clone();
         let query = query.to_string();
         let session_id = session_id.map(String::from);
+        let namespace = self.namespace.clone();
         let vector_weight = self.vector_weight;
         let keyword_weight = self.keyword_weight;
 
@@ -500,11 +1048,20 @@ impl Memory for SqliteMemory {
             let session_ref = session_id.as_deref();
 
             // FTS5 BM25 keyword search
-            let keyword_results = Self::fts5_search(&conn, &query, limit * 2).unwrap_or_default();
+            let keyword_results =
+                Self::fts5_search(&conn, &query, limit * 2, &namespace).unwrap_or_default();
 
             // Vector similarity search (if embeddings available)
             let vector_results = if let Some(ref qe) = query_embedding {
-                Self::vector_search(&conn, qe, limit * 2, None, session_ref).unwrap_or_default()
+                Self::indexed_vector_search(
+                    &conn,
+                    vector_index.as_deref(),
+                    qe,
+                    limit * 2,
+                    &namespace,
+                    session_ref,
+                )
+                .unwrap_or_default()
             } else {
                 Vec::new()
             };
@@ -534,49 +1091,35 @@ impl Memory for SqliteMemory {
             // instead of N round-trips (N+1 pattern).
             let mut results = Vec::new();
             if !merged.is_empty() {
-                let placeholders: String = (1..=merged.len())
+                let placeholders: String = (2..=merged.len() + 1)
                     .map(|i| format!("?{i}"))
                     .collect::<Vec<_>>()
                     .join(", ");
                 let sql = format!(
-                    "SELECT id, key, content, category, created_at, session_id \
-                     FROM memories WHERE id IN ({placeholders})"
+                    "SELECT {ENTRY_COLUMNS} FROM memories
+                     WHERE namespace = ?1 AND id IN ({placeholders})"
                 );
                 let mut stmt = conn.prepare(&sql)?;
-                let id_params: Vec<Box<dyn rusqlite::types::ToSql>> = merged
-                    .iter()
-                    .map(|s| Box::new(s.id.clone()) as Box<dyn rusqlite::types::ToSql>)
-                    .collect();
+                let mut id_params: Vec<Box<dyn rusqlite::types::ToSql>> =
+                    vec![Box::new(namespace.clone())];
+                id_params.extend(
+                    merged
+                        .iter()
+                        .map(|s| Box::new(s.id.clone()) as Box<dyn rusqlite::types::ToSql>),
+                );
                 let params_ref: Vec<&dyn rusqlite::types::ToSql> =
                     id_params.iter().map(AsRef::as_ref).collect();
-                let rows = stmt.query_map(params_ref.as_slice(), |row| {
-                    Ok((
-                        row.get::<_, String>(0)?,
-                        row.get::<_, String>(1)?,
-                        row.get::<_, String>(2)?,
-                        row.get::<_, String>(3)?,
-                        row.get::<_, String>(4)?,
-                        row.get::<_, Option<String>>(5)?,
-                    ))
-                })?;
+                let rows = stmt.query_map(params_ref.as_slice(), Self::row_to_entry)?;
 
                 let mut entry_map = std::collections::HashMap::new();
                 for row in rows {
-                    let (id, key, content, cat, ts, sid) = row?;
-                    entry_map.insert(id, (key, content, cat, ts, sid));
+                    let entry = row?;
+                    entry_map.insert(entry.id.clone(), entry);
                 }
 
                 for scored in &merged {
-                    if let Some((key, content, cat, ts, sid)) = entry_map.remove(&scored.id) {
-                        let entry = MemoryEntry {
-                            id: scored.id.clone(),
-                            key,
-                            content,
-                            category: Self::str_to_category(&cat),
-                            timestamp: ts,
-                            session_id: sid,
-                            score: Some(f64::from(scored.final_score)),
-                        };
+                    if let Some(mut entry) = entry_map.remove(&scored.id) {
+                        entry.score = Some(f64::from(scored.final_score));
                         if let Some(filter_sid) = session_ref {
                             if entry.session_id.as_deref() != Some(filter_sid) {
                                 continue;
@@ -607,10 +1150,11 @@ impl Memory for SqliteMemory {
                         .collect();
                     let where_clause = conditions.join(" OR ");
                     let sql = format!(
-                        "SELECT id, key, content, category, created_at, session_id FROM memories
-                         WHERE {where_clause}
+                        "SELECT {ENTRY_COLUMNS} FROM memories
+                         WHERE namespace = ?{} AND ({where_clause})
                          ORDER BY updated_at DESC
                          LIMIT ?{}",
+                        keywords.len() * 2 + 2,
                         keywords.len() * 2 + 1
                     );
                     let mut stmt = conn.prepare(&sql)?;
@@ -621,18 +1165,13 @@ impl Memory for SqliteMemory {
                     }
                     #[allow(clippy::cast_possible_wrap)]
                     param_values.push(Box::new(limit as i64));
+                    param_values.push(Box::new(namespace.clone()));
                     let params_ref: Vec<&dyn rusqlite::types::ToSql> =
                         param_values.iter().map(AsRef::as_ref).collect();
                     let rows = stmt.query_map(params_ref.as_slice(), |row| {
-                        Ok(MemoryEntry {
-                            id: row.get(0)?,
-                            key: row.get(1)?,
-                            content: row.get(2)?,
-                            category: Self::str_to_category(&row.get::<_, String>(3)?),
-                            timestamp: row.get(4)?,
-                            session_id: row.get(5)?,
-                            score: Some(1.0),
-                        })
+                        let mut entry = Self::row_to_entry(row)?;
+                        entry.score = Some(1.0);
+                        Ok(entry)
                     })?;
                     for row in rows {
                         let entry = row?;
@@ -646,7 +1185,22 @@ impl Memory for SqliteMemory {
                 }
             }
 
+            // Drop expired entries, then rank by relevance weighted with
+            // importance, access frequency and recency.
+            let now = Utc::now();
+            results.retain(|entry| !entry.is_expired(now));
+            for entry in &mut results {
+                let weight = entry.recall_weight(now);
+                entry.score = entry.score.map(|score| score * weight);
+            }
+            results.sort_by(|a, b| b.score.unwrap_or(0.0).total_cmp(&a.score.unwrap_or(0.0)));
             results.truncate(limit);
+
+            Self::record_access(
+                &conn,
+                &results,
+                &now.to_rfc3339_opts(SecondsFormat::Secs, true),
+            )?;
             Ok(results)
         })
         .await?
@@ -655,27 +1209,18 @@ impl Memory for SqliteMemory {
     async fn get(&self, key: &str) -> anyhow::Result<Option<MemoryEntry>> {
         let conn = self.conn.clone();
         let key = key.to_string();
+        let namespace = self.namespace.clone();
 
         tokio::task::spawn_blocking(move || -> anyhow::Result<Option<MemoryEntry>> {
             let conn = conn.lock();
-            let mut stmt = conn.prepare(
-                "SELECT id, key, content, category, created_at, session_id FROM memories WHERE key = ?1",
-            )?;
+            let mut stmt = conn.prepare(&format!(
+                "SELECT {ENTRY_COLUMNS} FROM memories WHERE namespace = ?1 AND key = ?2"
+            ))?;
 
-            let mut rows = stmt.query_map(params![key], |row| {
-                Ok(MemoryEntry {
-                    id: row.get(0)?,
-                    key: row.get(1)?,
-                    content: row.get(2)?,
-                    category: Self::str_to_category(&row.get::<_, String>(3)?),
-                    timestamp: row.get(4)?,
-                    session_id: row.get(5)?,
-                    score: None,
-                })
-            })?;
+            let mut rows = stmt.query_map(params![namespace, key], Self::row_to_entry)?;
 
             match rows.next() {
-                Some(Ok(entry)) => Ok(Some(entry)),
+                Some(Ok(entry)) if !entry.is_expired(Utc::now()) => Ok(Some(entry)),
                 _ => Ok(None),
             }
         })
@@ -692,31 +1237,24 @@ impl Memory for SqliteMemory {
         let conn = self.conn.clone();
         let category = category.cloned();
         let session_id = session_id.map(String::from);
+        let namespace = self.namespace.clone();
 
         tokio::task::spawn_blocking(move || -> anyhow::Result<Vec<MemoryEntry>> {
             let conn = conn.lock();
             let session_ref = session_id.as_deref();
+            let now = Utc::now();
             let mut results = Vec::new();
 
-            let row_mapper = |row: &rusqlite::Row| -> rusqlite::Result<MemoryEntry> {
-                Ok(MemoryEntry {
-                    id: row.get(0)?,
-                    key: row.get(1)?,
-                    content: row.get(2)?,
-                    category: Self::str_to_category(&row.get::<_, String>(3)?),
-                    timestamp: row.get(4)?,
-                    session_id: row.get(5)?,
-                    score: None,
-                })
-            };
+            let row_mapper = Self::row_to_entry;
 
             if let Some(ref cat) = category {
                 let cat_str = Self::category_to_str(cat);
-                let mut stmt = conn.prepare(
-                    "SELECT id, key, content, category, created_at, session_id FROM memories
-                     WHERE category = ?1 ORDER BY updated_at DESC LIMIT ?2",
-                )?;
-                let rows = stmt.query_map(params![cat_str, DEFAULT_LIST_LIMIT], row_mapper)?;
+                let mut stmt = conn.prepare(&format!(
+                    "SELECT {ENTRY_COLUMNS} FROM memories
+                     WHERE namespace = ?1 AND category = ?2 ORDER BY updated_at DESC LIMIT ?3"
+                ))?;
+                let rows =
+                    stmt.query_map(params![namespace, cat_str, DEFAULT_LIST_LIMIT], row_mapper)?;
                 for row in rows {
                     let entry = row?;
                     if let Some(sid) = session_ref {
@@ -724,14 +1262,16 @@ impl Memory for SqliteMemory {
                             continue;
                         }
                     }
-                    results.push(entry);
+                    if !entry.is_expired(now) {
+                        results.push(entry);
+                    }
                 }
             } else {
-                let mut stmt = conn.prepare(
-                    "SELECT id, key, content, category, created_at, session_id FROM memories
-                     ORDER BY updated_at DESC LIMIT ?1",
-                )?;
-                let rows = stmt.query_map(params![DEFAULT_LIST_LIMIT], row_mapper)?;
+                let mut stmt = conn.prepare(&format!(
+                    "SELECT {ENTRY_COLUMNS} FROM memories
+                     WHERE namespace = ?1 ORDER BY updated_at DESC LIMIT ?2"
+                ))?;
+                let rows = stmt.query_map(params![namespace, DEFAULT_LIST_LIMIT], row_mapper)?;
                 for row in rows {
                     let entry = row?;
                     if let Some(sid) = session_ref {
@@ -739,7 +1279,9 @@ impl Memory for SqliteMemory {
                             continue;
                         }
                     }
-                    results.push(entry);
+                    if !entry.is_expired(now) {
+                        results.push(entry);
+                    }
                 }
             }
 
@@ -750,11 +1292,23 @@ impl Memory for SqliteMemory {
 
     async fn forget(&self, key: &str) -> anyhow::Result<bool> {
         let conn = self.conn.clone();
+        let vector_index = self.vector_index.clone();
         let key = key.to_string();
+        let namespace = self.namespace.clone();
 
         tokio::task::spawn_blocking(move || -> anyhow::Result<bool> {
             let conn = conn.lock();
-            let affected = conn.execute("DELETE FROM memories WHERE key = ?1", params![key])?;
+            let affected = conn.execute(
+                "DELETE FROM memories WHERE namespace = ?1 AND key = ?2",
+                params![namespace, key],
+            )?;
+            Self::prune_vector_log(&conn)?;
+
+            if let Some(index) = vector_index.filter(|_| affected > 0) {
+                if let Err(e) = index.lock().sync(&conn) {
+                    tracing::warn!("vector index sync failed: {e:#}");
+                }
+            }
             Ok(affected > 0)
         })
         .await?
@@ -762,11 +1316,15 @@ impl Memory for SqliteMemory {
 
     async fn count(&self) -> anyhow::Result<usize> {
         let conn = self.conn.clone();
+        let namespace = self.namespace.clone();
 
         tokio::task::spawn_blocking(move || -> anyhow::Result<usize> {
             let conn = conn.lock();
-            let count: i64 =
-                conn.query_row("SELECT COUNT(*) FROM memories", [], |row| row.get(0))?;
+            let count: i64 = conn.query_row(
+                "SELECT COUNT(*) FROM memories WHERE namespace = ?1",
+                params![namespace],
+                |row| row.get(0),
+            )?;
             #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
             Ok(count as usize)
         })
@@ -779,6 +1337,140 @@ impl Memory for SqliteMemory {
             .await
             .unwrap_or(false)
     }
+
+    fn with_namespace(&self, namespace: &str) -> Option<Arc<dyn Memory>> {
+        Some(Arc::new(Self {
+            conn: Arc::clone(&self.conn),
+            db_path: self.db_path.clone(),
+            embedder: Arc::clone(&self.embedder),
+            vector_weight: self.vector_weight,
+            keyword_weight: self.keyword_weight,
+            cache_max: self.cache_max,
+            vector_index: self.vector_index.clone(),
+            namespace: namespace.to_string(),
+        }))
+    }
+
+    async fn reindex(&self) -> anyhow::Result<usize> {
+        SqliteMemory::reindex(self).await
+    }
+
+    /// The shared handle exports every namespace (tagged on each entry) so
+    /// backups are complete; a namespace handle exports only its own.
+    async fn export_entries(
+        &self,
+        with_embeddings: bool,
+    ) -> anyhow::Result<Vec<(MemoryEntry, Option<Vec<f32>>)>> {
+        let conn = self.conn.clone();
+        let namespace = self.namespace.clone();
+
+        tokio::task::spawn_blocking(
+            move || -> anyhow::Result<Vec<(MemoryEntry, Option<Vec<f32>>)>> {
+                let conn = conn.lock();
+                let mut stmt = conn.prepare(&format!(
+                    "SELECT {ENTRY_COLUMNS}, embedding FROM memories
+                     WHERE ?1 = '' OR namespace = ?1 ORDER BY created_at ASC"
+                ))?;
+                let rows = stmt.query_map(params![namespace], |row| {
+                    let entry = Self::row_to_entry(row)?;
+                    let blob: Option<Vec<u8>> = if with_embeddings { row.get(11)? } else { None };
+                    Ok((entry, blob.map(|bytes| vector::bytes_to_vec(&bytes))))
+                })?;
+                rows.collect::<rusqlite::Result<Vec<_>>>()
+                    .map_err(Into::into)
+            },
+        )
+        .await?
+    }
+
+    async fn restore(&self, entry: &MemoryEntry, embedding: Option<&[f32]>) -> anyhow::Result<()> {
+        let dims = self.embedder.dimensions();
+        let embedding_bytes = match embedding {
+            Some(embedding) if dims > 0 && embedding.len() == dims => {
+                Some(vector::vec_to_bytes(embedding))
+            }
+            _ => self
+                .get_or_compute_embedding(&entry.content)
+                .await?
+                .map(|emb| vector::vec_to_bytes(&emb)),
+        };
+
+        let conn = self.conn.clone();
+        let vector_index = self.vector_index.clone();
+        let entry = entry.clone();
+        let expires_at = entry
+            .expires_at
+            .as_deref()
+            .map(traits::normalize_time)
+            .transpose()?;
+        let access_count = i64::try_from(entry.access_count).unwrap_or(i64::MAX);
+        // Entries keep their exported namespace unless restored through a
+        // namespace handle, which owns everything written through it.
+        let namespace = if self.namespace.is_empty() {
+            entry.namespace.clone().unwrap_or_default()
+        } else {
+            self.namespace.clone()
+        };
+
+        tokio::task::spawn_blocking(move || -> anyhow::Result<()> {
+            let conn = conn.lock();
+            let cat = Self::category_to_str(&entry.category);
+
+            // Keep the exported id unless another key already owns it.
+            let id_taken: bool = conn.query_row(
+                "SELECT EXISTS(SELECT 1 FROM memories
+                 WHERE id = ?1 AND NOT (namespace = ?2 AND key = ?3))",
+                params![entry.id, namespace, entry.key],
+                |row| row.get(0),
+            )?;
+            let id = if id_taken || entry.id.is_empty() {
+                Uuid::new_v4().to_string()
+            } else {
+                entry.id
+            };
+
+            conn.execute(
+                "INSERT INTO memories (id, key, content, category, embedding, created_at, updated_at, session_id,
+                                       importance, expires_at, last_accessed, access_count, namespace)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)
+                 ON CONFLICT(namespace, key) DO UPDATE SET
+                    content = excluded.content,
+                    category = excluded.category,
+                    embedding = excluded.embedding,
+                    created_at = excluded.created_at,
+                    updated_at = excluded.updated_at,
+                    session_id = excluded.session_id,
+                    importance = excluded.importance,
+                    expires_at = excluded.expires_at,
+                    last_accessed = excluded.last_accessed,
+                    access_count = excluded.access_count",
+                params![
+                    id,
+                    entry.key,
+                    entry.content,
+                    cat,
+                    embedding_bytes,
+                    entry.timestamp,
+                    entry.timestamp,
+                    entry.session_id,
+                    entry.importance,
+                    expires_at,
+                    entry.last_accessed,
+                    access_count,
+                    namespace
+                ],
+            )?;
+            Self::prune_vector_log(&conn)?;
+
+            if let Some(index) = vector_index {
+                if let Err(e) = index.lock().sync(&conn) {
+                    tracing::warn!("vector index sync failed: {e:#}");
+                }
+            }
+            Ok(())
+        })
+        .await?
+    }
 }
 
 #[cfg(test)]
@@ -1563,6 +2255,262 @@ mod tests {
         assert_eq!(mem.count().await.unwrap(), 1);
     }
 
+    // ── Vector index (HNSW) ──────────────────────────────────────
+
+    const TOPICS: [&str; 4] = ["rust", "cooking", "music", "travel"];
+
+    /// Deterministic embedder: one dimension per topic word.
+    struct TopicEmbedding;
+
+    #[async_trait]
+    impl EmbeddingProvider for TopicEmbedding {
+        fn name(&self) -> &str {
+            "topic"
+        }
+
+        fn dimensions(&self) -> usize {
+            TOPICS.len()
+        }
+
+        async fn embed(&self, texts: &[&str]) -> anyhow::Result<Vec<Vec<f32>>> {
+            Ok(texts
+                .iter()
+                .map(|text| {
+                    let lower = text.to_lowercase();
+                    TOPICS
+                        .iter()
+                        .map(|topic| if lower.contains(topic) { 1.0 } else { 0.05 })
+                        .collect()
+                })
+                .collect())
+        }
+    }
+
+    fn topic_sqlite(dir: &Path) -> SqliteMemory {
+        SqliteMemory::with_embedder(dir, Arc::new(TopicEmbedding), 1.0, 0.0, 100, None).unwrap()
+    }
+
+    fn index_path(dir: &Path) -> PathBuf {
+        dir.join("memory").join(VECTOR_INDEX_FILE)
+    }
+
+    fn indexed_len(mem: &SqliteMemory) -> usize {
+        mem.vector_index
+            .as_ref()
+            .and_then(|index| index.lock().graph.as_ref().map(HnswIndex::len))
+            .unwrap_or_default()
+    }
+
+    async fn top_key(mem: &SqliteMemory, query: &str) -> String {
+        mem.recall(query, 1, None).await.unwrap()[0].key.clone()
+    }
+
+    #[tokio::test]
+    async fn vector_index_follows_store_and_forget() {
+        let tmp = TempDir::new().unwrap();
+        let mem = topic_sqlite(tmp.path());
+        mem.store("a", "rust borrow checker", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        mem.store("b", "cooking pasta", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        mem.store("c", "music theory", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+
+        assert!(!index_path(tmp.path()).exists(), "index is built lazily");
+        assert_eq!(top_key(&mem, "about rust").await, "a");
+        assert!(index_path(tmp.path()).exists());
+        assert_eq!(indexed_len(&mem), 3);
+
+        assert!(mem.forget("a").await.unwrap());
+        assert_eq!(indexed_len(&mem), 2);
+        let results = mem.recall("about rust", 3, None).await.unwrap();
+        assert!(results.iter().all(|e| e.key != "a"));
+
+        mem.store("b", "rust async runtimes", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        assert_eq!(indexed_len(&mem), 2);
+        assert_eq!(top_key(&mem, "about rust").await, "b");
+    }
+
+    #[tokio::test]
+    async fn vector_index_catches_up_with_other_connections() {
+        let tmp = TempDir::new().unwrap();
+        let mem = topic_sqlite(tmp.path());
+        mem.store("a", "rust notes", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        mem.store("b", "cooking notes", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        assert_eq!(top_key(&mem, "cooking").await, "b");
+
+        let other = topic_sqlite(tmp.path()).with_vector_index(false);
+        other
+            .store("c", "travel plans", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        other.forget("b").await.unwrap();
+
+        assert_eq!(top_key(&mem, "travel").await, "c");
+        assert_eq!(indexed_len(&mem), 2);
+        let results = mem.recall("cooking", 5, None).await.unwrap();
+        assert!(results.iter().all(|e| e.key != "b"));
+    }
+
+    #[tokio::test]
+    async fn vector_index_reloads_and_recovers_from_corruption() {
+        let tmp = TempDir::new().unwrap();
+        {
+            let mem = topic_sqlite(tmp.path());
+            mem.store("a", "music playlist", MemoryCategory::Core, None)
+                .await
+                .unwrap();
+            mem.store("b", "travel packing list", MemoryCategory::Core, None)
+                .await
+                .unwrap();
+            assert_eq!(top_key(&mem, "music").await, "a");
+            mem.store("c", "rust lifetimes", MemoryCategory::Core, None)
+                .await
+                .unwrap();
+        }
+        let saved = HnswIndex::load_graph(&index_path(tmp.path())).unwrap();
+        assert_eq!(saved.len(), 3, "drop persists pending updates");
+
+        let mem = topic_sqlite(tmp.path());
+        assert_eq!(top_key(&mem, "rust").await, "c");
+        drop(mem);
+
+        std::fs::write(index_path(tmp.path()), b"garbage").unwrap();
+        let mem = topic_sqlite(tmp.path());
+        assert_eq!(top_key(&mem, "travel").await, "b");
+        assert_eq!(
+            HnswIndex::load_graph(&index_path(tmp.path()))
+                .unwrap()
+                .len(),
+            3
+        );
+    }
+
+    #[tokio::test]
+    async fn vector_index_respects_session_scope() {
+        let tmp = TempDir::new().unwrap();
+        let mem = topic_sqlite(tmp.path());
+        mem.store(
+            "s1",
+            "rust in session one",
+            MemoryCategory::Core,
+            Some("one"),
+        )
+        .await
+        .unwrap();
+        mem.store(
+            "s2",
+            "rust in session two",
+            MemoryCategory::Core,
+            Some("two"),
+        )
+        .await
+        .unwrap();
+
+        let results = mem.recall("rust", 5, Some("one")).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].key, "s1");
+    }
+
+    #[tokio::test]
+    async fn reindex_rebuilds_vector_index() {
+        let tmp = TempDir::new().unwrap();
+        let mem = topic_sqlite(tmp.path());
+        mem.store("a", "rust", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        mem.store("b", "music", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        assert!(!index_path(tmp.path()).exists());
+
+        mem.reindex().await.unwrap();
+        let saved = HnswIndex::load_graph(&index_path(tmp.path())).unwrap();
+        assert_eq!(saved.len(), 2);
+        assert_eq!(top_key(&mem, "music").await, "b");
+    }
+
+    #[tokio::test]
+    async fn flat_vector_index_uses_exact_scan() {
+        let tmp = TempDir::new().unwrap();
+        let mem = topic_sqlite(tmp.path()).with_vector_index(false);
+        mem.store("a", "cooking rice", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        mem.store("b", "travel by train", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        assert_eq!(top_key(&mem, "cooking").await, "a");
+        drop(mem);
+        assert!(!index_path(tmp.path()).exists());
+    }
+
+    /// Records the size of every `embed` call.
+    struct RecordingEmbedding(Arc<Mutex<Vec<usize>>>);
+
+    #[async_trait]
+    impl EmbeddingProvider for RecordingEmbedding {
+        fn name(&self) -> &str {
+            "recording"
+        }
+
+        fn dimensions(&self) -> usize {
+            TOPICS.len()
+        }
+
+        async fn embed(&self, texts: &[&str]) -> anyhow::Result<Vec<Vec<f32>>> {
+            self.0.lock().push(texts.len());
+            TopicEmbedding.embed(texts).await
+        }
+    }
+
+    #[tokio::test]
+    async fn reindex_batches_misses_and_reuses_embedding_cache() {
+        let tmp = TempDir::new().unwrap();
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let mem = SqliteMemory::with_embedder(
+            tmp.path(),
+            Arc::new(RecordingEmbedding(calls.clone())),
+            1.0,
+            0.0,
+            100,
+            None,
+        )
+        .unwrap();
+        mem.store("a", "rust", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        {
+            // Rows written without embeddings, plus one whose vector was dropped.
+            let conn = mem.conn.lock();
+            for (key, content) in [("b", "music"), ("c", "cooking"), ("d", "travel")] {
+                conn.execute(
+                    "INSERT INTO memories (id, key, content, category, created_at, updated_at)
+                     VALUES (?1, ?1, ?2, 'core', '2026-01-01T00:00:00Z', '2026-01-01T00:00:00Z')",
+                    params![key, content],
+                )
+                .unwrap();
+            }
+            conn.execute("UPDATE memories SET embedding = NULL WHERE key = 'a'", [])
+                .unwrap();
+        }
+        calls.lock().clear();
+
+        assert_eq!(mem.reindex().await.unwrap(), 4);
+        // "rust" came from the cache; the three misses went out in one call.
+        assert_eq!(*calls.lock(), vec![3]);
+        assert_eq!(top_key(&mem, "cooking").await, "c");
+    }
+
     // ── Edge cases: reindex ──────────────────────────────────────
 
     #[tokio::test]
@@ -1897,4 +2845,297 @@ mod tests {
 
         assert_eq!(mem.count().await.unwrap(), 1);
     }
+
+    // ── Importance, expiry and access metadata ───────────────────
+
+    fn metadata(importance: Option<f64>, expires_at: Option<String>) -> MemoryMetadata {
+        MemoryMetadata {
+            importance,
+            expires_at,
+        }
+    }
+
+    #[tokio::test]
+    async fn metadata_persists_and_survives_content_updates() {
+        let (_tmp, mem) = temp_sqlite();
+        let expires_at = (Utc::now() + chrono::Duration::days(2)).to_rfc3339();
+        mem.store_with_metadata(
+            "trip",
+            "Flight to Lisbon on Friday",
+            MemoryCategory::Core,
+            None,
+            &metadata(Some(0.8), Some(expires_at)),
+        )
+        .await
+        .unwrap();
+
+        let entry = mem.get("trip").await.unwrap().unwrap();
+        assert_eq!(entry.importance, Some(0.8));
+        assert!(entry.expires_at.as_deref().unwrap().ends_with('Z'));
+        assert_eq!(entry.access_count, 0);
+
+        mem.recall("Lisbon", 5, None).await.unwrap();
+        mem.store(
+            "trip",
+            "Flight to Lisbon moved to Saturday",
+            MemoryCategory::Core,
+            None,
+        )
+        .await
+        .unwrap();
+        let entry = mem.get("trip").await.unwrap().unwrap();
+        assert_eq!(entry.access_count, 1, "access stats belong to the key");
+        assert!(entry.last_accessed.is_some());
+        assert_eq!(entry.importance, None, "a plain store replaces metadata");
+    }
+
+    #[tokio::test]
+    async fn expired_entries_are_hidden() {
+        let (_tmp, mem) = temp_sqlite();
+        let past = (Utc::now() - chrono::Duration::minutes(5)).to_rfc3339();
+        mem.store_with_metadata(
+            "promo",
+            "Discount code SPRING",
+            MemoryCategory::Core,
+            None,
+            &metadata(None, Some(past)),
+        )
+        .await
+        .unwrap();
+
+        assert!(mem.get("promo").await.unwrap().is_none());
+        assert!(mem.list(None, None).await.unwrap().is_empty());
+        assert!(mem.recall("Discount", 5, None).await.unwrap().is_empty());
+        assert_eq!(
+            mem.count().await.unwrap(),
+            1,
+            "rows stay until hygiene purges them"
+        );
+    }
+
+    #[tokio::test]
+    async fn recall_ranks_important_entries_first() {
+        let (_tmp, mem) = temp_sqlite();
+        mem.store_with_metadata(
+            "minor",
+            "coffee order oat latte",
+            MemoryCategory::Core,
+            None,
+            &metadata(Some(0.0), None),
+        )
+        .await
+        .unwrap();
+        mem.store_with_metadata(
+            "major",
+            "coffee allergy severe reaction",
+            MemoryCategory::Core,
+            None,
+            &metadata(Some(1.0), None),
+        )
+        .await
+        .unwrap();
+
+        let results = mem.recall("coffee", 5, None).await.unwrap();
+        assert_eq!(results[0].key, "major");
+        assert!(results[0].score.unwrap() > results[1].score.unwrap());
+    }
+
+    #[tokio::test]
+    async fn store_rejects_out_of_range_importance() {
+        let (_tmp, mem) = temp_sqlite();
+        let result = mem
+            .store_with_metadata(
+                "k",
+                "v",
+                MemoryCategory::Core,
+                None,
+                &metadata(Some(-0.1), None),
+            )
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn metadata_columns_are_added_to_existing_databases() {
+        let tmp = TempDir::new().unwrap();
+        let db_dir = tmp.path().join("memory");
+        std::fs::create_dir_all(&db_dir).unwrap();
+        let conn = Connection::open(db_dir.join("brain.db")).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE memories (
+                id TEXT PRIMARY KEY, key TEXT NOT NULL UNIQUE, content TEXT NOT NULL,
+                category TEXT NOT NULL DEFAULT 'core', embedding BLOB,
+                created_at TEXT NOT NULL, updated_at TEXT NOT NULL);
+             INSERT INTO memories VALUES ('1', 'old', 'legacy row', 'core', NULL,
+                '2026-01-01T00:00:00Z', '2026-01-01T00:00:00Z');",
+        )
+        .unwrap();
+        drop(conn);
+
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        let entry = mem.get("old").await.unwrap().unwrap();
+        assert_eq!(entry.importance, None);
+        assert_eq!(entry.access_count, 0);
+    }
+
+    // ── Tenant namespaces ────────────────────────────────────────
+
+    #[tokio::test]
+    async fn namespaces_isolate_reads_and_writes() {
+        let (_tmp, mem) = temp_sqlite();
+        let alice = mem.with_namespace("telegram:alice").unwrap();
+        let bob = mem.with_namespace("telegram:bob").unwrap();
+        alice
+            .store(
+                "allergy",
+                "Alice is allergic to peanuts",
+                MemoryCategory::Core,
+                None,
+            )
+            .await
+            .unwrap();
+        mem.store(
+            "motto",
+            "Shared motto about peanuts",
+            MemoryCategory::Core,
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert!(bob.get("allergy").await.unwrap().is_none());
+        assert!(bob.recall("peanuts", 10, None).await.unwrap().is_empty());
+        assert!(bob.list(None, None).await.unwrap().is_empty());
+        assert!(!bob.forget("allergy").await.unwrap());
+        assert_eq!(bob.count().await.unwrap(), 0);
+
+        let shared = mem.recall("peanuts", 10, None).await.unwrap();
+        assert_eq!(shared.len(), 1);
+        assert_eq!(shared[0].key, "motto");
+        assert_eq!(mem.count().await.unwrap(), 1);
+
+        let recalled = alice.recall("peanuts", 10, None).await.unwrap();
+        assert_eq!(recalled.len(), 1);
+        assert_eq!(recalled[0].namespace.as_deref(), Some("telegram:alice"));
+        assert!(alice.forget("allergy").await.unwrap());
+        assert!(alice.get("allergy").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn same_key_is_independent_per_namespace() {
+        let (_tmp, mem) = temp_sqlite();
+        let alice = mem.with_namespace("discord:alice").unwrap();
+        let bob = mem.with_namespace("discord:bob").unwrap();
+        alice
+            .store("city", "Lives in Berlin", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        bob.store("city", "Lives in Lisbon", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            alice.get("city").await.unwrap().unwrap().content,
+            "Lives in Berlin"
+        );
+        assert_eq!(
+            bob.get("city").await.unwrap().unwrap().content,
+            "Lives in Lisbon"
+        );
+        assert!(mem.get("city").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn vector_index_respects_namespaces() {
+        let tmp = TempDir::new().unwrap();
+        let mem = topic_sqlite(tmp.path());
+        let tenant = mem.with_namespace("slack").unwrap();
+        mem.store("shared", "rust notes", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        tenant
+            .store("private", "rust secrets", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+
+        let shared = mem.recall("rust", 5, None).await.unwrap();
+        assert_eq!(shared.len(), 1);
+        assert_eq!(shared[0].key, "shared");
+        let private = tenant.recall("rust", 5, None).await.unwrap();
+        assert_eq!(private.len(), 1);
+        assert_eq!(private[0].key, "private");
+    }
+
+    #[tokio::test]
+    async fn export_and_restore_keep_namespaces() {
+        let (_tmp, mem) = temp_sqlite();
+        let tenant = mem.with_namespace("telegram:alice").unwrap();
+        mem.store("shared", "shared fact", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        tenant
+            .store("private", "private fact", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+
+        let exported = mem.export_entries(false).await.unwrap();
+        assert_eq!(exported.len(), 2);
+        assert_eq!(tenant.export_entries(false).await.unwrap().len(), 1);
+
+        let (_tmp2, target) = temp_sqlite();
+        for (entry, embedding) in &exported {
+            target.restore(entry, embedding.as_deref()).await.unwrap();
+        }
+        assert!(target.get("private").await.unwrap().is_none());
+        let restored = target.with_namespace("telegram:alice").unwrap();
+        assert_eq!(
+            restored.get("private").await.unwrap().unwrap().content,
+            "private fact"
+        );
+    }
+
+    #[tokio::test]
+    async fn legacy_databases_migrate_to_per_namespace_keys() {
+        let tmp = TempDir::new().unwrap();
+        let db_dir = tmp.path().join("memory");
+        std::fs::create_dir_all(&db_dir).unwrap();
+        let conn = Connection::open(db_dir.join("brain.db")).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE memories (
+                id TEXT PRIMARY KEY, key TEXT NOT NULL UNIQUE, content TEXT NOT NULL,
+                category TEXT NOT NULL DEFAULT 'core', embedding BLOB,
+                created_at TEXT NOT NULL, updated_at TEXT NOT NULL, session_id TEXT);
+             CREATE VIRTUAL TABLE memories_fts USING fts5(
+                key, content, content=memories, content_rowid=rowid);
+             INSERT INTO memories (rowid, id, key, content, category, created_at, updated_at)
+                VALUES (7, '1', 'pet', 'legacy hamster', 'core',
+                        '2026-01-01T00:00:00Z', '2026-01-01T00:00:00Z');
+             INSERT INTO memories_fts (rowid, key, content) VALUES (7, 'pet', 'legacy hamster');",
+        )
+        .unwrap();
+        drop(conn);
+
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        assert_eq!(
+            mem.get("pet").await.unwrap().unwrap().content,
+            "legacy hamster"
+        );
+        let tenant = mem.with_namespace("irc:carol").unwrap();
+        tenant
+            .store("pet", "carol has a parrot", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        assert_eq!(tenant.recall("parrot", 5, None).await.unwrap().len(), 1);
+
+        let conn = mem.conn.lock();
+        let fts_hits: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM memories_fts f JOIN memories m ON m.rowid = f.rowid
+                 WHERE memories_fts MATCH 'hamster'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(fts_hits, 1);
+    }
 }
@@ -0,0 +1,122 @@
+//! Per-tenant memory namespaces.
+//!
+//! When one daemon serves many chat users, `memory.isolation` decides which
+//! tenant a message belongs to. The channel loop reads and writes through
+//! [`scoped`] and runs the tool loop inside [`with_namespace`], so the memory
+//! tools resolve the same tenant via [`current`].
+
+use super::namespaced::NamespacedMemory;
+use super::traits::Memory;
+use crate::config::MemoryIsolation;
+use std::future::Future;
+use std::sync::Arc;
+
+tokio::task_local! {
+    static CURRENT_NAMESPACE: Option<String>;
+}
+
+/// Tenant namespace for a message from `sender` on `channel`; `None` is the
+/// shared namespace.
+pub fn namespace_for(isolation: MemoryIsolation, channel: &str, sender: &str) -> Option<String> {
+    match isolation {
+        MemoryIsolation::Shared => None,
+        MemoryIsolation::PerSender => Some(format!("{channel}:{sender}")),
+        MemoryIsolation::PerChannel => Some(channel.to_string()),
+    }
+}
+
+/// `memory` confined to `namespace`. Backends without native namespaces
+/// fall back to `NamespacedMemory` key prefixing.
+pub fn scoped(memory: &Arc<dyn Memory>, namespace: Option<&str>) -> Arc<dyn Memory> {
+    match namespace.filter(|namespace| !namespace.is_empty()) {
+        None => Arc::clone(memory),
+        Some(namespace) => memory
+            .with_namespace(namespace)
+            .unwrap_or_else(|| Arc::new(NamespacedMemory::new(Arc::clone(memory), namespace))),
+    }
+}
+
+/// Run `fut` with `namespace` as the tenant the memory tools act for.
+pub async fn with_namespace<F: Future>(namespace: Option<String>, fut: F) -> F::Output {
+    CURRENT_NAMESPACE.scope(namespace, fut).await
+}
+
+/// Tenant namespace of the current task, if one was set.
+pub fn current_namespace() -> Option<String> {
+    CURRENT_NAMESPACE.try_with(Clone::clone).ok().flatten()
+}
+
+/// `memory` confined to the current task's tenant namespace.
+pub fn current(memory: &Arc<dyn Memory>) -> Arc<dyn Memory> {
+    scoped(memory, current_namespace().as_deref())
+}
+
+/// File-system safe, collision-free directory name for a namespace.
+pub fn directory_name(namespace: &str) -> String {
+    use sha2::{Digest, Sha256};
+    let readable: String = namespace
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(48)
+        .collect();
+    let hash = Sha256::digest(namespace.as_bytes());
+    format!("{readable}-{}", hex::encode(&hash[..4]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::{MemoryCategory, SqliteMemory};
+    use tempfile::TempDir;
+
+    #[test]
+    fn namespace_follows_isolation_mode() {
+        assert_eq!(
+            namespace_for(MemoryIsolation::Shared, "telegram", "alice"),
+            None
+        );
+        assert_eq!(
+            namespace_for(MemoryIsolation::PerSender, "telegram", "alice").as_deref(),
+            Some("telegram:alice")
+        );
+        assert_eq!(
+            namespace_for(MemoryIsolation::PerChannel, "telegram", "alice").as_deref(),
+            Some("telegram")
+        );
+    }
+
+    #[test]
+    fn directory_names_are_safe_and_distinct() {
+        let colon = directory_name("telegram:alice");
+        let underscore = directory_name("telegram_alice");
+        assert!(colon.starts_with("telegram_alice-"));
+        assert_ne!(colon, underscore);
+        assert!(!directory_name("../etc").contains('/'));
+    }
+
+    #[tokio::test]
+    async fn current_uses_task_namespace() {
+        let tmp = TempDir::new().unwrap();
+        let root: Arc<dyn Memory> = Arc::new(SqliteMemory::new(tmp.path()).unwrap());
+
+        with_namespace(Some("discord:bob".into()), async {
+            current(&root)
+                .store("pet", "Bob has a cat", MemoryCategory::Core, None)
+                .await
+                .unwrap();
+        })
+        .await;
+
+        assert!(root.get("pet").await.unwrap().is_none());
+        assert_eq!(current_namespace(), None);
+        let bob = scoped(&root, Some("discord:bob"));
+        let entry = bob.get("pet").await.unwrap().unwrap();
+        assert_eq!(entry.namespace.as_deref(), Some("discord:bob"));
+    }
+}
@@ -1,5 +1,16 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Importance assumed for entries stored without one.
+pub const DEFAULT_IMPORTANCE: f64 = 0.5;
+/// Days after which an untouched entry's recency boost has halved.
+const RECENCY_HALF_LIFE_DAYS: f64 = 30.0;
+/// Floor of the recency factor, so old facts fade but never vanish.
+const MIN_RECENCY_WEIGHT: f64 = 0.75;
+/// Cap of the access-count boost.
+const MAX_ACCESS_WEIGHT: f64 = 1.5;
 
 /// A single memory entry
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,6 +22,88 @@ pub struct MemoryEntry {
     pub timestamp: String,
     pub session_id: Option<String>,
     pub score: Option<f64>,
+    /// 0.0 (throwaway) to 1.0 (critical); `None` ranks as `DEFAULT_IMPORTANCE`
+    #[serde(default)]
+    pub importance: Option<f64>,
+    /// RFC 3339 time after which the entry is hidden and purged by hygiene
+    #[serde(default)]
+    pub expires_at: Option<String>,
+    /// RFC 3339 time the entry was last returned by `recall`
+    #[serde(default)]
+    pub last_accessed: Option<String>,
+    /// How many times `recall` has returned the entry
+    #[serde(default)]
+    pub access_count: u64,
+    /// Tenant the entry belongs to; `None` is the shared namespace
+    #[serde(default)]
+    pub namespace: Option<String>,
+}
+
+impl MemoryEntry {
+    /// Whether `expires_at` has passed. Unparseable expiry times never expire.
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at
+            .as_deref()
+            .and_then(|value| DateTime::parse_from_rfc3339(value).ok())
+            .is_some_and(|expires_at| expires_at <= now)
+    }
+
+    /// Multiplier applied to a recall score: importance scales it by
+    /// 0.5..1.5, frequent access boosts it up to `MAX_ACCESS_WEIGHT`, and it
+    /// decays towards `MIN_RECENCY_WEIGHT` as the entry goes untouched.
+    pub fn recall_weight(&self, now: DateTime<Utc>) -> f64 {
+        let importance = self
+            .importance
+            .unwrap_or(DEFAULT_IMPORTANCE)
+            .clamp(0.0, 1.0);
+
+        #[allow(clippy::cast_precision_loss)]
+        let access = (1.0 + 0.1 * (self.access_count as f64).ln_1p()).min(MAX_ACCESS_WEIGHT);
+
+        let touched = self.last_accessed.as_deref().unwrap_or(&self.timestamp);
+        let recency = DateTime::parse_from_rfc3339(touched).map_or(1.0, |touched| {
+            #[allow(clippy::cast_precision_loss)]
+            let days = (now - touched.with_timezone(&Utc)).num_seconds().max(0) as f64 / 86_400.0;
+            MIN_RECENCY_WEIGHT
+                + (1.0 - MIN_RECENCY_WEIGHT) * 0.5_f64.powf(days / RECENCY_HALF_LIFE_DAYS)
+        });
+
+        (0.5 + importance) * access * recency
+    }
+}
+
+/// Optional metadata supplied when storing an entry.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MemoryMetadata {
+    /// 0.0 (throwaway) to 1.0 (critical)
+    pub importance: Option<f64>,
+    /// RFC 3339 expiry time
+    pub expires_at: Option<String>,
+}
+
+impl MemoryMetadata {
+    /// Check `importance` is within 0.0..=1.0 and rewrite `expires_at` as
+    /// UTC (`...Z`), so backends can compare expiry times as text.
+    pub fn normalized(&self) -> anyhow::Result<Self> {
+        if let Some(importance) = self.importance {
+            if !(0.0..=1.0).contains(&importance) {
+                anyhow::bail!("memory importance must be between 0.0 and 1.0, got {importance}");
+            }
+        }
+        Ok(Self {
+            importance: self.importance,
+            expires_at: self.expires_at.as_deref().map(normalize_time).transpose()?,
+        })
+    }
+}
+
+/// Parse an RFC 3339 time and format it as UTC with second precision.
+pub fn normalize_time(value: &str) -> anyhow::Result<String> {
+    let time = DateTime::parse_from_rfc3339(value.trim())
+        .map_err(|e| anyhow::anyhow!("invalid RFC 3339 time '{value}': {e}"))?;
+    Ok(time
+        .with_timezone(&Utc)
+        .to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
 }
 
 /// Memory categories for organization
@@ -53,6 +146,20 @@ pub trait Memory: Send + Sync {
         session_id: Option<&str>,
     ) -> anyhow::Result<()>;
 
+    /// Store a memory entry with importance/expiry metadata. Backends that
+    /// don't persist metadata store the entry without it.
+    async fn store_with_metadata(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+        metadata: &MemoryMetadata,
+    ) -> anyhow::Result<()> {
+        let _ = metadata;
+        self.store(key, content, category, session_id).await
+    }
+
     /// Recall memories matching a query (keyword search), optionally scoped to a session
     async fn recall(
         &self,
@@ -79,6 +186,55 @@ pub trait Memory: Send + Sync {
 
     /// Health check
     async fn health_check(&self) -> bool;
+
+    /// A handle that reads and writes only the given tenant namespace.
+    /// Entries stored through it are invisible to every other namespace,
+    /// including the shared one this handle uses. Backends without native
+    /// namespaces return `None`; use `memory::tenant::scoped`, which falls
+    /// back to key prefixing.
+    fn with_namespace(&self, namespace: &str) -> Option<Arc<dyn Memory>> {
+        let _ = namespace;
+        None
+    }
+
+    /// Rebuild search indexes and embed entries that have no embedding yet.
+    /// Returns how many entries were (re-)embedded.
+    async fn reindex(&self) -> anyhow::Result<usize> {
+        anyhow::bail!(
+            "memory backend '{}' does not support reindexing",
+            self.name()
+        )
+    }
+
+    /// Every stored entry for a portable export, with its stored embedding
+    /// when `with_embeddings` is set and the backend keeps one. Unlike `list`
+    /// this must not truncate.
+    async fn export_entries(
+        &self,
+        with_embeddings: bool,
+    ) -> anyhow::Result<Vec<(MemoryEntry, Option<Vec<f32>>)>> {
+        let _ = with_embeddings;
+        Ok(self
+            .list(None, None)
+            .await?
+            .into_iter()
+            .map(|entry| (entry, None))
+            .collect())
+    }
+
+    /// Write back an exported entry, keeping its id, timestamp and session
+    /// where the backend can. A supplied embedding is reused instead of
+    /// re-embedding the content.
+    async fn restore(&self, entry: &MemoryEntry, embedding: Option<&[f32]>) -> anyhow::Result<()> {
+        let _ = embedding;
+        self.store(
+            &entry.key,
+            &entry.content,
+            entry.category.clone(),
+            entry.session_id.as_deref(),
+        )
+        .await
+    }
 }
 
 #[cfg(test)]
@@ -117,6 +273,11 @@ mod tests {
             timestamp: "2026-02-16T00:00:00Z".into(),
             session_id: Some("session-abc".into()),
             score: Some(0.98),
+            importance: Some(0.9),
+            expires_at: None,
+            last_accessed: None,
+            access_count: 3,
+            namespace: Some("telegram:alice".into()),
         };
 
         let json = serde_json::to_string(&entry).unwrap();
@@ -128,5 +289,90 @@ mod tests {
         assert_eq!(parsed.category, MemoryCategory::Core);
         assert_eq!(parsed.session_id.as_deref(), Some("session-abc"));
         assert_eq!(parsed.score, Some(0.98));
+        assert_eq!(parsed.importance, Some(0.9));
+        assert_eq!(parsed.access_count, 3);
+        assert_eq!(parsed.namespace.as_deref(), Some("telegram:alice"));
+    }
+
+    #[test]
+    fn memory_entry_deserializes_without_metadata_fields() {
+        let parsed: MemoryEntry = serde_json::from_str(
+            r#"{"id":"1","key":"k","content":"c","category":"core","timestamp":"2026-02-16T00:00:00Z","session_id":null,"score":null}"#,
+        )
+        .unwrap();
+        assert_eq!(parsed.importance, None);
+        assert_eq!(parsed.access_count, 0);
+        assert_eq!(parsed.namespace, None);
+    }
+
+    fn weighted_entry(importance: Option<f64>, access_count: u64, touched: &str) -> MemoryEntry {
+        MemoryEntry {
+            id: "id".into(),
+            key: "key".into(),
+            content: "content".into(),
+            category: MemoryCategory::Core,
+            timestamp: touched.into(),
+            session_id: None,
+            score: None,
+            importance,
+            expires_at: None,
+            last_accessed: None,
+            access_count,
+            namespace: None,
+        }
+    }
+
+    #[test]
+    fn recall_weight_favours_important_frequent_and_recent_entries() {
+        let now = DateTime::parse_from_rfc3339("2026-03-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let recent = "2026-03-01T00:00:00Z";
+        let old = "2025-03-01T00:00:00Z";
+
+        let baseline = weighted_entry(None, 0, recent).recall_weight(now);
+        assert!((baseline - 1.0).abs() < 1e-9);
+        assert!(weighted_entry(Some(1.0), 0, recent).recall_weight(now) > baseline);
+        assert!(weighted_entry(Some(0.0), 0, recent).recall_weight(now) < baseline);
+        assert!(weighted_entry(None, 20, recent).recall_weight(now) > baseline);
+        assert!(weighted_entry(None, 1_000_000, recent).recall_weight(now) <= MAX_ACCESS_WEIGHT);
+
+        let stale = weighted_entry(None, 0, old).recall_weight(now);
+        assert!(stale < baseline && stale >= MIN_RECENCY_WEIGHT);
+    }
+
+    #[test]
+    fn metadata_normalization_validates_and_converts_to_utc() {
+        let metadata = MemoryMetadata {
+            importance: Some(0.8),
+            expires_at: Some("2026-03-01T02:00:00+02:00".into()),
+        }
+        .normalized()
+        .unwrap();
+        assert_eq!(metadata.expires_at.as_deref(), Some("2026-03-01T00:00:00Z"));
+
+        let too_important = MemoryMetadata {
+            importance: Some(1.5),
+            expires_at: None,
+        };
+        assert!(too_important.normalized().is_err());
+        let bad_time = MemoryMetadata {
+            importance: None,
+            expires_at: Some("tomorrow".into()),
+        };
+        assert!(bad_time.normalized().is_err());
+    }
+
+    #[test]
+    fn expiry_is_checked_against_now() {
+        let now = DateTime::parse_from_rfc3339("2026-03-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let mut entry = weighted_entry(None, 0, "2026-02-01T00:00:00Z");
+        assert!(!entry.is_expired(now));
+        entry.expires_at = Some("2026-02-28T23:59:59+00:00".into());
+        assert!(entry.is_expired(now));
+        entry.expires_at = Some("2026-03-02T00:00:00Z".into());
+        assert!(!entry.is_expired(now));
     }
 }
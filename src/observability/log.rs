@@ -47,6 +47,9 @@ impl Observer for LogObserver {
             ObserverEvent::HeartbeatTick => {
                 info!("heartbeat.tick");
             }
+            ObserverEvent::CacheLookup { hit, semantic } => {
+                info!(hit = hit, semantic = semantic, "response_cache.lookup");
+            }
             ObserverEvent::Error { component, message } => {
                 info!(component = %component, error = %message, "error");
             }
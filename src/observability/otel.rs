@@ -186,7 +186,8 @@ impl Observer for OtelObserver {
             }
             ObserverEvent::LlmRequest { .. }
             | ObserverEvent::ToolCallStart { .. }
-            | ObserverEvent::TurnComplete => {}
+            | ObserverEvent::TurnComplete
+            | ObserverEvent::CacheLookup { .. } => {}
             ObserverEvent::LlmResponse {
                 provider,
                 model,
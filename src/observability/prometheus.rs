@@ -12,6 +12,7 @@ pub struct PrometheusObserver {
     tool_calls: IntCounterVec,
     channel_messages: IntCounterVec,
     heartbeat_ticks: prometheus::IntCounter,
+    cache_lookups: IntCounterVec,
     errors: IntCounterVec,
 
     // Histograms
@@ -51,6 +52,15 @@ impl PrometheusObserver {
             prometheus::IntCounter::new("zeroclaw_heartbeat_ticks_total", "Total heartbeat ticks")
                 .expect("valid metric");
 
+        let cache_lookups = IntCounterVec::new(
+            prometheus::Opts::new(
+                "zeroclaw_response_cache_lookups_total",
+                "Response cache lookups by result (exact_hit, semantic_hit, miss)",
+            ),
+            &["result"],
+        )
+        .expect("valid metric");
+
         let errors = IntCounterVec::new(
             prometheus::Opts::new("zeroclaw_errors_total", "Total errors by component"),
             &["component"],
@@ -109,6 +119,7 @@ impl PrometheusObserver {
         registry.register(Box::new(tool_calls.clone())).ok();
         registry.register(Box::new(channel_messages.clone())).ok();
         registry.register(Box::new(heartbeat_ticks.clone())).ok();
+        registry.register(Box::new(cache_lookups.clone())).ok();
         registry.register(Box::new(errors.clone())).ok();
         registry.register(Box::new(agent_duration.clone())).ok();
         registry.register(Box::new(tool_duration.clone())).ok();
@@ -123,6 +134,7 @@ impl PrometheusObserver {
             tool_calls,
             channel_messages,
             heartbeat_ticks,
+            cache_lookups,
             errors,
             agent_duration,
             tool_duration,
@@ -191,6 +203,14 @@ impl Observer for PrometheusObserver {
             ObserverEvent::HeartbeatTick => {
                 self.heartbeat_ticks.inc();
             }
+            ObserverEvent::CacheLookup { hit, semantic } => {
+                let result = match (hit, semantic) {
+                    (false, _) => "miss",
+                    (true, false) => "exact_hit",
+                    (true, true) => "semantic_hit",
+                };
+                self.cache_lookups.with_label_values(&[result]).inc();
+            }
             ObserverEvent::Error {
                 component,
                 message: _,
@@ -372,6 +392,21 @@ mod tests {
         assert!(output.contains(r#"zeroclaw_errors_total{component="channels"} 1"#));
     }
 
+    #[test]
+    fn cache_lookups_track_hits_and_misses() {
+        let obs = PrometheusObserver::new();
+        for (hit, semantic) in [(true, false), (true, true), (true, true), (false, false)] {
+            obs.record_event(&ObserverEvent::CacheLookup { hit, semantic });
+        }
+
+        let output = obs.encode();
+        assert!(output.contains(r#"zeroclaw_response_cache_lookups_total{result="exact_hit"} 1"#));
+        assert!(
+            output.contains(r#"zeroclaw_response_cache_lookups_total{result="semantic_hit"} 2"#)
+        );
+        assert!(output.contains(r#"zeroclaw_response_cache_lookups_total{result="miss"} 1"#));
+    }
+
     #[test]
     fn gauge_reflects_latest_value() {
         let obs = PrometheusObserver::new();
@@ -47,6 +47,12 @@ pub enum ObserverEvent {
         direction: String,
     },
     HeartbeatTick,
+    /// A response cache lookup; `semantic` marks hits on a similar rather
+    /// than identical prompt.
+    CacheLookup {
+        hit: bool,
+        semantic: bool,
+    },
     Error {
         component: String,
         message: String,
@@ -157,7 +163,7 @@ impl InterventionChain {
         let mut current = content.to_string();
         for handler in &self.handlers {
             match handler.intercept(&current, ctx) {
-                InterventionVerdict::Allow => continue,
+                InterventionVerdict::Allow => {}
                 InterventionVerdict::Modify(new) => {
                     tracing::debug!(handler = handler.name(), "InterventionHandler modified message");
                     current = new;
@@ -172,7 +178,7 @@ impl InterventionChain {
                 }
             }
         }
-        if current != content { InterventionVerdict::Modify(current) } else { InterventionVerdict::Allow }
+        if current == content { InterventionVerdict::Allow } else { InterventionVerdict::Modify(current) }
     }
 
     pub fn is_empty(&self) -> bool { self.handlers.is_empty() }
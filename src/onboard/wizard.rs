@@ -125,13 +125,17 @@ pub fn run_wizard() -> Result<Config> {
         model_routes: Vec::new(),
         heartbeat: HeartbeatConfig::default(),
         cron: crate::config::CronConfig::default(),
+        sessions: crate::config::SessionsConfig::default(),
+        workflows: crate::config::WorkflowConfig::default(),
         channels_config,
         memory: memory_config, // User-selected memory backend
         storage: StorageConfig::default(),
         tunnel: tunnel_config,
         gateway: crate::config::GatewayConfig::default(),
         composio: composio_config,
+        mcp: crate::config::McpConfig::default(),
         secrets: secrets_config,
+        security: crate::config::SecurityConfig::default(),
         browser: BrowserConfig::default(),
         http_request: crate::config::HttpRequestConfig::default(),
         web_search: crate::config::WebSearchConfig::default(),
@@ -279,6 +283,8 @@ fn memory_config_defaults_for_backend(backend: &str) -> MemoryConfig {
         archive_after_days: if profile.uses_sqlite_hygiene { 7 } else { 0 },
         purge_after_days: if profile.uses_sqlite_hygiene { 30 } else { 0 },
         conversation_retention_days: 30,
+        consolidation_enabled: false,
+        consolidation_model: None,
         embedding_provider: "none".to_string(),
         embedding_model: "text-embedding-3-small".to_string(),
         embedding_dimensions: 1536,
@@ -294,10 +300,14 @@ fn memory_config_defaults_for_backend(backend: &str) -> MemoryConfig {
         response_cache_enabled: false,
         response_cache_ttl_minutes: 60,
         response_cache_max_entries: 5_000,
+        response_cache_semantic: false,
+        response_cache_similarity_threshold: 0.95,
         snapshot_enabled: false,
         snapshot_on_hygiene: false,
         auto_hydrate: true,
         sqlite_open_timeout_secs: None,
+        vector_index: "hnsw".into(),
+        isolation: crate::config::MemoryIsolation::Shared,
     }
 }
 
@@ -351,13 +361,17 @@ pub fn run_quick_setup(
         model_routes: Vec::new(),
         heartbeat: HeartbeatConfig::default(),
         cron: crate::config::CronConfig::default(),
+        sessions: crate::config::SessionsConfig::default(),
+        workflows: crate::config::WorkflowConfig::default(),
         channels_config: ChannelsConfig::default(),
         memory: memory_config,
         storage: StorageConfig::default(),
         tunnel: crate::config::TunnelConfig::default(),
         gateway: crate::config::GatewayConfig::default(),
         composio: ComposioConfig::default(),
+        mcp: crate::config::McpConfig::default(),
         secrets: SecretsConfig::default(),
+        security: crate::config::SecurityConfig::default(),
         browser: BrowserConfig::default(),
         http_request: crate::config::HttpRequestConfig::default(),
         web_search: crate::config::WebSearchConfig::default(),
@@ -500,7 +514,6 @@ const MINIMAX_ONBOARD_MODELS: [(&str, &str); 5] = [
 fn default_model_for_provider(provider: &str) -> String {
     match canonical_provider_name(provider) {
         "anthropic" => "claude-sonnet-4-5-20250929".into(),
-        "openrouter" => "anthropic/claude-sonnet-4.6".into(),
         "openai" => "gpt-5.2".into(),
         "openai-codex" => "gpt-5-codex".into(),
         "venice" => "zai-org-glm-5".into(),
@@ -520,7 +533,7 @@ fn default_model_for_provider(provider: &str) -> String {
         "gemini" => "gemini-2.5-pro".into(),
         "kimi-code" => "kimi-for-coding".into(),
         "nvidia" => "meta/llama-3.3-70b-instruct".into(),
-        "astrai" => "anthropic/claude-sonnet-4.6".into(),
+        // openrouter, astrai, and anything unrecognised
         _ => "anthropic/claude-sonnet-4.6".into(),
     }
 }
@@ -2760,6 +2773,8 @@ fn setup_channels() -> Result<ChannelsConfig> {
                     allowed_users,
                     listen_to_bots: false,
                     mention_only: false,
+                    stream_mode: StreamMode::default(),
+                    draft_update_interval_ms: 1000,
                 });
             }
             2 => {
@@ -5190,7 +5205,7 @@ mod tests {
 
         let config = Config {
             workspace_dir: tmp.path().to_path_buf(),
-            default_provider: Some("venice".to_string()),
+            default_provider: Some("minimax".to_string()),
             ..Config::default()
         };
 
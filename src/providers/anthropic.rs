@@ -1,6 +1,6 @@
 use crate::providers::traits::{
     ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
-    Provider, ToolCall as ProviderToolCall,
+    Provider, ProviderCapabilities, ToolCall as ProviderToolCall,
 };
 use crate::tools::ToolSpec;
 use async_trait::async_trait;
@@ -83,6 +83,16 @@ enum NativeContentOut {
         #[serde(skip_serializing_if = "Option::is_none")]
         cache_control: Option<CacheControl>,
     },
+    #[serde(rename = "image")]
+    Image { source: NativeImageSource },
+}
+
+#[derive(Debug, Serialize)]
+struct NativeImageSource {
+    #[serde(rename = "type")]
+    source_type: String,
+    media_type: String,
+    data: String,
 }
 
 #[derive(Debug, Serialize)]
@@ -200,7 +210,7 @@ impl AnthropicProvider {
                     | NativeContentOut::ToolResult { cache_control, .. } => {
                         *cache_control = Some(CacheControl::ephemeral());
                     }
-                    NativeContentOut::ToolUse { .. } => {}
+                    NativeContentOut::ToolUse { .. } | NativeContentOut::Image { .. } => {}
                 }
             }
         }
@@ -322,12 +332,24 @@ impl AnthropicProvider {
                     }
                 }
                 _ => {
+                    // Images go first: Anthropic recommends image-then-text ordering.
+                    let mut content: Vec<NativeContentOut> = msg
+                        .images()
+                        .map(|(media_type, data)| NativeContentOut::Image {
+                            source: NativeImageSource {
+                                source_type: "base64".to_string(),
+                                media_type: media_type.to_string(),
+                                data: data.to_string(),
+                            },
+                        })
+                        .collect();
+                    content.push(NativeContentOut::Text {
+                        text: msg.content.clone(),
+                        cache_control: None,
+                    });
                     native_messages.push(NativeMessage {
                         role: "user".to_string(),
-                        content: vec![NativeContentOut::Text {
-                            text: msg.content.clone(),
-                            cache_control: None,
-                        }],
+                        content,
                     });
                 }
             }
@@ -493,6 +515,13 @@ impl Provider for AnthropicProvider {
         Ok(Self::parse_native_response(native_response))
     }
 
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            native_tool_calling: true,
+            vision: true,
+        }
+    }
+
     fn supports_native_tools(&self) -> bool {
         true
     }
@@ -879,14 +908,17 @@ mod tests {
             ChatMessage {
                 role: "system".to_string(),
                 content: "System prompt".to_string(),
+                parts: Vec::new(),
             },
             ChatMessage {
                 role: "user".to_string(),
                 content: "Hello".to_string(),
+                parts: Vec::new(),
             },
             ChatMessage {
                 role: "assistant".to_string(),
                 content: "Hi".to_string(),
+                parts: Vec::new(),
             },
         ];
         // Only 2 non-system messages
@@ -898,12 +930,14 @@ mod tests {
         let mut messages = vec![ChatMessage {
             role: "system".to_string(),
             content: "System prompt".to_string(),
+            parts: Vec::new(),
         }];
         // Add 5 non-system messages
         for i in 0..5 {
             messages.push(ChatMessage {
                 role: if i % 2 == 0 { "user" } else { "assistant" }.to_string(),
                 content: format!("Message {i}"),
+                parts: Vec::new(),
             });
         }
         assert!(AnthropicProvider::should_cache_conversation(&messages));
@@ -917,6 +951,7 @@ mod tests {
             messages.push(ChatMessage {
                 role: if i % 2 == 0 { "user" } else { "assistant" }.to_string(),
                 content: format!("Message {i}"),
+                parts: Vec::new(),
             });
         }
         assert!(!AnthropicProvider::should_cache_conversation(&messages));
@@ -925,6 +960,7 @@ mod tests {
         messages.push(ChatMessage {
             role: "user".to_string(),
             content: "One more".to_string(),
+            parts: Vec::new(),
         });
         assert!(AnthropicProvider::should_cache_conversation(&messages));
     }
@@ -1042,6 +1078,7 @@ mod tests {
         let messages = vec![ChatMessage {
             role: "system".to_string(),
             content: "Short system prompt".to_string(),
+            parts: Vec::new(),
         }];
 
         let (system_prompt, _) = AnthropicProvider::convert_messages(&messages);
@@ -1060,6 +1097,7 @@ mod tests {
         let messages = vec![ChatMessage {
             role: "system".to_string(),
             content: large_content.clone(),
+            parts: Vec::new(),
         }];
 
         let (system_prompt, _) = AnthropicProvider::convert_messages(&messages);
@@ -1097,6 +1135,30 @@ mod tests {
         assert!(json.contains(r#""system":"System""#));
     }
 
+    #[test]
+    fn convert_messages_places_image_blocks_before_text() {
+        let messages = vec![ChatMessage::user("What is in this photo?").with_parts(vec![
+            crate::providers::ContentPart::image("image/png", "iVBORw0KGgo="),
+        ])];
+
+        let (_, native) = AnthropicProvider::convert_messages(&messages);
+        let json = serde_json::to_value(&native[0]).unwrap();
+
+        assert_eq!(json["content"][0]["type"], "image");
+        assert_eq!(json["content"][0]["source"]["type"], "base64");
+        assert_eq!(json["content"][0]["source"]["media_type"], "image/png");
+        assert_eq!(json["content"][0]["source"]["data"], "iVBORw0KGgo=");
+        assert_eq!(json["content"][1]["type"], "text");
+        assert_eq!(json["content"][1]["text"], "What is in this photo?");
+    }
+
+    #[test]
+    fn capabilities_report_vision_support() {
+        let provider = AnthropicProvider::new(Some("key"));
+        assert!(provider.capabilities().vision);
+        assert!(provider.supports_native_tools());
+    }
+
     #[tokio::test]
     async fn warmup_without_key_is_noop() {
         let provider = AnthropicProvider::new(None);
@@ -360,6 +360,161 @@ struct StreamDelta {
     /// Reasoning/thinking models may stream output via `reasoning_content`.
     #[serde(default)]
     reasoning_content: Option<String>,
+    /// Native tool calls arrive as fragments keyed by `index`.
+    #[serde(default)]
+    tool_calls: Option<Vec<StreamToolCallDelta>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamToolCallDelta {
+    #[serde(default)]
+    index: Option<usize>,
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    function: Option<Function>,
+}
+
+#[derive(Debug, Default)]
+struct StreamedToolCall {
+    id: Option<String>,
+    name: String,
+    arguments: String,
+}
+
+/// Reassembles a streamed chat completion (content, reasoning and native
+/// tool-call fragments) into a single provider response.
+#[derive(Debug, Default)]
+struct NativeStreamAccumulator {
+    content: String,
+    reasoning: String,
+    tool_calls: Vec<StreamedToolCall>,
+}
+
+impl NativeStreamAccumulator {
+    /// Feed one SSE line. Returns the content delta to forward, if any.
+    fn push_line(&mut self, provider_name: &str, line: &str) -> anyhow::Result<Option<String>> {
+        let Some(data) = line.trim().strip_prefix("data:") else {
+            return Ok(None);
+        };
+        let data = data.trim();
+        if data.is_empty() || data == "[DONE]" {
+            return Ok(None);
+        }
+
+        let chunk: StreamChunkResponse = serde_json::from_str(data).map_err(|e| {
+            anyhow::anyhow!(
+                "{provider_name} stream chunk parse error: {e}. Chunk: {}",
+                compact_sanitized_body_snippet(data)
+            )
+        })?;
+        let Some(choice) = chunk.choices.into_iter().next() else {
+            return Ok(None);
+        };
+
+        if let Some(reasoning) = choice.delta.reasoning_content {
+            self.reasoning.push_str(&reasoning);
+        }
+
+        for fragment in choice.delta.tool_calls.unwrap_or_default() {
+            // Some servers omit `index` and send each call whole; a new id
+            // then marks a new call.
+            let index = match fragment.index {
+                Some(index) => index,
+                None if fragment.id.is_some() || self.tool_calls.is_empty() => {
+                    self.tool_calls.len()
+                }
+                None => self.tool_calls.len() - 1,
+            };
+            if self.tool_calls.len() <= index {
+                self.tool_calls
+                    .resize_with(index + 1, StreamedToolCall::default);
+            }
+            let slot = &mut self.tool_calls[index];
+            if fragment.id.is_some() {
+                slot.id = fragment.id;
+            }
+            if let Some(function) = fragment.function {
+                if let Some(name) = function.name {
+                    slot.name.push_str(&name);
+                }
+                if let Some(arguments) = function.arguments {
+                    slot.arguments.push_str(&arguments);
+                }
+            }
+        }
+
+        match choice.delta.content {
+            Some(content) if !content.is_empty() => {
+                self.content.push_str(&content);
+                Ok(Some(content))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn finish(self) -> ProviderChatResponse {
+        let text = if !self.content.is_empty() {
+            Some(self.content)
+        } else if !self.reasoning.is_empty() {
+            Some(self.reasoning)
+        } else {
+            None
+        };
+
+        let tool_calls = self
+            .tool_calls
+            .into_iter()
+            .filter(|call| !call.name.is_empty())
+            .map(|call| ProviderToolCall {
+                id: call.id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+                name: call.name,
+                arguments: if call.arguments.trim().is_empty() {
+                    "{}".to_string()
+                } else {
+                    call.arguments
+                },
+            })
+            .collect();
+
+        ProviderChatResponse { text, tool_calls }
+    }
+}
+
+/// Read a streamed (`stream: true`) chat completions response, forwarding
+/// content deltas to `on_delta` and returning the reassembled response.
+///
+/// Shared by the OpenAI-format providers (compatible, OpenAI, OpenRouter).
+pub(crate) async fn read_native_chat_stream(
+    provider_name: &str,
+    response: reqwest::Response,
+    on_delta: &tokio::sync::mpsc::Sender<String>,
+) -> anyhow::Result<ProviderChatResponse> {
+    let mut accumulator = NativeStreamAccumulator::default();
+    // Split on raw bytes so multi-byte characters spanning network chunks
+    // are decoded only once the full line has arrived.
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut bytes_stream = response.bytes_stream();
+
+    while let Some(item) = bytes_stream.next().await {
+        buffer.extend_from_slice(&item?);
+        while let Some(pos) = buffer.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = buffer.drain(..=pos).collect();
+            if let Some(delta) =
+                accumulator.push_line(provider_name, &String::from_utf8_lossy(&line))?
+            {
+                // The receiver may already be gone (e.g. draft creation
+                // failed); keep reading so the response is still complete.
+                let _ = on_delta.send(delta).await;
+            }
+        }
+    }
+    let tail = String::from_utf8_lossy(&buffer);
+    if let Some(delta) = accumulator.push_line(provider_name, &tail)? {
+        let _ = on_delta.send(delta).await;
+    }
+
+    Ok(accumulator.finish())
 }
 
 /// Parse SSE (Server-Sent Events) stream from OpenAI-compatible providers.
@@ -446,7 +601,6 @@ fn sse_bytes_to_chunks(
                     // Process complete lines
                     while let Some(pos) = buffer.find('\n') {
                         let line = buffer.drain(..=pos).collect::<String>();
-                        buffer = buffer[pos + 1..].to_string();
 
                         match parse_sse_line(&line) {
                             Ok(Some(content)) => {
@@ -767,6 +921,7 @@ impl Provider for OpenAiCompatibleProvider {
     fn capabilities(&self) -> crate::providers::traits::ProviderCapabilities {
         crate::providers::traits::ProviderCapabilities {
             native_tool_calling: true,
+            vision: false,
         }
     }
 
@@ -1114,6 +1269,59 @@ impl Provider for OpenAiCompatibleProvider {
         Ok(Self::parse_native_response(message))
     }
 
+    async fn stream_chat(
+        &self,
+        request: ProviderChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+        on_delta: &tokio::sync::mpsc::Sender<String>,
+    ) -> anyhow::Result<ProviderChatResponse> {
+        let credential = self.credential.as_ref().ok_or_else(|| {
+            anyhow::anyhow!(
+                "{} API key not set. Run `zeroclaw onboard` or set the appropriate env var.",
+                self.name
+            )
+        })?;
+
+        let tools = Self::convert_tool_specs(request.tools);
+        let native_request = NativeChatRequest {
+            model: model.to_string(),
+            messages: Self::convert_messages_for_native(request.messages),
+            temperature,
+            stream: Some(true),
+            tool_choice: tools.as_ref().map(|_| "auto".to_string()),
+            tools,
+        };
+
+        let url = self.chat_completions_url();
+        let response = self
+            .apply_auth_header(
+                self.http_client()
+                    .post(&url)
+                    .header("Accept", "text/event-stream")
+                    .json(&native_request),
+                credential,
+            )
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            // Tool-schema and /v1/responses fallbacks live in the non-streaming path.
+            if matches!(
+                status,
+                reqwest::StatusCode::BAD_REQUEST
+                    | reqwest::StatusCode::NOT_FOUND
+                    | reqwest::StatusCode::UNPROCESSABLE_ENTITY
+            ) {
+                return self.chat(request, model, temperature).await;
+            }
+            return Err(super::api_error(&self.name, response).await);
+        }
+
+        read_native_chat_stream(&self.name, response, on_delta).await
+    }
+
     fn supports_native_tools(&self) -> bool {
         true
     }
@@ -1849,6 +2057,7 @@ mod tests {
         let messages = vec![ChatMessage {
             role: "user".to_string(),
             content: "hello".to_string(),
+            parts: Vec::new(),
         }];
         let tools = vec![serde_json::json!({
             "type": "function",
@@ -1975,4 +2184,66 @@ mod tests {
         let result = parse_sse_line(line).unwrap();
         assert_eq!(result, None);
     }
+
+    #[test]
+    fn stream_accumulator_forwards_content_deltas() {
+        let mut acc = NativeStreamAccumulator::default();
+        let first = acc
+            .push_line("test", r#"data: {"choices":[{"delta":{"content":"Hel"}}]}"#)
+            .unwrap();
+        let second = acc
+            .push_line("test", r#"data: {"choices":[{"delta":{"content":"lo"}}]}"#)
+            .unwrap();
+        assert_eq!(first.as_deref(), Some("Hel"));
+        assert_eq!(second.as_deref(), Some("lo"));
+        assert_eq!(acc.push_line("test", "data: [DONE]").unwrap(), None);
+        assert_eq!(acc.push_line("test", ": keep-alive").unwrap(), None);
+
+        let resp = acc.finish();
+        assert_eq!(resp.text.as_deref(), Some("Hello"));
+        assert!(resp.tool_calls.is_empty());
+    }
+
+    #[test]
+    fn stream_accumulator_reassembles_tool_call_fragments() {
+        let mut acc = NativeStreamAccumulator::default();
+        for line in [
+            r#"data: {"choices":[{"delta":{"tool_calls":[{"index":0,"id":"call_1","type":"function","function":{"name":"shell","arguments":""}}]}}]}"#,
+            r#"data: {"choices":[{"delta":{"tool_calls":[{"index":0,"function":{"arguments":"{\"command\":"}}]}}]}"#,
+            r#"data: {"choices":[{"delta":{"tool_calls":[{"index":0,"function":{"arguments":"\"ls\"}"}}]}}]}"#,
+            r#"data: {"choices":[{"delta":{"tool_calls":[{"index":1,"id":"call_2","function":{"name":"file_read"}}]}}]}"#,
+            r#"data: {"choices":[{"delta":{},"finish_reason":"tool_calls"}]}"#,
+        ] {
+            assert_eq!(acc.push_line("test", line).unwrap(), None);
+        }
+
+        let resp = acc.finish();
+        assert!(resp.text.is_none());
+        assert_eq!(resp.tool_calls.len(), 2);
+        assert_eq!(resp.tool_calls[0].id, "call_1");
+        assert_eq!(resp.tool_calls[0].name, "shell");
+        assert_eq!(resp.tool_calls[0].arguments, r#"{"command":"ls"}"#);
+        assert_eq!(resp.tool_calls[1].name, "file_read");
+        assert_eq!(resp.tool_calls[1].arguments, "{}");
+    }
+
+    #[test]
+    fn stream_accumulator_falls_back_to_reasoning_content() {
+        let mut acc = NativeStreamAccumulator::default();
+        let forwarded = acc
+            .push_line(
+                "test",
+                r#"data: {"choices":[{"delta":{"reasoning_content":"thinking"}}]}"#,
+            )
+            .unwrap();
+        assert_eq!(forwarded, None);
+        assert_eq!(acc.finish().text.as_deref(), Some("thinking"));
+    }
+
+    #[test]
+    fn stream_accumulator_reports_malformed_chunk() {
+        let mut acc = NativeStreamAccumulator::default();
+        let err = acc.push_line("test", "data: {not json").unwrap_err();
+        assert!(err.to_string().contains("test stream chunk parse error"));
+    }
 }
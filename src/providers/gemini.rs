@@ -3,7 +3,7 @@
 //! - Gemini CLI OAuth tokens (reuse existing ~/.gemini/ authentication)
 //! - Google Cloud ADC (`GOOGLE_APPLICATION_CREDENTIALS`)
 
-use crate::providers::traits::Provider;
+use crate::providers::traits::{ChatMessage, Provider, ProviderCapabilities};
 use async_trait::async_trait;
 use directories::UserDirs;
 use reqwest::Client;
@@ -79,16 +79,44 @@ struct InternalGenerateContentRequest {
     system_instruction: Option<Content>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Clone)]
 struct Content {
     #[serde(skip_serializing_if = "Option::is_none")]
     role: Option<String>,
     parts: Vec<Part>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Clone)]
 struct Part {
-    text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    inline_data: Option<InlineData>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+struct InlineData {
+    mime_type: String,
+    data: String,
+}
+
+impl Part {
+    fn text(text: impl Into<String>) -> Self {
+        Self {
+            text: Some(text.into()),
+            inline_data: None,
+        }
+    }
+
+    fn inline_data(mime_type: &str, data: &str) -> Self {
+        Self {
+            text: None,
+            inline_data: Some(InlineData {
+                mime_type: mime_type.to_string(),
+                data: data.to_string(),
+            }),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -277,6 +305,96 @@ impl GeminiProvider {
         crate::config::build_runtime_proxy_client_with_timeouts("provider.gemini", 120, 10)
     }
 
+    fn require_auth(&self) -> anyhow::Result<&GeminiAuth> {
+        self.auth.as_ref().ok_or_else(|| {
+            anyhow::anyhow!(
+                "Gemini API key not found. Options:\n\
+                 1. Set GEMINI_API_KEY env var\n\
+                 2. Run `gemini` CLI to authenticate (tokens will be reused)\n\
+                 3. Get an API key from https://aistudio.google.com/app/apikey\n\
+                 4. Run `zeroclaw onboard` to configure"
+            )
+        })
+    }
+
+    /// Convert chat history into Gemini `contents`. System messages become the
+    /// system instruction, assistant turns use the `model` role, and image
+    /// parts are sent as `inline_data` ahead of the text.
+    fn convert_messages(messages: &[ChatMessage]) -> (Option<Content>, Vec<Content>) {
+        let mut system_parts = Vec::new();
+        let mut contents: Vec<Content> = Vec::new();
+
+        for msg in messages {
+            if msg.role == "system" {
+                system_parts.push(Part::text(msg.content.as_str()));
+                continue;
+            }
+
+            let role = if msg.role == "assistant" {
+                "model"
+            } else {
+                "user"
+            };
+            let mut parts: Vec<Part> = msg
+                .images()
+                .map(|(mime_type, data)| Part::inline_data(mime_type, data))
+                .collect();
+            if !msg.content.is_empty() || parts.is_empty() {
+                parts.push(Part::text(msg.content.as_str()));
+            }
+
+            // Gemini expects alternating turns, so merge consecutive same-role messages.
+            match contents.last_mut() {
+                Some(last) if last.role.as_deref() == Some(role) => last.parts.extend(parts),
+                _ => contents.push(Content {
+                    role: Some(role.to_string()),
+                    parts,
+                }),
+            }
+        }
+
+        let system_instruction = (!system_parts.is_empty()).then(|| Content {
+            role: None,
+            parts: system_parts,
+        });
+        (system_instruction, contents)
+    }
+
+    async fn send_generate_content(
+        &self,
+        auth: &GeminiAuth,
+        request: &GenerateContentRequest,
+        model: &str,
+    ) -> anyhow::Result<String> {
+        let url = Self::build_generate_content_url(model, auth);
+
+        let response = self
+            .build_generate_content_request(auth, &url, request, model)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            anyhow::bail!("Gemini API error ({status}): {error_text}");
+        }
+
+        let result: GenerateContentResponse = response.json().await?;
+
+        // Check for API error in response body
+        if let Some(err) = result.error {
+            anyhow::bail!("Gemini API error: {}", err.message);
+        }
+
+        // Extract text from response
+        result
+            .candidates
+            .and_then(|c| c.into_iter().next())
+            .and_then(|c| c.content.parts.into_iter().next())
+            .and_then(|p| p.text)
+            .ok_or_else(|| anyhow::anyhow!("No response from Gemini"))
+    }
+
     fn build_generate_content_request(
         &self,
         auth: &GeminiAuth,
@@ -291,30 +409,8 @@ impl GeminiProvider {
                 let internal_request = InternalGenerateContentRequest {
                     model: Self::format_model_name(model),
                     generation_config: request.generation_config.clone(),
-                    contents: request
-                        .contents
-                        .iter()
-                        .map(|c| Content {
-                            role: c.role.clone(),
-                            parts: c
-                                .parts
-                                .iter()
-                                .map(|p| Part {
-                                    text: p.text.clone(),
-                                })
-                                .collect(),
-                        })
-                        .collect(),
-                    system_instruction: request.system_instruction.as_ref().map(|si| Content {
-                        role: si.role.clone(),
-                        parts: si
-                            .parts
-                            .iter()
-                            .map(|p| Part {
-                                text: p.text.clone(),
-                            })
-                            .collect(),
-                    }),
+                    contents: request.contents.clone(),
+                    system_instruction: request.system_instruction.clone(),
                 };
                 self.http_client()
                     .post(url)
@@ -335,30 +431,18 @@ impl Provider for GeminiProvider {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<String> {
-        let auth = self.auth.as_ref().ok_or_else(|| {
-            anyhow::anyhow!(
-                "Gemini API key not found. Options:\n\
-                 1. Set GEMINI_API_KEY env var\n\
-                 2. Run `gemini` CLI to authenticate (tokens will be reused)\n\
-                 3. Get an API key from https://aistudio.google.com/app/apikey\n\
-                 4. Run `zeroclaw onboard` to configure"
-            )
-        })?;
+        let auth = self.require_auth()?;
 
         // Build request
         let system_instruction = system_prompt.map(|sys| Content {
             role: None,
-            parts: vec![Part {
-                text: sys.to_string(),
-            }],
+            parts: vec![Part::text(sys)],
         });
 
         let request = GenerateContentRequest {
             contents: vec![Content {
                 role: Some("user".to_string()),
-                parts: vec![Part {
-                    text: message.to_string(),
-                }],
+                parts: vec![Part::text(message)],
             }],
             system_instruction,
             generation_config: GenerationConfig {
@@ -367,33 +451,35 @@ impl Provider for GeminiProvider {
             },
         };
 
-        let url = Self::build_generate_content_url(model, auth);
+        self.send_generate_content(auth, &request, model).await
+    }
 
-        let response = self
-            .build_generate_content_request(auth, &url, &request, model)
-            .send()
-            .await?;
+    async fn chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let auth = self.require_auth()?;
+        let (system_instruction, contents) = Self::convert_messages(messages);
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-            anyhow::bail!("Gemini API error ({status}): {error_text}");
-        }
+        let request = GenerateContentRequest {
+            contents,
+            system_instruction,
+            generation_config: GenerationConfig {
+                temperature,
+                max_output_tokens: 8192,
+            },
+        };
 
-        let result: GenerateContentResponse = response.json().await?;
+        self.send_generate_content(auth, &request, model).await
+    }
 
-        // Check for API error in response body
-        if let Some(err) = result.error {
-            anyhow::bail!("Gemini API error: {}", err.message);
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            native_tool_calling: false,
+            vision: true,
         }
-
-        // Extract text from response
-        result
-            .candidates
-            .and_then(|c| c.into_iter().next())
-            .and_then(|c| c.content.parts.into_iter().next())
-            .and_then(|p| p.text)
-            .ok_or_else(|| anyhow::anyhow!("No response from Gemini"))
     }
 
     async fn warmup(&self) -> anyhow::Result<()> {
@@ -534,9 +620,7 @@ mod tests {
         let body = GenerateContentRequest {
             contents: vec![Content {
                 role: Some("user".into()),
-                parts: vec![Part {
-                    text: "hello".into(),
-                }],
+                parts: vec![Part::text("hello")],
             }],
             system_instruction: None,
             generation_config: GenerationConfig {
@@ -569,9 +653,7 @@ mod tests {
         let body = GenerateContentRequest {
             contents: vec![Content {
                 role: Some("user".into()),
-                parts: vec![Part {
-                    text: "hello".into(),
-                }],
+                parts: vec![Part::text("hello")],
             }],
             system_instruction: None,
             generation_config: GenerationConfig {
@@ -593,15 +675,11 @@ mod tests {
         let request = GenerateContentRequest {
             contents: vec![Content {
                 role: Some("user".to_string()),
-                parts: vec![Part {
-                    text: "Hello".to_string(),
-                }],
+                parts: vec![Part::text("Hello")],
             }],
             system_instruction: Some(Content {
                 role: None,
-                parts: vec![Part {
-                    text: "You are helpful".to_string(),
-                }],
+                parts: vec![Part::text("You are helpful")],
             }),
             generation_config: GenerationConfig {
                 temperature: 0.7,
@@ -616,6 +694,39 @@ mod tests {
         assert!(json.contains("\"maxOutputTokens\":8192"));
     }
 
+    #[test]
+    fn convert_messages_maps_roles_and_inline_images() {
+        let messages = vec![
+            ChatMessage::system("Be brief"),
+            ChatMessage::user("What is this?").with_parts(vec![
+                crate::providers::ContentPart::image("image/png", "iVBORw0KGgo="),
+            ]),
+            ChatMessage::assistant("A cat."),
+            ChatMessage::user("[Tool results]\nok"),
+            ChatMessage::user("Thanks"),
+        ];
+
+        let (system, contents) = GeminiProvider::convert_messages(&messages);
+        let system = serde_json::to_value(system.unwrap()).unwrap();
+        let contents = serde_json::to_value(&contents).unwrap();
+
+        assert_eq!(system["parts"][0]["text"], "Be brief");
+        assert_eq!(contents.as_array().unwrap().len(), 3);
+        assert_eq!(contents[0]["role"], "user");
+        assert_eq!(
+            contents[0]["parts"][0]["inline_data"]["mime_type"],
+            "image/png"
+        );
+        assert_eq!(
+            contents[0]["parts"][0]["inline_data"]["data"],
+            "iVBORw0KGgo="
+        );
+        assert!(contents[0]["parts"][0].get("text").is_none());
+        assert_eq!(contents[0]["parts"][1]["text"], "What is this?");
+        assert_eq!(contents[1]["role"], "model");
+        assert_eq!(contents[2]["parts"].as_array().unwrap().len(), 2);
+    }
+
     #[test]
     fn internal_request_includes_model() {
         let request = InternalGenerateContentRequest {
@@ -626,9 +737,7 @@ mod tests {
             },
             contents: vec![Content {
                 role: Some("user".to_string()),
-                parts: vec![Part {
-                    text: "Hello".to_string(),
-                }],
+                parts: vec![Part::text("Hello")],
             }],
             system_instruction: None,
         };
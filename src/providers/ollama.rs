@@ -1,4 +1,4 @@
-use crate::providers::traits::Provider;
+use crate::providers::traits::{Provider, ProviderCapabilities};
 use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
@@ -22,6 +22,8 @@ struct ChatRequest {
 struct Message {
     role: String,
     content: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    images: Vec<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -272,12 +274,14 @@ impl Provider for OllamaProvider {
             messages.push(Message {
                 role: "system".to_string(),
                 content: sys.to_string(),
+                images: Vec::new(),
             });
         }
 
         messages.push(Message {
             role: "user".to_string(),
             content: message.to_string(),
+            images: Vec::new(),
         });
 
         let response = self
@@ -327,6 +331,7 @@ impl Provider for OllamaProvider {
             .map(|m| Message {
                 role: m.role.clone(),
                 content: m.content.clone(),
+                images: m.images().map(|(_, data)| data.to_string()).collect(),
             })
             .collect();
 
@@ -366,6 +371,15 @@ impl Provider for OllamaProvider {
         Ok(content)
     }
 
+    fn capabilities(&self) -> ProviderCapabilities {
+        // Vision depends on the pulled model (llava, llama3.2-vision, ...);
+        // text-only models ignore the `images` field.
+        ProviderCapabilities {
+            native_tool_calling: false,
+            vision: true,
+        }
+    }
+
     fn supports_native_tools(&self) -> bool {
         // Return false since loop_.rs uses XML-style tool parsing via system prompt
         // The model may return native tool_calls but we convert them to JSON format
@@ -448,6 +462,25 @@ mod tests {
         assert!(!should_auth);
     }
 
+    #[test]
+    fn message_serializes_images_only_when_present() {
+        let plain = Message {
+            role: "user".into(),
+            content: "hi".into(),
+            images: Vec::new(),
+        };
+        let with_image = Message {
+            role: "user".into(),
+            content: "what is this?".into(),
+            images: vec!["iVBORw0KGgo=".into()],
+        };
+
+        let plain = serde_json::to_value(&plain).unwrap();
+        let with_image = serde_json::to_value(&with_image).unwrap();
+        assert!(plain.get("images").is_none());
+        assert_eq!(with_image["images"][0], "iVBORw0KGgo=");
+    }
+
     #[test]
     fn response_deserializes() {
         let json = r#"{"message":{"role":"assistant","content":"Hello from Ollama!"}}"#;
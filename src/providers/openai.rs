@@ -1,6 +1,6 @@
 use crate::providers::traits::{
     ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
-    Provider, ToolCall as ProviderToolCall,
+    Provider, ProviderCapabilities, ToolCall as ProviderToolCall,
 };
 use crate::tools::ToolSpec;
 use async_trait::async_trait;
@@ -59,6 +59,8 @@ struct NativeChatRequest {
     messages: Vec<NativeMessage>,
     temperature: f64,
     #[serde(skip_serializing_if = "Option::is_none")]
+    stream: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     tools: Option<Vec<NativeToolSpec>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tool_choice: Option<String>,
@@ -68,13 +70,33 @@ struct NativeChatRequest {
 struct NativeMessage {
     role: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    content: Option<String>,
+    content: Option<NativeMessageContent>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tool_call_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tool_calls: Option<Vec<NativeToolCall>>,
 }
 
+/// Plain string content, or a list of parts when the message carries images.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum NativeMessageContent {
+    Text(String),
+    Parts(Vec<NativeContentPart>),
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum NativeContentPart {
+    Text { text: String },
+    ImageUrl { image_url: NativeImageUrl },
+}
+
+#[derive(Debug, Serialize)]
+struct NativeImageUrl {
+    url: String,
+}
+
 #[derive(Debug, Serialize)]
 struct NativeToolSpec {
     #[serde(rename = "type")]
@@ -195,7 +217,7 @@ impl OpenAiProvider {
                                     .map(ToString::to_string);
                                 return NativeMessage {
                                     role: "assistant".to_string(),
-                                    content,
+                                    content: content.map(NativeMessageContent::Text),
                                     tool_call_id: None,
                                     tool_calls: Some(tool_calls),
                                 };
@@ -216,16 +238,30 @@ impl OpenAiProvider {
                             .map(ToString::to_string);
                         return NativeMessage {
                             role: "tool".to_string(),
-                            content,
+                            content: content.map(NativeMessageContent::Text),
                             tool_call_id,
                             tool_calls: None,
                         };
                     }
                 }
 
+                let content = if m.parts.is_empty() {
+                    NativeMessageContent::Text(m.content.clone())
+                } else {
+                    let mut parts = vec![NativeContentPart::Text {
+                        text: m.content.clone(),
+                    }];
+                    parts.extend(m.parts.iter().map(|part| NativeContentPart::ImageUrl {
+                        image_url: NativeImageUrl {
+                            url: part.to_data_uri(),
+                        },
+                    }));
+                    NativeMessageContent::Parts(parts)
+                };
+
                 NativeMessage {
                     role: m.role.clone(),
-                    content: Some(m.content.clone()),
+                    content: Some(content),
                     tool_call_id: None,
                     tool_calls: None,
                 }
@@ -324,6 +360,7 @@ impl Provider for OpenAiProvider {
             model: model.to_string(),
             messages: Self::convert_messages(request.messages),
             temperature,
+            stream: None,
             tool_choice: tools.as_ref().map(|_| "auto".to_string()),
             tools,
         };
@@ -350,6 +387,54 @@ impl Provider for OpenAiProvider {
         Ok(Self::parse_native_response(message))
     }
 
+    async fn stream_chat(
+        &self,
+        request: ProviderChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+        on_delta: &tokio::sync::mpsc::Sender<String>,
+    ) -> anyhow::Result<ProviderChatResponse> {
+        let credential = self.credential.as_ref().ok_or_else(|| {
+            anyhow::anyhow!("OpenAI API key not set. Set OPENAI_API_KEY or edit config.toml.")
+        })?;
+
+        let tools = Self::convert_tools(request.tools);
+        let native_request = NativeChatRequest {
+            model: model.to_string(),
+            messages: Self::convert_messages(request.messages),
+            temperature,
+            stream: Some(true),
+            tool_choice: tools.as_ref().map(|_| "auto".to_string()),
+            tools,
+        };
+
+        let response = self
+            .http_client()
+            .post(format!("{}/chat/completions", self.base_url))
+            .header("Authorization", format!("Bearer {credential}"))
+            .header("Accept", "text/event-stream")
+            .json(&native_request)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(super::api_error("OpenAI", response).await);
+        }
+
+        super::compatible::read_native_chat_stream("OpenAI", response, on_delta).await
+    }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            native_tool_calling: true,
+            vision: true,
+        }
+    }
+
     fn supports_native_tools(&self) -> bool {
         true
     }
@@ -537,4 +622,28 @@ mod tests {
         let msg = &resp.choices[0].message;
         assert_eq!(msg.effective_content(), Some("Real answer".to_string()));
     }
+
+    #[test]
+    fn convert_messages_keeps_plain_string_content_without_images() {
+        let messages = vec![ChatMessage::user("hello")];
+        let json = serde_json::to_value(OpenAiProvider::convert_messages(&messages)).unwrap();
+        assert_eq!(json[0]["content"], "hello");
+    }
+
+    #[test]
+    fn convert_messages_emits_image_url_parts_for_images() {
+        let messages = vec![ChatMessage::user("describe").with_parts(vec![
+            crate::providers::ContentPart::image("image/jpeg", "/9j/4AAQ"),
+        ])];
+        let json = serde_json::to_value(OpenAiProvider::convert_messages(&messages)).unwrap();
+
+        assert_eq!(json[0]["content"][0]["type"], "text");
+        assert_eq!(json[0]["content"][0]["text"], "describe");
+        assert_eq!(json[0]["content"][1]["type"], "image_url");
+        assert_eq!(
+            json[0]["content"][1]["image_url"]["url"],
+            "data:image/jpeg;base64,/9j/4AAQ"
+        );
+        assert!(OpenAiProvider::new(None).capabilities().vision);
+    }
 }
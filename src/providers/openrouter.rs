@@ -45,6 +45,8 @@ struct NativeChatRequest {
     messages: Vec<NativeMessage>,
     temperature: f64,
     #[serde(skip_serializing_if = "Option::is_none")]
+    stream: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     tools: Option<Vec<NativeToolSpec>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tool_choice: Option<String>,
@@ -365,6 +367,7 @@ impl Provider for OpenRouterProvider {
             model: model.to_string(),
             messages: Self::convert_messages(request.messages),
             temperature,
+            stream: None,
             tool_choice: tools.as_ref().map(|_| "auto".to_string()),
             tools,
         };
@@ -396,6 +399,54 @@ impl Provider for OpenRouterProvider {
         Ok(Self::parse_native_response(message))
     }
 
+    async fn stream_chat(
+        &self,
+        request: ProviderChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+        on_delta: &tokio::sync::mpsc::Sender<String>,
+    ) -> anyhow::Result<ProviderChatResponse> {
+        let credential = self.credential.as_ref().ok_or_else(|| {
+            anyhow::anyhow!(
+                "OpenRouter API key not set. Run `zeroclaw onboard` or set OPENROUTER_API_KEY env var."
+            )
+        })?;
+
+        let tools = Self::convert_tools(request.tools);
+        let native_request = NativeChatRequest {
+            model: model.to_string(),
+            messages: Self::convert_messages(request.messages),
+            temperature,
+            stream: Some(true),
+            tool_choice: tools.as_ref().map(|_| "auto".to_string()),
+            tools,
+        };
+
+        let response = self
+            .http_client()
+            .post("https://openrouter.ai/api/v1/chat/completions")
+            .header("Authorization", format!("Bearer {credential}"))
+            .header(
+                "HTTP-Referer",
+                "https://github.com/theonlyhennygod/zeroclaw",
+            )
+            .header("X-Title", "ZeroClaw")
+            .header("Accept", "text/event-stream")
+            .json(&native_request)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(super::api_error("OpenRouter", response).await);
+        }
+
+        super::compatible::read_native_chat_stream("OpenRouter", response, on_delta).await
+    }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
     fn supports_native_tools(&self) -> bool {
         // Disabled: many models on OpenRouter ignore or mishandle the native
         // tools API parameter, especially when the system prompt already
@@ -458,6 +509,7 @@ impl Provider for OpenRouterProvider {
             model: model.to_string(),
             messages: native_messages,
             temperature,
+            stream: None,
             tool_choice: native_tools.as_ref().map(|_| "auto".to_string()),
             tools: native_tools,
         };
@@ -535,10 +587,12 @@ mod tests {
             ChatMessage {
                 role: "system".into(),
                 content: "be concise".into(),
+                parts: Vec::new(),
             },
             ChatMessage {
                 role: "user".into(),
                 content: "hello".into(),
+                parts: Vec::new(),
             },
         ];
 
@@ -581,10 +635,12 @@ mod tests {
             ChatMessage {
                 role: "assistant".into(),
                 content: "Previous answer".into(),
+                parts: Vec::new(),
             },
             ChatMessage {
                 role: "user".into(),
                 content: "Follow-up".into(),
+                parts: Vec::new(),
             },
         ];
 
@@ -631,6 +687,7 @@ mod tests {
         let messages = vec![ChatMessage {
             role: "user".into(),
             content: "What is the date?".into(),
+            parts: Vec::new(),
         }];
         let tools = vec![serde_json::json!({
             "type": "function",
@@ -725,6 +782,7 @@ mod tests {
             role: "assistant".into(),
             content: r#"{"content":"Using tool","tool_calls":[{"id":"call_abc","name":"shell","arguments":"{\"command\":\"pwd\"}"}]}"#
                 .into(),
+            parts: Vec::new(),
         }];
 
         let converted = OpenRouterProvider::convert_messages(&messages);
@@ -744,6 +802,7 @@ mod tests {
         let messages = vec![ChatMessage {
             role: "tool".into(),
             content: r#"{"tool_call_id":"call_xyz","content":"done"}"#.into(),
+            parts: Vec::new(),
         }];
 
         let converted = OpenRouterProvider::convert_messages(&messages);
@@ -1,4 +1,6 @@
-use super::traits::{ChatMessage, ChatResponse, StreamChunk, StreamOptions, StreamResult};
+use super::traits::{
+    ChatMessage, ChatRequest, ChatResponse, StreamChunk, StreamOptions, StreamResult,
+};
 use super::Provider;
 use async_trait::async_trait;
 use futures_util::{stream, StreamExt};
@@ -45,14 +47,12 @@ fn is_non_retryable(err: &anyhow::Error) -> bool {
         return true;
     }
 
-    let model_catalog_mismatch = msg_lower.contains("model")
+    msg_lower.contains("model")
         && (msg_lower.contains("not found")
             || msg_lower.contains("unknown")
             || msg_lower.contains("unsupported")
             || msg_lower.contains("does not exist")
-            || msg_lower.contains("invalid"));
-
-    model_catalog_mismatch
+            || msg_lower.contains("invalid"))
 }
 
 /// Check if an error is a rate-limit (429) error.
@@ -477,6 +477,13 @@ impl Provider for ReliableProvider {
             .unwrap_or(false)
     }
 
+    fn supports_vision(&self) -> bool {
+        self.providers
+            .first()
+            .map(|(_, p)| p.supports_vision())
+            .unwrap_or(false)
+    }
+
     async fn chat_with_tools(
         &self,
         messages: &[ChatMessage],
@@ -578,6 +585,38 @@ impl Provider for ReliableProvider {
         )
     }
 
+    async fn stream_chat(
+        &self,
+        request: ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+        on_delta: &tokio::sync::mpsc::Sender<String>,
+    ) -> anyhow::Result<ChatResponse> {
+        // Only the primary provider is streamed. Retrying a half-delivered
+        // stream would repeat text, so failures fall back to the regular
+        // retry/failover chain without streaming.
+        if let Some((provider_name, provider)) = self.providers.first() {
+            if provider.supports_streaming() {
+                match provider
+                    .stream_chat(request, model, temperature, on_delta)
+                    .await
+                {
+                    Ok(resp) => return Ok(resp),
+                    Err(e) => {
+                        tracing::warn!(
+                            provider = provider_name,
+                            model,
+                            error = %compact_error_detail(&e),
+                            "Streaming call failed, falling back to non-streaming chain"
+                        );
+                    }
+                }
+            }
+        }
+
+        self.chat(request, model, temperature).await
+    }
+
     fn supports_streaming(&self) -> bool {
         self.providers.iter().any(|(_, p)| p.supports_streaming())
     }
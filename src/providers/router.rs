@@ -137,6 +137,20 @@ impl Provider for RouterProvider {
         provider.chat(request, &resolved_model, temperature).await
     }
 
+    async fn stream_chat(
+        &self,
+        request: ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+        on_delta: &tokio::sync::mpsc::Sender<String>,
+    ) -> anyhow::Result<ChatResponse> {
+        let (provider_idx, resolved_model) = self.resolve(model);
+        let (_, provider) = &self.providers[provider_idx];
+        provider
+            .stream_chat(request, &resolved_model, temperature, on_delta)
+            .await
+    }
+
     async fn chat_with_tools(
         &self,
         messages: &[ChatMessage],
@@ -158,6 +172,17 @@ impl Provider for RouterProvider {
             .unwrap_or(false)
     }
 
+    fn supports_vision(&self) -> bool {
+        self.providers
+            .get(self.default_index)
+            .map(|(_, p)| p.supports_vision())
+            .unwrap_or(false)
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.providers.iter().any(|(_, p)| p.supports_streaming())
+    }
+
     async fn warmup(&self) -> anyhow::Result<()> {
         for (name, provider) in &self.providers {
             tracing::info!(provider = name, "Warming up routed provider");
@@ -412,6 +437,7 @@ mod tests {
         let messages = vec![ChatMessage {
             role: "user".to_string(),
             content: "use tools".to_string(),
+            parts: Vec::new(),
         }];
         let tools = vec![serde_json::json!({
             "type": "function",
@@ -443,6 +469,7 @@ mod tests {
         let messages = vec![ChatMessage {
             role: "user".to_string(),
             content: "reason about this".to_string(),
+            parts: Vec::new(),
         }];
         let tools = vec![serde_json::json!({"type": "function", "function": {"name": "test"}})];
 
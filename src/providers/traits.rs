@@ -9,6 +9,34 @@ use std::fmt::Write;
 pub struct ChatMessage {
     pub role: String,
     pub content: String,
+    /// Non-text content (images) sent alongside `content`. Only honored by
+    /// providers that report `ProviderCapabilities::vision`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parts: Vec<ContentPart>,
+}
+
+/// A non-text content part attached to a chat message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentPart {
+    /// Inline image payload, base64-encoded without the `data:` prefix.
+    Image { mime_type: String, data: String },
+}
+
+impl ContentPart {
+    pub fn image(mime_type: impl Into<String>, data: impl Into<String>) -> Self {
+        Self::Image {
+            mime_type: mime_type.into(),
+            data: data.into(),
+        }
+    }
+
+    /// Render the part as a `data:` URI (OpenAI-style `image_url`).
+    pub fn to_data_uri(&self) -> String {
+        match self {
+            Self::Image { mime_type, data } => format!("data:{mime_type};base64,{data}"),
+        }
+    }
 }
 
 impl ChatMessage {
@@ -16,6 +44,7 @@ impl ChatMessage {
         Self {
             role: "system".into(),
             content: content.into(),
+            parts: Vec::new(),
         }
     }
 
@@ -23,6 +52,7 @@ impl ChatMessage {
         Self {
             role: "user".into(),
             content: content.into(),
+            parts: Vec::new(),
         }
     }
 
@@ -30,6 +60,7 @@ impl ChatMessage {
         Self {
             role: "assistant".into(),
             content: content.into(),
+            parts: Vec::new(),
         }
     }
 
@@ -37,8 +68,23 @@ impl ChatMessage {
         Self {
             role: "tool".into(),
             content: content.into(),
+            parts: Vec::new(),
         }
     }
+
+    /// Attach non-text content parts to this message.
+    #[must_use]
+    pub fn with_parts(mut self, parts: Vec<ContentPart>) -> Self {
+        self.parts = parts;
+        self
+    }
+
+    /// Iterate over attached images as `(mime_type, base64_data)` pairs.
+    pub fn images(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.parts.iter().map(|part| match part {
+            ContentPart::Image { mime_type, data } => (mime_type.as_str(), data.as_str()),
+        })
+    }
 }
 
 /// A tool call requested by the LLM.
@@ -205,6 +251,11 @@ pub struct ProviderCapabilities {
     ///
     /// When `false`, tools must be injected via system prompt as text.
     pub native_tool_calling: bool,
+    /// Whether the provider accepts image content parts in chat messages.
+    ///
+    /// When `false`, image parts are replaced with a short text note before
+    /// the request is sent.
+    pub vision: bool,
 }
 
 /// Provider-specific tool payload formats.
@@ -351,6 +402,11 @@ pub trait Provider: Send + Sync {
         self.capabilities().native_tool_calling
     }
 
+    /// Whether provider accepts image content parts in chat messages.
+    fn supports_vision(&self) -> bool {
+        self.capabilities().vision
+    }
+
     /// Warm up the HTTP connection pool (TLS handshake, DNS, HTTP/2 setup).
     /// Default implementation is a no-op; providers with HTTP clients should override.
     async fn warmup(&self) -> anyhow::Result<()> {
@@ -380,6 +436,20 @@ pub trait Provider: Send + Sync {
         false
     }
 
+    /// Structured chat that forwards assistant text deltas to `on_delta` as
+    /// they arrive and returns the complete response once the stream ends.
+    /// Tool calls are only reported in the returned response.
+    /// Default implementation falls back to [`Provider::chat`] without deltas.
+    async fn stream_chat(
+        &self,
+        request: ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+        _on_delta: &tokio::sync::mpsc::Sender<String>,
+    ) -> anyhow::Result<ChatResponse> {
+        self.chat(request, model, temperature).await
+    }
+
     /// Streaming chat with optional system prompt.
     /// Returns an async stream of text chunks.
     /// Default implementation falls back to non-streaming chat.
@@ -458,6 +528,7 @@ mod tests {
         fn capabilities(&self) -> ProviderCapabilities {
             ProviderCapabilities {
                 native_tool_calling: true,
+                vision: false,
             }
         }
 
@@ -472,6 +543,29 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn default_stream_chat_falls_back_to_chat_without_deltas() {
+        let provider = CapabilityMockProvider;
+        let (tx, mut rx) = tokio::sync::mpsc::channel(4);
+        let messages = [ChatMessage::user("hello")];
+        let resp = provider
+            .stream_chat(
+                ChatRequest {
+                    messages: &messages,
+                    tools: None,
+                },
+                "model",
+                0.0,
+                &tx,
+            )
+            .await
+            .unwrap();
+        drop(tx);
+
+        assert_eq!(resp.text.as_deref(), Some("ok"));
+        assert!(rx.recv().await.is_none());
+    }
+
     #[test]
     fn chat_message_constructors() {
         let sys = ChatMessage::system("Be helpful");
@@ -488,6 +582,32 @@ mod tests {
         assert_eq!(tool.role, "tool");
     }
 
+    #[test]
+    fn chat_message_parts_serialize_only_when_present() {
+        let plain = serde_json::to_value(ChatMessage::user("Hello")).unwrap();
+        assert!(plain.get("parts").is_none());
+
+        let with_image = ChatMessage::user("Look")
+            .with_parts(vec![ContentPart::image("image/png", "iVBORw0KGgo=")]);
+        assert_eq!(
+            with_image.images().collect::<Vec<_>>(),
+            vec![("image/png", "iVBORw0KGgo=")]
+        );
+        assert_eq!(
+            with_image.parts[0].to_data_uri(),
+            "data:image/png;base64,iVBORw0KGgo="
+        );
+
+        let json = serde_json::to_value(&with_image).unwrap();
+        assert_eq!(json["parts"][0]["type"], "image");
+        let restored: ChatMessage = serde_json::from_value(json).unwrap();
+        assert_eq!(restored.parts, with_image.parts);
+
+        let legacy: ChatMessage =
+            serde_json::from_str(r#"{"role":"user","content":"hi"}"#).unwrap();
+        assert!(legacy.parts.is_empty());
+    }
+
     #[test]
     fn chat_response_helpers() {
         let empty = ChatResponse {
@@ -539,18 +659,22 @@ mod tests {
     fn provider_capabilities_default() {
         let caps = ProviderCapabilities::default();
         assert!(!caps.native_tool_calling);
+        assert!(!caps.vision);
     }
 
     #[test]
     fn provider_capabilities_equality() {
         let caps1 = ProviderCapabilities {
             native_tool_calling: true,
+            vision: false,
         };
         let caps2 = ProviderCapabilities {
             native_tool_calling: true,
+            vision: false,
         };
         let caps3 = ProviderCapabilities {
             native_tool_calling: false,
+            vision: false,
         };
 
         assert_eq!(caps1, caps2);
@@ -1,17 +1,47 @@
 //! Audit logging for security events
+//!
+//! Events form a hash chain: each carries a sequence number, the hash of the
+//! previous event and its own hash over the canonical JSON of the event. With
+//! `sign_events`, the hash is also HMAC-signed with a key derived from the
+//! secret store. A `<log_path>.head` file records the latest event so that
+//! cutting events off the end is detected too. [`AuditLogger::verify`] checks
+//! the chain across the current and rotated files. Writers hold an exclusive
+//! lock on `<log_path>.lock` while extending the chain, so processes sharing
+//! the log (daemon, CLI) never fork it.
+//!
+//! The agent writes through a process-wide logger ([`init`], [`record`]):
+//! tool calls, approval decisions and policy decisions all end up here.
 
+use super::secrets::SecretStore;
 use crate::config::AuditConfig;
-use anyhow::Result;
+use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
 use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::fs::OpenOptions;
-use std::io::Write;
-use std::path::PathBuf;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 use uuid::Uuid;
 
+static GLOBAL: OnceLock<AuditLogger> = OnceLock::new();
+
+/// `prev_hash` of the first event in a chain.
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Secret store purpose for the signing key.
+const SIGNING_KEY_PURPOSE: &str = "zeroclaw-audit-log";
+
+/// Rotated files kept by [`AuditLogger::rotate`] (`<log>.1.log` … `<log>.10.log`).
+const MAX_ROTATED_FILES: usize = 10;
+
+/// Tail read when recovering the chain head from the log itself.
+const TAIL_READ_BYTES: u64 = 64 * 1024;
+
 /// Audit event types
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum AuditEventType {
     CommandExecution,
@@ -21,6 +51,59 @@ pub enum AuditEventType {
     AuthFailure,
     PolicyViolation,
     SecurityEvent,
+    /// A tool call by the agent, run or refused
+    ToolCall,
+    /// A person (or the approval timeout) answered a tool approval request
+    ApprovalDecision,
+    /// A tool argument rule (`[autonomy] policy_file`) decided on a tool call
+    PolicyDecision,
+}
+
+impl AuditEventType {
+    pub const ALL: [Self; 10] = [
+        Self::CommandExecution,
+        Self::FileAccess,
+        Self::ConfigChange,
+        Self::AuthSuccess,
+        Self::AuthFailure,
+        Self::PolicyViolation,
+        Self::SecurityEvent,
+        Self::ToolCall,
+        Self::ApprovalDecision,
+        Self::PolicyDecision,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::CommandExecution => "command_execution",
+            Self::FileAccess => "file_access",
+            Self::ConfigChange => "config_change",
+            Self::AuthSuccess => "auth_success",
+            Self::AuthFailure => "auth_failure",
+            Self::PolicyViolation => "policy_violation",
+            Self::SecurityEvent => "security_event",
+            Self::ToolCall => "tool_call",
+            Self::ApprovalDecision => "approval_decision",
+            Self::PolicyDecision => "policy_decision",
+        }
+    }
+}
+
+impl std::str::FromStr for AuditEventType {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.as_str() == value)
+            .ok_or_else(|| {
+                let names: Vec<&str> = Self::ALL.iter().map(|kind| kind.as_str()).collect();
+                anyhow::anyhow!(
+                    "unknown event type '{value}' (expected one of: {})",
+                    names.join(", ")
+                )
+            })
+    }
 }
 
 /// Actor information (who performed the action)
@@ -38,6 +121,12 @@ pub struct Action {
     pub risk_level: Option<String>,
     pub approved: bool,
     pub allowed: bool,
+    /// Tool name for tool calls and approval decisions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+    /// Summary of the tool arguments, credentials redacted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arguments: Option<String>,
 }
 
 /// Execution result
@@ -55,6 +144,12 @@ pub struct SecurityContext {
     pub policy_violation: bool,
     pub rate_limit_remaining: Option<u32>,
     pub sandbox_backend: Option<String>,
+    /// Tool argument rule that decided the call ("default" when none matched)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy_rule: Option<String>,
+    /// Decision of that rule: allow, deny or require_approval
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy_decision: Option<String>,
 }
 
 /// Complete audit event
@@ -67,6 +162,24 @@ pub struct AuditEvent {
     pub action: Option<Action>,
     pub result: Option<ExecutionResult>,
     pub security: SecurityContext,
+    /// Position in the hash chain, starting at 1. Zero for events written
+    /// before chaining existed.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub seq: u64,
+    /// Hash of the previous event ([`GENESIS_HASH`] for the first).
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub prev_hash: String,
+    /// SHA-256 over the event without `hash` and `signature`.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub hash: String,
+    /// HMAC-SHA256 of `hash` when `sign_events` is on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn is_zero(value: &u64) -> bool {
+    *value == 0
 }
 
 impl AuditEvent {
@@ -83,7 +196,13 @@ impl AuditEvent {
                 policy_violation: false,
                 rate_limit_remaining: None,
                 sandbox_backend: None,
+                policy_rule: None,
+                policy_decision: None,
             },
+            seq: 0,
+            prev_hash: String::new(),
+            hash: String::new(),
+            signature: None,
         }
     }
 
@@ -115,10 +234,52 @@ impl AuditEvent {
             risk_level: Some(risk_level),
             approved,
             allowed,
+            tool: None,
+            arguments: None,
         });
         self
     }
 
+    /// Set a tool call as the action
+    pub fn with_tool(
+        mut self,
+        tool: String,
+        arguments: String,
+        approved: bool,
+        allowed: bool,
+    ) -> Self {
+        self.action = Some(Action {
+            command: None,
+            risk_level: None,
+            approved,
+            allowed,
+            tool: Some(tool),
+            arguments: Some(arguments),
+        });
+        self
+    }
+
+    /// Flag the event as a policy violation
+    pub fn with_policy_violation(mut self) -> Self {
+        self.security.policy_violation = true;
+        self
+    }
+
+    /// Record the tool argument rule that decided the action
+    pub fn with_policy_rule(mut self, rule: String, decision: &str) -> Self {
+        self.security.policy_rule = Some(rule);
+        self.security.policy_decision = Some(decision.to_string());
+        self
+    }
+
+    /// Whether the action went ahead. Events without an action count as
+    /// allowed unless they are policy violations.
+    pub fn allowed(&self) -> bool {
+        self.action
+            .as_ref()
+            .map_or(!self.security.policy_violation, |action| action.allowed)
+    }
+
     /// Set the result
     pub fn with_result(
         mut self,
@@ -148,6 +309,88 @@ pub struct AuditLogger {
     log_path: PathBuf,
     config: AuditConfig,
     buffer: Mutex<Vec<AuditEvent>>,
+    signing_key: Option<Vec<u8>>,
+}
+
+/// Latest chained event, stored in `<log_path>.head`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct ChainHead {
+    seq: u64,
+    hash: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    signature: Option<String>,
+}
+
+/// Filters for [`AuditLogger::search`]. Unset fields match everything.
+#[derive(Debug, Clone, Default)]
+pub struct AuditFilter {
+    pub event_type: Option<AuditEventType>,
+    pub channel: Option<String>,
+    /// Matches the actor's user id or username
+    pub user: Option<String>,
+    pub tool: Option<String>,
+    /// Matched case-insensitively
+    pub risk_level: Option<String>,
+    pub allowed: Option<bool>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl AuditFilter {
+    pub fn matches(&self, event: &AuditEvent) -> bool {
+        let actor = event.actor.as_ref();
+        let action = event.action.as_ref();
+        self.event_type.is_none_or(|kind| event.event_type == kind)
+            && self
+                .channel
+                .as_deref()
+                .is_none_or(|channel| actor.is_some_and(|a| a.channel == channel))
+            && self.user.as_deref().is_none_or(|user| {
+                actor.is_some_and(|a| {
+                    a.user_id.as_deref() == Some(user) || a.username.as_deref() == Some(user)
+                })
+            })
+            && self
+                .tool
+                .as_deref()
+                .is_none_or(|tool| action.is_some_and(|a| a.tool.as_deref() == Some(tool)))
+            && self.risk_level.as_deref().is_none_or(|risk| {
+                action
+                    .and_then(|a| a.risk_level.as_deref())
+                    .is_some_and(|level| level.eq_ignore_ascii_case(risk))
+            })
+            && self
+                .allowed
+                .is_none_or(|allowed| event.allowed() == allowed)
+            && self.since.is_none_or(|since| event.timestamp >= since)
+            && self.until.is_none_or(|until| event.timestamp <= until)
+    }
+}
+
+/// Outcome of [`AuditLogger::verify`].
+#[derive(Debug, Default)]
+pub struct VerifyReport {
+    /// Files checked, oldest first.
+    pub files: Vec<PathBuf>,
+    /// Chained events checked.
+    pub events: u64,
+    /// Events written before chaining existed.
+    pub unchained: u64,
+    pub first_seq: Option<u64>,
+    pub last_seq: Option<u64>,
+    /// Whether signatures were checked (`sign_events` on).
+    pub signatures_checked: bool,
+    /// Observations that are not tampering, e.g. a chain starting mid-way
+    /// because older files were rotated out.
+    pub notes: Vec<String>,
+    /// Signs of tampering: edits, removals, reordering or truncation.
+    pub problems: Vec<String>,
+}
+
+impl VerifyReport {
+    pub fn is_intact(&self) -> bool {
+        self.problems.is_empty()
+    }
 }
 
 /// Structured command execution details for audit logging.
@@ -166,24 +409,58 @@ impl AuditLogger {
     /// Create a new audit logger
     pub fn new(config: AuditConfig, zeroclaw_dir: PathBuf) -> Result<Self> {
         let log_path = zeroclaw_dir.join(&config.log_path);
+        let signing_key = if config.sign_events {
+            Some(
+                SecretStore::new(&zeroclaw_dir, true)
+                    .derive_key(SIGNING_KEY_PURPOSE)
+                    .context("Failed to load audit signing key")?,
+            )
+        } else {
+            None
+        };
         Ok(Self {
             log_path,
             config,
             buffer: Mutex::new(Vec::new()),
+            signing_key,
         })
     }
 
-    /// Log an event
+    /// Logger for `[security.audit]`, rooted next to the config file.
+    pub fn from_config(config: &crate::Config) -> Result<Self> {
+        Self::new(
+            config.security.audit.clone(),
+            crate::auth::state_dir_from_config(config),
+        )
+    }
+
+    /// Log an event, appending it to the hash chain
     pub fn log(&self, event: &AuditEvent) -> Result<()> {
         if !self.config.enabled {
             return Ok(());
         }
 
+        // Held until the head is written: reading the head, appending and
+        // writing the new head must not interleave with another writer.
+        let _chain = self.lock_chain(true)?;
+
         // Check log size and rotate if needed
         self.rotate_if_needed()?;
 
+        // Re-read the head on every write so other processes sharing the log
+        // (daemon, CLI) extend the same chain.
+        let (seq, prev_hash) = match self.read_head().or_else(|| self.recover_head()) {
+            Some(head) => (head.seq + 1, head.hash),
+            None => (1, GENESIS_HASH.to_string()),
+        };
+        let mut event = event.clone();
+        event.seq = seq;
+        event.prev_hash = prev_hash;
+        event.hash = event_hash(&serde_json::to_value(&event)?)?;
+        event.signature = self.sign(&event.hash);
+
         // Serialize and write
-        let line = serde_json::to_string(event)?;
+        let line = serde_json::to_string(&event)?;
         let mut file = OpenOptions::new()
             .create(true)
             .append(true)
@@ -192,7 +469,11 @@ impl AuditLogger {
         writeln!(file, "{}", line)?;
         file.sync_all()?;
 
-        Ok(())
+        self.write_head(&ChainHead {
+            seq: event.seq,
+            signature: self.sign(&head_message(event.seq, &event.hash)),
+            hash: event.hash,
+        })
     }
 
     /// Log a command execution event.
@@ -233,6 +514,221 @@ impl AuditLogger {
         })
     }
 
+    /// Events matching `filter` across rotated and current files, oldest
+    /// first. Lines that do not parse are skipped; `verify` reports them.
+    pub fn search(&self, filter: &AuditFilter) -> Result<Vec<AuditEvent>> {
+        let mut events = Vec::new();
+        for path in self.log_files() {
+            let content = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            events.extend(
+                content
+                    .lines()
+                    .filter_map(|line| serde_json::from_str::<AuditEvent>(line).ok())
+                    .filter(|event| filter.matches(event)),
+            );
+        }
+        Ok(events)
+    }
+
+    /// Check the hash chain across rotated and current files, oldest first,
+    /// and against the recorded head.
+    pub fn verify(&self) -> Result<VerifyReport> {
+        // Keep writers from moving the head mid-check. Best effort, so a
+        // read-only copy of the log can still be verified.
+        let _chain = self.lock_chain(false).ok();
+        let mut report = VerifyReport {
+            files: self.log_files(),
+            signatures_checked: self.signing_key.is_some(),
+            ..VerifyReport::default()
+        };
+        let mut last: Option<(u64, String)> = None;
+
+        for path in report.files.clone() {
+            let content = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            for (index, line) in content.lines().enumerate() {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let at = format!("{name}:{}", index + 1);
+                let Ok(value) = serde_json::from_str::<serde_json::Value>(line) else {
+                    report.problems.push(format!("{at}: not a valid event"));
+                    continue;
+                };
+                let Ok(event) = serde_json::from_value::<AuditEvent>(value.clone()) else {
+                    report.problems.push(format!("{at}: not a valid event"));
+                    continue;
+                };
+
+                if event.hash.is_empty() {
+                    if last.is_some() {
+                        report
+                            .problems
+                            .push(format!("{at}: event without hash inside the chain"));
+                    } else {
+                        report.unchained += 1;
+                    }
+                    continue;
+                }
+
+                report.events += 1;
+                if event_hash(&value)? != event.hash {
+                    report
+                        .problems
+                        .push(format!("{at}: seq {} was modified", event.seq));
+                }
+                if let Some(key) = &self.signing_key {
+                    if event.signature.as_deref() != Some(hmac_hex(key, &event.hash).as_str()) {
+                        report
+                            .problems
+                            .push(format!("{at}: seq {} has a bad signature", event.seq));
+                    }
+                }
+
+                match &last {
+                    None if event.seq == 1 && event.prev_hash == GENESIS_HASH => {}
+                    None => report.notes.push(format!(
+                        "{at}: chain starts at seq {}; earlier events were rotated out",
+                        event.seq
+                    )),
+                    Some((_, hash)) if event.seq == 1 && event.prev_hash == GENESIS_HASH => {
+                        report.problems.push(format!(
+                            "{at}: new chain started after {}; head file was lost or reset",
+                            &hash[..12.min(hash.len())]
+                        ));
+                    }
+                    Some((seq, hash)) => {
+                        if event.seq != seq + 1 {
+                            report.problems.push(format!(
+                                "{at}: expected seq {}, found {} (events removed or reordered)",
+                                seq + 1,
+                                event.seq
+                            ));
+                        } else if event.prev_hash != *hash {
+                            report.problems.push(format!(
+                                "{at}: seq {} does not follow the previous event",
+                                event.seq
+                            ));
+                        }
+                    }
+                }
+                report.first_seq.get_or_insert(event.seq);
+                report.last_seq = Some(event.seq);
+                last = Some((event.seq, event.hash));
+            }
+        }
+
+        let head_path = self.head_path();
+        if head_path.exists() {
+            match self.read_head() {
+                None => report.problems.push(format!(
+                    "{} is unreadable",
+                    head_path.file_name().unwrap_or_default().to_string_lossy()
+                )),
+                Some(head) => {
+                    if let Some(key) = &self.signing_key {
+                        let expected = hmac_hex(key, &head_message(head.seq, &head.hash));
+                        if head.signature.as_deref() != Some(expected.as_str()) {
+                            report
+                                .problems
+                                .push("chain head has a bad signature".into());
+                        }
+                    }
+                    match &last {
+                        Some((seq, _)) if head.seq > *seq => report.problems.push(format!(
+                            "log truncated: head is seq {} but the last event is seq {seq}",
+                            head.seq
+                        )),
+                        None => report.problems.push(format!(
+                            "log truncated: head is seq {} but no chained events remain",
+                            head.seq
+                        )),
+                        Some((seq, hash)) if head.seq == *seq && head.hash != *hash => {
+                            report
+                                .problems
+                                .push(format!("seq {seq} does not match the chain head"));
+                        }
+                        Some(_) => {}
+                    }
+                }
+            }
+        } else if last.is_some() {
+            // `log` always writes the head, so chained events without one
+            // mean it was deleted, possibly to hide a cut-off tail.
+            report.problems.push(format!(
+                "{} is missing; events may have been cut off the end",
+                head_path.file_name().unwrap_or_default().to_string_lossy()
+            ));
+        }
+
+        Ok(report)
+    }
+
+    /// Rotated files oldest first, then the current log. Only existing files.
+    pub fn log_files(&self) -> Vec<PathBuf> {
+        let mut files: Vec<PathBuf> = (1..=MAX_ROTATED_FILES)
+            .rev()
+            .map(|i| PathBuf::from(format!("{}.{i}.log", self.log_path.display())))
+            .filter(|path| path.exists())
+            .collect();
+        if self.log_path.exists() {
+            files.push(self.log_path.clone());
+        }
+        files
+    }
+
+    /// Lock `<log_path>.lock`, exclusively for writers and shared for
+    /// readers. Released when the returned file is dropped.
+    fn lock_chain(&self, exclusive: bool) -> Result<std::fs::File> {
+        let path = PathBuf::from(format!("{}.lock", self.log_path.display()));
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        if exclusive {
+            file.lock()
+        } else {
+            file.lock_shared()
+        }
+        .with_context(|| format!("Failed to lock {}", path.display()))?;
+        Ok(file)
+    }
+
+    fn head_path(&self) -> PathBuf {
+        PathBuf::from(format!("{}.head", self.log_path.display()))
+    }
+
+    fn read_head(&self) -> Option<ChainHead> {
+        let raw = std::fs::read_to_string(self.head_path()).ok()?;
+        serde_json::from_str(&raw).ok()
+    }
+
+    fn write_head(&self, head: &ChainHead) -> Result<()> {
+        std::fs::write(self.head_path(), serde_json::to_string(head)?)
+            .context("Failed to write audit chain head")
+    }
+
+    /// Chain head from the last event on disk, for logs without a head file.
+    fn recover_head(&self) -> Option<ChainHead> {
+        self.log_files().iter().rev().find_map(|path| {
+            let event =
+                last_line(path).and_then(|line| serde_json::from_str::<AuditEvent>(&line).ok())?;
+            (!event.hash.is_empty()).then_some(ChainHead {
+                seq: event.seq,
+                hash: event.hash,
+                signature: None,
+            })
+        })
+    }
+
+    fn sign(&self, message: &str) -> Option<String> {
+        self.signing_key.as_ref().map(|key| hmac_hex(key, message))
+    }
+
     /// Rotate log if it exceeds max size
     fn rotate_if_needed(&self) -> Result<()> {
         if let Ok(metadata) = std::fs::metadata(&self.log_path) {
@@ -246,7 +742,7 @@ impl AuditLogger {
 
     /// Rotate the log file
     fn rotate(&self) -> Result<()> {
-        for i in (1..10).rev() {
+        for i in (1..MAX_ROTATED_FILES).rev() {
             let old_name = format!("{}.{}.log", self.log_path.display(), i);
             let new_name = format!("{}.{}.log", self.log_path.display(), i + 1);
             let _ = std::fs::rename(&old_name, &new_name);
@@ -258,9 +754,64 @@ impl AuditLogger {
     }
 }
 
+/// Install the process-wide logger used by [`record`]. Later calls are
+/// ignored.
+pub fn init(config: &crate::Config) -> Result<()> {
+    if GLOBAL.get().is_none() {
+        let _ = GLOBAL.set(AuditLogger::from_config(config)?);
+    }
+    Ok(())
+}
+
+/// Append to the process-wide log. A no-op before [`init`]; write failures
+/// are logged rather than returned so auditing never aborts the action.
+pub fn record(event: &AuditEvent) {
+    if let Some(logger) = GLOBAL.get() {
+        if let Err(e) = logger.log(event) {
+            tracing::warn!("Failed to write audit event: {e:#}");
+        }
+    }
+}
+
+/// SHA-256 over an event's JSON with `hash` and `signature` removed. Works
+/// on the parsed value so re-verifying a line hashes exactly what was written.
+fn event_hash(event: &serde_json::Value) -> Result<String> {
+    let mut value = event.clone();
+    if let Some(fields) = value.as_object_mut() {
+        fields.remove("hash");
+        fields.remove("signature");
+    }
+    Ok(hex::encode(Sha256::digest(serde_json::to_vec(&value)?)))
+}
+
+fn hmac_hex(key: &[u8], message: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(message.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+fn head_message(seq: u64, hash: &str) -> String {
+    format!("head:{seq}:{hash}")
+}
+
+/// Last non-empty line of a file, reading only its tail.
+fn last_line(path: &Path) -> Option<String> {
+    let mut file = std::fs::File::open(path).ok()?;
+    let len = file.metadata().ok()?.len();
+    file.seek(SeekFrom::Start(len.saturating_sub(TAIL_READ_BYTES)))
+        .ok()?;
+    let mut tail = String::new();
+    file.read_to_string(&mut tail).ok()?;
+    tail.lines()
+        .rev()
+        .find(|line| !line.trim().is_empty())
+        .map(str::to_string)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::Arc;
     use tempfile::TempDir;
 
     #[test]
@@ -420,4 +971,288 @@ mod tests {
         );
         Ok(())
     }
+
+    // ── Hash chain ──────────────────────────────────────────
+
+    fn chained_logger(tmp: &TempDir, max_size_mb: u32, sign_events: bool) -> AuditLogger {
+        let config = AuditConfig {
+            enabled: true,
+            max_size_mb,
+            sign_events,
+            ..Default::default()
+        };
+        AuditLogger::new(config, tmp.path().to_path_buf()).unwrap()
+    }
+
+    fn log_commands(logger: &AuditLogger, commands: &[&str]) {
+        for command in commands {
+            let event = AuditEvent::new(AuditEventType::CommandExecution)
+                .with_actor("cli".to_string(), None, None)
+                .with_action((*command).to_string(), "low".to_string(), false, true);
+            logger.log(&event).unwrap();
+        }
+    }
+
+    fn rewrite_lines(path: &Path, edit: impl FnOnce(&mut Vec<String>)) {
+        let mut lines: Vec<String> = std::fs::read_to_string(path)
+            .unwrap()
+            .lines()
+            .map(str::to_string)
+            .collect();
+        edit(&mut lines);
+        std::fs::write(path, lines.join("\n") + "\n").unwrap();
+    }
+
+    #[test]
+    fn logged_events_form_a_verifiable_chain() {
+        let tmp = TempDir::new().unwrap();
+        let logger = chained_logger(&tmp, 10, false);
+        log_commands(&logger, &["ls", "pwd", "whoami"]);
+
+        let content = std::fs::read_to_string(tmp.path().join("audit.log")).unwrap();
+        let events: Vec<AuditEvent> = content
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(events[0].seq, 1);
+        assert_eq!(events[0].prev_hash, GENESIS_HASH);
+        assert_eq!(events[1].prev_hash, events[0].hash);
+        assert_eq!(events[2].seq, 3);
+
+        let report = logger.verify().unwrap();
+        assert!(report.is_intact(), "{:?}", report.problems);
+        assert_eq!(report.events, 3);
+        assert_eq!((report.first_seq, report.last_seq), (Some(1), Some(3)));
+    }
+
+    #[test]
+    fn verify_detects_edited_removed_and_reordered_events() {
+        let tmp = TempDir::new().unwrap();
+        let log_path = tmp.path().join("audit.log");
+        let logger = chained_logger(&tmp, 10, false);
+        log_commands(&logger, &["ls", "rm -rf build", "pwd", "whoami"]);
+        let original = std::fs::read_to_string(&log_path).unwrap();
+
+        rewrite_lines(&log_path, |lines| {
+            lines[1] = lines[1].replace("rm -rf build", "echo ok");
+        });
+        let report = logger.verify().unwrap();
+        assert!(report.problems.iter().any(|p| p.contains("was modified")));
+
+        std::fs::write(&log_path, &original).unwrap();
+        rewrite_lines(&log_path, |lines| {
+            lines.remove(1);
+        });
+        let report = logger.verify().unwrap();
+        assert!(report.problems.iter().any(|p| p.contains("expected seq 2")));
+
+        std::fs::write(&log_path, &original).unwrap();
+        rewrite_lines(&log_path, |lines| lines.swap(1, 2));
+        assert!(!logger.verify().unwrap().is_intact());
+    }
+
+    #[test]
+    fn verify_detects_truncated_tail() {
+        let tmp = TempDir::new().unwrap();
+        let log_path = tmp.path().join("audit.log");
+        let logger = chained_logger(&tmp, 10, false);
+        log_commands(&logger, &["ls", "pwd", "whoami"]);
+
+        rewrite_lines(&log_path, |lines| {
+            lines.pop();
+        });
+        let report = logger.verify().unwrap();
+        assert!(report.problems.iter().any(|p| p.contains("log truncated")));
+    }
+
+    #[test]
+    fn verify_rejects_a_deleted_head_file() {
+        let tmp = TempDir::new().unwrap();
+        let log_path = tmp.path().join("audit.log");
+        let logger = chained_logger(&tmp, 10, true);
+        log_commands(&logger, &["ls", "pwd", "whoami"]);
+
+        std::fs::remove_file(tmp.path().join("audit.log.head")).unwrap();
+        rewrite_lines(&log_path, |lines| {
+            lines.pop();
+        });
+        let report = logger.verify().unwrap();
+        assert!(!report.is_intact());
+        assert!(report
+            .problems
+            .iter()
+            .any(|p| p.contains("audit.log.head is missing")));
+    }
+
+    #[test]
+    fn concurrent_loggers_on_one_log_keep_a_single_chain() {
+        let tmp = TempDir::new().unwrap();
+        let loggers: Vec<Arc<AuditLogger>> = (0..2)
+            .map(|_| Arc::new(chained_logger(&tmp, 10, false)))
+            .collect();
+        let writers: Vec<_> = loggers
+            .iter()
+            .enumerate()
+            .map(|(n, logger)| {
+                let logger = Arc::clone(logger);
+                std::thread::spawn(move || {
+                    for i in 0..25 {
+                        log_commands(&logger, &[&format!("writer {n} event {i}")]);
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        let report = loggers[0].verify().unwrap();
+        assert!(report.is_intact(), "{:?}", report.problems);
+        assert_eq!(report.events, 50);
+        assert_eq!(report.last_seq, Some(50));
+    }
+
+    #[test]
+    fn chain_continues_across_rotated_files() {
+        let tmp = TempDir::new().unwrap();
+        // Rotate before every write after the first.
+        let logger = chained_logger(&tmp, 0, false);
+        log_commands(&logger, &["ls", "pwd", "whoami"]);
+
+        let report = logger.verify().unwrap();
+        assert_eq!(report.files.len(), 3);
+        assert!(report.files[0].ends_with("audit.log.2.log"));
+        assert!(report.is_intact(), "{:?}", report.problems);
+        assert_eq!(report.events, 3);
+
+        std::fs::remove_file(tmp.path().join("audit.log.1.log")).unwrap();
+        let report = logger.verify().unwrap();
+        assert!(report.problems.iter().any(|p| p.contains("expected seq 2")));
+    }
+
+    #[test]
+    fn signed_chain_rejects_recomputed_hashes() {
+        let tmp = TempDir::new().unwrap();
+        let log_path = tmp.path().join("audit.log");
+        let logger = chained_logger(&tmp, 10, true);
+        log_commands(&logger, &["ls", "pwd"]);
+        assert!(logger.verify().unwrap().is_intact());
+        assert!(logger.verify().unwrap().signatures_checked);
+
+        // Edit an event and fix up its hash without the key.
+        rewrite_lines(&log_path, |lines| {
+            let mut value: serde_json::Value = serde_json::from_str(&lines[1]).unwrap();
+            value["action"]["command"] = "echo ok".into();
+            value["hash"] = event_hash(&value).unwrap().into();
+            lines[1] = value.to_string();
+        });
+        let report = logger.verify().unwrap();
+        assert!(report.problems.iter().any(|p| p.contains("bad signature")));
+        assert!(!report.problems.iter().any(|p| p.contains("was modified")));
+    }
+
+    #[test]
+    fn legacy_lines_before_the_chain_are_counted_not_rejected() {
+        let tmp = TempDir::new().unwrap();
+        let log_path = tmp.path().join("audit.log");
+        let legacy = serde_json::to_string(&AuditEvent::new(AuditEventType::AuthSuccess)).unwrap();
+        std::fs::write(&log_path, format!("{legacy}\n")).unwrap();
+
+        let logger = chained_logger(&tmp, 10, false);
+        log_commands(&logger, &["ls"]);
+        let report = logger.verify().unwrap();
+        assert!(report.is_intact(), "{:?}", report.problems);
+        assert_eq!((report.unchained, report.events), (1, 1));
+    }
+
+    // ── Search ──────────────────────────────────────────────
+
+    #[test]
+    fn search_filters_events_across_rotated_files() {
+        let tmp = TempDir::new().unwrap();
+        let logger = chained_logger(&tmp, 0, false);
+        logger
+            .log(
+                &AuditEvent::new(AuditEventType::ToolCall)
+                    .with_actor("slack".into(), Some("U42".into()), None)
+                    .with_tool("shell".into(), "command: ls".into(), false, true)
+                    .with_result(true, None, 5, None),
+            )
+            .unwrap();
+        logger
+            .log(
+                &AuditEvent::new(AuditEventType::ApprovalDecision)
+                    .with_actor("slack".into(), Some("telegram:alice".into()), None)
+                    .with_tool(
+                        "shell".into(),
+                        "command: rm -rf /tmp/x".into(),
+                        false,
+                        false,
+                    ),
+            )
+            .unwrap();
+        logger
+            .log(
+                &AuditEvent::new(AuditEventType::CommandExecution)
+                    .with_actor("cli".into(), None, None)
+                    .with_action("git push".into(), "High".into(), true, true),
+            )
+            .unwrap();
+        assert_eq!(logger.log_files().len(), 3);
+
+        let all = logger.search(&AuditFilter::default()).unwrap();
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0].seq, 1, "search returns events oldest first");
+
+        let search = |filter: AuditFilter| logger.search(&filter).unwrap().len();
+        assert_eq!(
+            search(AuditFilter {
+                event_type: Some(AuditEventType::ToolCall),
+                ..Default::default()
+            }),
+            1
+        );
+        assert_eq!(
+            search(AuditFilter {
+                channel: Some("slack".into()),
+                tool: Some("shell".into()),
+                ..Default::default()
+            }),
+            2
+        );
+        assert_eq!(
+            search(AuditFilter {
+                user: Some("telegram:alice".into()),
+                allowed: Some(false),
+                ..Default::default()
+            }),
+            1
+        );
+        assert_eq!(
+            search(AuditFilter {
+                risk_level: Some("high".into()),
+                ..Default::default()
+            }),
+            1
+        );
+        assert_eq!(
+            search(AuditFilter {
+                since: Some(Utc::now() + chrono::Duration::hours(1)),
+                ..Default::default()
+            }),
+            0
+        );
+    }
+
+    #[test]
+    fn event_type_names_roundtrip() {
+        for kind in AuditEventType::ALL {
+            assert_eq!(kind.as_str().parse::<AuditEventType>().unwrap(), kind);
+            assert_eq!(
+                serde_json::to_value(kind).unwrap(),
+                serde_json::json!(kind.as_str())
+            );
+        }
+        assert!("shell".parse::<AuditEventType>().is_err());
+    }
 }
@@ -0,0 +1,311 @@
+//! `zeroclaw audit` — verify, search and export the security audit log,
+//! across the current and rotated files.
+
+use super::audit::{AuditEvent, AuditFilter, AuditLogger};
+use crate::config::Config;
+use crate::memory::parse_date_bound;
+use crate::util::truncate_with_ellipsis;
+use anyhow::{Context, Result};
+use std::fmt::Write as _;
+use std::io::Write;
+
+/// Problems listed before the rest are summarized as a count.
+const MAX_LISTED_PROBLEMS: usize = 20;
+
+/// Columns of `audit export --format csv`.
+const CSV_COLUMNS: [&str; 21] = [
+    "timestamp",
+    "event_id",
+    "seq",
+    "event_type",
+    "channel",
+    "user_id",
+    "username",
+    "tool",
+    "arguments",
+    "command",
+    "risk_level",
+    "approved",
+    "allowed",
+    "success",
+    "exit_code",
+    "duration_ms",
+    "error",
+    "policy_violation",
+    "policy_rule",
+    "policy_decision",
+    "hash",
+];
+
+pub fn handle_command(command: crate::AuditCommands, config: &Config) -> Result<()> {
+    let logger = AuditLogger::from_config(config)?;
+    match command {
+        crate::AuditCommands::Verify => verify(&logger),
+        crate::AuditCommands::Search {
+            filter,
+            limit,
+            json,
+        } => {
+            let mut events = logger.search(&filter_from_args(filter)?)?;
+            events.reverse();
+            events.truncate(limit);
+            if json {
+                println!("{}", serde_json::to_string_pretty(&events)?);
+            } else {
+                print_events(&events);
+            }
+            Ok(())
+        }
+        crate::AuditCommands::Export {
+            filter,
+            format,
+            output,
+        } => {
+            let format = format.to_ascii_lowercase();
+            if format != "csv" && format != "json" {
+                anyhow::bail!("unknown export format '{format}' (expected csv or json)");
+            }
+            let events = logger.search(&filter_from_args(filter)?)?;
+            let rendered = if format == "csv" {
+                to_csv(&events)
+            } else {
+                serde_json::to_string_pretty(&events)? + "\n"
+            };
+            match &output {
+                Some(path) => {
+                    std::fs::write(path, rendered)
+                        .with_context(|| format!("failed to write {}", path.display()))?;
+                    println!(
+                        "🔐 Exported {} audit events to {}",
+                        events.len(),
+                        path.display()
+                    );
+                }
+                None => std::io::stdout().lock().write_all(rendered.as_bytes())?,
+            }
+            Ok(())
+        }
+    }
+}
+
+fn filter_from_args(args: crate::AuditFilterArgs) -> Result<AuditFilter> {
+    Ok(AuditFilter {
+        event_type: args.event_type.as_deref().map(str::parse).transpose()?,
+        channel: args.channel,
+        user: args.user,
+        tool: args.tool,
+        risk_level: args.risk,
+        allowed: match (args.allowed, args.denied) {
+            (true, _) => Some(true),
+            (_, true) => Some(false),
+            _ => None,
+        },
+        since: args
+            .since
+            .as_deref()
+            .map(|value| parse_date_bound(value, false))
+            .transpose()?,
+        until: args
+            .until
+            .as_deref()
+            .map(|value| parse_date_bound(value, true))
+            .transpose()?,
+    })
+}
+
+fn print_events(events: &[AuditEvent]) {
+    if events.is_empty() {
+        println!("No audit events found.");
+        return;
+    }
+    for event in events {
+        let mut meta = event.timestamp.format("%Y-%m-%d %H:%M:%S UTC").to_string();
+        if let Some(actor) = &event.actor {
+            let _ = write!(meta, " | {}", actor.channel);
+            if let Some(user) = actor.username.as_ref().or(actor.user_id.as_ref()) {
+                let _ = write!(meta, ":{user}");
+            }
+        }
+        if let Some(risk) = event.action.as_ref().and_then(|a| a.risk_level.as_ref()) {
+            let _ = write!(meta, " | risk {risk}");
+        }
+        if let (Some(rule), Some(decision)) = (
+            &event.security.policy_rule,
+            &event.security.policy_decision,
+        ) {
+            let _ = write!(meta, " | rule {rule}: {decision}");
+        }
+        meta.push_str(if event.allowed() {
+            " | allowed"
+        } else {
+            " | denied"
+        });
+        if let Some(result) = &event.result {
+            let _ = write!(meta, " | {}", if result.success { "ok" } else { "failed" });
+            if let Some(ms) = result.duration_ms {
+                let _ = write!(meta, " {ms}ms");
+            }
+        }
+        println!("- {} ({meta})", event.event_type.as_str());
+
+        if let Some(action) = &event.action {
+            let detail = match (&action.tool, &action.arguments, &action.command) {
+                (Some(tool), Some(args), _) if !args.is_empty() => format!("{tool} — {args}"),
+                (Some(tool), _, _) => tool.clone(),
+                (None, _, Some(command)) => command.clone(),
+                _ => continue,
+            };
+            println!(
+                "    {}",
+                truncate_with_ellipsis(&detail.replace('\n', " "), 160)
+            );
+        }
+    }
+}
+
+fn to_csv(events: &[AuditEvent]) -> String {
+    let mut out = CSV_COLUMNS.join(",");
+    out.push('\n');
+    for event in events {
+        let actor = event.actor.as_ref();
+        let action = event.action.as_ref();
+        let result = event.result.as_ref();
+        let text = |value: Option<&String>| value.cloned().unwrap_or_default();
+        let row = [
+            event.timestamp.to_rfc3339(),
+            event.event_id.clone(),
+            event.seq.to_string(),
+            event.event_type.as_str().to_string(),
+            actor.map(|a| a.channel.clone()).unwrap_or_default(),
+            text(actor.and_then(|a| a.user_id.as_ref())),
+            text(actor.and_then(|a| a.username.as_ref())),
+            text(action.and_then(|a| a.tool.as_ref())),
+            text(action.and_then(|a| a.arguments.as_ref())),
+            text(action.and_then(|a| a.command.as_ref())),
+            text(action.and_then(|a| a.risk_level.as_ref())),
+            action.map(|a| a.approved.to_string()).unwrap_or_default(),
+            event.allowed().to_string(),
+            result.map(|r| r.success.to_string()).unwrap_or_default(),
+            result
+                .and_then(|r| r.exit_code)
+                .map(|code| code.to_string())
+                .unwrap_or_default(),
+            result
+                .and_then(|r| r.duration_ms)
+                .map(|ms| ms.to_string())
+                .unwrap_or_default(),
+            text(result.and_then(|r| r.error.as_ref())),
+            event.security.policy_violation.to_string(),
+            text(event.security.policy_rule.as_ref()),
+            text(event.security.policy_decision.as_ref()),
+            event.hash.clone(),
+        ];
+        let fields: Vec<String> = row.iter().map(|field| csv_field(field)).collect();
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// Quote a CSV field when it contains a separator, quote or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn verify(logger: &AuditLogger) -> Result<()> {
+    let report = logger.verify()?;
+    if report.files.is_empty() {
+        println!("No audit log yet.");
+        return Ok(());
+    }
+
+    println!("🔐 Audit log ({} files):", report.files.len());
+    for path in &report.files {
+        println!("  {}", path.display());
+    }
+    let range = match (report.first_seq, report.last_seq) {
+        (Some(first), Some(last)) => format!(" (seq {first}–{last})"),
+        _ => String::new(),
+    };
+    println!("  Chained events: {}{range}", report.events);
+    if report.unchained > 0 {
+        println!(
+            "  Unchained events: {} (written before hash chaining)",
+            report.unchained
+        );
+    }
+    println!(
+        "  Signatures: {}",
+        if report.signatures_checked {
+            "checked"
+        } else {
+            "not checked (sign_events = false)"
+        }
+    );
+    for note in &report.notes {
+        println!("  ℹ️  {note}");
+    }
+
+    if report.is_intact() {
+        println!("✅ Hash chain intact");
+        return Ok(());
+    }
+
+    println!("❌ {} problems:", report.problems.len());
+    for problem in report.problems.iter().take(MAX_LISTED_PROBLEMS) {
+        println!("  - {problem}");
+    }
+    if report.problems.len() > MAX_LISTED_PROBLEMS {
+        println!(
+            "  … and {} more",
+            report.problems.len() - MAX_LISTED_PROBLEMS
+        );
+    }
+    anyhow::bail!("Audit log failed verification")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::audit::AuditEventType;
+
+    #[test]
+    fn filter_args_resolve_type_outcome_and_dates() {
+        let filter = filter_from_args(crate::AuditFilterArgs {
+            event_type: Some("tool_call".into()),
+            denied: true,
+            since: Some("2026-03-01".into()),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(filter.event_type, Some(AuditEventType::ToolCall));
+        assert_eq!(filter.allowed, Some(false));
+        assert!(filter.since.is_some());
+
+        let err = filter_from_args(crate::AuditFilterArgs {
+            event_type: Some("shell".into()),
+            ..Default::default()
+        })
+        .unwrap_err();
+        assert!(err.to_string().contains("approval_decision"));
+    }
+
+    #[test]
+    fn csv_export_quotes_fields_and_keeps_columns() {
+        let event = AuditEvent::new(AuditEventType::ToolCall)
+            .with_actor("slack".into(), Some("U1".into()), None)
+            .with_tool("shell".into(), "command: echo \"a, b\"".into(), true, true)
+            .with_result(true, Some(0), 12, None);
+
+        let csv = to_csv(&[event]);
+        let mut lines = csv.lines();
+        assert_eq!(lines.next().unwrap(), CSV_COLUMNS.join(","));
+        let row = lines.next().unwrap();
+        assert!(row.contains(",tool_call,slack,U1,,shell,\"command: echo \"\"a, b\"\"\",,"));
+        assert!(row.contains(",true,true,true,0,12,,false,"));
+    }
+}
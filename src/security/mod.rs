@@ -1,4 +1,5 @@
 pub mod audit;
+pub mod audit_cli;
 #[cfg(feature = "sandbox-bubblewrap")]
 pub mod bubblewrap;
 pub mod detect;
@@ -9,7 +10,10 @@ pub mod firejail;
 pub mod landlock;
 pub mod pairing;
 pub mod policy;
+pub mod policy_cli;
+pub mod rule_expr;
 pub mod secrets;
+pub mod tool_rules;
 pub mod traits;
 
 #[allow(unused_imports)]
@@ -192,6 +192,12 @@ fn hash_token(token: &str) -> String {
     format!("{:x}", Sha256::digest(token.as_bytes()))
 }
 
+/// Short stable id for a bearer token (a prefix of its hash), for keying
+/// per-client state without keeping the token around.
+pub fn token_id(token: &str) -> String {
+    hash_token(token)[..16].to_string()
+}
+
 /// Check if a stored value looks like a SHA-256 hash (64 hex chars)
 /// rather than a plaintext token.
 fn is_token_hash(value: &str) -> bool {
@@ -2,8 +2,12 @@ use parking_lot::Mutex;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::Instant;
 
+use super::audit::{self, AuditEvent, AuditEventType};
+use super::tool_rules::{self, RuleAction, RuleDecision, ToolRules};
+
 /// How much autonomy the agent has
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -100,6 +104,8 @@ pub struct SecurityPolicy {
     /// Per-agent tool allow list. If non-empty, only these tools are permitted.
     /// Takes precedence over agent_denied_tools.
     pub agent_allowed_tools: Vec<String>,
+    /// Tool argument rules from `[autonomy] policy_file` (None = no rules).
+    pub tool_rules: Option<Arc<ToolRules>>,
 }
 
 impl Default for SecurityPolicy {
@@ -153,6 +159,7 @@ impl Default for SecurityPolicy {
             tripwire_patterns: Vec::new(),
             agent_denied_tools: Vec::new(),
             agent_allowed_tools: Vec::new(),
+            tool_rules: None,
         }
     }
 }
@@ -636,6 +643,37 @@ impl SecurityPolicy {
         Ok(())
     }
 
+    /// Check a tool call's arguments against the policy rules and record the
+    /// decision in the audit log. Allows everything when no rule file is set.
+    pub fn check_tool_args(
+        &self,
+        tool_name: &str,
+        args: &serde_json::Value,
+        channel: &str,
+    ) -> RuleDecision {
+        let decision =
+            tool_rules::evaluate_optional(self.tool_rules.as_deref(), tool_name, args, channel);
+        if self.tool_rules.is_some() {
+            let mut event = AuditEvent::new(AuditEventType::PolicyDecision)
+                .with_actor(channel.to_string(), self.agent_id.clone(), None)
+                .with_tool(
+                    tool_name.to_string(),
+                    crate::approval::audit_summary(args),
+                    false,
+                    decision.action != RuleAction::Deny,
+                )
+                .with_policy_rule(
+                    decision.rule.clone().unwrap_or_else(|| "default".into()),
+                    decision.action.as_str(),
+                );
+            if decision.action == RuleAction::Deny {
+                event = event.with_policy_violation();
+            }
+            audit::record(&event);
+        }
+        decision
+    }
+
     /// Check output content against tripwire patterns.
     /// Returns Err with the matching pattern if a tripwire is triggered.
     pub fn check_output(&self, content: &str) -> Result<(), String> {
@@ -683,6 +721,13 @@ impl SecurityPolicy {
             })
             .collect();
 
+        let tool_rules = autonomy_config.policy_file.as_deref().map(|file| {
+            Arc::new(ToolRules::load_or_deny(&tool_rules::resolve_path(
+                file,
+                workspace_dir,
+            )))
+        });
+
         Self {
             autonomy: autonomy_config.level,
             workspace_dir: workspace_dir.to_path_buf(),
@@ -698,6 +743,7 @@ impl SecurityPolicy {
             tripwire_patterns,
             agent_denied_tools: Vec::new(),
             agent_allowed_tools: Vec::new(),
+            tool_rules,
         }
     }
 }
@@ -0,0 +1,82 @@
+use super::tool_rules::{self, RuleDecision, ToolRules};
+use crate::config::Config;
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+
+pub fn handle_command(command: crate::PolicyCommands, config: &Config) -> Result<()> {
+    match command {
+        crate::PolicyCommands::Test {
+            file,
+            tool,
+            args,
+            channel,
+        } => {
+            let path = match file {
+                Some(path) => path,
+                None => policy_path(config)?,
+            };
+            let rules = ToolRules::load(&path)?;
+            println!(
+                "📜 Loaded {} rule(s) from {}",
+                rules.rule_count(),
+                rules.path().display()
+            );
+
+            match tool {
+                Some(tool) => {
+                    let args: serde_json::Value =
+                        serde_json::from_str(&args).context("--args must be valid JSON")?;
+                    let decision = rules.evaluate(&tool, &args, &channel);
+                    println!("{tool}: {}", describe(&decision));
+                    Ok(())
+                }
+                None => run_tests(&rules),
+            }
+        }
+    }
+}
+
+fn policy_path(config: &Config) -> Result<PathBuf> {
+    let file = config.autonomy.policy_file.as_deref().context(
+        "no policy file: set [autonomy] policy_file in config.toml or pass --file",
+    )?;
+    Ok(tool_rules::resolve_path(file, &config.workspace_dir))
+}
+
+fn describe(decision: &RuleDecision) -> String {
+    let rule = decision.rule.as_deref().unwrap_or("default");
+    format!("{} (rule {rule}) — {}", decision.action, decision.reason)
+}
+
+fn run_tests(rules: &ToolRules) -> Result<()> {
+    if rules.tests().is_empty() {
+        println!("No [[tests]] in the policy file; use --tool and --args to dry-run a call.");
+        return Ok(());
+    }
+
+    let mut failed = 0;
+    for (index, test) in rules.tests().iter().enumerate() {
+        let name = test
+            .name
+            .clone()
+            .unwrap_or_else(|| format!("test {} ({})", index + 1, test.tool));
+        let decision = rules.evaluate(&test.tool, &test.args, &test.channel);
+        if decision.action == test.expect {
+            println!("  ✅ {name}: {}", describe(&decision));
+        } else {
+            failed += 1;
+            println!(
+                "  ❌ {name}: expected {}, got {}",
+                test.expect,
+                describe(&decision)
+            );
+        }
+    }
+
+    let total = rules.tests().len();
+    if failed > 0 {
+        anyhow::bail!("{failed} of {total} policy test(s) failed");
+    }
+    println!("All {total} policy test(s) passed.");
+    Ok(())
+}
@@ -0,0 +1,659 @@
+//! Expression language for tool argument rules.
+//!
+//! A small, CEL-like language evaluated against the JSON of a tool call:
+//!
+//! - literals: `"text"`, `'text'`, `42`, `1.5`, `true`, `false`, `null`,
+//!   `["a", "b"]`
+//! - paths: `tool`, `channel`, `args.url`, `args.items[0]`,
+//!   `args["content-type"]`
+//! - operators: `!`, `&&`, `||`, `==`, `!=`, `<`, `<=`, `>`, `>=`, `in`,
+//!   parentheses
+//! - functions: `len`, `lower`, `glob`, `matches`, `starts_with`,
+//!   `ends_with`, `contains`, `host`, `exists`
+//!
+//! Missing fields are `null`, and string functions treat `null` as `""`, so
+//! `glob(args.branch, "agent/*")` is simply false when there is no branch.
+//! Type errors (comparing a string with a number, `!` on a string) are
+//! evaluation errors; the rule engine fails closed on them.
+
+use anyhow::{bail, Context, Result};
+use serde_json::Value;
+use std::cmp::Ordering;
+
+/// A parsed rule condition.
+#[derive(Debug, Clone)]
+pub struct Expr(Node);
+
+#[derive(Debug, Clone)]
+enum Node {
+    Literal(Value),
+    List(Vec<Node>),
+    Path(Root, Vec<Segment>),
+    Not(Box<Node>),
+    And(Box<Node>, Box<Node>),
+    Or(Box<Node>, Box<Node>),
+    Compare(CompareOp, Box<Node>, Box<Node>),
+    In(Box<Node>, Box<Node>),
+    Call(Function, Vec<Node>),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Root {
+    Tool,
+    Channel,
+    Args,
+}
+
+#[derive(Debug, Clone)]
+enum Segment {
+    Member(String),
+    Index(Node),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Function {
+    Len,
+    Lower,
+    Glob,
+    Matches,
+    StartsWith,
+    EndsWith,
+    Contains,
+    Host,
+    Exists,
+}
+
+impl Function {
+    fn lookup(name: &str) -> Option<(Self, usize)> {
+        Some(match name {
+            "len" => (Self::Len, 1),
+            "lower" => (Self::Lower, 1),
+            "glob" => (Self::Glob, 2),
+            "matches" => (Self::Matches, 2),
+            "starts_with" => (Self::StartsWith, 2),
+            "ends_with" => (Self::EndsWith, 2),
+            "contains" => (Self::Contains, 2),
+            "host" => (Self::Host, 1),
+            "exists" => (Self::Exists, 1),
+            _ => return None,
+        })
+    }
+}
+
+/// The call an expression is evaluated against.
+pub struct CallContext<'a> {
+    pub tool: &'a str,
+    pub channel: &'a str,
+    pub args: &'a Value,
+}
+
+impl Expr {
+    pub fn parse(source: &str) -> Result<Self> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let node = parser.or()?;
+        if let Some(token) = parser.peek() {
+            bail!("unexpected {} after the end of the expression", token.describe());
+        }
+        Ok(Self(node))
+    }
+
+    /// Evaluate to a boolean; any other result is an error.
+    pub fn eval(&self, call: &CallContext<'_>) -> Result<bool> {
+        match eval(&self.0, call)? {
+            Value::Bool(result) => Ok(result),
+            other => bail!("condition evaluated to {}, not true or false", kind(&other)),
+        }
+    }
+}
+
+// ── Lexer ──────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Str(String),
+    Num(f64),
+    Ident(String),
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Comma,
+    Dot,
+    Not,
+    And,
+    Or,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Self::Str(s) => format!("string \"{s}\""),
+            Self::Num(n) => format!("number {n}"),
+            Self::Ident(name) => format!("'{name}'"),
+            Self::LParen => "'('".into(),
+            Self::RParen => "')'".into(),
+            Self::LBracket => "'['".into(),
+            Self::RBracket => "']'".into(),
+            Self::Comma => "','".into(),
+            Self::Dot => "'.'".into(),
+            Self::Not => "'!'".into(),
+            Self::And => "'&&'".into(),
+            Self::Or => "'||'".into(),
+            Self::Eq => "'=='".into(),
+            Self::Ne => "'!='".into(),
+            Self::Lt => "'<'".into(),
+            Self::Le => "'<='".into(),
+            Self::Gt => "'>'".into(),
+            Self::Ge => "'>='".into(),
+        }
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        let (token, width) = match (c, next) {
+            (c, _) if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            ('(', _) => (Token::LParen, 1),
+            (')', _) => (Token::RParen, 1),
+            ('[', _) => (Token::LBracket, 1),
+            (']', _) => (Token::RBracket, 1),
+            (',', _) => (Token::Comma, 1),
+            ('.', _) => (Token::Dot, 1),
+            ('&', Some('&')) => (Token::And, 2),
+            ('|', Some('|')) => (Token::Or, 2),
+            ('=', Some('=')) => (Token::Eq, 2),
+            ('!', Some('=')) => (Token::Ne, 2),
+            ('!', _) => (Token::Not, 1),
+            ('<', Some('=')) => (Token::Le, 2),
+            ('<', _) => (Token::Lt, 1),
+            ('>', Some('=')) => (Token::Ge, 2),
+            ('>', _) => (Token::Gt, 1),
+            ('"' | '\'', _) => {
+                let (text, end) = lex_string(&chars, i)?;
+                tokens.push(Token::Str(text));
+                i = end;
+                continue;
+            }
+            (c, _) if c.is_ascii_digit() => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                let number = text
+                    .parse()
+                    .with_context(|| format!("invalid number '{text}'"))?;
+                tokens.push(Token::Num(number));
+                continue;
+            }
+            (c, _) if c.is_ascii_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                tokens.push(Token::Ident(chars[start..i].iter().collect()));
+                continue;
+            }
+            (c, _) => bail!("unexpected character '{c}' at position {}", i + 1),
+        };
+        tokens.push(token);
+        i += width;
+    }
+    Ok(tokens)
+}
+
+/// String literal starting at `start` (the quote). Returns the text and the
+/// index after the closing quote.
+fn lex_string(chars: &[char], start: usize) -> Result<(String, usize)> {
+    let quote = chars[start];
+    let mut text = String::new();
+    let mut i = start + 1;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => {
+                let escaped = chars.get(i + 1).context("unterminated string")?;
+                text.push(match escaped {
+                    'n' => '\n',
+                    't' => '\t',
+                    other => *other,
+                });
+                i += 2;
+            }
+            c if c == quote => return Ok((text, i + 1)),
+            c => {
+                text.push(c);
+                i += 1;
+            }
+        }
+    }
+    bail!("unterminated string starting at position {}", start + 1)
+}
+
+// ── Parser ─────────────────────────────────────────────────────
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &Token) -> Result<()> {
+        match self.next() {
+            Some(found) if found == *token => Ok(()),
+            Some(found) => bail!("expected {}, found {}", token.describe(), found.describe()),
+            None => bail!("expected {}, found the end of the expression", token.describe()),
+        }
+    }
+
+    fn or(&mut self) -> Result<Node> {
+        let mut node = self.and()?;
+        while self.eat(&Token::Or) {
+            node = Node::Or(Box::new(node), Box::new(self.and()?));
+        }
+        Ok(node)
+    }
+
+    fn and(&mut self) -> Result<Node> {
+        let mut node = self.comparison()?;
+        while self.eat(&Token::And) {
+            node = Node::And(Box::new(node), Box::new(self.comparison()?));
+        }
+        Ok(node)
+    }
+
+    fn comparison(&mut self) -> Result<Node> {
+        let left = self.unary()?;
+        let op = match self.peek() {
+            Some(Token::Eq) => CompareOp::Eq,
+            Some(Token::Ne) => CompareOp::Ne,
+            Some(Token::Lt) => CompareOp::Lt,
+            Some(Token::Le) => CompareOp::Le,
+            Some(Token::Gt) => CompareOp::Gt,
+            Some(Token::Ge) => CompareOp::Ge,
+            Some(Token::Ident(word)) if word == "in" => {
+                self.pos += 1;
+                return Ok(Node::In(Box::new(left), Box::new(self.unary()?)));
+            }
+            _ => return Ok(left),
+        };
+        self.pos += 1;
+        Ok(Node::Compare(op, Box::new(left), Box::new(self.unary()?)))
+    }
+
+    fn unary(&mut self) -> Result<Node> {
+        if self.eat(&Token::Not) {
+            return Ok(Node::Not(Box::new(self.unary()?)));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Node> {
+        match self.next() {
+            Some(Token::Str(text)) => Ok(Node::Literal(Value::String(text))),
+            Some(Token::Num(number)) => Ok(Node::Literal(number_literal(number))),
+            Some(Token::LParen) => {
+                let node = self.or()?;
+                self.expect(&Token::RParen)?;
+                Ok(node)
+            }
+            Some(Token::LBracket) => {
+                let mut items = Vec::new();
+                if !self.eat(&Token::RBracket) {
+                    loop {
+                        items.push(self.or()?);
+                        if self.eat(&Token::RBracket) {
+                            break;
+                        }
+                        self.expect(&Token::Comma)?;
+                    }
+                }
+                Ok(Node::List(items))
+            }
+            Some(Token::Ident(name)) => self.identifier(&name),
+            Some(token) => bail!("unexpected {}", token.describe()),
+            None => bail!("unexpected end of the expression"),
+        }
+    }
+
+    fn identifier(&mut self, name: &str) -> Result<Node> {
+        let root = match name {
+            "true" => return Ok(Node::Literal(Value::Bool(true))),
+            "false" => return Ok(Node::Literal(Value::Bool(false))),
+            "null" => return Ok(Node::Literal(Value::Null)),
+            "tool" => Root::Tool,
+            "channel" => Root::Channel,
+            "args" => Root::Args,
+            _ if self.peek() == Some(&Token::LParen) => return self.call(name),
+            _ => bail!("unknown name '{name}' (use tool, channel or args.<field>)"),
+        };
+
+        let mut segments = Vec::new();
+        loop {
+            if self.eat(&Token::Dot) {
+                match self.next() {
+                    Some(Token::Ident(member)) => segments.push(Segment::Member(member)),
+                    _ => bail!("expected a field name after '.'"),
+                }
+            } else if self.eat(&Token::LBracket) {
+                segments.push(Segment::Index(self.or()?));
+                self.expect(&Token::RBracket)?;
+            } else {
+                return Ok(Node::Path(root, segments));
+            }
+        }
+    }
+
+    fn call(&mut self, name: &str) -> Result<Node> {
+        let (function, arity) =
+            Function::lookup(name).with_context(|| format!("unknown function '{name}'"))?;
+        self.expect(&Token::LParen)?;
+        let mut args = Vec::new();
+        if !self.eat(&Token::RParen) {
+            loop {
+                args.push(self.or()?);
+                if self.eat(&Token::RParen) {
+                    break;
+                }
+                self.expect(&Token::Comma)?;
+            }
+        }
+        if args.len() != arity {
+            bail!("{name}() takes {arity} argument(s), got {}", args.len());
+        }
+        // Catch bad literal patterns when the rule file loads.
+        if let Some(Node::Literal(Value::String(pattern))) = args.get(1) {
+            match function {
+                Function::Glob => {
+                    glob::Pattern::new(pattern)
+                        .with_context(|| format!("invalid glob pattern '{pattern}'"))?;
+                }
+                Function::Matches => {
+                    regex::Regex::new(pattern)
+                        .with_context(|| format!("invalid regex '{pattern}'"))?;
+                }
+                _ => {}
+            }
+        }
+        Ok(Node::Call(function, args))
+    }
+}
+
+/// Whole numbers stay integers so they can index lists.
+fn number_literal(number: f64) -> Value {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    if number.fract() == 0.0 && number <= u64::MAX as f64 {
+        Value::from(number as u64)
+    } else {
+        Value::from(number)
+    }
+}
+
+// ── Evaluation ─────────────────────────────────────────────────
+
+fn eval(node: &Node, call: &CallContext<'_>) -> Result<Value> {
+    Ok(match node {
+        Node::Literal(value) => value.clone(),
+        Node::List(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| eval(item, call))
+                .collect::<Result<_>>()?,
+        ),
+        Node::Path(root, segments) => {
+            let mut value = match root {
+                Root::Tool => Value::String(call.tool.to_string()),
+                Root::Channel => Value::String(call.channel.to_string()),
+                Root::Args => call.args.clone(),
+            };
+            for segment in segments {
+                value = match segment {
+                    Segment::Member(name) => value.get(name).cloned().unwrap_or(Value::Null),
+                    Segment::Index(index) => match eval(index, call)? {
+                        Value::String(key) => value.get(&key).cloned().unwrap_or(Value::Null),
+                        Value::Number(n) => n
+                            .as_u64()
+                            .and_then(|i| value.get(usize::try_from(i).ok()?).cloned())
+                            .unwrap_or(Value::Null),
+                        other => bail!("cannot index with {}", kind(&other)),
+                    },
+                };
+            }
+            value
+        }
+        Node::Not(inner) => Value::Bool(!truth(&eval(inner, call)?, "!")?),
+        Node::And(left, right) => {
+            Value::Bool(truth(&eval(left, call)?, "&&")? && truth(&eval(right, call)?, "&&")?)
+        }
+        Node::Or(left, right) => {
+            Value::Bool(truth(&eval(left, call)?, "||")? || truth(&eval(right, call)?, "||")?)
+        }
+        Node::Compare(op, left, right) => {
+            let (left, right) = (eval(left, call)?, eval(right, call)?);
+            Value::Bool(match op {
+                CompareOp::Eq => equal(&left, &right),
+                CompareOp::Ne => !equal(&left, &right),
+                CompareOp::Lt => order(&left, &right)? == Ordering::Less,
+                CompareOp::Le => order(&left, &right)? != Ordering::Greater,
+                CompareOp::Gt => order(&left, &right)? == Ordering::Greater,
+                CompareOp::Ge => order(&left, &right)? != Ordering::Less,
+            })
+        }
+        Node::In(needle, haystack) => {
+            let (needle, haystack) = (eval(needle, call)?, eval(haystack, call)?);
+            Value::Bool(contains(&haystack, &needle)?)
+        }
+        Node::Call(function, args) => {
+            let args: Vec<Value> = args
+                .iter()
+                .map(|arg| eval(arg, call))
+                .collect::<Result<_>>()?;
+            call_function(*function, &args)?
+        }
+    })
+}
+
+fn call_function(function: Function, args: &[Value]) -> Result<Value> {
+    Ok(match function {
+        Function::Len => Value::from(match &args[0] {
+            Value::Null => 0,
+            Value::String(s) => s.len(),
+            Value::Array(items) => items.len(),
+            Value::Object(fields) => fields.len(),
+            other => bail!("len() does not apply to {}", kind(other)),
+        }),
+        Function::Lower => Value::String(text(&args[0], "lower")?.to_lowercase()),
+        Function::Glob => {
+            let pattern = glob::Pattern::new(text(&args[1], "glob")?)
+                .context("invalid glob pattern")?;
+            let options = glob::MatchOptions {
+                require_literal_separator: true,
+                ..glob::MatchOptions::new()
+            };
+            Value::Bool(pattern.matches_with(text(&args[0], "glob")?, options))
+        }
+        Function::Matches => {
+            let regex = regex::Regex::new(text(&args[1], "matches")?).context("invalid regex")?;
+            Value::Bool(regex.is_match(text(&args[0], "matches")?))
+        }
+        Function::StartsWith => {
+            Value::Bool(text(&args[0], "starts_with")?.starts_with(text(&args[1], "starts_with")?))
+        }
+        Function::EndsWith => {
+            Value::Bool(text(&args[0], "ends_with")?.ends_with(text(&args[1], "ends_with")?))
+        }
+        Function::Contains => Value::Bool(contains(&args[0], &args[1])?),
+        Function::Host => Value::String(
+            reqwest::Url::parse(text(&args[0], "host")?)
+                .ok()
+                .and_then(|url| url.host_str().map(str::to_lowercase))
+                .unwrap_or_default(),
+        ),
+        Function::Exists => Value::Bool(!args[0].is_null()),
+    })
+}
+
+fn kind(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "a list",
+        Value::Object(_) => "an object",
+    }
+}
+
+fn truth(value: &Value, op: &str) -> Result<bool> {
+    match value {
+        Value::Bool(b) => Ok(*b),
+        other => bail!("'{op}' needs true or false, got {}", kind(other)),
+    }
+}
+
+/// String argument of a function; `null` reads as "".
+fn text<'a>(value: &'a Value, function: &str) -> Result<&'a str> {
+    match value {
+        Value::String(s) => Ok(s),
+        Value::Null => Ok(""),
+        other => bail!("{function}() needs a string, got {}", kind(other)),
+    }
+}
+
+/// JSON equality, with numbers compared by value (`1 == 1.0`).
+fn equal(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64() == b.as_f64(),
+        _ => left == right,
+    }
+}
+
+fn order(left: &Value, right: &Value) -> Result<Ordering> {
+    match (left, right) {
+        (Value::Number(a), Value::Number(b)) => a
+            .as_f64()
+            .zip(b.as_f64())
+            .and_then(|(a, b)| a.partial_cmp(&b))
+            .context("numbers cannot be compared"),
+        (Value::String(a), Value::String(b)) => Ok(a.cmp(b)),
+        _ => bail!("cannot compare {} with {}", kind(left), kind(right)),
+    }
+}
+
+/// `needle in haystack`: list membership, substring or object key.
+fn contains(haystack: &Value, needle: &Value) -> Result<bool> {
+    Ok(match haystack {
+        Value::Null => false,
+        Value::Array(items) => items.iter().any(|item| equal(item, needle)),
+        Value::String(s) => s.contains(text(needle, "contains")?),
+        Value::Object(fields) => fields.contains_key(text(needle, "contains")?),
+        other => bail!("cannot look inside {}", kind(other)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn check(source: &str, tool: &str, args: &Value) -> Result<bool> {
+        Expr::parse(source)?.eval(&CallContext {
+            tool,
+            channel: "cli",
+            args,
+        })
+    }
+
+    #[test]
+    fn operators_and_paths() {
+        let args = json!({
+            "method": "POST",
+            "url": "https://api.internal/v1",
+            "items": [1, 2],
+            "headers": {"content-type": "text/plain"}
+        });
+        assert!(check(r#"args.method == "POST" && tool == 'http_request'"#, "http_request", &args).unwrap());
+        assert!(check("args.items[1] >= 2 && len(args.items) == 2", "t", &args).unwrap());
+        assert!(check(r#"args.headers["content-type"] != "application/json""#, "t", &args).unwrap());
+        assert!(check(r#"lower(args.method) in ["post", "put"]"#, "t", &args).unwrap());
+        assert!(check(r#"!(args.method == "GET" || exists(args.missing))"#, "t", &args).unwrap());
+        assert!(check(r#"host(args.url) == "api.internal""#, "t", &args).unwrap());
+    }
+
+    #[test]
+    fn string_functions_treat_missing_fields_as_empty() {
+        let args = json!({"operation": "push", "branch": "agent/fix-1"});
+        assert!(check(r#"glob(args.branch, "agent/*")"#, "git", &args).unwrap());
+        assert!(!check(r#"glob(args.branch, "main")"#, "git", &args).unwrap());
+        assert!(!check(r#"glob(args.remote, "origin")"#, "git", &args).unwrap());
+        assert!(check(r#"matches(args.branch, "^agent/[a-z0-9-]+$")"#, "git", &args).unwrap());
+        assert!(check(r#"starts_with(args.branch, "agent/") && !ends_with(args.branch, "/")"#, "git", &args).unwrap());
+        assert!(check(r#"len(args.content) == 0"#, "git", &args).unwrap());
+    }
+
+    #[test]
+    fn parse_errors_are_reported() {
+        for source in [
+            "args.method ==",
+            "unknown.field == 1",
+            "nope(args.x)",
+            "glob(args.path)",
+            r#"matches(args.x, "(")"#,
+            r#"args.x == "open"#,
+            "args.x == 1 )",
+        ] {
+            assert!(Expr::parse(source).is_err(), "{source} should not parse");
+        }
+    }
+
+    #[test]
+    fn type_errors_fail_evaluation() {
+        let args = json!({"size": "big"});
+        assert!(check("args.size > 10", "t", &args).is_err());
+        assert!(check("!args.size", "t", &args).is_err());
+        assert!(check("args.size", "t", &args).is_err());
+    }
+}
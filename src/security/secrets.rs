@@ -167,6 +167,19 @@ impl SecretStore {
         value.starts_with("enc2:")
     }
 
+    /// Derive a purpose-bound key from the store key (HMAC-SHA256 over
+    /// `purpose`), creating the key file on first use. Signing keys come from
+    /// here so the encryption key itself is never reused.
+    pub fn derive_key(&self, purpose: &str) -> Result<Vec<u8>> {
+        use hmac::{Hmac, Mac};
+
+        let key = self.load_or_create_key()?;
+        let mut mac = <Hmac<sha2::Sha256> as Mac>::new_from_slice(&key)
+            .map_err(|e| anyhow::anyhow!("Invalid secret key: {e}"))?;
+        mac.update(purpose.as_bytes());
+        Ok(mac.finalize().into_bytes().to_vec())
+    }
+
     /// Load the encryption key from disk, or create one if it doesn't exist.
     fn load_or_create_key(&self) -> Result<Vec<u8>> {
         if self.key_path.exists() {
@@ -351,6 +364,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn derived_keys_are_stable_and_purpose_bound() {
+        let tmp = TempDir::new().unwrap();
+        let store = SecretStore::new(tmp.path(), true);
+
+        let audit = store.derive_key("audit-log").unwrap();
+        assert_eq!(audit.len(), 32);
+        assert_eq!(audit, store.derive_key("audit-log").unwrap());
+        assert_ne!(audit, store.derive_key("other").unwrap());
+        assert_ne!(audit, store.load_or_create_key().unwrap());
+    }
+
     #[test]
     fn encrypting_same_value_produces_different_ciphertext() {
         let tmp = TempDir::new().unwrap();
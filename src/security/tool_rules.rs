@@ -0,0 +1,454 @@
+//! Policy-as-code for tool arguments.
+//!
+//! `[autonomy] policy_file` points at a TOML rule file. Before a tool runs,
+//! its JSON arguments are checked against the rules top to bottom; the first
+//! rule whose `tool` pattern and `when` condition match decides (allow, deny
+//! or require approval). Calls that no rule matches get the file's `default`.
+//!
+//! ```toml
+//! default = "allow"
+//!
+//! [[rules]]
+//! name = "http-post-internal-only"
+//! tool = "http_request"
+//! when = 'args.method == "POST" && !glob(host(args.url), "*.internal")'
+//! action = "deny"
+//! reason = "POST is only allowed to *.internal hosts"
+//!
+//! [[tests]]
+//! tool = "http_request"
+//! args = { method = "POST", url = "https://example.com" }
+//! expect = "deny"
+//! ```
+//!
+//! Conditions use the expression language in [`super::rule_expr`]. The
+//! engine fails closed: a rule file that does not load denies every call,
+//! and a condition that errors at runtime denies the call it was checking.
+
+use super::rule_expr::{CallContext, Expr};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// What a rule does with a matching call
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleAction {
+    Allow,
+    Deny,
+    RequireApproval,
+}
+
+impl RuleAction {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Allow => "allow",
+            Self::Deny => "deny",
+            Self::RequireApproval => "require_approval",
+        }
+    }
+}
+
+impl fmt::Display for RuleAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Outcome of checking one tool call
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleDecision {
+    pub action: RuleAction,
+    /// Rule that decided (None = the file default, or no rule file)
+    pub rule: Option<String>,
+    pub reason: String,
+}
+
+impl RuleDecision {
+    fn allow_unconfigured() -> Self {
+        Self {
+            action: RuleAction::Allow,
+            rule: None,
+            reason: String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleFile {
+    #[serde(default = "default_action")]
+    default: RuleAction,
+    #[serde(default)]
+    rules: Vec<RuleSpec>,
+    #[serde(default)]
+    tests: Vec<RuleTest>,
+}
+
+fn default_action() -> RuleAction {
+    RuleAction::Allow
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleSpec {
+    name: String,
+    #[serde(default = "default_tool_pattern")]
+    tool: String,
+    #[serde(default)]
+    when: Option<String>,
+    action: RuleAction,
+    #[serde(default)]
+    reason: Option<String>,
+}
+
+fn default_tool_pattern() -> String {
+    "*".into()
+}
+
+/// Example call in the rule file, checked by `zeroclaw policy test`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RuleTest {
+    #[serde(default)]
+    pub name: Option<String>,
+    pub tool: String,
+    #[serde(default = "empty_args")]
+    pub args: serde_json::Value,
+    #[serde(default = "default_test_channel")]
+    pub channel: String,
+    pub expect: RuleAction,
+}
+
+fn empty_args() -> serde_json::Value {
+    serde_json::json!({})
+}
+
+fn default_test_channel() -> String {
+    "cli".into()
+}
+
+#[derive(Debug)]
+struct ToolRule {
+    name: String,
+    tool: glob::Pattern,
+    when: Option<Expr>,
+    action: RuleAction,
+    reason: Option<String>,
+}
+
+/// Compiled rule file
+#[derive(Debug)]
+pub struct ToolRules {
+    path: PathBuf,
+    default: RuleAction,
+    rules: Vec<ToolRule>,
+    tests: Vec<RuleTest>,
+    /// Set when the file failed to load; every call is denied.
+    load_error: Option<String>,
+}
+
+impl ToolRules {
+    /// Load and compile a rule file, failing on any error.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read policy file {}", path.display()))?;
+        Self::parse(&content, path)
+            .with_context(|| format!("invalid policy file {}", path.display()))
+    }
+
+    /// Load a rule file for enforcement. A file that does not load yields a
+    /// rule set that denies every call.
+    pub fn load_or_deny(path: &Path) -> Self {
+        Self::load(path).unwrap_or_else(|e| {
+            tracing::error!("Policy file failed to load, denying all tool calls: {e:#}");
+            Self {
+                path: path.to_path_buf(),
+                default: RuleAction::Deny,
+                rules: Vec::new(),
+                tests: Vec::new(),
+                load_error: Some(format!("{e:#}")),
+            }
+        })
+    }
+
+    pub fn parse(content: &str, path: &Path) -> Result<Self> {
+        let file: RuleFile = toml::from_str(content)?;
+        let mut rules = Vec::with_capacity(file.rules.len());
+        for spec in file.rules {
+            if rules.iter().any(|rule: &ToolRule| rule.name == spec.name) {
+                bail!("duplicate rule name '{}'", spec.name);
+            }
+            let tool = glob::Pattern::new(&spec.tool)
+                .with_context(|| format!("rule '{}': invalid tool pattern", spec.name))?;
+            let when = spec
+                .when
+                .as_deref()
+                .map(Expr::parse)
+                .transpose()
+                .with_context(|| format!("rule '{}': invalid condition", spec.name))?;
+            rules.push(ToolRule {
+                name: spec.name,
+                tool,
+                when,
+                action: spec.action,
+                reason: spec.reason,
+            });
+        }
+        Ok(Self {
+            path: path.to_path_buf(),
+            default: file.default,
+            rules,
+            tests: file.tests,
+            load_error: None,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn rule_count(&self) -> usize {
+        self.rules.len()
+    }
+
+    pub fn tests(&self) -> &[RuleTest] {
+        &self.tests
+    }
+
+    /// Decide a tool call. The first matching rule wins.
+    pub fn evaluate(&self, tool: &str, args: &serde_json::Value, channel: &str) -> RuleDecision {
+        if let Some(error) = &self.load_error {
+            return RuleDecision {
+                action: RuleAction::Deny,
+                rule: None,
+                reason: format!("policy file failed to load: {error}"),
+            };
+        }
+
+        let call = CallContext {
+            tool,
+            channel,
+            args,
+        };
+        for rule in &self.rules {
+            if !rule.tool.matches(tool) {
+                continue;
+            }
+            let matched = match &rule.when {
+                None => true,
+                Some(condition) => match condition.eval(&call) {
+                    Ok(matched) => matched,
+                    Err(e) => {
+                        return RuleDecision {
+                            action: RuleAction::Deny,
+                            rule: Some(rule.name.clone()),
+                            reason: format!("rule '{}' could not be evaluated: {e:#}", rule.name),
+                        };
+                    }
+                },
+            };
+            if matched {
+                return RuleDecision {
+                    action: rule.action,
+                    rule: Some(rule.name.clone()),
+                    reason: rule
+                        .reason
+                        .clone()
+                        .unwrap_or_else(|| format!("matched policy rule '{}'", rule.name)),
+                };
+            }
+        }
+
+        RuleDecision {
+            action: self.default,
+            rule: None,
+            reason: "no policy rule matched".into(),
+        }
+    }
+}
+
+/// Resolve `[autonomy] policy_file`. Relative paths are taken from the
+/// ZeroClaw directory (the workspace's parent), so the agent cannot edit the
+/// rules through its workspace.
+pub fn resolve_path(policy_file: &str, workspace_dir: &Path) -> PathBuf {
+    let path = PathBuf::from(shellexpand::tilde(policy_file).as_ref());
+    if path.is_absolute() {
+        path
+    } else {
+        workspace_dir.parent().unwrap_or(workspace_dir).join(path)
+    }
+}
+
+/// Check a call against optional rules; no rule file allows everything.
+pub(crate) fn evaluate_optional(
+    rules: Option<&ToolRules>,
+    tool: &str,
+    args: &serde_json::Value,
+    channel: &str,
+) -> RuleDecision {
+    rules.map_or_else(RuleDecision::allow_unconfigured, |rules| {
+        rules.evaluate(tool, args, channel)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const EXAMPLE: &str = r#"
+default = "allow"
+
+[[rules]]
+name = "http-post-internal-only"
+tool = "http_request"
+when = 'args.method == "POST" && !glob(host(args.url), "*.internal")'
+action = "deny"
+reason = "POST is only allowed to *.internal hosts"
+
+[[rules]]
+name = "git-push-agent-branches"
+tool = "git_operations"
+when = 'args.operation == "push" && !glob(args.branch, "agent/*")'
+action = "deny"
+
+[[rules]]
+name = "large-writes-outside-docs"
+tool = "file_write"
+when = 'len(args.content) > 1048576 && !starts_with(args.path, "docs/")'
+action = "require_approval"
+
+[[rules]]
+name = "shell-needs-a-person"
+tool = "shell"
+action = "require_approval"
+"#;
+
+    fn example() -> ToolRules {
+        ToolRules::parse(EXAMPLE, Path::new("policy.toml")).unwrap()
+    }
+
+    #[test]
+    fn example_rules_decide_calls() {
+        let rules = example();
+        let decide = |tool: &str, args: serde_json::Value| rules.evaluate(tool, &args, "cli");
+
+        let denied = decide(
+            "http_request",
+            json!({"method": "POST", "url": "https://example.com/hook"}),
+        );
+        assert_eq!(denied.action, RuleAction::Deny);
+        assert_eq!(denied.rule.as_deref(), Some("http-post-internal-only"));
+        assert_eq!(denied.reason, "POST is only allowed to *.internal hosts");
+        assert_eq!(
+            decide(
+                "http_request",
+                json!({"method": "POST", "url": "https://build.internal/hook"})
+            )
+            .action,
+            RuleAction::Allow
+        );
+        assert_eq!(
+            decide("http_request", json!({"method": "GET", "url": "https://example.com"})).action,
+            RuleAction::Allow
+        );
+
+        assert_eq!(
+            decide("git_operations", json!({"operation": "push", "branch": "main"})).action,
+            RuleAction::Deny
+        );
+        assert_eq!(
+            decide("git_operations", json!({"operation": "push", "branch": "agent/fix"})).action,
+            RuleAction::Allow
+        );
+
+        let big = "x".repeat(2 * 1024 * 1024);
+        assert_eq!(
+            decide("file_write", json!({"path": "src/big.txt", "content": big})).action,
+            RuleAction::RequireApproval
+        );
+        assert_eq!(
+            decide("file_write", json!({"path": "docs/big.md", "content": big})).action,
+            RuleAction::Allow
+        );
+
+        let unmatched = decide("memory_recall", json!({"query": "x"}));
+        assert_eq!(unmatched.action, RuleAction::Allow);
+        assert!(unmatched.rule.is_none());
+    }
+
+    #[test]
+    fn first_matching_rule_wins() {
+        let rules = ToolRules::parse(
+            r#"
+default = "deny"
+
+[[rules]]
+name = "read-docs"
+tool = "file_*"
+when = 'starts_with(args.path, "docs/")'
+action = "allow"
+
+[[rules]]
+name = "no-files"
+tool = "file_*"
+action = "deny"
+"#,
+            Path::new("policy.toml"),
+        )
+        .unwrap();
+
+        let docs = rules.evaluate("file_read", &json!({"path": "docs/a.md"}), "cli");
+        assert_eq!(docs.rule.as_deref(), Some("read-docs"));
+        assert_eq!(docs.action, RuleAction::Allow);
+        let src = rules.evaluate("file_read", &json!({"path": "src/a.rs"}), "cli");
+        assert_eq!(src.rule.as_deref(), Some("no-files"));
+        assert_eq!(src.action, RuleAction::Deny);
+        assert_eq!(
+            rules.evaluate("shell", &json!({}), "cli").action,
+            RuleAction::Deny
+        );
+    }
+
+    #[test]
+    fn broken_rules_fail_closed() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("policy.toml");
+        std::fs::write(
+            &path,
+            "[[rules]]\nname = \"bad\"\nwhen = \"args.x ==\"\naction = \"allow\"\n",
+        )
+        .unwrap();
+        assert!(ToolRules::load(&path).is_err());
+        let rules = ToolRules::load_or_deny(&path);
+        let decision = rules.evaluate("memory_recall", &json!({}), "cli");
+        assert_eq!(decision.action, RuleAction::Deny);
+        assert!(decision.reason.contains("failed to load"));
+
+        let missing = ToolRules::load_or_deny(&tmp.path().join("missing.toml"));
+        assert_eq!(
+            missing.evaluate("shell", &json!({}), "cli").action,
+            RuleAction::Deny
+        );
+
+        // A runtime type error denies the call it was checking.
+        let rules = ToolRules::parse(
+            "[[rules]]\nname = \"size\"\nwhen = \"args.size > 10\"\naction = \"allow\"\n",
+            Path::new("policy.toml"),
+        )
+        .unwrap();
+        let decision = rules.evaluate("t", &json!({"size": "big"}), "cli");
+        assert_eq!(decision.action, RuleAction::Deny);
+        assert_eq!(decision.rule.as_deref(), Some("size"));
+    }
+
+    #[test]
+    fn relative_policy_path_resolves_outside_workspace() {
+        let resolved = resolve_path("policy.toml", Path::new("/home/u/.zeroclaw/workspace"));
+        assert_eq!(resolved, PathBuf::from("/home/u/.zeroclaw/policy.toml"));
+        let absolute = resolve_path("/etc/zeroclaw/policy.toml", Path::new("/w"));
+        assert_eq!(absolute, PathBuf::from("/etc/zeroclaw/policy.toml"));
+    }
+}
@@ -0,0 +1,204 @@
+//! Persistent conversation sessions.
+//!
+//! CLI and channel transcripts are stored in `workspace/memory/sessions.db`
+//! so conversations survive restarts and can be resumed with
+//! `zeroclaw agent --session <id>` or `zeroclaw sessions resume <id>`.
+
+mod store;
+
+pub use store::SessionStore;
+
+use crate::config::Config;
+use crate::providers::{ChatMessage, ConversationMessage};
+use crate::util::truncate_with_ellipsis;
+use anyhow::Result;
+
+/// Session channel label used by `zeroclaw agent`.
+pub const CLI_CHANNEL: &str = "cli";
+/// Session sender label used by `zeroclaw agent`.
+pub const CLI_SENDER: &str = "local";
+
+/// Convert an agent-loop history into a storable transcript.
+///
+/// System messages are dropped: the system prompt is rebuilt on every run.
+pub fn transcript_from_chat(history: &[ChatMessage]) -> Vec<ConversationMessage> {
+    history
+        .iter()
+        .filter(|message| message.role != "system")
+        .cloned()
+        .map(ConversationMessage::Chat)
+        .collect()
+}
+
+/// Convert a stored transcript back into agent-loop history.
+///
+/// Leading tool results are dropped because their originating tool call
+/// was trimmed away and providers reject orphaned tool messages.
+pub fn chat_from_transcript(transcript: Vec<ConversationMessage>) -> Vec<ChatMessage> {
+    let mut history: Vec<ChatMessage> = Vec::with_capacity(transcript.len());
+    for message in transcript {
+        match message {
+            ConversationMessage::Chat(chat) => history.push(chat),
+            ConversationMessage::AssistantToolCalls { text, .. } => {
+                if let Some(text) = text.filter(|t| !t.is_empty()) {
+                    history.push(ChatMessage::assistant(text));
+                }
+            }
+            ConversationMessage::ToolResults(results) => {
+                let mut content = String::from("[Tool results]");
+                for result in results {
+                    content.push('\n');
+                    content.push_str(&result.content);
+                }
+                history.push(ChatMessage::user(content));
+            }
+        }
+    }
+
+    let orphaned = history
+        .iter()
+        .take_while(|message| message.role == "tool")
+        .count();
+    history.drain(..orphaned);
+    history
+}
+
+fn open_store(config: &Config) -> Result<SessionStore> {
+    SessionStore::from_config(config)?.ok_or_else(|| {
+        anyhow::anyhow!("Sessions are disabled. Set [sessions] enabled = true in config.toml")
+    })
+}
+
+fn describe_message(message: &ConversationMessage) -> (String, String) {
+    match message {
+        ConversationMessage::Chat(chat) => (chat.role.clone(), chat.content.clone()),
+        ConversationMessage::AssistantToolCalls { text, tool_calls } => {
+            let names: Vec<&str> = tool_calls.iter().map(|call| call.name.as_str()).collect();
+            let mut content = format!("→ tool calls: {}", names.join(", "));
+            if let Some(text) = text.as_deref().filter(|t| !t.is_empty()) {
+                content = format!("{text}\n{content}");
+            }
+            ("assistant".into(), content)
+        }
+        ConversationMessage::ToolResults(results) => (
+            "tool".into(),
+            results
+                .iter()
+                .map(|result| result.content.as_str())
+                .collect::<Vec<_>>()
+                .join("\n"),
+        ),
+    }
+}
+
+pub async fn handle_command(command: crate::SessionCommands, config: Config) -> Result<()> {
+    match command {
+        crate::SessionCommands::List { channel, limit } => {
+            let store = open_store(&config)?;
+            let sessions = store.list(channel.as_deref(), limit)?;
+            if sessions.is_empty() {
+                println!("No sessions yet.");
+                println!("\nStart one with:");
+                println!("  zeroclaw agent");
+                return Ok(());
+            }
+
+            println!("💬 Sessions ({}):", sessions.len());
+            for session in sessions {
+                println!(
+                    "- {} | {}/{} | {} messages | updated {}",
+                    session.id,
+                    session.channel,
+                    session.sender,
+                    session.message_count,
+                    session.updated_at.to_rfc3339(),
+                );
+                if let Some(title) = &session.title {
+                    println!("    {title}");
+                }
+            }
+            Ok(())
+        }
+        crate::SessionCommands::Show { id } => {
+            let store = open_store(&config)?;
+            let id = store.resolve_id(&id)?;
+            let Some(session) = store.get(&id)? else {
+                anyhow::bail!("Session '{id}' not found");
+            };
+
+            println!("💬 Session {}", session.id);
+            println!("  Peer   : {}/{}", session.channel, session.sender);
+            println!("  Created: {}", session.created_at.to_rfc3339());
+            println!("  Updated: {}", session.updated_at.to_rfc3339());
+            println!();
+            for message in store.load(&id)? {
+                let (role, content) = describe_message(&message);
+                println!("[{role}] {}", truncate_with_ellipsis(content.trim(), 2000));
+                println!();
+            }
+            Ok(())
+        }
+        crate::SessionCommands::Resume {
+            id,
+            provider,
+            model,
+        } => {
+            let id = open_store(&config)?.resolve_id(&id)?;
+            let temperature = config.default_temperature;
+            Box::pin(crate::agent::run(
+                config,
+                None,
+                provider,
+                model,
+                temperature,
+                vec![],
+                Some(id),
+            ))
+            .await
+            .map(|_| ())
+        }
+        crate::SessionCommands::Delete { id } => {
+            let store = open_store(&config)?;
+            let id = store.resolve_id(&id)?;
+            if !store.delete(&id)? {
+                anyhow::bail!("Session '{id}' not found");
+            }
+            println!("✅ Deleted session {id}");
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::ToolResultMessage;
+
+    #[test]
+    fn transcript_from_chat_drops_system_prompt() {
+        let history = vec![
+            ChatMessage::system("prompt"),
+            ChatMessage::user("hi"),
+            ChatMessage::assistant("hello"),
+        ];
+        let transcript = transcript_from_chat(&history);
+        assert_eq!(transcript.len(), 2);
+        assert!(matches!(&transcript[0], ConversationMessage::Chat(c) if c.role == "user"));
+    }
+
+    #[test]
+    fn chat_from_transcript_drops_orphaned_tool_messages() {
+        let transcript = vec![
+            ConversationMessage::Chat(ChatMessage::tool(r#"{"tool_call_id":"a"}"#)),
+            ConversationMessage::Chat(ChatMessage::user("next question")),
+            ConversationMessage::ToolResults(vec![ToolResultMessage {
+                tool_call_id: "b".into(),
+                content: "42".into(),
+            }]),
+        ];
+        let history = chat_from_transcript(transcript);
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].content, "next question");
+        assert_eq!(history[1].content, "[Tool results]\n42");
+    }
+}
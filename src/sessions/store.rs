@@ -0,0 +1,434 @@
+use crate::providers::ConversationMessage;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+use std::path::Path;
+use uuid::Uuid;
+
+/// Maximum characters kept for an auto-derived session title.
+const SESSION_TITLE_MAX_CHARS: usize = 60;
+
+/// Session metadata (everything except the transcript).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SessionSummary {
+    pub id: String,
+    pub channel: String,
+    pub sender: String,
+    pub title: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub message_count: usize,
+}
+
+/// SQLite-backed transcript store, kept next to `brain.db`.
+///
+/// One row per session in `sessions`, one row per `ConversationMessage`
+/// in `session_messages` (JSON payload, ordered by `seq`).
+pub struct SessionStore {
+    conn: Mutex<Connection>,
+    max_messages: usize,
+}
+
+impl SessionStore {
+    /// Open (or create) `workspace/memory/sessions.db`.
+    pub fn open(workspace_dir: &Path, max_messages: usize) -> Result<Self> {
+        let db_path = workspace_dir.join("memory").join("sessions.db");
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent).with_context(|| {
+                format!("Failed to create sessions directory: {}", parent.display())
+            })?;
+        }
+
+        let conn = Connection::open(&db_path)
+            .with_context(|| format!("Failed to open sessions DB: {}", db_path.display()))?;
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             PRAGMA synchronous  = NORMAL;
+             PRAGMA foreign_keys = ON;
+             CREATE TABLE IF NOT EXISTS sessions (
+                id         TEXT PRIMARY KEY,
+                channel    TEXT NOT NULL,
+                sender     TEXT NOT NULL,
+                title      TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+             );
+             CREATE INDEX IF NOT EXISTS idx_sessions_peer ON sessions(channel, sender, updated_at);
+             CREATE INDEX IF NOT EXISTS idx_sessions_updated ON sessions(updated_at);
+
+             CREATE TABLE IF NOT EXISTS session_messages (
+                session_id TEXT NOT NULL,
+                seq        INTEGER NOT NULL,
+                payload    TEXT NOT NULL,
+                PRIMARY KEY (session_id, seq),
+                FOREIGN KEY (session_id) REFERENCES sessions(id) ON DELETE CASCADE
+             );",
+        )
+        .context("Failed to initialize sessions schema")?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+            max_messages: max_messages.max(1),
+        })
+    }
+
+    /// Open the store configured in `[sessions]`, or `None` when disabled.
+    pub fn from_config(config: &crate::config::Config) -> Result<Option<Self>> {
+        if !config.sessions.enabled {
+            return Ok(None);
+        }
+        Self::open(&config.workspace_dir, config.sessions.max_messages).map(Some)
+    }
+
+    /// Start a new, empty session for a channel peer.
+    pub fn create(&self, channel: &str, sender: &str) -> Result<SessionSummary> {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now();
+        self.conn
+            .lock()
+            .execute(
+                "INSERT INTO sessions (id, channel, sender, title, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, NULL, ?4, ?4)",
+                params![id, channel, sender, now.to_rfc3339()],
+            )
+            .context("Failed to insert session")?;
+
+        Ok(SessionSummary {
+            id,
+            channel: channel.to_string(),
+            sender: sender.to_string(),
+            title: None,
+            created_at: now,
+            updated_at: now,
+            message_count: 0,
+        })
+    }
+
+    /// Most recently updated session for a channel peer.
+    pub fn latest_for(&self, channel: &str, sender: &str) -> Result<Option<SessionSummary>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(&format!(
+            "{SUMMARY_SELECT} WHERE s.channel = ?1 AND s.sender = ?2
+             ORDER BY s.updated_at DESC, s.rowid DESC LIMIT 1"
+        ))?;
+        stmt.query_row(params![channel, sender], map_summary_row)
+            .optional()
+            .context("Failed to query latest session")
+    }
+
+    /// Latest session for a channel peer, creating one if none exists yet.
+    pub fn current_for(&self, channel: &str, sender: &str) -> Result<SessionSummary> {
+        match self.latest_for(channel, sender)? {
+            Some(summary) => Ok(summary),
+            None => self.create(channel, sender),
+        }
+    }
+
+    pub fn get(&self, id: &str) -> Result<Option<SessionSummary>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(&format!("{SUMMARY_SELECT} WHERE s.id = ?1"))?;
+        stmt.query_row(params![id], map_summary_row)
+            .optional()
+            .context("Failed to query session")
+    }
+
+    /// Resolve a full session id or a unique id prefix.
+    pub fn resolve_id(&self, id_or_prefix: &str) -> Result<String> {
+        let needle = id_or_prefix.trim();
+        if needle.is_empty() {
+            anyhow::bail!("Session id must not be empty");
+        }
+
+        let conn = self.conn.lock();
+        let mut stmt =
+            conn.prepare("SELECT id FROM sessions WHERE id = ?1 OR substr(id, 1, ?2) = ?1")?;
+        let matches = stmt
+            .query_map(params![needle, needle.len()], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        if let Some(exact) = matches.iter().find(|id| *id == needle) {
+            return Ok(exact.clone());
+        }
+        match matches.len() {
+            0 => anyhow::bail!("Session '{needle}' not found"),
+            1 => Ok(matches.into_iter().next().unwrap_or_default()),
+            n => anyhow::bail!("Session prefix '{needle}' is ambiguous ({n} matches)"),
+        }
+    }
+
+    /// List sessions, most recently updated first.
+    pub fn list(&self, channel: Option<&str>, limit: usize) -> Result<Vec<SessionSummary>> {
+        let limit = i64::try_from(limit.max(1)).unwrap_or(i64::MAX);
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(&format!(
+            "{SUMMARY_SELECT} WHERE (?1 IS NULL OR s.channel = ?1)
+             ORDER BY s.updated_at DESC, s.rowid DESC LIMIT ?2"
+        ))?;
+        let rows = stmt.query_map(params![channel, limit], map_summary_row)?;
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to list sessions")
+    }
+
+    /// Load a session transcript in conversation order.
+    pub fn load(&self, id: &str) -> Result<Vec<ConversationMessage>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn
+            .prepare("SELECT payload FROM session_messages WHERE session_id = ?1 ORDER BY seq")?;
+        let rows = stmt.query_map(params![id], |row| row.get::<_, String>(0))?;
+
+        let mut messages = Vec::new();
+        for payload in rows {
+            let payload = payload?;
+            let message = serde_json::from_str(&payload)
+                .with_context(|| format!("Corrupt message in session '{id}'"))?;
+            messages.push(message);
+        }
+        Ok(messages)
+    }
+
+    /// Replace a session transcript, keeping only the newest `max_messages`.
+    pub fn save(&self, id: &str, messages: &[ConversationMessage]) -> Result<()> {
+        let start = messages.len().saturating_sub(self.max_messages);
+        let kept = &messages[start..];
+        let title = derive_title(kept);
+        let now = Utc::now().to_rfc3339();
+
+        let mut conn = self.conn.lock();
+        let tx = conn.transaction()?;
+        let updated = tx.execute(
+            "UPDATE sessions SET updated_at = ?2, title = COALESCE(title, ?3) WHERE id = ?1",
+            params![id, now, title],
+        )?;
+        if updated == 0 {
+            anyhow::bail!("Session '{id}' not found");
+        }
+
+        tx.execute(
+            "DELETE FROM session_messages WHERE session_id = ?1",
+            params![id],
+        )?;
+        {
+            let mut insert = tx.prepare(
+                "INSERT INTO session_messages (session_id, seq, payload) VALUES (?1, ?2, ?3)",
+            )?;
+            for (seq, message) in kept.iter().enumerate() {
+                let seq = i64::try_from(seq).context("Session transcript too long")?;
+                insert.execute(params![id, seq, serde_json::to_string(message)?])?;
+            }
+        }
+        tx.commit().context("Failed to save session transcript")
+    }
+
+    /// Delete a session and its transcript. Returns `false` if it did not exist.
+    pub fn delete(&self, id: &str) -> Result<bool> {
+        let changed = self
+            .conn
+            .lock()
+            .execute("DELETE FROM sessions WHERE id = ?1", params![id])
+            .context("Failed to delete session")?;
+        Ok(changed > 0)
+    }
+}
+
+const SUMMARY_SELECT: &str =
+    "SELECT s.id, s.channel, s.sender, s.title, s.created_at, s.updated_at,
+        (SELECT COUNT(*) FROM session_messages m WHERE m.session_id = s.id)
+     FROM sessions s";
+
+fn map_summary_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<SessionSummary> {
+    let created_at: String = row.get(4)?;
+    let updated_at: String = row.get(5)?;
+    let message_count: i64 = row.get(6)?;
+    Ok(SessionSummary {
+        id: row.get(0)?,
+        channel: row.get(1)?,
+        sender: row.get(2)?,
+        title: row.get(3)?,
+        created_at: parse_rfc3339(&created_at)?,
+        updated_at: parse_rfc3339(&updated_at)?,
+        message_count: usize::try_from(message_count).unwrap_or(0),
+    })
+}
+
+fn parse_rfc3339(raw: &str) -> rusqlite::Result<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(raw)
+        .map(|parsed| parsed.with_timezone(&Utc))
+        .map_err(|e| rusqlite::Error::ToSqlConversionFailure(e.into()))
+}
+
+/// First line of the first user message, used as a human-readable label.
+fn derive_title(messages: &[ConversationMessage]) -> Option<String> {
+    messages.iter().find_map(|message| match message {
+        ConversationMessage::Chat(chat) if chat.role == "user" => {
+            let line = strip_context_preamble(&chat.content)
+                .lines()
+                .map(str::trim)
+                .find(|line| !line.is_empty())?;
+            Some(crate::util::truncate_with_ellipsis(
+                line,
+                SESSION_TITLE_MAX_CHARS,
+            ))
+        }
+        _ => None,
+    })
+}
+
+/// Skip the `[Memory context]` block the agent prepends to user messages.
+fn strip_context_preamble(content: &str) -> &str {
+    if !content.starts_with('[') {
+        return content;
+    }
+    content
+        .split_once("\n\n")
+        .map_or(content, |(_, message)| message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::{ChatMessage, ToolCall, ToolResultMessage};
+    use tempfile::TempDir;
+
+    fn store(tmp: &TempDir) -> SessionStore {
+        SessionStore::open(tmp.path(), 200).unwrap()
+    }
+
+    #[test]
+    fn create_save_load_roundtrip() {
+        let tmp = TempDir::new().unwrap();
+        let store = store(&tmp);
+        let session = store.create("cli", "local").unwrap();
+
+        let transcript = vec![
+            ConversationMessage::Chat(ChatMessage::user("hello there")),
+            ConversationMessage::AssistantToolCalls {
+                text: None,
+                tool_calls: vec![ToolCall {
+                    id: "tc1".into(),
+                    name: "shell".into(),
+                    arguments: "{}".into(),
+                }],
+            },
+            ConversationMessage::ToolResults(vec![ToolResultMessage {
+                tool_call_id: "tc1".into(),
+                content: "ok".into(),
+            }]),
+            ConversationMessage::Chat(ChatMessage::assistant("done")),
+        ];
+        store.save(&session.id, &transcript).unwrap();
+
+        let loaded = store.load(&session.id).unwrap();
+        assert_eq!(loaded.len(), 4);
+        assert!(matches!(&loaded[0], ConversationMessage::Chat(c) if c.content == "hello there"));
+        assert!(matches!(&loaded[2], ConversationMessage::ToolResults(r) if r[0].content == "ok"));
+
+        let summary = store.get(&session.id).unwrap().unwrap();
+        assert_eq!(summary.message_count, 4);
+        assert_eq!(summary.title.as_deref(), Some("hello there"));
+    }
+
+    #[test]
+    fn sessions_survive_reopen() {
+        let tmp = TempDir::new().unwrap();
+        let id = {
+            let store = store(&tmp);
+            let session = store.create("telegram", "alice").unwrap();
+            store
+                .save(
+                    &session.id,
+                    &[ConversationMessage::Chat(ChatMessage::user("remember me"))],
+                )
+                .unwrap();
+            session.id
+        };
+
+        let reopened = store(&tmp);
+        let latest = reopened.latest_for("telegram", "alice").unwrap().unwrap();
+        assert_eq!(latest.id, id);
+        assert_eq!(reopened.load(&id).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn save_keeps_newest_messages_only() {
+        let tmp = TempDir::new().unwrap();
+        let store = SessionStore::open(tmp.path(), 3).unwrap();
+        let session = store.create("cli", "local").unwrap();
+
+        let transcript: Vec<_> = (0..5)
+            .map(|i| ConversationMessage::Chat(ChatMessage::user(format!("msg {i}"))))
+            .collect();
+        store.save(&session.id, &transcript).unwrap();
+
+        let loaded = store.load(&session.id).unwrap();
+        assert_eq!(loaded.len(), 3);
+        assert!(matches!(&loaded[0], ConversationMessage::Chat(c) if c.content == "msg 2"));
+    }
+
+    #[test]
+    fn title_skips_memory_context_preamble() {
+        let messages = vec![ConversationMessage::Chat(ChatMessage::user(
+            "[Memory context]\n- pref: tea\n\nWhat should I drink?",
+        ))];
+        assert_eq!(
+            derive_title(&messages).as_deref(),
+            Some("What should I drink?")
+        );
+    }
+
+    #[test]
+    fn current_for_reuses_latest_session() {
+        let tmp = TempDir::new().unwrap();
+        let store = store(&tmp);
+
+        let first = store.current_for("discord", "bob").unwrap();
+        let again = store.current_for("discord", "bob").unwrap();
+        assert_eq!(first.id, again.id);
+
+        let other = store.current_for("discord", "carol").unwrap();
+        assert_ne!(first.id, other.id);
+    }
+
+    #[test]
+    fn list_filters_by_channel() {
+        let tmp = TempDir::new().unwrap();
+        let store = store(&tmp);
+        store.create("cli", "local").unwrap();
+        store.create("telegram", "alice").unwrap();
+
+        assert_eq!(store.list(None, 10).unwrap().len(), 2);
+        let telegram = store.list(Some("telegram"), 10).unwrap();
+        assert_eq!(telegram.len(), 1);
+        assert_eq!(telegram[0].sender, "alice");
+    }
+
+    #[test]
+    fn resolve_id_accepts_unique_prefix() {
+        let tmp = TempDir::new().unwrap();
+        let store = store(&tmp);
+        let session = store.create("cli", "local").unwrap();
+
+        assert_eq!(store.resolve_id(&session.id).unwrap(), session.id);
+        assert_eq!(store.resolve_id(&session.id[..8]).unwrap(), session.id);
+        assert!(store.resolve_id("does-not-exist").is_err());
+    }
+
+    #[test]
+    fn delete_removes_session_and_transcript() {
+        let tmp = TempDir::new().unwrap();
+        let store = store(&tmp);
+        let session = store.create("cli", "local").unwrap();
+        store
+            .save(
+                &session.id,
+                &[ConversationMessage::Chat(ChatMessage::user("bye"))],
+            )
+            .unwrap();
+
+        assert!(store.delete(&session.id).unwrap());
+        assert!(store.get(&session.id).unwrap().is_none());
+        assert!(store.load(&session.id).unwrap().is_empty());
+        assert!(!store.delete(&session.id).unwrap());
+    }
+}
@@ -24,7 +24,7 @@ impl Tool for CronAddTool {
     }
 
     fn description(&self) -> &str {
-        "Create a scheduled cron job (shell or agent) with cron/at/every schedules"
+        "Create a scheduled cron job (shell, agent or workflow) with cron/at/every schedules"
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -36,8 +36,12 @@ impl Tool for CronAddTool {
                     "type": "object",
                     "description": "Schedule object: {kind:'cron',expr,tz?} | {kind:'at',at} | {kind:'every',every_ms}"
                 },
-                "job_type": { "type": "string", "enum": ["shell", "agent"] },
+                "job_type": { "type": "string", "enum": ["shell", "agent", "workflow"] },
                 "command": { "type": "string" },
+                "workflow": {
+                    "type": "string",
+                    "description": "Workflow name from workspace/workflows (workflow jobs)"
+                },
                 "prompt": { "type": "string" },
                 "session_target": { "type": "string", "enum": ["isolated", "main"] },
                 "model": { "type": "string" },
@@ -85,6 +89,7 @@ impl Tool for CronAddTool {
         let job_type = match args.get("job_type").and_then(serde_json::Value::as_str) {
             Some("agent") => JobType::Agent,
             Some("shell") => JobType::Shell,
+            Some("workflow") => JobType::Workflow,
             Some(other) => {
                 return Ok(ToolResult {
                     success: false,
@@ -95,6 +100,8 @@ impl Tool for CronAddTool {
             None => {
                 if args.get("prompt").is_some() {
                     JobType::Agent
+                } else if args.get("workflow").is_some() {
+                    JobType::Workflow
                 } else {
                     JobType::Shell
                 }
@@ -186,6 +193,29 @@ impl Tool for CronAddTool {
                     delete_after_run,
                 )
             }
+            JobType::Workflow => {
+                let workflow = match args.get("workflow").and_then(serde_json::Value::as_str) {
+                    Some(workflow) if !workflow.trim().is_empty() => workflow.trim(),
+                    _ => {
+                        return Ok(ToolResult {
+                            success: false,
+                            output: String::new(),
+                            error: Some("Missing 'workflow' for workflow job".to_string()),
+                        });
+                    }
+                };
+
+                if let Err(e) = crate::workflow::find_workflow(&self.config.workspace_dir, workflow)
+                {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(e.to_string()),
+                    });
+                }
+
+                cron::add_workflow_job(&self.config, name, schedule, workflow)
+            }
         };
 
         match result {
@@ -323,4 +353,34 @@ mod tests {
             .unwrap_or_default()
             .contains("Missing 'prompt'"));
     }
+
+    #[tokio::test]
+    async fn workflow_job_requires_existing_workflow() {
+        let tmp = TempDir::new().unwrap();
+        let cfg = test_config(&tmp);
+        let tool = CronAddTool::new(cfg.clone(), test_security(&cfg));
+        let schedule = json!({ "kind": "cron", "expr": "0 9 * * *" });
+
+        let result = tool
+            .execute(json!({ "schedule": schedule, "workflow": "digest" }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap_or_default().contains("not found"));
+
+        let dir = cfg.workspace_dir.join("workflows");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("digest.yaml"),
+            "name: digest\nsteps:\n  - { id: hi, type: agent, prompt: hello }\n",
+        )
+        .unwrap();
+
+        let result = tool
+            .execute(json!({ "schedule": schedule, "workflow": "digest" }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("\"workflow\""));
+    }
 }
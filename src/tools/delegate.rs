@@ -1,7 +1,13 @@
-use crate::persona;
+use super::delegate_parallel::DelegateParallelTool;
 use super::traits::{Tool, ToolResult};
-use crate::config::DelegateAgentConfig;
-use crate::providers::{self, Provider};
+use crate::agent::loop_::{build_intervention_chain, build_tool_instructions, run_tool_call_loop};
+use crate::approval::ApprovalManager;
+use crate::config::{Config, DelegateAgentConfig};
+use crate::memory::{Memory, NamespacedMemory};
+use crate::observability::NoopObserver;
+use crate::persona;
+use crate::providers::{self, ChatMessage, Provider};
+use crate::runtime::RuntimeAdapter;
 use crate::security::policy::ToolOperation;
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
@@ -13,10 +19,22 @@ use std::time::Duration;
 /// Default timeout for sub-agent provider calls.
 const DELEGATE_TIMEOUT_SECS: u64 = 120;
 
+/// Timeout for a sub-agent running its own tool loop.
+const DELEGATE_AGENT_LOOP_TIMEOUT_SECS: u64 = 600;
+
+/// Shared runtime pieces a sub-agent needs to build its own tool registry.
+#[derive(Clone)]
+pub struct SubAgentToolkit {
+    pub config: Arc<Config>,
+    pub runtime: Arc<dyn RuntimeAdapter>,
+    pub memory: Arc<dyn Memory>,
+}
+
 /// Tool that delegates a subtask to a named agent with a different
 /// provider/model configuration. Enables multi-agent workflows where
 /// a primary agent can hand off specialized work (research, coding,
 /// summarization) to purpose-built sub-agents.
+#[derive(Clone)]
 pub struct DelegateTool {
     agents: Arc<HashMap<String, DelegateAgentConfig>>,
     security: Arc<SecurityPolicy>,
@@ -24,6 +42,8 @@ pub struct DelegateTool {
     fallback_credential: Option<String>,
     /// Depth at which this tool instance lives in the delegation chain.
     depth: u32,
+    /// When set, sub-agents run a full tool loop instead of a single prompt.
+    toolkit: Option<SubAgentToolkit>,
 }
 
 impl DelegateTool {
@@ -37,12 +57,13 @@ impl DelegateTool {
             security,
             fallback_credential,
             depth: 0,
+            toolkit: None,
         }
     }
 
     /// Create a DelegateTool for a sub-agent (with incremented depth).
-    /// When sub-agents eventually get their own tool registry, construct
-    /// their DelegateTool via this method with `depth: parent.depth + 1`.
+    /// Sub-agent registries construct their DelegateTool via this method
+    /// with `depth: parent.depth + 1`.
     pub fn with_depth(
         agents: HashMap<String, DelegateAgentConfig>,
         fallback_credential: Option<String>,
@@ -54,8 +75,83 @@ impl DelegateTool {
             security,
             fallback_credential,
             depth,
+            toolkit: None,
         }
     }
+
+    /// Give sub-agents their own tool registry and tool-call loop.
+    #[must_use]
+    pub fn with_toolkit(mut self, toolkit: SubAgentToolkit) -> Self {
+        self.toolkit = Some(toolkit);
+        self
+    }
+
+    /// Names of the configured agents.
+    pub fn agent_names(&self) -> Vec<&str> {
+        self.agents.keys().map(String::as_str).collect()
+    }
+
+    pub fn has_agent(&self, name: &str) -> bool {
+        self.agents.contains_key(name)
+    }
+
+    /// Build the tool registry a sub-agent sees: the regular tools bound to
+    /// its scoped policy and memory namespace, a nested `delegate` one level
+    /// deeper (plus its parallel fan-out), filtered down to what the agent's allow/deny lists permit.
+    /// Nobody can answer approval prompts inside a sub-agent, so tools that
+    /// need approval under `[autonomy]` are left out.
+    fn sub_agent_tools(
+        &self,
+        toolkit: &SubAgentToolkit,
+        agent_name: &str,
+        agent_config: &DelegateAgentConfig,
+        security: &Arc<SecurityPolicy>,
+    ) -> Vec<Box<dyn Tool>> {
+        let config = &toolkit.config;
+        let namespace = agent_config
+            .memory_namespace
+            .clone()
+            .unwrap_or_else(|| format!("agent:{agent_name}"));
+        let memory: Arc<dyn Memory> =
+            Arc::new(NamespacedMemory::new(toolkit.memory.clone(), namespace));
+        let (composio_key, composio_entity_id) = if config.composio.enabled {
+            (
+                config.composio.api_key.as_deref(),
+                Some(config.composio.entity_id.as_str()),
+            )
+        } else {
+            (None, None)
+        };
+
+        let mut tools = super::all_tools_with_runtime(
+            config.clone(),
+            security,
+            toolkit.runtime.clone(),
+            memory,
+            composio_key,
+            composio_entity_id,
+            &config.browser,
+            &config.http_request,
+            &config.workspace_dir,
+            &HashMap::new(),
+            self.fallback_credential.as_deref(),
+            config,
+        );
+        let nested = DelegateTool::with_depth(
+            (*self.agents).clone(),
+            self.fallback_credential.clone(),
+            security.clone(),
+            self.depth + 1,
+        )
+        .with_toolkit(toolkit.clone());
+        tools.push(Box::new(DelegateParallelTool::new(nested.clone())));
+        tools.push(Box::new(nested));
+        let approval = ApprovalManager::from_config(&config.autonomy);
+        tools.retain(|tool| {
+            security.check_tool(tool.name()).is_ok() && !approval.needs_approval(tool.name())
+        });
+        tools
+    }
 }
 
 #[async_trait]
@@ -66,8 +162,8 @@ impl Tool for DelegateTool {
 
     fn description(&self) -> &str {
         "Delegate a subtask to a specialized agent. Use when: a task benefits from a different model \
-         (e.g. fast summarization, deep reasoning, code generation). The sub-agent runs a single \
-         prompt and returns its response."
+         (e.g. fast summarization, deep reasoning, code generation). The sub-agent works the task \
+         with its own tools and returns its final answer."
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -137,6 +233,20 @@ impl Tool for DelegateTool {
             .map(str::trim)
             .unwrap_or("");
 
+        self.run(agent_name, prompt, context, None).await
+    }
+}
+
+impl DelegateTool {
+    /// Run one delegation. `system_prompt_override` replaces the agent's
+    /// persona prompt (used to run an agent as a reviewer).
+    pub(crate) async fn run(
+        &self,
+        agent_name: &str,
+        prompt: &str,
+        context: &str,
+        system_prompt_override: Option<String>,
+    ) -> anyhow::Result<ToolResult> {
         // Look up agent config
         let agent_config = match self.agents.get(agent_name) {
             Some(cfg) => cfg,
@@ -239,25 +349,68 @@ impl Tool for DelegateTool {
         };
 
         // ── Persona scaffolding: select compiled template based on agent persona ──
-        let effective_system_prompt = persona::persona_system_prompt(
-            agent_name,
-            agent_config.persona.as_deref(),
-            agent_config.system_prompt.as_deref(),
-        );
+        let effective_system_prompt = system_prompt_override.unwrap_or_else(|| {
+            persona::persona_system_prompt(
+                agent_name,
+                agent_config.persona.as_deref(),
+                agent_config.system_prompt.as_deref(),
+            )
+        });
 
         let temperature = agent_config.temperature.unwrap_or(0.7);
 
-        // Wrap the provider call in a timeout to prevent indefinite blocking
-        let result = tokio::time::timeout(
-            Duration::from_secs(DELEGATE_TIMEOUT_SECS),
-            provider.chat_with_system(
-                Some(&effective_system_prompt),
-                &full_prompt,
-                &agent_config.model,
-                temperature,
-            ),
-        )
-        .await;
+        let (result, timeout_secs) = if let Some(toolkit) = self.toolkit.as_ref() {
+            // ── Full sub-agent: own tool registry, loop and memory namespace ──
+            let scoped_security = Arc::new(scoped_security.clone());
+            let tools = self.sub_agent_tools(toolkit, agent_name, agent_config, &scoped_security);
+            let mut system_prompt = effective_system_prompt;
+            system_prompt.push_str(&build_tool_instructions(&tools));
+            let mut history = vec![
+                ChatMessage::system(system_prompt),
+                ChatMessage::user(full_prompt),
+            ];
+            let max_iterations = if agent_config.max_iterations == 0 {
+                toolkit.config.agent.max_tool_iterations
+            } else {
+                agent_config.max_iterations
+            };
+            let chain = build_intervention_chain(&toolkit.config.autonomy);
+
+            let result = tokio::time::timeout(
+                Duration::from_secs(DELEGATE_AGENT_LOOP_TIMEOUT_SECS),
+                run_tool_call_loop(
+                    provider.as_ref(),
+                    &mut history,
+                    &tools,
+                    &NoopObserver,
+                    &agent_config.provider,
+                    &agent_config.model,
+                    temperature,
+                    true,
+                    None,
+                    "delegate",
+                    max_iterations,
+                    None,
+                    &scoped_security,
+                    &chain,
+                ),
+            )
+            .await;
+            (result, DELEGATE_AGENT_LOOP_TIMEOUT_SECS)
+        } else {
+            // Wrap the provider call in a timeout to prevent indefinite blocking
+            let result = tokio::time::timeout(
+                Duration::from_secs(DELEGATE_TIMEOUT_SECS),
+                provider.chat_with_system(
+                    Some(&effective_system_prompt),
+                    &full_prompt,
+                    &agent_config.model,
+                    temperature,
+                ),
+            )
+            .await;
+            (result, DELEGATE_TIMEOUT_SECS)
+        };
 
         let result = match result {
             Ok(inner) => inner,
@@ -266,7 +419,7 @@ impl Tool for DelegateTool {
                     success: false,
                     output: String::new(),
                     error: Some(format!(
-                        "Agent '{agent_name}' timed out after {DELEGATE_TIMEOUT_SECS}s"
+                        "Agent '{agent_name}' timed out after {timeout_secs}s"
                     )),
                 });
             }
@@ -330,6 +483,8 @@ mod tests {
                 max_depth: 3,
                 denied_tools: Vec::new(),
                 allowed_tools: Vec::new(),
+                max_iterations: 0,
+                memory_namespace: None,
             },
         );
         agents.insert(
@@ -344,6 +499,8 @@ mod tests {
                 max_depth: 2,
                 denied_tools: Vec::new(),
                 allowed_tools: Vec::new(),
+                max_iterations: 0,
+                memory_namespace: None,
             },
         );
         agents
@@ -454,6 +611,8 @@ mod tests {
                 max_depth: 3,
                 denied_tools: Vec::new(),
                 allowed_tools: Vec::new(),
+                max_iterations: 0,
+                memory_namespace: None,
             },
         );
         let tool = DelegateTool::new(agents, None, test_security());
@@ -560,6 +719,8 @@ mod tests {
                 max_depth: 3,
                 denied_tools: Vec::new(),
                 allowed_tools: Vec::new(),
+                max_iterations: 0,
+                memory_namespace: None,
             },
         );
         let tool = DelegateTool::new(agents, None, test_security());
@@ -595,6 +756,8 @@ mod tests {
                 max_depth: 3,
                 denied_tools: Vec::new(),
                 allowed_tools: Vec::new(),
+                max_iterations: 0,
+                memory_namespace: None,
             },
         );
         let tool = DelegateTool::new(agents, None, test_security());
@@ -631,4 +794,111 @@ mod tests {
         assert!(!result.success);
         assert!(result.error.unwrap().contains("none configured"));
     }
+
+    fn test_toolkit(tmp: &tempfile::TempDir) -> SubAgentToolkit {
+        let mut toolkit = supervised_toolkit(tmp);
+        let mut config = (*toolkit.config).clone();
+        config.autonomy.level = AutonomyLevel::Full;
+        toolkit.config = Arc::new(config);
+        toolkit
+    }
+
+    fn supervised_toolkit(tmp: &tempfile::TempDir) -> SubAgentToolkit {
+        let config = Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        };
+        let memory = crate::memory::SqliteMemory::new(tmp.path()).unwrap();
+        SubAgentToolkit {
+            config: Arc::new(config),
+            runtime: Arc::new(crate::runtime::NativeRuntime::new()),
+            memory: Arc::new(memory),
+        }
+    }
+
+    fn scoped_tool_names(agent_config: &DelegateAgentConfig) -> Vec<String> {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let toolkit = test_toolkit(&tmp);
+        let security = Arc::new(test_security().scoped_for_agent(
+            "researcher",
+            agent_config.denied_tools.clone(),
+            agent_config.allowed_tools.clone(),
+        ));
+        let tool = DelegateTool::new(sample_agents(), None, test_security());
+        let mut names: Vec<String> = tool
+            .sub_agent_tools(&toolkit, "researcher", agent_config, &security)
+            .iter()
+            .map(|t| t.name().to_string())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn sub_agent_registry_honors_allowlist() {
+        let mut agent = sample_agents().remove("researcher").unwrap();
+        agent.allowed_tools = vec!["file_read".into(), "delegate".into()];
+        assert_eq!(scoped_tool_names(&agent), vec!["delegate", "file_read"]);
+    }
+
+    #[test]
+    fn sub_agent_registry_honors_denylist() {
+        let mut agent = sample_agents().remove("researcher").unwrap();
+        agent.denied_tools = vec!["shell".into()];
+        let names = scoped_tool_names(&agent);
+        assert!(!names.contains(&"shell".to_string()));
+        assert!(names.contains(&"file_read".to_string()));
+        assert!(names.contains(&"memory_store".to_string()));
+        assert_eq!(names.iter().filter(|n| *n == "delegate").count(), 1);
+    }
+
+    #[test]
+    fn sub_agent_registry_drops_tools_that_need_approval() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let toolkit = supervised_toolkit(&tmp);
+        let agent = sample_agents().remove("researcher").unwrap();
+        let security =
+            Arc::new(test_security().scoped_for_agent("researcher", Vec::new(), Vec::new()));
+        let tool = DelegateTool::new(sample_agents(), None, test_security());
+        let names: Vec<String> = tool
+            .sub_agent_tools(&toolkit, "researcher", &agent, &security)
+            .iter()
+            .map(|t| t.name().to_string())
+            .collect();
+
+        // Supervised mode only auto-approves file_read and memory_recall.
+        for supervised in ["shell", "file_write", "delegate", "delegate_parallel"] {
+            assert!(!names.contains(&supervised.to_string()), "{supervised}");
+        }
+        assert!(names.contains(&"file_read".to_string()));
+        assert!(names.contains(&"memory_recall".to_string()));
+    }
+
+    #[tokio::test]
+    async fn sub_agent_memory_is_namespaced() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let toolkit = test_toolkit(&tmp);
+        let agent = sample_agents().remove("researcher").unwrap();
+        let security =
+            Arc::new(test_security().scoped_for_agent("researcher", Vec::new(), Vec::new()));
+        let tool = DelegateTool::new(sample_agents(), None, test_security());
+        let tools = tool.sub_agent_tools(&toolkit, "researcher", &agent, &security);
+        let store = tools.iter().find(|t| t.name() == "memory_store").unwrap();
+
+        let result = store
+            .execute(json!({"key": "finding", "content": "sources agree"}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+
+        assert!(toolkit.memory.get("finding").await.unwrap().is_none());
+        let stored = toolkit
+            .memory
+            .get("agent:researcher/finding")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.content, "sources agree");
+    }
 }
@@ -0,0 +1,618 @@
+use super::delegate::DelegateTool;
+use super::traits::{Tool, ToolResult};
+use crate::persona;
+use async_trait::async_trait;
+use serde_json::json;
+use std::fmt::Write;
+use std::time::Duration;
+
+/// Default time limit for a whole fan-out call, reconciliation included.
+const DEFAULT_PARALLEL_TIMEOUT_SECS: u64 = 300;
+
+/// Upper bound on the time limit a caller may request.
+const MAX_PARALLEL_TIMEOUT_SECS: u64 = 600;
+
+/// Default and maximum number of sub-agent runs per call, reviewer included.
+const DEFAULT_PARALLEL_BUDGET: usize = 4;
+const MAX_PARALLEL_BUDGET: usize = 8;
+
+const PICK_INSTRUCTIONS: &str = "\
+You are reconciling independent answers to the same task. Compare them, point \
+out where they disagree and which claims are unsupported, then choose the \
+single best answer. Start your reply with `Chosen: Answer N`, then give the \
+final answer, corrected where the others exposed a flaw.";
+
+const MERGE_INSTRUCTIONS: &str = "\
+You are reconciling independent answers to the same task. Compare them, point \
+out where they disagree and which claims are unsupported, then write one final \
+answer that keeps what is correct from each and resolves every disagreement \
+explicitly.";
+
+/// How collected answers are reconciled into a final answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Reconcile {
+    None,
+    Pick,
+    Merge,
+}
+
+impl Reconcile {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "none" => Some(Self::None),
+            "pick" => Some(Self::Pick),
+            "merge" => Some(Self::Merge),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Pick => "pick",
+            Self::Merge => "merge",
+        }
+    }
+}
+
+/// Outcome of one sub-agent run in a fan-out.
+struct RunOutcome {
+    agent: String,
+    answer: Result<String, String>,
+}
+
+/// Tool that sends one task to several agents (or several copies of one
+/// agent) concurrently and optionally has a reviewer pick or merge a final
+/// answer. One time limit and one run budget cover the whole call.
+pub struct DelegateParallelTool {
+    delegate: DelegateTool,
+}
+
+impl DelegateParallelTool {
+    pub fn new(delegate: DelegateTool) -> Self {
+        Self { delegate }
+    }
+
+    fn failure(error: String) -> ToolResult {
+        ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(error),
+        }
+    }
+
+    /// Resolve the list of agent runs from `agents` or `agent` + `copies`.
+    fn planned_runs(args: &serde_json::Value) -> Result<Vec<String>, String> {
+        let agents = args.get("agents").and_then(|v| v.as_array());
+        let agent = args
+            .get("agent")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|name| !name.is_empty());
+
+        match (agents, agent) {
+            (Some(_), Some(_)) => Err("Pass either 'agents' or 'agent', not both".into()),
+            (Some(list), None) => {
+                let names: Vec<String> = list
+                    .iter()
+                    .filter_map(|v| v.as_str())
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .map(str::to_string)
+                    .collect();
+                if names.is_empty() {
+                    return Err("'agents' must list at least one agent name".into());
+                }
+                Ok(names)
+            }
+            (None, Some(name)) => {
+                let copies = args.get("copies").and_then(|v| v.as_u64()).unwrap_or(2);
+                if copies == 0 {
+                    return Err("'copies' must be at least 1".into());
+                }
+                match usize::try_from(copies) {
+                    Ok(copies) if copies <= MAX_PARALLEL_BUDGET => {
+                        Ok(vec![name.to_string(); copies])
+                    }
+                    _ => Err(format!("'copies' must be at most {MAX_PARALLEL_BUDGET}")),
+                }
+            }
+            (None, None) => Err("Missing 'agents' (or 'agent' with 'copies') parameter".into()),
+        }
+    }
+
+    /// Build the reviewer's task from the original prompt and collected answers.
+    fn reconcile_prompt(prompt: &str, answers: &[&RunOutcome]) -> String {
+        let mut out = format!("[Task]\n{prompt}\n\n[Answers]\n");
+        for (i, run) in answers.iter().enumerate() {
+            if let Ok(answer) = &run.answer {
+                let _ = write!(
+                    out,
+                    "\n### Answer {n} ({agent})\n{answer}\n",
+                    n = i + 1,
+                    agent = run.agent
+                );
+            }
+        }
+        out
+    }
+
+    /// True when every answer is the same after whitespace normalization.
+    fn answers_converged(answers: &[&RunOutcome]) -> bool {
+        let mut bodies = answers.iter().filter_map(|run| {
+            run.answer
+                .as_ref()
+                .ok()
+                .map(|a| a.split_whitespace().collect::<Vec<_>>().join(" "))
+        });
+        let Some(first) = bodies.next() else {
+            return false;
+        };
+        let mut count = 1;
+        for body in bodies {
+            if body != first {
+                return false;
+            }
+            count += 1;
+        }
+        count > 1
+    }
+
+    fn reviewer_instructions(mode: Reconcile, converged: bool) -> String {
+        let base = match mode {
+            Reconcile::Merge => MERGE_INSTRUCTIONS,
+            Reconcile::Pick | Reconcile::None => PICK_INSTRUCTIONS,
+        };
+        if converged {
+            format!("{base}\n\n{}", persona::ANTI_CONVERGENCE_PROMPT)
+        } else {
+            base.to_string()
+        }
+    }
+
+    /// Strip the `[Agent 'x' (provider/model)]` header `delegate` adds so the
+    /// reviewer compares answers, not labels.
+    fn answer_body(output: &str) -> String {
+        match output.split_once('\n') {
+            Some((header, body)) if header.starts_with("[Agent '") => body.to_string(),
+            _ => output.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for DelegateParallelTool {
+    fn name(&self) -> &str {
+        "delegate_parallel"
+    }
+
+    fn description(&self) -> &str {
+        "Send the same task to several specialized agents (or several copies of one agent) at once \
+         and collect every answer. Optionally have a reviewer agent pick the best answer or merge \
+         them into one. Use when: a question benefits from independent opinions to cross-check."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        let agent_names = self.delegate.agent_names();
+        let available = if agent_names.is_empty() {
+            "(none configured)".to_string()
+        } else {
+            agent_names.join(", ")
+        };
+        json!({
+            "type": "object",
+            "additionalProperties": false,
+            "properties": {
+                "prompt": {
+                    "type": "string",
+                    "minLength": 1,
+                    "description": "The task/prompt every agent receives"
+                },
+                "context": {
+                    "type": "string",
+                    "description": "Optional context to prepend (e.g. relevant code, prior findings)"
+                },
+                "agents": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": format!("Agents to ask; repeat a name to ask it more than once. Available: {available}")
+                },
+                "agent": {
+                    "type": "string",
+                    "description": "Single agent to run several copies of (use with 'copies' instead of 'agents')"
+                },
+                "copies": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "Number of copies of 'agent' to run (default: 2)"
+                },
+                "timeout_secs": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "maximum": MAX_PARALLEL_TIMEOUT_SECS,
+                    "description": format!("Time limit for the whole call, reconciliation included, in seconds (default: {DEFAULT_PARALLEL_TIMEOUT_SECS})")
+                },
+                "budget": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "maximum": MAX_PARALLEL_BUDGET,
+                    "description": format!("Maximum number of agent runs this call may start, counting the reviewer (default: {DEFAULT_PARALLEL_BUDGET})")
+                },
+                "reconcile": {
+                    "type": "string",
+                    "enum": ["none", "pick", "merge"],
+                    "description": "Have a reviewer pick the best answer or merge them (default: none)"
+                },
+                "reviewer": {
+                    "type": "string",
+                    "description": "Agent that reconciles the answers (default: the first agent asked)"
+                }
+            },
+            "required": ["prompt"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let prompt = args
+            .get("prompt")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .ok_or_else(|| anyhow::anyhow!("Missing 'prompt' parameter"))?;
+
+        if prompt.is_empty() {
+            return Ok(Self::failure("'prompt' parameter must not be empty".into()));
+        }
+
+        let context = args
+            .get("context")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .unwrap_or("");
+
+        let runs = match Self::planned_runs(&args) {
+            Ok(runs) => runs,
+            Err(error) => return Ok(Self::failure(error)),
+        };
+
+        let reconcile = match args.get("reconcile").and_then(|v| v.as_str()) {
+            None => Reconcile::None,
+            Some(value) => match Reconcile::parse(value.trim()) {
+                Some(mode) => mode,
+                None => {
+                    return Ok(Self::failure(format!(
+                        "Unknown reconcile mode '{value}'. Use none, pick or merge"
+                    )));
+                }
+            },
+        };
+
+        let budget = args
+            .get("budget")
+            .and_then(|v| v.as_u64())
+            .map_or(DEFAULT_PARALLEL_BUDGET, |b| {
+                usize::try_from(b).unwrap_or(usize::MAX)
+            })
+            .clamp(1, MAX_PARALLEL_BUDGET);
+        let planned = runs.len() + usize::from(reconcile != Reconcile::None);
+        if planned > budget {
+            return Ok(Self::failure(format!(
+                "Requested {planned} agent runs (reviewer included) but the budget allows {budget} (max {MAX_PARALLEL_BUDGET})"
+            )));
+        }
+
+        let timeout_secs = args
+            .get("timeout_secs")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(DEFAULT_PARALLEL_TIMEOUT_SECS)
+            .clamp(1, MAX_PARALLEL_TIMEOUT_SECS);
+
+        let reviewer = args
+            .get("reviewer")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map_or_else(|| runs[0].clone(), str::to_string);
+
+        // Validate every name before spending any of the budget.
+        let mut checked: Vec<&str> = runs.iter().map(String::as_str).collect();
+        if reconcile != Reconcile::None {
+            checked.push(&reviewer);
+        }
+        if let Some(unknown) = checked.iter().find(|name| !self.delegate.has_agent(name)) {
+            let available = self.delegate.agent_names();
+            return Ok(Self::failure(format!(
+                "Unknown agent '{unknown}'. Available agents: {}",
+                if available.is_empty() {
+                    "(none configured)".to_string()
+                } else {
+                    available.join(", ")
+                }
+            )));
+        }
+
+        // Runs and the reviewer share one deadline.
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(timeout_secs);
+        let outcomes = futures::future::join_all(runs.iter().map(|agent| async move {
+            let answer = match tokio::time::timeout_at(
+                deadline,
+                self.delegate.run(agent, prompt, context, None),
+            )
+            .await
+            {
+                Ok(Ok(result)) if result.success => Ok(result.output),
+                Ok(Ok(result)) => Err(result.error.unwrap_or_else(|| "unknown error".into())),
+                Ok(Err(e)) => Err(e.to_string()),
+                Err(_elapsed) => Err(format!("timed out after {timeout_secs}s")),
+            };
+            RunOutcome {
+                agent: agent.clone(),
+                answer,
+            }
+        }))
+        .await;
+
+        let answered: Vec<&RunOutcome> = outcomes.iter().filter(|o| o.answer.is_ok()).collect();
+        let mut output = format!(
+            "[delegate_parallel] {}/{} runs answered\n",
+            answered.len(),
+            outcomes.len()
+        );
+        for (i, outcome) in outcomes.iter().enumerate() {
+            match &outcome.answer {
+                Ok(answer) => {
+                    let _ = write!(
+                        output,
+                        "\n── Run {}: {} ──\n{answer}\n",
+                        i + 1,
+                        outcome.agent
+                    );
+                }
+                Err(error) => {
+                    let _ = write!(
+                        output,
+                        "\n── Run {}: {} (failed) ──\n{error}\n",
+                        i + 1,
+                        outcome.agent
+                    );
+                }
+            }
+        }
+
+        if answered.is_empty() {
+            return Ok(ToolResult {
+                success: false,
+                output,
+                error: Some("No agent produced an answer".into()),
+            });
+        }
+
+        if reconcile != Reconcile::None {
+            let bodies: Vec<RunOutcome> = answered
+                .iter()
+                .map(|run| RunOutcome {
+                    agent: run.agent.clone(),
+                    answer: run
+                        .answer
+                        .as_deref()
+                        .map(Self::answer_body)
+                        .map_err(Clone::clone),
+                })
+                .collect();
+            let bodies: Vec<&RunOutcome> = bodies.iter().collect();
+            let instructions =
+                Self::reviewer_instructions(reconcile, Self::answers_converged(&bodies));
+            let system_prompt = persona::reviewer_system_prompt(&reviewer, Some(&instructions));
+            let task = Self::reconcile_prompt(prompt, &bodies);
+
+            let verdict = match tokio::time::timeout_at(
+                deadline,
+                self.delegate.run(&reviewer, &task, "", Some(system_prompt)),
+            )
+            .await
+            {
+                Ok(Ok(result)) if result.success => Ok(result.output),
+                Ok(Ok(result)) => Err(result.error.unwrap_or_else(|| "unknown error".into())),
+                Ok(Err(e)) => Err(e.to_string()),
+                Err(_elapsed) => Err(format!("timed out after {timeout_secs}s")),
+            };
+            let _ = match verdict {
+                Ok(answer) => write!(
+                    output,
+                    "\n── Final answer ({}, reviewer: {reviewer}) ──\n{answer}\n",
+                    reconcile.as_str()
+                ),
+                Err(error) => write!(
+                    output,
+                    "\n── Reconciliation failed (reviewer: {reviewer}) ──\n{error}\n"
+                ),
+            };
+        }
+
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DelegateAgentConfig;
+    use crate::security::SecurityPolicy;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    fn agent(provider: &str) -> DelegateAgentConfig {
+        DelegateAgentConfig {
+            provider: provider.to_string(),
+            model: "test-model".to_string(),
+            system_prompt: None,
+            api_key: None,
+            temperature: None,
+            persona: None,
+            max_depth: 3,
+            denied_tools: Vec::new(),
+            allowed_tools: Vec::new(),
+            max_iterations: 0,
+            memory_namespace: None,
+        }
+    }
+
+    fn test_tool() -> DelegateParallelTool {
+        let mut agents = HashMap::new();
+        agents.insert("alpha".to_string(), agent("totally-invalid-provider"));
+        agents.insert("beta".to_string(), agent("another-invalid-provider"));
+        DelegateParallelTool::new(DelegateTool::new(
+            agents,
+            None,
+            Arc::new(SecurityPolicy::default()),
+        ))
+    }
+
+    fn outcome(agent: &str, answer: &str) -> RunOutcome {
+        RunOutcome {
+            agent: agent.to_string(),
+            answer: Ok(answer.to_string()),
+        }
+    }
+
+    #[test]
+    fn name_and_schema() {
+        let tool = test_tool();
+        assert_eq!(tool.name(), "delegate_parallel");
+        let schema = tool.parameters_schema();
+        assert_eq!(schema["required"], json!(["prompt"]));
+        assert!(schema["properties"]["agents"].is_object());
+        assert!(schema["properties"]["copies"].is_object());
+        assert_eq!(
+            schema["properties"]["reconcile"]["enum"],
+            json!(["none", "pick", "merge"])
+        );
+        let desc = schema["properties"]["agents"]["description"]
+            .as_str()
+            .unwrap();
+        assert!(desc.contains("alpha"));
+        assert!(desc.contains("beta"));
+    }
+
+    #[test]
+    fn planned_runs_supports_names_and_copies() {
+        let runs = DelegateParallelTool::planned_runs(&json!({"agents": ["alpha", " beta ", ""]}))
+            .unwrap();
+        assert_eq!(runs, vec!["alpha", "beta"]);
+
+        let runs =
+            DelegateParallelTool::planned_runs(&json!({"agent": "alpha", "copies": 3})).unwrap();
+        assert_eq!(runs, vec!["alpha"; 3]);
+
+        assert!(
+            DelegateParallelTool::planned_runs(&json!({"agents": ["alpha"], "agent": "beta"}))
+                .is_err()
+        );
+        assert!(
+            DelegateParallelTool::planned_runs(&json!({"agent": "alpha", "copies": 0})).is_err()
+        );
+        assert!(DelegateParallelTool::planned_runs(&json!({})).is_err());
+    }
+
+    #[tokio::test]
+    async fn budget_exceeded_is_rejected() {
+        let result = test_tool()
+            .execute(json!({"prompt": "q", "agent": "alpha", "copies": 3, "budget": 2}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("budget allows 2"));
+    }
+
+    #[tokio::test]
+    async fn budget_counts_the_reviewer() {
+        let result = test_tool()
+            .execute(json!({"prompt": "q", "agents": ["alpha", "beta"], "reconcile": "pick", "budget": 2}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result
+            .error
+            .unwrap()
+            .contains("Requested 3 agent runs (reviewer included)"));
+    }
+
+    #[tokio::test]
+    async fn unknown_agent_rejected_before_running() {
+        let result = test_tool()
+            .execute(json!({"prompt": "q", "agents": ["alpha", "ghost"]}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("Unknown agent 'ghost'"));
+
+        let result = test_tool()
+            .execute(json!({"prompt": "q", "agents": ["alpha"], "reconcile": "pick", "reviewer": "ghost"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("Unknown agent 'ghost'"));
+    }
+
+    #[tokio::test]
+    async fn unknown_reconcile_mode_rejected() {
+        let result = test_tool()
+            .execute(json!({"prompt": "q", "agents": ["alpha"], "reconcile": "vote"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("Unknown reconcile mode"));
+    }
+
+    #[tokio::test]
+    async fn failures_are_collected_per_run() {
+        let result = test_tool()
+            .execute(json!({"prompt": "q", "agents": ["alpha", "beta"], "timeout_secs": 5}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert_eq!(result.error.as_deref(), Some("No agent produced an answer"));
+        assert!(result.output.contains("0/2 runs answered"));
+        assert!(result.output.contains("Run 1: alpha (failed)"));
+        assert!(result.output.contains("Run 2: beta (failed)"));
+        assert!(result.output.contains("Failed to create provider"));
+    }
+
+    #[test]
+    fn reconcile_prompt_numbers_answers() {
+        let a = outcome("alpha", "42");
+        let b = outcome("beta", "41");
+        let prompt = DelegateParallelTool::reconcile_prompt("What is it?", &[&a, &b]);
+        assert!(prompt.starts_with("[Task]\nWhat is it?"));
+        assert!(prompt.contains("### Answer 1 (alpha)\n42"));
+        assert!(prompt.contains("### Answer 2 (beta)\n41"));
+    }
+
+    #[test]
+    fn converged_answers_add_anti_convergence_prompt() {
+        let a = outcome("alpha", "The answer is 42.");
+        let b = outcome("beta", "The answer   is\n42.");
+        let c = outcome("beta", "It is 41.");
+        assert!(DelegateParallelTool::answers_converged(&[&a, &b]));
+        assert!(!DelegateParallelTool::answers_converged(&[&a, &c]));
+        assert!(!DelegateParallelTool::answers_converged(&[&a]));
+
+        let instructions = DelegateParallelTool::reviewer_instructions(Reconcile::Merge, true);
+        assert!(instructions.starts_with(MERGE_INSTRUCTIONS));
+        assert!(instructions.contains(persona::ANTI_CONVERGENCE_PROMPT));
+        let instructions = DelegateParallelTool::reviewer_instructions(Reconcile::Pick, false);
+        assert_eq!(instructions, PICK_INSTRUCTIONS);
+    }
+
+    #[test]
+    fn answer_body_strips_delegate_header() {
+        assert_eq!(
+            DelegateParallelTool::answer_body("[Agent 'alpha' (p/m)]\nhello\nworld"),
+            "hello\nworld"
+        );
+        assert_eq!(DelegateParallelTool::answer_body("plain"), "plain");
+    }
+}
@@ -0,0 +1,181 @@
+use super::traits::{Tool, ToolResult};
+use crate::memory::{self, ingest, Memory};
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Let the agent index workspace documents for later recall
+pub struct IngestDocumentTool {
+    memory: Arc<dyn Memory>,
+    security: Arc<SecurityPolicy>,
+}
+
+impl IngestDocumentTool {
+    pub fn new(memory: Arc<dyn Memory>, security: Arc<SecurityPolicy>) -> Self {
+        Self { memory, security }
+    }
+}
+
+#[async_trait]
+impl Tool for IngestDocumentTool {
+    fn name(&self) -> &str {
+        "ingest_document"
+    }
+
+    fn description(&self) -> &str {
+        "Index a workspace file or directory (Markdown, text, source code) into memory so memory_recall can find passages from it, cited by path and line range. Unchanged files are skipped when ingested again."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Relative path to a file or directory within the workspace"
+                }
+            },
+            "required": ["path"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let path = args
+            .get("path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'path' parameter"))?;
+
+        if !self.security.is_path_allowed(path) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Path not allowed by security policy: {path}")),
+            });
+        }
+
+        if let Err(error) = self
+            .security
+            .enforce_tool_operation(ToolOperation::Act, "ingest_document")
+        {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error),
+            });
+        }
+
+        // Resolve before walking so symlinks cannot point outside the workspace.
+        let resolved = match tokio::fs::canonicalize(self.security.workspace_dir.join(path)).await {
+            Ok(resolved) => resolved,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Failed to resolve path: {e}")),
+                });
+            }
+        };
+        if !self.security.is_resolved_path_allowed(&resolved) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!(
+                    "Resolved path escapes workspace: {}",
+                    resolved.display()
+                )),
+            });
+        }
+
+        let memory = memory::tenant::current(&self.memory);
+        match ingest::ingest_path(memory.as_ref(), &resolved, &self.security.workspace_dir).await {
+            Ok(report) => Ok(ToolResult {
+                success: true,
+                output: format!(
+                    "Ingested {} files ({} chunks); {} unchanged, {} removed, {} skipped",
+                    report.ingested,
+                    report.chunks,
+                    report.unchanged,
+                    report.removed,
+                    report.skipped
+                ),
+                error: None,
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Failed to ingest documents: {e}")),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::SqliteMemory;
+    use crate::security::{AutonomyLevel, SecurityPolicy};
+    use tempfile::TempDir;
+
+    fn setup() -> (TempDir, Arc<dyn Memory>, Arc<SecurityPolicy>) {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        std::fs::write(
+            tmp.path().join("notes.md"),
+            "# Deploy\nShip on Fridays only.\n",
+        )
+        .unwrap();
+        let security = Arc::new(SecurityPolicy {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..SecurityPolicy::default()
+        });
+        (tmp, Arc::new(mem), security)
+    }
+
+    #[test]
+    fn name_and_schema() {
+        let (_tmp, mem, security) = setup();
+        let tool = IngestDocumentTool::new(mem, security);
+        assert_eq!(tool.name(), "ingest_document");
+        assert!(tool.parameters_schema()["properties"]["path"].is_object());
+    }
+
+    #[tokio::test]
+    async fn ingests_workspace_file_for_recall() {
+        let (_tmp, mem, security) = setup();
+        let tool = IngestDocumentTool::new(mem.clone(), security);
+        let result = tool.execute(json!({"path": "notes.md"})).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("Ingested 1 files (1 chunks)"));
+
+        let hits = mem.recall("Fridays", 5, None).await.unwrap();
+        assert_eq!(ingest::cite(&hits[0].key), "notes.md:1-2");
+
+        let again = tool.execute(json!({"path": "notes.md"})).await.unwrap();
+        assert!(again.output.contains("1 unchanged"));
+    }
+
+    #[tokio::test]
+    async fn rejects_paths_outside_workspace() {
+        let (_tmp, mem, security) = setup();
+        let tool = IngestDocumentTool::new(mem, security);
+        let result = tool.execute(json!({"path": "../etc"})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("not allowed"));
+    }
+
+    #[tokio::test]
+    async fn blocked_in_readonly_mode() {
+        let (tmp, mem, _) = setup();
+        let readonly = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            workspace_dir: tmp.path().to_path_buf(),
+            ..SecurityPolicy::default()
+        });
+        let tool = IngestDocumentTool::new(mem.clone(), readonly);
+        let result = tool.execute(json!({"path": "notes.md"})).await.unwrap();
+        assert!(!result.success);
+        assert!(mem.recall("Fridays", 5, None).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,208 @@
+use super::traits::{Tool, ToolResult};
+use crate::mcp::protocol::McpToolDef;
+use crate::mcp::McpClient;
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// Provider tool names are limited to `^[a-zA-Z0-9_-]{1,64}$`.
+const MAX_TOOL_NAME_LEN: usize = 64;
+
+/// A tool discovered on an MCP server, proxied through `tools/call`.
+pub struct McpTool {
+    client: Arc<McpClient>,
+    remote_name: String,
+    name: String,
+    description: String,
+    schema: serde_json::Value,
+    read_only: bool,
+    security: Arc<SecurityPolicy>,
+}
+
+impl McpTool {
+    pub fn new(client: Arc<McpClient>, def: McpToolDef, security: Arc<SecurityPolicy>) -> Self {
+        let name = qualified_tool_name(client.server_name(), &def.name);
+        let description = format!(
+            "[MCP:{}] {}",
+            client.server_name(),
+            def.description
+                .as_deref()
+                .or_else(|| def.annotations.as_ref().and_then(|a| a.title.as_deref()))
+                .unwrap_or(&def.name)
+        );
+        let read_only = def
+            .annotations
+            .as_ref()
+            .and_then(|a| a.read_only_hint)
+            .unwrap_or(false);
+        Self {
+            client,
+            remote_name: def.name,
+            name,
+            description,
+            schema: def.input_schema,
+            read_only,
+            security,
+        }
+    }
+}
+
+/// Build the agent-facing name `<server>__<tool>`, restricted to the
+/// character set every provider accepts.
+pub fn qualified_tool_name(server: &str, tool: &str) -> String {
+    let mut name: String = format!("{server}__{tool}")
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    name.truncate(MAX_TOOL_NAME_LEN);
+    name
+}
+
+#[async_trait]
+impl Tool for McpTool {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        self.schema.clone()
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if let Err(error) = self.security.check_tool(&self.name) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error),
+            });
+        }
+
+        let operation = if self.read_only {
+            ToolOperation::Read
+        } else {
+            ToolOperation::Act
+        };
+        if let Err(error) = self.security.enforce_tool_operation(operation, &self.name) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error),
+            });
+        }
+
+        match self.client.call_tool(&self.remote_name, args).await {
+            Ok(result) => {
+                let text = result.render_text();
+                if result.is_error {
+                    Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(if text.is_empty() {
+                            format!("MCP tool '{}' reported an error", self.remote_name)
+                        } else {
+                            text
+                        }),
+                    })
+                } else {
+                    Ok(ToolResult {
+                        success: true,
+                        output: text,
+                        error: None,
+                    })
+                }
+            }
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("MCP call failed: {e:#}")),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::client::tests::connect_fake_server;
+    use crate::security::AutonomyLevel;
+    use serde_json::json;
+
+    async fn fake_tools(security: SecurityPolicy) -> Vec<McpTool> {
+        let client = Arc::new(connect_fake_server("fake").await);
+        let security = Arc::new(security);
+        client
+            .list_tools()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|def| McpTool::new(client.clone(), def, security.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn qualified_name_is_sanitized_and_bounded() {
+        assert_eq!(
+            qualified_tool_name("git hub", "search.code"),
+            "git_hub__search_code"
+        );
+        let long = qualified_tool_name("server", &"x".repeat(100));
+        assert_eq!(long.len(), MAX_TOOL_NAME_LEN);
+    }
+
+    #[tokio::test]
+    async fn exposes_remote_spec() {
+        let tools = fake_tools(SecurityPolicy::default()).await;
+        let echo = &tools[0];
+        assert_eq!(echo.name(), "fake__echo");
+        assert_eq!(echo.description(), "[MCP:fake] Echo text back");
+        assert_eq!(echo.parameters_schema()["required"][0], "text");
+        assert_eq!(tools[1].description(), "[MCP:fake] lookup");
+    }
+
+    #[tokio::test]
+    async fn execute_proxies_to_server() {
+        let tools = fake_tools(SecurityPolicy::default()).await;
+        let result = tools[0].execute(json!({"text": "ping"})).await.unwrap();
+        assert!(result.success);
+        assert_eq!(result.output, "ping");
+    }
+
+    #[tokio::test]
+    async fn execute_respects_agent_deny_list() {
+        let security = SecurityPolicy {
+            agent_denied_tools: vec!["fake__echo".into()],
+            ..SecurityPolicy::default()
+        };
+        let tools = fake_tools(security).await;
+        let result = tools[0].execute(json!({"text": "ping"})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("fake__echo"));
+    }
+
+    #[tokio::test]
+    async fn read_only_autonomy_blocks_mutating_tools_only() {
+        let security = SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            ..SecurityPolicy::default()
+        };
+        let tools = fake_tools(security).await;
+
+        let blocked = tools[0].execute(json!({"text": "ping"})).await.unwrap();
+        assert!(!blocked.success);
+        assert!(blocked.error.unwrap().contains("read-only"));
+
+        let allowed = tools[1].execute(json!({})).await.unwrap();
+        assert!(allowed.success);
+    }
+}
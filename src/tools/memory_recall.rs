@@ -1,5 +1,5 @@
 use super::traits::{Tool, ToolResult};
-use crate::memory::Memory;
+use crate::memory::{self, Memory};
 use async_trait::async_trait;
 use serde_json::json;
 use std::fmt::Write;
@@ -55,7 +55,10 @@ impl Tool for MemoryRecallTool {
             .and_then(serde_json::Value::as_u64)
             .map_or(5, |v| v as usize);
 
-        match self.memory.recall(query, limit, None).await {
+        match memory::tenant::current(&self.memory)
+            .recall(query, limit, None)
+            .await
+        {
             Ok(entries) if entries.is_empty() => Ok(ToolResult {
                 success: true,
                 output: "No memories found matching that query.".into(),
@@ -70,7 +73,9 @@ impl Tool for MemoryRecallTool {
                     let _ = writeln!(
                         output,
                         "- [{}] {}: {}{score}",
-                        entry.category, entry.key, entry.content
+                        entry.category,
+                        memory::ingest::cite(&entry.key),
+                        entry.content
                     );
                 }
                 Ok(ToolResult {
@@ -149,6 +154,25 @@ mod tests {
         assert!(result.output.contains("Found 3"));
     }
 
+    #[tokio::test]
+    async fn recall_cites_ingested_documents() {
+        let (_tmp, mem) = seeded_mem();
+        mem.store(
+            "doc:0011aabb:docs/deploy.md:3-8",
+            "Deploys go out on Fridays",
+            MemoryCategory::Custom("document".into()),
+            None,
+        )
+        .await
+        .unwrap();
+
+        let tool = MemoryRecallTool::new(mem);
+        let result = tool.execute(json!({"query": "Fridays"})).await.unwrap();
+        assert!(result
+            .output
+            .contains("[document] docs/deploy.md:3-8: Deploys go out on Fridays"));
+    }
+
     #[tokio::test]
     async fn recall_missing_query() {
         let (_tmp, mem) = seeded_mem();
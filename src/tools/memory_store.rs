@@ -1,11 +1,14 @@
 use super::traits::{Tool, ToolResult};
-use crate::memory::{Memory, MemoryCategory};
+use crate::memory::{self, Memory, MemoryCategory, MemoryMetadata};
 use crate::security::policy::ToolOperation;
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
 use serde_json::json;
 use std::sync::Arc;
 
+/// Longest `ttl_hours` accepted (100 years).
+const MAX_TTL_HOURS: f64 = 876_000.0;
+
 /// Let the agent store memories — its own brain writes
 pub struct MemoryStoreTool {
     memory: Arc<dyn Memory>,
@@ -25,7 +28,7 @@ impl Tool for MemoryStoreTool {
     }
 
     fn description(&self) -> &str {
-        "Store a fact, preference, or note in long-term memory. Use category 'core' for permanent facts, 'daily' for session notes, 'conversation' for chat context, or a custom category name."
+        "Store a fact, preference, or note in long-term memory. Use category 'core' for permanent facts, 'daily' for session notes, 'conversation' for chat context, or a custom category name. Set importance for facts that matter more or less than usual, and ttl_hours or expires_at for information that goes stale."
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -43,6 +46,22 @@ impl Tool for MemoryStoreTool {
                 "category": {
                     "type": "string",
                     "description": "Memory category: 'core' (permanent), 'daily' (session), 'conversation' (chat), or a custom category name. Defaults to 'core'."
+                },
+                "importance": {
+                    "type": "number",
+                    "minimum": 0,
+                    "maximum": 1,
+                    "description": "How much this memory matters when recalling, from 0.0 (throwaway) to 1.0 (critical). Defaults to 0.5."
+                },
+                "ttl_hours": {
+                    "type": "number",
+                    "exclusiveMinimum": 0,
+                    "maximum": MAX_TTL_HOURS,
+                    "description": "Forget this memory after this many hours"
+                },
+                "expires_at": {
+                    "type": "string",
+                    "description": "Forget this memory at this RFC 3339 time (e.g. '2026-03-01T00:00:00Z'). Use either ttl_hours or expires_at."
                 }
             },
             "required": ["key", "content"]
@@ -67,6 +86,38 @@ impl Tool for MemoryStoreTool {
             Some(other) => MemoryCategory::Custom(other.to_string()),
         };
 
+        let expires_at = match (
+            args.get("ttl_hours").and_then(serde_json::Value::as_f64),
+            args.get("expires_at").and_then(|v| v.as_str()),
+        ) {
+            (Some(_), Some(_)) => {
+                anyhow::bail!("Use either 'ttl_hours' or 'expires_at', not both")
+            }
+            (Some(hours), None) => {
+                if !hours.is_finite() || hours <= 0.0 {
+                    anyhow::bail!("'ttl_hours' must be a positive number");
+                }
+                #[allow(clippy::cast_possible_truncation)]
+                let expires_at = (hours <= MAX_TTL_HOURS)
+                    .then(|| chrono::TimeDelta::try_seconds((hours * 3600.0) as i64))
+                    .flatten()
+                    .and_then(|ttl| chrono::Utc::now().checked_add_signed(ttl));
+                let Some(expires_at) = expires_at else {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!("'ttl_hours' must be at most {MAX_TTL_HOURS} hours")),
+                    });
+                };
+                Some(expires_at.to_rfc3339())
+            }
+            (None, expires_at) => expires_at.map(str::to_string),
+        };
+        let metadata = MemoryMetadata {
+            importance: args.get("importance").and_then(serde_json::Value::as_f64),
+            expires_at,
+        };
+
         if let Err(error) = self
             .security
             .enforce_tool_operation(ToolOperation::Act, "memory_store")
@@ -78,7 +129,10 @@ impl Tool for MemoryStoreTool {
             });
         }
 
-        match self.memory.store(key, content, category, None).await {
+        match memory::tenant::current(&self.memory)
+            .store_with_metadata(key, content, category, None, &metadata)
+            .await
+        {
             Ok(()) => Ok(ToolResult {
                 success: true,
                 output: format!("Stored memory: {key}"),
@@ -164,6 +218,65 @@ mod tests {
         assert_eq!(entry.category, MemoryCategory::Custom("project".into()));
     }
 
+    #[tokio::test]
+    async fn store_with_importance_and_ttl() {
+        let (_tmp, mem) = test_mem();
+        let tool = MemoryStoreTool::new(mem.clone(), test_security());
+        let result = tool
+            .execute(json!({
+                "key": "standup",
+                "content": "Standup moved to 10:00 today",
+                "importance": 0.9,
+                "ttl_hours": 12
+            }))
+            .await
+            .unwrap();
+        assert!(result.success);
+
+        let entry = mem.get("standup").await.unwrap().unwrap();
+        assert_eq!(entry.importance, Some(0.9));
+        let expires_at =
+            chrono::DateTime::parse_from_rfc3339(entry.expires_at.as_deref().unwrap()).unwrap();
+        assert!(expires_at > chrono::Utc::now() + chrono::Duration::hours(11));
+    }
+
+    #[tokio::test]
+    async fn store_rejects_invalid_metadata() {
+        let (_tmp, mem) = test_mem();
+        let tool = MemoryStoreTool::new(mem.clone(), test_security());
+        let result = tool
+            .execute(json!({"key": "k", "content": "c", "importance": 2.0}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+
+        let both = tool
+            .execute(json!({
+                "key": "k",
+                "content": "c",
+                "ttl_hours": 1,
+                "expires_at": "2030-01-01T00:00:00Z"
+            }))
+            .await;
+        assert!(both.is_err());
+        assert!(mem.get("k").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn store_rejects_huge_ttl_without_panicking() {
+        let (_tmp, mem) = test_mem();
+        let tool = MemoryStoreTool::new(mem.clone(), test_security());
+        for ttl_hours in [1e10, 1e300] {
+            let result = tool
+                .execute(json!({"key": "k", "content": "c", "ttl_hours": ttl_hours}))
+                .await
+                .unwrap();
+            assert!(!result.success);
+            assert!(result.error.as_deref().unwrap().contains("ttl_hours"));
+        }
+        assert!(mem.get("k").await.unwrap().is_none());
+    }
+
     #[tokio::test]
     async fn store_missing_key() {
         let (_tmp, mem) = test_mem();
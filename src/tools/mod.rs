@@ -8,6 +8,7 @@ pub mod cron_run;
 pub mod cron_runs;
 pub mod cron_update;
 pub mod delegate;
+pub mod delegate_parallel;
 pub mod file_read;
 pub mod file_write;
 pub mod git_operations;
@@ -16,6 +17,8 @@ pub mod hardware_memory_map;
 pub mod hardware_memory_read;
 pub mod http_request;
 pub mod image_info;
+pub mod ingest_document;
+pub mod mcp_tool;
 pub mod memory_forget;
 pub mod memory_recall;
 pub mod memory_store;
@@ -24,6 +27,7 @@ pub mod pushover;
 pub mod schedule;
 pub mod schema;
 pub mod screenshot;
+pub mod send_file;
 pub mod shell;
 pub mod traits;
 pub mod web_search_tool;
@@ -37,7 +41,8 @@ pub use cron_remove::CronRemoveTool;
 pub use cron_run::CronRunTool;
 pub use cron_runs::CronRunsTool;
 pub use cron_update::CronUpdateTool;
-pub use delegate::DelegateTool;
+pub use delegate::{DelegateTool, SubAgentToolkit};
+pub use delegate_parallel::DelegateParallelTool;
 pub use file_read::FileReadTool;
 pub use file_write::FileWriteTool;
 pub use git_operations::GitOperationsTool;
@@ -46,6 +51,8 @@ pub use hardware_memory_map::HardwareMemoryMapTool;
 pub use hardware_memory_read::HardwareMemoryReadTool;
 pub use http_request::HttpRequestTool;
 pub use image_info::ImageInfoTool;
+pub use ingest_document::IngestDocumentTool;
+pub use mcp_tool::McpTool;
 pub use memory_forget::MemoryForgetTool;
 pub use memory_recall::MemoryRecallTool;
 pub use memory_store::MemoryStoreTool;
@@ -55,6 +62,7 @@ pub use schedule::ScheduleTool;
 #[allow(unused_imports)]
 pub use schema::{CleaningStrategy, SchemaCleanr};
 pub use screenshot::ScreenshotTool;
+pub use send_file::SendFileTool;
 pub use shell::ShellTool;
 pub use traits::Tool;
 #[allow(unused_imports)]
@@ -133,9 +141,10 @@ pub fn all_tools_with_runtime(
     root_config: &crate::config::Config,
 ) -> Vec<Box<dyn Tool>> {
     let mut tools: Vec<Box<dyn Tool>> = vec![
-        Box::new(ShellTool::new(security.clone(), runtime)),
+        Box::new(ShellTool::new(security.clone(), runtime.clone())),
         Box::new(FileReadTool::new(security.clone())),
         Box::new(FileWriteTool::new(security.clone())),
+        Box::new(SendFileTool::new(security.clone())),
         Box::new(CronAddTool::new(config.clone(), security.clone())),
         Box::new(CronListTool::new(config.clone())),
         Box::new(CronRemoveTool::new(config.clone())),
@@ -144,7 +153,8 @@ pub fn all_tools_with_runtime(
         Box::new(CronRunsTool::new(config.clone())),
         Box::new(MemoryStoreTool::new(memory.clone(), security.clone())),
         Box::new(MemoryRecallTool::new(memory.clone())),
-        Box::new(MemoryForgetTool::new(memory, security.clone())),
+        Box::new(MemoryForgetTool::new(memory.clone(), security.clone())),
+        Box::new(IngestDocumentTool::new(memory.clone(), security.clone())),
         Box::new(ScheduleTool::new(security.clone(), root_config.clone())),
         Box::new(ProxyConfigTool::new(config.clone(), security.clone())),
         Box::new(GitOperationsTool::new(
@@ -227,11 +237,23 @@ pub fn all_tools_with_runtime(
             let trimmed_value = value.trim();
             (!trimmed_value.is_empty()).then(|| trimmed_value.to_owned())
         });
-        tools.push(Box::new(DelegateTool::new(
+        let delegate = DelegateTool::new(
             delegate_agents,
             delegate_fallback_credential,
             security.clone(),
-        )));
+        )
+        .with_toolkit(SubAgentToolkit {
+            config: config.clone(),
+            runtime,
+            memory,
+        });
+        tools.push(Box::new(DelegateParallelTool::new(delegate.clone())));
+        tools.push(Box::new(delegate));
+    }
+
+    // Add tools discovered on connected MCP servers
+    if let Some(mcp) = crate::mcp::registry() {
+        tools.extend(mcp.tools(security));
     }
 
     tools
@@ -296,6 +318,7 @@ mod tests {
         assert!(names.contains(&"schedule"));
         assert!(names.contains(&"pushover"));
         assert!(names.contains(&"proxy_config"));
+        assert!(names.contains(&"send_file"));
     }
 
     #[test]
@@ -459,6 +482,8 @@ mod tests {
                 max_depth: 3,
                 denied_tools: Vec::new(),
                 allowed_tools: Vec::new(),
+                max_iterations: 0,
+                memory_namespace: None,
             },
         );
 
@@ -477,6 +502,7 @@ mod tests {
         );
         let names: Vec<&str> = tools.iter().map(|t| t.name()).collect();
         assert!(names.contains(&"delegate"));
+        assert!(names.contains(&"delegate_parallel"));
     }
 
     #[test]
@@ -509,5 +535,6 @@ mod tests {
         );
         let names: Vec<&str> = tools.iter().map(|t| t.name()).collect();
         assert!(!names.contains(&"delegate"));
+        assert!(!names.contains(&"delegate_parallel"));
     }
 }
@@ -0,0 +1,370 @@
+use super::traits::{Tool, ToolResult};
+use crate::channels::traits::{Channel, OutgoingAttachment};
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::future::Future;
+use std::sync::Arc;
+
+const MAX_FILE_SIZE_BYTES: u64 = 20 * 1024 * 1024;
+
+tokio::task_local! {
+    static CURRENT_CONVERSATION: ConversationTarget;
+}
+
+/// Channel conversation that `send_file` delivers to
+#[derive(Clone)]
+pub struct ConversationTarget {
+    pub channel: Arc<dyn Channel>,
+    pub recipient: String,
+}
+
+/// Run `fut` with `target` as the conversation files are delivered to.
+pub async fn with_conversation<F: Future>(target: ConversationTarget, fut: F) -> F::Output {
+    CURRENT_CONVERSATION.scope(target, fut).await
+}
+
+fn current_conversation() -> Option<ConversationTarget> {
+    CURRENT_CONVERSATION.try_with(Clone::clone).ok()
+}
+
+/// Deliver a workspace file to the conversation the agent is replying in
+pub struct SendFileTool {
+    security: Arc<SecurityPolicy>,
+}
+
+impl SendFileTool {
+    pub fn new(security: Arc<SecurityPolicy>) -> Self {
+        Self { security }
+    }
+}
+
+#[async_trait]
+impl Tool for SendFileTool {
+    fn name(&self) -> &str {
+        "send_file"
+    }
+
+    fn description(&self) -> &str {
+        "Send a file from the workspace to the user in the current chat conversation (screenshots, reports, diffs)"
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Relative path to the file within the workspace"
+                },
+                "caption": {
+                    "type": "string",
+                    "description": "Optional text shown with the file"
+                }
+            },
+            "required": ["path"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let path = args
+            .get("path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'path' parameter"))?;
+        let caption = args
+            .get("caption")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|c| !c.is_empty());
+
+        if !self.security.can_act() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Action blocked: autonomy is read-only".into()),
+            });
+        }
+
+        if self.security.is_rate_limited() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: too many actions in the last hour".into()),
+            });
+        }
+
+        // Security check: validate path is within workspace
+        if !self.security.is_path_allowed(path) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Path not allowed by security policy: {path}")),
+            });
+        }
+
+        let Some(target) = current_conversation() else {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("send_file is only available while replying in a channel".into()),
+            });
+        };
+
+        if !target.channel.supports_files() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!(
+                    "The {} channel does not support file attachments",
+                    target.channel.name()
+                )),
+            });
+        }
+
+        if !self.security.record_action() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: action budget exhausted".into()),
+            });
+        }
+
+        let full_path = self.security.workspace_dir.join(path);
+
+        // Resolve path before reading to block symlink escapes.
+        let resolved_path = match tokio::fs::canonicalize(&full_path).await {
+            Ok(p) => p,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Failed to resolve file path: {e}")),
+                });
+            }
+        };
+
+        if !self.security.is_resolved_path_allowed(&resolved_path) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!(
+                    "Resolved path escapes workspace: {}",
+                    resolved_path.display()
+                )),
+            });
+        }
+
+        let size = match tokio::fs::metadata(&resolved_path).await {
+            Ok(meta) if !meta.is_file() => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Not a regular file: {path}")),
+                });
+            }
+            Ok(meta) if meta.len() > MAX_FILE_SIZE_BYTES => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!(
+                        "File too large: {} bytes (limit: {MAX_FILE_SIZE_BYTES} bytes)",
+                        meta.len()
+                    )),
+                });
+            }
+            Ok(meta) => meta.len(),
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Failed to read file metadata: {e}")),
+                });
+            }
+        };
+
+        let attachment = OutgoingAttachment::from_path(resolved_path);
+        match target
+            .channel
+            .send_file(&target.recipient, &attachment, caption)
+            .await
+        {
+            Ok(()) => Ok(ToolResult {
+                success: true,
+                output: format!(
+                    "Sent {} ({size} bytes) to the conversation",
+                    attachment.file_name()
+                ),
+                error: None,
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Failed to send file: {e}")),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channels::traits::{ChannelMessage, SendMessage};
+    use crate::security::AutonomyLevel;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct RecordingChannel {
+        files: bool,
+        sent: Mutex<Vec<(String, OutgoingAttachment, Option<String>)>>,
+    }
+
+    #[async_trait]
+    impl Channel for RecordingChannel {
+        fn name(&self) -> &str {
+            "recording"
+        }
+
+        async fn send(&self, _message: &SendMessage) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn listen(
+            &self,
+            _tx: tokio::sync::mpsc::Sender<ChannelMessage>,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        fn supports_files(&self) -> bool {
+            self.files
+        }
+
+        async fn send_file(
+            &self,
+            recipient: &str,
+            attachment: &OutgoingAttachment,
+            caption: Option<&str>,
+        ) -> anyhow::Result<()> {
+            self.sent.lock().unwrap().push((
+                recipient.to_string(),
+                attachment.clone(),
+                caption.map(str::to_string),
+            ));
+            Ok(())
+        }
+    }
+
+    fn test_security(workspace: std::path::PathBuf) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Supervised,
+            workspace_dir: workspace,
+            ..SecurityPolicy::default()
+        })
+    }
+
+    fn target(channel: &Arc<RecordingChannel>) -> ConversationTarget {
+        ConversationTarget {
+            channel: channel.clone(),
+            recipient: "chat-42".into(),
+        }
+    }
+
+    #[test]
+    fn send_file_schema_requires_path() {
+        let tool = SendFileTool::new(test_security(std::env::temp_dir()));
+        assert_eq!(tool.name(), "send_file");
+        let schema = tool.parameters_schema();
+        assert!(schema["properties"]["caption"].is_object());
+        assert_eq!(schema["required"], json!(["path"]));
+    }
+
+    #[tokio::test]
+    async fn send_file_delivers_workspace_file_to_current_conversation() {
+        let dir = std::env::temp_dir().join("zeroclaw_test_send_file_ok");
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        tokio::fs::write(dir.join("report.pdf"), b"%PDF-1.7")
+            .await
+            .unwrap();
+
+        let channel = Arc::new(RecordingChannel {
+            files: true,
+            ..RecordingChannel::default()
+        });
+        let tool = SendFileTool::new(test_security(dir.clone()));
+        let result = with_conversation(
+            target(&channel),
+            tool.execute(json!({"path": "report.pdf", "caption": "Weekly report"})),
+        )
+        .await
+        .unwrap();
+
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("report.pdf"));
+        let sent = channel.sent.lock().unwrap().clone();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].0, "chat-42");
+        assert_eq!(sent[0].1.mime_type, "application/pdf");
+        assert_eq!(sent[0].2.as_deref(), Some("Weekly report"));
+
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+    }
+
+    #[tokio::test]
+    async fn send_file_blocks_paths_outside_workspace() {
+        let channel = Arc::new(RecordingChannel {
+            files: true,
+            ..RecordingChannel::default()
+        });
+        let tool = SendFileTool::new(test_security(std::env::temp_dir()));
+
+        for path in ["/etc/passwd", "../../../etc/passwd"] {
+            let result = with_conversation(target(&channel), tool.execute(json!({"path": path})))
+                .await
+                .unwrap();
+            assert!(!result.success);
+            assert!(result.error.as_ref().unwrap().contains("not allowed"));
+        }
+        assert!(channel.sent.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn send_file_requires_channel_conversation() {
+        let tool = SendFileTool::new(test_security(std::env::temp_dir()));
+        let result = tool.execute(json!({"path": "a.txt"})).await.unwrap();
+        assert!(!result.success);
+        assert!(result
+            .error
+            .as_ref()
+            .unwrap()
+            .contains("only available while replying in a channel"));
+    }
+
+    #[tokio::test]
+    async fn send_file_rejects_channels_without_file_support() {
+        let channel = Arc::new(RecordingChannel::default());
+        let tool = SendFileTool::new(test_security(std::env::temp_dir()));
+        let result = with_conversation(target(&channel), tool.execute(json!({"path": "a.txt"})))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result
+            .error
+            .as_ref()
+            .unwrap()
+            .contains("does not support file attachments"));
+    }
+
+    #[tokio::test]
+    async fn send_file_blocked_in_readonly_mode() {
+        let security = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            workspace_dir: std::env::temp_dir(),
+            ..SecurityPolicy::default()
+        });
+        let tool = SendFileTool::new(security);
+        let result = tool.execute(json!({"path": "a.txt"})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.as_ref().unwrap().contains("read-only"));
+    }
+}
@@ -0,0 +1,603 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Directory under the workspace that holds workflow files.
+pub const WORKFLOWS_DIR: &str = "workflows";
+
+const WORKFLOW_EXTENSIONS: [&str; 3] = ["yaml", "yml", "toml"];
+
+/// A workflow: named inputs plus a DAG of steps.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WorkflowDefinition {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub inputs: BTreeMap<String, WorkflowInput>,
+    pub steps: Vec<WorkflowStep>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WorkflowInput {
+    /// Value used when the trigger does not supply one. Inputs without a
+    /// default are required.
+    #[serde(default)]
+    pub default: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WorkflowStep {
+    pub id: String,
+    /// Steps that must finish before this one starts.
+    #[serde(default)]
+    pub needs: Vec<String>,
+    /// Run only when the named condition step evaluated true
+    /// (`"!check"` runs only when it evaluated false).
+    #[serde(default)]
+    pub when: Option<String>,
+    #[serde(flatten)]
+    pub action: StepAction,
+}
+
+/// What a step does. Text fields accept `{{inputs.<name>}}` and
+/// `{{steps.<id>.output}}` placeholders.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StepAction {
+    /// Call a registered tool with JSON arguments.
+    Tool {
+        tool: String,
+        #[serde(default = "empty_args")]
+        args: serde_json::Value,
+    },
+    /// Run a full agent turn with the default provider.
+    Agent {
+        prompt: String,
+        #[serde(default)]
+        model: Option<String>,
+    },
+    /// Hand the prompt to a configured `[agents.<name>]` sub-agent.
+    Delegate {
+        agent: String,
+        prompt: String,
+        #[serde(default)]
+        context: Option<String>,
+    },
+    /// Evaluate `value` and output `true` or `false`. Without `equals` or
+    /// `contains`, any non-empty value other than `false`/`0`/`no` is true.
+    Condition {
+        value: String,
+        #[serde(default)]
+        equals: Option<String>,
+        #[serde(default)]
+        contains: Option<String>,
+    },
+    /// Pause the run until an operator approves or rejects it.
+    Approval {
+        #[serde(default)]
+        message: Option<String>,
+    },
+}
+
+fn empty_args() -> serde_json::Value {
+    serde_json::Value::Object(serde_json::Map::new())
+}
+
+impl StepAction {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Tool { .. } => "tool",
+            Self::Agent { .. } => "agent",
+            Self::Delegate { .. } => "delegate",
+            Self::Condition { .. } => "condition",
+            Self::Approval { .. } => "approval",
+        }
+    }
+
+    /// Every templated string in the step, for reference validation.
+    fn templates(&self) -> Vec<&str> {
+        match self {
+            Self::Tool { args, .. } => {
+                let mut out = Vec::new();
+                collect_json_strings(args, &mut out);
+                out
+            }
+            Self::Agent { prompt, .. } => vec![prompt.as_str()],
+            Self::Delegate {
+                prompt, context, ..
+            } => std::iter::once(prompt.as_str())
+                .chain(context.as_deref())
+                .collect(),
+            Self::Condition {
+                value,
+                equals,
+                contains,
+            } => std::iter::once(value.as_str())
+                .chain(equals.as_deref())
+                .chain(contains.as_deref())
+                .collect(),
+            Self::Approval { message } => message.as_deref().into_iter().collect(),
+        }
+    }
+}
+
+fn collect_json_strings<'a>(value: &'a serde_json::Value, out: &mut Vec<&'a str>) {
+    match value {
+        serde_json::Value::String(s) => out.push(s),
+        serde_json::Value::Array(items) => {
+            for item in items {
+                collect_json_strings(item, out);
+            }
+        }
+        serde_json::Value::Object(map) => {
+            for item in map.values() {
+                collect_json_strings(item, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// A `{{...}}` placeholder reference.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Placeholder<'a> {
+    Input(&'a str),
+    StepOutput(&'a str),
+}
+
+fn parse_placeholder(expr: &str) -> Option<Placeholder<'_>> {
+    let expr = expr.trim();
+    if let Some(name) = expr.strip_prefix("inputs.") {
+        return Some(Placeholder::Input(name));
+    }
+    let rest = expr.strip_prefix("steps.")?;
+    let id = rest.strip_suffix(".output")?;
+    Some(Placeholder::StepOutput(id))
+}
+
+/// Yield `(start, end, inner)` for every `{{inner}}` in `text`.
+fn placeholders(text: &str) -> Vec<(usize, usize, &str)> {
+    let mut found = Vec::new();
+    let mut cursor = 0;
+    while let Some(open) = text[cursor..].find("{{") {
+        let start = cursor + open;
+        let Some(close) = text[start + 2..].find("}}") else {
+            break;
+        };
+        let end = start + 2 + close + 2;
+        found.push((start, end, &text[start + 2..end - 2]));
+        cursor = end;
+    }
+    found
+}
+
+/// Values available to placeholders while a run executes.
+#[derive(Debug)]
+pub struct TemplateContext<'a> {
+    pub inputs: &'a HashMap<String, String>,
+    pub outputs: &'a HashMap<String, String>,
+}
+
+impl TemplateContext<'_> {
+    /// Substitute known placeholders; unknown ones are left as written.
+    pub fn render(&self, text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut last = 0;
+        for (start, end, inner) in placeholders(text) {
+            let value = match parse_placeholder(inner) {
+                Some(Placeholder::Input(name)) => self.inputs.get(name),
+                Some(Placeholder::StepOutput(id)) => self.outputs.get(id),
+                None => None,
+            };
+            if let Some(value) = value {
+                out.push_str(&text[last..start]);
+                out.push_str(value);
+                last = end;
+            }
+        }
+        out.push_str(&text[last..]);
+        out
+    }
+
+    pub fn render_json(&self, value: &serde_json::Value) -> serde_json::Value {
+        match value {
+            serde_json::Value::String(s) => serde_json::Value::String(self.render(s)),
+            serde_json::Value::Array(items) => {
+                serde_json::Value::Array(items.iter().map(|v| self.render_json(v)).collect())
+            }
+            serde_json::Value::Object(map) => serde_json::Value::Object(
+                map.iter()
+                    .map(|(k, v)| (k.clone(), self.render_json(v)))
+                    .collect(),
+            ),
+            other => other.clone(),
+        }
+    }
+}
+
+impl WorkflowDefinition {
+    /// Parse a workflow from YAML or TOML text.
+    pub fn parse(text: &str, format: &str) -> Result<Self> {
+        let definition: Self = match format {
+            "toml" => toml::from_str(text).context("Invalid workflow TOML")?,
+            "yaml" | "yml" => serde_yaml::from_str(text).context("Invalid workflow YAML")?,
+            other => anyhow::bail!("Unsupported workflow format '{other}' (use yaml or toml)"),
+        };
+        definition.validate()?;
+        Ok(definition)
+    }
+
+    /// Load and validate a workflow file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let format = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read workflow {}", path.display()))?;
+        Self::parse(&text, &format).with_context(|| format!("In {}", path.display()))
+    }
+
+    pub fn step(&self, id: &str) -> Option<&WorkflowStep> {
+        self.steps.iter().find(|step| step.id == id)
+    }
+
+    /// Check ids, dependencies, `when` targets and placeholders, and reject cycles.
+    pub fn validate(&self) -> Result<()> {
+        if self.name.trim().is_empty() {
+            anyhow::bail!("Workflow name must not be empty");
+        }
+        if self.steps.is_empty() {
+            anyhow::bail!("Workflow '{}' has no steps", self.name);
+        }
+
+        let mut ids = HashSet::new();
+        for step in &self.steps {
+            if step.id.trim().is_empty() {
+                anyhow::bail!("Every step needs a non-empty id");
+            }
+            if !ids.insert(step.id.as_str()) {
+                anyhow::bail!("Duplicate step id '{}'", step.id);
+            }
+        }
+
+        for step in &self.steps {
+            for need in &step.needs {
+                if !ids.contains(need.as_str()) {
+                    anyhow::bail!("Step '{}' needs unknown step '{need}'", step.id);
+                }
+            }
+        }
+
+        let order = self.topological_order()?;
+        let ancestors = self.ancestors(&order);
+
+        for step in &self.steps {
+            let upstream = &ancestors[step.id.as_str()];
+            if let Some(when) = &step.when {
+                let target = when.trim().trim_start_matches('!').trim();
+                match self.step(target) {
+                    Some(cond) if matches!(cond.action, StepAction::Condition { .. }) => {}
+                    Some(_) => anyhow::bail!(
+                        "Step '{}' uses when = '{when}' but '{target}' is not a condition step",
+                        step.id
+                    ),
+                    None => anyhow::bail!(
+                        "Step '{}' uses when = '{when}' on unknown step '{target}'",
+                        step.id
+                    ),
+                }
+                if !upstream.contains(target) {
+                    anyhow::bail!(
+                        "Step '{}' uses when = '{when}' but does not depend on '{target}' (add it to needs)",
+                        step.id
+                    );
+                }
+            }
+
+            for text in step.action.templates() {
+                for (_, _, inner) in placeholders(text) {
+                    match parse_placeholder(inner) {
+                        Some(Placeholder::Input(name)) => {
+                            if !self.inputs.contains_key(name) {
+                                anyhow::bail!(
+                                    "Step '{}' references undeclared input '{name}'",
+                                    step.id
+                                );
+                            }
+                        }
+                        Some(Placeholder::StepOutput(id)) => {
+                            if !upstream.contains(id) {
+                                anyhow::bail!(
+                                    "Step '{}' references steps.{id}.output but does not depend on '{id}' (add it to needs)",
+                                    step.id
+                                );
+                            }
+                        }
+                        None => anyhow::bail!(
+                            "Step '{}' has unknown placeholder '{{{{{inner}}}}}'",
+                            step.id
+                        ),
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Step ids ordered so every step comes after the steps it needs.
+    pub fn topological_order(&self) -> Result<Vec<String>> {
+        let mut remaining: Vec<&WorkflowStep> = self.steps.iter().collect();
+        let mut done: HashSet<&str> = HashSet::new();
+        let mut order = Vec::with_capacity(self.steps.len());
+
+        while !remaining.is_empty() {
+            let (ready, blocked): (Vec<_>, Vec<_>) = remaining
+                .into_iter()
+                .partition(|step| step.needs.iter().all(|need| done.contains(need.as_str())));
+            if ready.is_empty() {
+                let stuck: Vec<&str> = blocked.iter().map(|step| step.id.as_str()).collect();
+                anyhow::bail!(
+                    "Workflow has a dependency cycle among: {}",
+                    stuck.join(", ")
+                );
+            }
+            for step in ready {
+                done.insert(step.id.as_str());
+                order.push(step.id.clone());
+            }
+            remaining = blocked;
+        }
+
+        Ok(order)
+    }
+
+    /// Transitive dependencies of every step.
+    fn ancestors(&self, order: &[String]) -> HashMap<&str, HashSet<&str>> {
+        let mut ancestors: HashMap<&str, HashSet<&str>> = HashMap::new();
+        for id in order {
+            let Some(step) = self.step(id) else {
+                continue;
+            };
+            let mut set = HashSet::new();
+            for need in &step.needs {
+                set.insert(need.as_str());
+                if let Some(upstream) = ancestors.get(need.as_str()) {
+                    set.extend(upstream.iter().copied());
+                }
+            }
+            ancestors.insert(step.id.as_str(), set);
+        }
+        ancestors
+    }
+
+    /// Merge trigger-supplied inputs with declared defaults.
+    pub fn resolve_inputs(
+        &self,
+        provided: &HashMap<String, String>,
+    ) -> Result<HashMap<String, String>> {
+        if let Some(unknown) = provided.keys().find(|k| !self.inputs.contains_key(*k)) {
+            anyhow::bail!("Workflow '{}' has no input named '{unknown}'", self.name);
+        }
+        let mut resolved = HashMap::new();
+        for (name, input) in &self.inputs {
+            let value = provided
+                .get(name)
+                .cloned()
+                .or_else(|| input.default.clone())
+                .ok_or_else(|| {
+                    anyhow::anyhow!("Workflow '{}' requires input '{name}'", self.name)
+                })?;
+            resolved.insert(name.clone(), value);
+        }
+        Ok(resolved)
+    }
+}
+
+/// Workflow files in `workspace/workflows`, sorted by path.
+pub fn workflow_files(workspace_dir: &Path) -> Result<Vec<PathBuf>> {
+    let dir = workspace_dir.join(WORKFLOWS_DIR);
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(&dir)
+        .with_context(|| format!("Failed to read workflows directory {}", dir.display()))?
+    {
+        let path = entry?.path();
+        let is_workflow = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| WORKFLOW_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()));
+        if path.is_file() && is_workflow {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Find a workflow by its `name` field.
+pub fn find_workflow(workspace_dir: &Path, name: &str) -> Result<WorkflowDefinition> {
+    for path in workflow_files(workspace_dir)? {
+        match WorkflowDefinition::load(&path) {
+            Ok(definition) if definition.name == name => return Ok(definition),
+            Ok(_) => {}
+            Err(e) => tracing::debug!("Skipping invalid workflow {}: {e:#}", path.display()),
+        }
+    }
+    anyhow::bail!(
+        "Workflow '{name}' not found in {}",
+        workspace_dir.join(WORKFLOWS_DIR).display()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REVIEW_YAML: &str = r#"
+name: nightly-review
+inputs:
+  repo:
+    default: "."
+steps:
+  - id: diff
+    type: tool
+    tool: shell
+    args:
+      command: "git -C {{inputs.repo}} diff HEAD~1"
+  - id: has_changes
+    type: condition
+    needs: [diff]
+    value: "{{steps.diff.output}}"
+  - id: review
+    type: delegate
+    needs: [has_changes]
+    when: has_changes
+    agent: reviewer
+    prompt: "Review this diff:\n{{steps.diff.output}}"
+  - id: approve
+    type: approval
+    needs: [review]
+    message: "Post the review?"
+"#;
+
+    #[test]
+    fn parses_yaml_workflow() {
+        let def = WorkflowDefinition::parse(REVIEW_YAML, "yaml").unwrap();
+        assert_eq!(def.name, "nightly-review");
+        assert_eq!(def.steps.len(), 4);
+        assert_eq!(def.steps[0].action.kind(), "tool");
+        assert_eq!(def.steps[2].when.as_deref(), Some("has_changes"));
+        assert_eq!(
+            def.topological_order().unwrap(),
+            vec!["diff", "has_changes", "review", "approve"]
+        );
+    }
+
+    #[test]
+    fn parses_toml_workflow() {
+        let def = WorkflowDefinition::parse(
+            r#"
+name = "digest"
+
+[[steps]]
+id = "fetch"
+type = "tool"
+tool = "http_request"
+args = { url = "https://example.com/feed", method = "GET" }
+
+[[steps]]
+id = "summarize"
+type = "agent"
+needs = ["fetch"]
+prompt = "Summarize: {{steps.fetch.output}}"
+"#,
+            "toml",
+        )
+        .unwrap();
+        assert_eq!(def.steps[0].action.kind(), "tool");
+        assert_eq!(def.steps[1].needs, vec!["fetch"]);
+    }
+
+    #[test]
+    fn rejects_cycles_and_unknown_needs() {
+        let cyclic = r#"
+name: loop
+steps:
+  - { id: a, type: approval, needs: [b] }
+  - { id: b, type: approval, needs: [a] }
+"#;
+        let err = WorkflowDefinition::parse(cyclic, "yaml").unwrap_err();
+        assert!(format!("{err:#}").contains("cycle"));
+
+        let unknown = r#"
+name: broken
+steps:
+  - { id: a, type: approval, needs: [ghost] }
+"#;
+        let err = WorkflowDefinition::parse(unknown, "yaml").unwrap_err();
+        assert!(format!("{err:#}").contains("unknown step 'ghost'"));
+    }
+
+    #[test]
+    fn rejects_references_to_non_ancestors() {
+        let yaml = r#"
+name: racy
+steps:
+  - { id: a, type: agent, prompt: "hi" }
+  - { id: b, type: agent, prompt: "{{steps.a.output}}" }
+"#;
+        let err = WorkflowDefinition::parse(yaml, "yaml").unwrap_err();
+        assert!(format!("{err:#}").contains("does not depend on 'a'"));
+
+        let yaml = r#"
+name: inputs
+steps:
+  - { id: a, type: agent, prompt: "{{inputs.topic}}" }
+"#;
+        let err = WorkflowDefinition::parse(yaml, "yaml").unwrap_err();
+        assert!(format!("{err:#}").contains("undeclared input 'topic'"));
+    }
+
+    #[test]
+    fn when_must_target_condition_step() {
+        let yaml = r#"
+name: bad-when
+steps:
+  - { id: a, type: agent, prompt: "hi" }
+  - { id: b, type: agent, prompt: "hi", needs: [a], when: "!a" }
+"#;
+        let err = WorkflowDefinition::parse(yaml, "yaml").unwrap_err();
+        assert!(format!("{err:#}").contains("not a condition step"));
+    }
+
+    #[test]
+    fn render_substitutes_inputs_and_outputs() {
+        let inputs = HashMap::from([("repo".to_string(), "/src".to_string())]);
+        let outputs = HashMap::from([("diff".to_string(), "+fn main()".to_string())]);
+        let ctx = TemplateContext {
+            inputs: &inputs,
+            outputs: &outputs,
+        };
+        assert_eq!(
+            ctx.render("{{ inputs.repo }}: {{steps.diff.output}} {{steps.x.output}}"),
+            "/src: +fn main() {{steps.x.output}}"
+        );
+        let args = ctx.render_json(&serde_json::json!({"cmd": ["ls", "{{inputs.repo}}"], "n": 1}));
+        assert_eq!(args, serde_json::json!({"cmd": ["ls", "/src"], "n": 1}));
+    }
+
+    #[test]
+    fn resolve_inputs_applies_defaults_and_requires_missing() {
+        let def = WorkflowDefinition::parse(REVIEW_YAML, "yaml").unwrap();
+        let resolved = def.resolve_inputs(&HashMap::new()).unwrap();
+        assert_eq!(resolved["repo"], ".");
+
+        let err = def
+            .resolve_inputs(&HashMap::from([("nope".to_string(), "x".to_string())]))
+            .unwrap_err();
+        assert!(err.to_string().contains("no input named 'nope'"));
+    }
+
+    #[test]
+    fn find_workflow_scans_workspace_directory() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let dir = tmp.path().join(WORKFLOWS_DIR);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("review.yaml"), REVIEW_YAML).unwrap();
+        std::fs::write(dir.join("notes.txt"), "not a workflow").unwrap();
+
+        assert_eq!(workflow_files(tmp.path()).unwrap().len(), 1);
+        let def = find_workflow(tmp.path(), "nightly-review").unwrap();
+        assert_eq!(def.steps.len(), 4);
+        assert!(find_workflow(tmp.path(), "missing").is_err());
+    }
+}
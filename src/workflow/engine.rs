@@ -0,0 +1,771 @@
+use super::definition::{StepAction, TemplateContext, WorkflowDefinition, WorkflowStep};
+use super::store::{RunStatus, StepState, StepStatus, WorkflowRun, WorkflowStore};
+use crate::approval::ApprovalManager;
+use crate::config::Config;
+use crate::memory::{self, Memory};
+use crate::runtime;
+use crate::security::tool_rules::RuleAction;
+use crate::security::SecurityPolicy;
+use crate::tools::{self, Tool};
+use anyhow::Result;
+use chrono::Utc;
+use futures_util::{stream, StreamExt};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// How a step finished.
+enum StepOutcome {
+    Succeeded(String),
+    Failed(String),
+    WaitingApproval,
+}
+
+/// Executes workflow runs step by step, persisting every state change so an
+/// interrupted run can be resumed where it stopped.
+pub struct WorkflowEngine {
+    config: Arc<Config>,
+    store: Arc<WorkflowStore>,
+    tools: Arc<Vec<Box<dyn Tool>>>,
+    security: Arc<SecurityPolicy>,
+    /// Which tools need a person's approval (`[autonomy]`). Nobody is asked
+    /// mid-run, so those tools only run behind an approved `approval` step.
+    approval: ApprovalManager,
+}
+
+impl WorkflowEngine {
+    pub fn new(
+        config: Arc<Config>,
+        tools: Arc<Vec<Box<dyn Tool>>>,
+        security: Arc<SecurityPolicy>,
+    ) -> Result<Self> {
+        let store = Arc::new(WorkflowStore::open(&config.workspace_dir)?);
+        let approval = ApprovalManager::from_config(&config.autonomy);
+        Ok(Self {
+            config,
+            store,
+            tools,
+            security,
+            approval,
+        })
+    }
+
+    /// Build an engine with the regular tool registry for `config`.
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let config = Arc::new(config.clone());
+        let security = Arc::new(SecurityPolicy::from_config(
+            &config.autonomy,
+            &config.workspace_dir,
+        ));
+        let runtime: Arc<dyn runtime::RuntimeAdapter> =
+            Arc::from(runtime::create_runtime(&config.runtime)?);
+        let mem: Arc<dyn Memory> = Arc::from(memory::create_memory_with_storage(
+            &config.memory,
+            Some(&config.storage.provider.config),
+            &config.workspace_dir,
+            config.api_key.as_deref(),
+        )?);
+        let (composio_key, composio_entity_id) = if config.composio.enabled {
+            (
+                config.composio.api_key.as_deref(),
+                Some(config.composio.entity_id.as_str()),
+            )
+        } else {
+            (None, None)
+        };
+        let tools = tools::all_tools_with_runtime(
+            config.clone(),
+            &security,
+            runtime,
+            mem,
+            composio_key,
+            composio_entity_id,
+            &config.browser,
+            &config.http_request,
+            &config.workspace_dir,
+            &config.agents,
+            config.api_key.as_deref(),
+            &config,
+        );
+        Self::new(config.clone(), Arc::new(tools), security)
+    }
+
+    pub fn store(&self) -> &WorkflowStore {
+        &self.store
+    }
+
+    /// Validate inputs and record a new run without executing it.
+    pub fn create_run(
+        &self,
+        definition: &WorkflowDefinition,
+        inputs: &HashMap<String, String>,
+        trigger: &str,
+    ) -> Result<WorkflowRun> {
+        definition.validate()?;
+        let inputs = definition.resolve_inputs(inputs)?;
+        self.store.create_run(definition, &inputs, trigger)
+    }
+
+    /// Start a run and drive it until it finishes or waits for approval.
+    pub async fn start(
+        &self,
+        definition: &WorkflowDefinition,
+        inputs: &HashMap<String, String>,
+        trigger: &str,
+    ) -> Result<WorkflowRun> {
+        let run = self.create_run(definition, inputs, trigger)?;
+        self.drive(&run.id).await
+    }
+
+    /// Continue an interrupted or failed run. Steps that already succeeded
+    /// keep their outputs; failed and interrupted steps run again.
+    pub async fn resume(&self, run_id: &str) -> Result<WorkflowRun> {
+        let run = self.store.get_run(run_id)?;
+        if run.status == RunStatus::Succeeded {
+            anyhow::bail!("Workflow run {} already succeeded", run.id);
+        }
+        for state in &run.steps {
+            if state.status == StepStatus::Failed {
+                self.store
+                    .save_step(&run.id, &StepState::pending(&state.step_id))?;
+            }
+        }
+        self.store
+            .set_run_status(&run.id, RunStatus::Running, None)?;
+        self.drive(&run.id).await
+    }
+
+    /// Approve or reject a step waiting for approval, then continue the run.
+    pub async fn decide(
+        &self,
+        run_id: &str,
+        step_id: &str,
+        approved: bool,
+        note: Option<&str>,
+    ) -> Result<WorkflowRun> {
+        let run = self.store.get_run(run_id)?;
+        let Some(state) = run.step(step_id) else {
+            anyhow::bail!("Workflow run {} has no step '{step_id}'", run.id);
+        };
+        if state.status != StepStatus::WaitingApproval {
+            anyhow::bail!(
+                "Step '{step_id}' is {}, not waiting for approval",
+                state.status.as_str()
+            );
+        }
+
+        let mut state = state.clone();
+        state.finished_at = Some(Utc::now());
+        if approved {
+            state.status = StepStatus::Succeeded;
+            state.output = Some(note.unwrap_or("approved").to_string());
+        } else {
+            state.status = StepStatus::Failed;
+            state.error = Some(match note {
+                Some(note) => format!("rejected: {note}"),
+                None => "rejected".to_string(),
+            });
+        }
+        self.store.save_step(&run.id, &state)?;
+        self.store
+            .set_run_status(&run.id, RunStatus::Running, None)?;
+        self.drive(&run.id).await
+    }
+
+    /// Execute ready steps wave by wave until the run finishes, fails or
+    /// blocks on an approval.
+    pub async fn drive(&self, run_id: &str) -> Result<WorkflowRun> {
+        let run = self.store.get_run(run_id)?;
+        if run.status.is_finished() {
+            return Ok(run);
+        }
+        let definition = run.definition.clone();
+        let inputs = run.inputs.clone();
+        let mut states: HashMap<String, StepState> = run
+            .steps
+            .into_iter()
+            .map(|state| (state.step_id.clone(), state))
+            .collect();
+
+        // A step still marked running was interrupted by a crash.
+        for state in states.values_mut() {
+            if state.status == StepStatus::Running {
+                *state = StepState::pending(&state.step_id);
+                self.store.save_step(run_id, state)?;
+            }
+        }
+
+        let max_parallel = self.config.workflows.max_parallel_steps.max(1);
+
+        loop {
+            if let Some(failed) = definition
+                .steps
+                .iter()
+                .filter_map(|step| states.get(&step.id))
+                .find(|state| state.status == StepStatus::Failed)
+            {
+                let error = format!(
+                    "step '{}' failed: {}",
+                    failed.step_id,
+                    failed.error.as_deref().unwrap_or("unknown error")
+                );
+                self.store
+                    .set_run_status(run_id, RunStatus::Failed, Some(&error))?;
+                break;
+            }
+
+            let ready: Vec<&WorkflowStep> = definition
+                .steps
+                .iter()
+                .filter(|step| status_of(&states, &step.id) == StepStatus::Pending)
+                .filter(|step| {
+                    step.needs
+                        .iter()
+                        .all(|need| status_of(&states, need).is_settled())
+                })
+                .collect();
+
+            if ready.is_empty() {
+                let waiting = states
+                    .values()
+                    .any(|state| state.status == StepStatus::WaitingApproval);
+                if waiting {
+                    self.store
+                        .set_run_status(run_id, RunStatus::WaitingApproval, None)?;
+                } else if states.values().all(|state| state.status.is_settled()) {
+                    self.store
+                        .set_run_status(run_id, RunStatus::Succeeded, None)?;
+                } else {
+                    self.store.set_run_status(
+                        run_id,
+                        RunStatus::Failed,
+                        Some("no runnable steps left"),
+                    )?;
+                }
+                break;
+            }
+
+            let outputs = step_outputs(&states);
+            let mut runnable = Vec::new();
+            for step in ready {
+                if let Some(reason) = skip_reason(step, &states, &outputs) {
+                    let mut state = StepState::pending(&step.id);
+                    state.status = StepStatus::Skipped;
+                    state.output = Some(String::new());
+                    state.error = Some(reason);
+                    state.finished_at = Some(Utc::now());
+                    self.store.save_step(run_id, &state)?;
+                    states.insert(step.id.clone(), state);
+                } else {
+                    runnable.push(step);
+                }
+            }
+
+            for step in &runnable {
+                let mut state = StepState::pending(&step.id);
+                state.status = StepStatus::Running;
+                state.started_at = Some(Utc::now());
+                self.store.save_step(run_id, &state)?;
+                states.insert(step.id.clone(), state);
+            }
+
+            let pending: Vec<_> = runnable
+                .into_iter()
+                .map(|step| {
+                    let approved = approved_upstream(&definition, step, &states);
+                    self.execute_step(step, &inputs, &outputs, approved)
+                })
+                .collect();
+            let results: Vec<(String, StepOutcome)> = stream::iter(pending)
+                .buffer_unordered(max_parallel)
+                .collect()
+                .await;
+
+            for (step_id, outcome) in results {
+                let Some(state) = states.get_mut(&step_id) else {
+                    continue;
+                };
+                match outcome {
+                    StepOutcome::Succeeded(output) => {
+                        state.status = StepStatus::Succeeded;
+                        state.output = Some(output);
+                        state.finished_at = Some(Utc::now());
+                    }
+                    StepOutcome::Failed(error) => {
+                        state.status = StepStatus::Failed;
+                        state.error = Some(error);
+                        state.finished_at = Some(Utc::now());
+                    }
+                    StepOutcome::WaitingApproval => {
+                        state.status = StepStatus::WaitingApproval;
+                    }
+                }
+                self.store.save_step(run_id, state)?;
+            }
+        }
+
+        self.store.get_run(run_id)
+    }
+
+    async fn execute_step(
+        &self,
+        step: &WorkflowStep,
+        inputs: &HashMap<String, String>,
+        outputs: &HashMap<String, String>,
+        approved: bool,
+    ) -> (String, StepOutcome) {
+        let ctx = TemplateContext { inputs, outputs };
+        let outcome = match &step.action {
+            StepAction::Tool { tool, args } => {
+                self.call_tool(tool, ctx.render_json(args), approved).await
+            }
+            StepAction::Delegate {
+                agent,
+                prompt,
+                context,
+            } => {
+                let mut args = serde_json::json!({
+                    "agent": agent,
+                    "prompt": ctx.render(prompt),
+                });
+                if let Some(context) = context {
+                    args["context"] = serde_json::Value::String(ctx.render(context));
+                }
+                self.call_tool("delegate", args, approved).await
+            }
+            StepAction::Agent { prompt, model } => {
+                let prompt = format!("[workflow step {}] {}", step.id, ctx.render(prompt));
+                match crate::agent::run(
+                    (*self.config).clone(),
+                    Some(prompt),
+                    None,
+                    model.clone(),
+                    self.config.default_temperature,
+                    vec![],
+                    None,
+                )
+                .await
+                {
+                    Ok(response) => StepOutcome::Succeeded(response),
+                    Err(e) => StepOutcome::Failed(format!("agent step failed: {e}")),
+                }
+            }
+            StepAction::Condition {
+                value,
+                equals,
+                contains,
+            } => {
+                let value = ctx.render(value);
+                let result = evaluate_condition(
+                    &value,
+                    equals.as_deref().map(|v| ctx.render(v)).as_deref(),
+                    contains.as_deref().map(|v| ctx.render(v)).as_deref(),
+                );
+                StepOutcome::Succeeded(result.to_string())
+            }
+            StepAction::Approval { .. } => StepOutcome::WaitingApproval,
+        };
+        (step.id.clone(), outcome)
+    }
+
+    /// Run a tool step. `approved` is whether an approved `approval` step
+    /// comes before it; tools that need approval fail without one.
+    async fn call_tool(
+        &self,
+        name: &str,
+        mut args: serde_json::Value,
+        approved: bool,
+    ) -> StepOutcome {
+        // The tool's own `approved` flag is for calls a person approved one
+        // by one; a workflow file cannot set it.
+        if let Some(args) = args.as_object_mut() {
+            args.remove("approved");
+        }
+        if let Err(error) = self.security.check_tool(name) {
+            return StepOutcome::Failed(error);
+        }
+        let rule = self.security.check_tool_args(name, &args, "workflow");
+        if rule.action != RuleAction::Allow {
+            return StepOutcome::Failed(format!("{} ({})", rule.reason, rule.action));
+        }
+        let Some(tool) = self.tools.iter().find(|tool| tool.name() == name) else {
+            return StepOutcome::Failed(if name == "delegate" {
+                "delegate steps need at least one [agents.<name>] in config.toml".to_string()
+            } else {
+                format!("Unknown tool '{name}'")
+            });
+        };
+        if !approved && self.approval.needs_approval(name) {
+            return StepOutcome::Failed(format!(
+                "{name} needs approval; add an approval step to this step's needs"
+            ));
+        }
+        match tool.execute(args).await {
+            Ok(result) if result.success => StepOutcome::Succeeded(result.output),
+            Ok(result) => StepOutcome::Failed(
+                result
+                    .error
+                    .unwrap_or_else(|| format!("{name} reported failure")),
+            ),
+            Err(e) => StepOutcome::Failed(format!("{name} failed: {e}")),
+        }
+    }
+}
+
+/// Whether an approval step that `step` depends on, directly or through
+/// other steps, was approved.
+fn approved_upstream(
+    definition: &WorkflowDefinition,
+    step: &WorkflowStep,
+    states: &HashMap<String, StepState>,
+) -> bool {
+    let mut pending: Vec<&str> = step.needs.iter().map(String::as_str).collect();
+    let mut seen = std::collections::HashSet::new();
+    while let Some(id) = pending.pop() {
+        if !seen.insert(id) {
+            continue;
+        }
+        let Some(upstream) = definition.steps.iter().find(|s| s.id == id) else {
+            continue;
+        };
+        if matches!(upstream.action, StepAction::Approval { .. })
+            && status_of(states, id) == StepStatus::Succeeded
+        {
+            return true;
+        }
+        pending.extend(upstream.needs.iter().map(String::as_str));
+    }
+    false
+}
+
+fn status_of(states: &HashMap<String, StepState>, step_id: &str) -> StepStatus {
+    states
+        .get(step_id)
+        .map_or(StepStatus::Pending, |state| state.status)
+}
+
+/// Outputs of settled steps (skipped steps contribute an empty string).
+fn step_outputs(states: &HashMap<String, StepState>) -> HashMap<String, String> {
+    states
+        .values()
+        .filter(|state| state.status.is_settled())
+        .map(|state| {
+            (
+                state.step_id.clone(),
+                state.output.clone().unwrap_or_default(),
+            )
+        })
+        .collect()
+}
+
+/// Why a ready step should be skipped instead of run, if at all.
+fn skip_reason(
+    step: &WorkflowStep,
+    states: &HashMap<String, StepState>,
+    outputs: &HashMap<String, String>,
+) -> Option<String> {
+    if let Some(need) = step
+        .needs
+        .iter()
+        .find(|need| status_of(states, need) == StepStatus::Skipped)
+    {
+        return Some(format!("dependency '{need}' was skipped"));
+    }
+    let when = step.when.as_deref()?.trim();
+    let (negate, target) = match when.strip_prefix('!') {
+        Some(target) => (true, target.trim()),
+        None => (false, when),
+    };
+    let holds = outputs.get(target).is_some_and(|output| output == "true");
+    (holds == negate).then(|| format!("condition '{when}' was not met"))
+}
+
+/// Condition semantics: `equals` and `contains` compare trimmed text;
+/// otherwise any non-empty value other than false/0/no is true.
+fn evaluate_condition(value: &str, equals: Option<&str>, contains: Option<&str>) -> bool {
+    let value = value.trim();
+    if let Some(expected) = equals {
+        return value == expected.trim();
+    }
+    if let Some(needle) = contains {
+        return value.contains(needle);
+    }
+    !value.is_empty() && !matches!(value.to_ascii_lowercase().as_str(), "false" | "0" | "no")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::ToolResult;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tempfile::TempDir;
+
+    /// Echoes its `text` argument; fails when `fail` is true.
+    struct EchoTool {
+        calls: Arc<AtomicUsize>,
+    }
+
+    /// Reports whether it received an `approved` flag. Not auto-approved.
+    struct DeployTool;
+
+    #[async_trait]
+    impl Tool for DeployTool {
+        fn name(&self) -> &str {
+            "deploy"
+        }
+
+        fn description(&self) -> &str {
+            "deploy"
+        }
+
+        fn parameters_schema(&self) -> serde_json::Value {
+            serde_json::json!({"type": "object", "properties": {"approved": {"type": "boolean"}}})
+        }
+
+        async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+            Ok(ToolResult {
+                success: true,
+                output: format!("approved flag: {}", args.get("approved").is_some()),
+                error: None,
+            })
+        }
+    }
+
+    #[async_trait]
+    impl Tool for EchoTool {
+        fn name(&self) -> &str {
+            "echo"
+        }
+
+        fn description(&self) -> &str {
+            "echo"
+        }
+
+        fn parameters_schema(&self) -> serde_json::Value {
+            serde_json::json!({"type": "object"})
+        }
+
+        async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let text = args["text"].as_str().unwrap_or_default().to_string();
+            if args["fail"].as_bool().unwrap_or(false) {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("failed on {text}")),
+                });
+            }
+            Ok(ToolResult {
+                success: true,
+                output: text,
+                error: None,
+            })
+        }
+    }
+
+    fn engine(tmp: &TempDir) -> (WorkflowEngine, Arc<AtomicUsize>) {
+        let mut config = Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        };
+        config.autonomy.auto_approve.push("echo".into());
+        let calls = Arc::new(AtomicUsize::new(0));
+        let tools: Vec<Box<dyn Tool>> = vec![
+            Box::new(EchoTool {
+                calls: calls.clone(),
+            }),
+            Box::new(DeployTool),
+        ];
+        let security = Arc::new(SecurityPolicy {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..SecurityPolicy::default()
+        });
+        let engine = WorkflowEngine::new(Arc::new(config), Arc::new(tools), security).unwrap();
+        (engine, calls)
+    }
+
+    fn parse(yaml: &str) -> WorkflowDefinition {
+        WorkflowDefinition::parse(yaml, "yaml").unwrap()
+    }
+
+    #[tokio::test]
+    async fn outputs_flow_between_steps() {
+        let tmp = TempDir::new().unwrap();
+        let (engine, _) = engine(&tmp);
+        let def = parse(
+            r#"
+name: chain
+inputs:
+  who: { default: "world" }
+steps:
+  - { id: greet, type: tool, tool: echo, args: { text: "hello {{inputs.who}}" } }
+  - { id: shout, type: tool, tool: echo, needs: [greet], args: { text: "{{steps.greet.output}}!" } }
+"#,
+        );
+        let run = engine.start(&def, &HashMap::new(), "cli").await.unwrap();
+        assert_eq!(run.status, RunStatus::Succeeded);
+        assert_eq!(
+            run.step("shout").unwrap().output.as_deref(),
+            Some("hello world!")
+        );
+    }
+
+    #[tokio::test]
+    async fn conditions_skip_branches_and_propagate() {
+        let tmp = TempDir::new().unwrap();
+        let (engine, _) = engine(&tmp);
+        let def = parse(
+            r#"
+name: branch
+steps:
+  - { id: probe, type: tool, tool: echo, args: { text: "all clear" } }
+  - { id: alarm, type: condition, needs: [probe], value: "{{steps.probe.output}}", contains: "ERROR" }
+  - { id: page, type: tool, tool: echo, needs: [alarm], when: alarm, args: { text: "paging" } }
+  - { id: after_page, type: tool, tool: echo, needs: [page], args: { text: "follow-up" } }
+  - { id: report, type: tool, tool: echo, needs: [alarm], when: "!alarm", args: { text: "ok" } }
+"#,
+        );
+        let run = engine.start(&def, &HashMap::new(), "cli").await.unwrap();
+        assert_eq!(run.status, RunStatus::Succeeded);
+        assert_eq!(run.step("alarm").unwrap().output.as_deref(), Some("false"));
+        assert_eq!(run.step("page").unwrap().status, StepStatus::Skipped);
+        assert_eq!(run.step("after_page").unwrap().status, StepStatus::Skipped);
+        assert_eq!(run.step("report").unwrap().status, StepStatus::Succeeded);
+    }
+
+    #[tokio::test]
+    async fn approval_pauses_until_decided() {
+        let tmp = TempDir::new().unwrap();
+        let (engine, calls) = engine(&tmp);
+        let def = parse(
+            r#"
+name: gated
+steps:
+  - { id: prepare, type: tool, tool: echo, args: { text: "draft" } }
+  - { id: gate, type: approval, needs: [prepare], message: "Ship {{steps.prepare.output}}?" }
+  - { id: ship, type: tool, tool: echo, needs: [gate], args: { text: "shipped {{steps.gate.output}}" } }
+"#,
+        );
+        let run = engine.start(&def, &HashMap::new(), "cli").await.unwrap();
+        assert_eq!(run.status, RunStatus::WaitingApproval);
+        assert_eq!(
+            run.step("gate").unwrap().status,
+            StepStatus::WaitingApproval
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let run = engine
+            .decide(&run.id, "gate", true, Some("by ops"))
+            .await
+            .unwrap();
+        assert_eq!(run.status, RunStatus::Succeeded);
+        assert_eq!(
+            run.step("ship").unwrap().output.as_deref(),
+            Some("shipped by ops")
+        );
+
+        let run = engine.start(&def, &HashMap::new(), "cli").await.unwrap();
+        let run = engine.decide(&run.id, "gate", false, None).await.unwrap();
+        assert_eq!(run.status, RunStatus::Failed);
+        assert!(run.error.as_deref().unwrap().contains("rejected"));
+        assert_eq!(run.step("ship").unwrap().status, StepStatus::Pending);
+    }
+
+    #[tokio::test]
+    async fn failed_step_stops_run_and_resume_reruns_only_unfinished_steps() {
+        let tmp = TempDir::new().unwrap();
+        let (engine, calls) = engine(&tmp);
+        let def = parse(
+            r#"
+name: flaky
+steps:
+  - { id: one, type: tool, tool: echo, args: { text: "1" } }
+  - { id: two, type: tool, tool: echo, needs: [one], args: { text: "2", fail: true } }
+  - { id: three, type: tool, tool: echo, needs: [two], args: { text: "3" } }
+"#,
+        );
+        let run = engine.start(&def, &HashMap::new(), "cli").await.unwrap();
+        assert_eq!(run.status, RunStatus::Failed);
+        assert!(run.error.as_deref().unwrap().contains("step 'two' failed"));
+        assert_eq!(run.step("three").unwrap().status, StepStatus::Pending);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        // Simulate a crash while step three was running after a fixed step two.
+        let mut two = run.step("two").unwrap().clone();
+        two.status = StepStatus::Succeeded;
+        two.output = Some("2".into());
+        engine.store().save_step(&run.id, &two).unwrap();
+        let mut three = StepState::pending("three");
+        three.status = StepStatus::Running;
+        engine.store().save_step(&run.id, &three).unwrap();
+
+        let run = engine.resume(&run.id).await.unwrap();
+        assert_eq!(run.status, RunStatus::Succeeded);
+        assert_eq!(run.step("three").unwrap().output.as_deref(), Some("3"));
+        // Only step three ran again.
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        assert!(engine.resume(&run.id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn supervised_tools_need_an_approval_step() {
+        let tmp = TempDir::new().unwrap();
+        let (engine, _) = engine(&tmp);
+        let def = parse(
+            r#"
+name: ungated
+steps:
+  - { id: deploy, type: tool, tool: deploy, args: { approved: true } }
+"#,
+        );
+        let run = engine.start(&def, &HashMap::new(), "cron").await.unwrap();
+        assert_eq!(run.status, RunStatus::Failed);
+        assert!(run.error.unwrap().contains("deploy needs approval"));
+
+        let def = parse(
+            r#"
+name: gated
+steps:
+  - { id: gate, type: approval, message: "Deploy?" }
+  - { id: build, type: tool, tool: echo, needs: [gate], args: { text: "built" } }
+  - { id: deploy, type: tool, tool: deploy, needs: [build], args: { approved: true } }
+"#,
+        );
+        let run = engine.start(&def, &HashMap::new(), "cron").await.unwrap();
+        assert_eq!(run.status, RunStatus::WaitingApproval);
+        let run = engine.decide(&run.id, "gate", true, None).await.unwrap();
+        assert_eq!(run.status, RunStatus::Succeeded);
+        // The workflow file's `approved` flag never reaches the tool.
+        assert_eq!(
+            run.step("deploy").unwrap().output.as_deref(),
+            Some("approved flag: false")
+        );
+    }
+
+    #[tokio::test]
+    async fn unknown_tool_and_missing_delegate_fail_the_step() {
+        let tmp = TempDir::new().unwrap();
+        let (engine, _) = engine(&tmp);
+        let def = parse(
+            r#"
+name: missing
+steps:
+  - { id: ask, type: delegate, agent: researcher, prompt: "hi" }
+"#,
+        );
+        let run = engine.start(&def, &HashMap::new(), "cli").await.unwrap();
+        assert_eq!(run.status, RunStatus::Failed);
+        assert!(run.error.unwrap().contains("[agents.<name>]"));
+    }
+
+    #[test]
+    fn condition_semantics() {
+        assert!(evaluate_condition(" yes ", None, None));
+        assert!(!evaluate_condition("False", None, None));
+        assert!(!evaluate_condition("   ", None, None));
+        assert!(evaluate_condition("ok\n", Some("ok"), None));
+        assert!(evaluate_condition("3 ERRORs", None, Some("ERROR")));
+        assert!(!evaluate_condition("fine", None, Some("ERROR")));
+    }
+}
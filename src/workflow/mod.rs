@@ -0,0 +1,228 @@
+//! Declarative multi-step workflows.
+//!
+//! Workflow files live in `workspace/workflows/*.{yaml,yml,toml}`. Each one
+//! is a DAG of tool, agent, delegate, condition and approval steps whose
+//! outputs feed later steps through `{{steps.<id>.output}}` placeholders.
+//! Runs are triggered from the CLI, cron (`zeroclaw cron add-workflow`) or
+//! the gateway (`POST /workflows/{name}/run`), and every step transition is
+//! persisted in `workspace/workflows/runs.db` so an interrupted run can be
+//! resumed with `zeroclaw workflow resume <run-id>`.
+
+mod definition;
+mod engine;
+mod store;
+
+#[allow(unused_imports)]
+pub use definition::{
+    find_workflow, workflow_files, StepAction, WorkflowDefinition, WorkflowInput, WorkflowStep,
+    WORKFLOWS_DIR,
+};
+pub use engine::WorkflowEngine;
+#[allow(unused_imports)]
+pub use store::{RunStatus, StepState, StepStatus, WorkflowRun, WorkflowStore};
+
+use crate::config::Config;
+use crate::util::truncate_with_ellipsis;
+use anyhow::Result;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Characters of step output shown by `zeroclaw workflow status`.
+const STATUS_OUTPUT_PREVIEW_CHARS: usize = 200;
+
+/// Parse `key=value` CLI inputs.
+fn parse_inputs(raw: &[String]) -> Result<HashMap<String, String>> {
+    raw.iter()
+        .map(|pair| {
+            let (key, value) = pair
+                .split_once('=')
+                .ok_or_else(|| anyhow::anyhow!("Invalid input '{pair}', expected key=value"))?;
+            Ok((key.trim().to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// One-line summary of a run, used by the CLI and the cron trigger.
+pub fn describe_run(run: &WorkflowRun) -> String {
+    let done = run
+        .steps
+        .iter()
+        .filter(|step| step.status.is_settled())
+        .count();
+    let mut line = format!(
+        "workflow '{}' run {} {} ({done}/{} steps done)",
+        run.workflow,
+        run.id,
+        run.status.as_str(),
+        run.steps.len()
+    );
+    if let Some(error) = &run.error {
+        line.push_str(": ");
+        line.push_str(error);
+    }
+    if run.status == RunStatus::WaitingApproval {
+        let waiting: Vec<&str> = run
+            .steps
+            .iter()
+            .filter(|step| step.status == StepStatus::WaitingApproval)
+            .map(|step| step.step_id.as_str())
+            .collect();
+        let _ = write!(line, " — waiting on: {}", waiting.join(", "));
+    }
+    line
+}
+
+fn print_run(run: &WorkflowRun) {
+    println!("{}", describe_run(run));
+    println!(
+        "  trigger: {} | started {} | updated {}",
+        run.trigger,
+        run.created_at.to_rfc3339(),
+        run.updated_at.to_rfc3339()
+    );
+    for state in &run.steps {
+        let kind = run
+            .definition
+            .step(&state.step_id)
+            .map_or("?", |step| step.action.kind());
+        println!("  - {} [{kind}] {}", state.step_id, state.status.as_str());
+        if state.status == StepStatus::WaitingApproval {
+            if let Some(StepAction::Approval {
+                message: Some(message),
+            }) = run.definition.step(&state.step_id).map(|step| &step.action)
+            {
+                println!("      {message}");
+            }
+        }
+        if let Some(output) = state.output.as_deref().filter(|o| !o.trim().is_empty()) {
+            println!(
+                "      → {}",
+                truncate_with_ellipsis(output.trim(), STATUS_OUTPUT_PREVIEW_CHARS)
+            );
+        }
+        if let Some(error) = &state.error {
+            println!("      ! {error}");
+        }
+    }
+}
+
+pub async fn handle_command(command: crate::WorkflowCommands, config: Config) -> Result<()> {
+    match command {
+        crate::WorkflowCommands::List => {
+            let files = workflow_files(&config.workspace_dir)?;
+            if files.is_empty() {
+                println!(
+                    "No workflows yet. Add YAML or TOML files to {}",
+                    config.workspace_dir.join(WORKFLOWS_DIR).display()
+                );
+                return Ok(());
+            }
+
+            println!("🧩 Workflows ({}):", files.len());
+            for path in files {
+                let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+                match WorkflowDefinition::load(&path) {
+                    Ok(def) => {
+                        println!("- {} ({file_name}) | {} steps", def.name, def.steps.len());
+                        if let Some(description) = &def.description {
+                            println!("    {description}");
+                        }
+                    }
+                    Err(e) => println!("- ⚠️  {file_name}: {e:#}"),
+                }
+            }
+            Ok(())
+        }
+        crate::WorkflowCommands::Show { name } => {
+            let def = find_workflow(&config.workspace_dir, &name)?;
+            println!("🧩 {}", def.name);
+            if let Some(description) = &def.description {
+                println!("{description}");
+            }
+            if !def.inputs.is_empty() {
+                println!("\nInputs:");
+                for (input, spec) in &def.inputs {
+                    let default = spec
+                        .default
+                        .as_deref()
+                        .map_or_else(|| "required".to_string(), |d| format!("default: {d}"));
+                    println!("  - {input} ({default})");
+                }
+            }
+            println!("\nSteps (execution order):");
+            for id in def.topological_order()? {
+                if let Some(step) = def.step(&id) {
+                    let mut line = format!("  - {id} [{}]", step.action.kind());
+                    if !step.needs.is_empty() {
+                        let _ = write!(line, " needs {}", step.needs.join(", "));
+                    }
+                    if let Some(when) = &step.when {
+                        let _ = write!(line, " when {when}");
+                    }
+                    println!("{line}");
+                }
+            }
+            Ok(())
+        }
+        crate::WorkflowCommands::Run { name, input } => {
+            let def = find_workflow(&config.workspace_dir, &name)?;
+            let inputs = parse_inputs(&input)?;
+            let engine = WorkflowEngine::from_config(&config)?;
+            let run = engine.start(&def, &inputs, "cli").await?;
+            print_run(&run);
+            if run.status == RunStatus::WaitingApproval {
+                println!(
+                    "\nApprove with: zeroclaw workflow approve {} <step>",
+                    run.id
+                );
+            }
+            Ok(())
+        }
+        crate::WorkflowCommands::Runs { workflow, limit } => {
+            let store = WorkflowStore::open(&config.workspace_dir)?;
+            let runs = store.list_runs(workflow.as_deref(), limit)?;
+            if runs.is_empty() {
+                println!("No workflow runs yet.");
+                return Ok(());
+            }
+            println!("🧩 Workflow runs ({}):", runs.len());
+            for run in runs {
+                println!("- {}", describe_run(&run));
+            }
+            Ok(())
+        }
+        crate::WorkflowCommands::Status { id } => {
+            let store = WorkflowStore::open(&config.workspace_dir)?;
+            print_run(&store.get_run(&id)?);
+            Ok(())
+        }
+        crate::WorkflowCommands::Resume { id } => {
+            let engine = WorkflowEngine::from_config(&config)?;
+            print_run(&engine.resume(&id).await?);
+            Ok(())
+        }
+        crate::WorkflowCommands::Approve { id, step, note } => {
+            let engine = WorkflowEngine::from_config(&config)?;
+            print_run(&engine.decide(&id, &step, true, note.as_deref()).await?);
+            Ok(())
+        }
+        crate::WorkflowCommands::Reject { id, step, note } => {
+            let engine = WorkflowEngine::from_config(&config)?;
+            print_run(&engine.decide(&id, &step, false, note.as_deref()).await?);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_inputs_splits_on_first_equals() {
+        let inputs = parse_inputs(&["repo=/src".into(), "query=a=b".into()]).unwrap();
+        assert_eq!(inputs["repo"], "/src");
+        assert_eq!(inputs["query"], "a=b");
+        assert!(parse_inputs(&["novalue".into()]).is_err());
+    }
+}
@@ -0,0 +1,496 @@
+use super::definition::WorkflowDefinition;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+use uuid::Uuid;
+
+const MAX_STEP_OUTPUT_BYTES: usize = 64 * 1024;
+const TRUNCATED_OUTPUT_MARKER: &str = "\n...[truncated]";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunStatus {
+    Running,
+    WaitingApproval,
+    Succeeded,
+    Failed,
+}
+
+impl RunStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Running => "running",
+            Self::WaitingApproval => "waiting_approval",
+            Self::Succeeded => "succeeded",
+            Self::Failed => "failed",
+        }
+    }
+
+    fn parse(raw: &str) -> Self {
+        match raw {
+            "waiting_approval" => Self::WaitingApproval,
+            "succeeded" => Self::Succeeded,
+            "failed" => Self::Failed,
+            _ => Self::Running,
+        }
+    }
+
+    pub fn is_finished(self) -> bool {
+        matches!(self, Self::Succeeded | Self::Failed)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StepStatus {
+    Pending,
+    Running,
+    WaitingApproval,
+    Succeeded,
+    Skipped,
+    Failed,
+}
+
+impl StepStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Running => "running",
+            Self::WaitingApproval => "waiting_approval",
+            Self::Succeeded => "succeeded",
+            Self::Skipped => "skipped",
+            Self::Failed => "failed",
+        }
+    }
+
+    fn parse(raw: &str) -> Self {
+        match raw {
+            "running" => Self::Running,
+            "waiting_approval" => Self::WaitingApproval,
+            "succeeded" => Self::Succeeded,
+            "skipped" => Self::Skipped,
+            "failed" => Self::Failed,
+            _ => Self::Pending,
+        }
+    }
+
+    /// Finished in a way that lets dependents proceed.
+    pub fn is_settled(self) -> bool {
+        matches!(self, Self::Succeeded | Self::Skipped)
+    }
+}
+
+/// Persisted state of one step in a run.
+#[derive(Debug, Clone, Serialize)]
+pub struct StepState {
+    pub step_id: String,
+    pub status: StepStatus,
+    pub output: Option<String>,
+    pub error: Option<String>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl StepState {
+    pub fn pending(step_id: &str) -> Self {
+        Self {
+            step_id: step_id.to_string(),
+            status: StepStatus::Pending,
+            output: None,
+            error: None,
+            started_at: None,
+            finished_at: None,
+        }
+    }
+}
+
+/// A workflow run with the definition snapshot it was started from, so a
+/// resumed run is not affected by later edits to the workflow file.
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkflowRun {
+    pub id: String,
+    pub workflow: String,
+    pub definition: WorkflowDefinition,
+    pub inputs: HashMap<String, String>,
+    pub trigger: String,
+    pub status: RunStatus,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub steps: Vec<StepState>,
+}
+
+impl WorkflowRun {
+    pub fn step(&self, step_id: &str) -> Option<&StepState> {
+        self.steps.iter().find(|step| step.step_id == step_id)
+    }
+}
+
+/// SQLite-backed run state in `workspace/workflows/runs.db`.
+pub struct WorkflowStore {
+    conn: Mutex<Connection>,
+}
+
+impl WorkflowStore {
+    pub fn open(workspace_dir: &Path) -> Result<Self> {
+        let db_path = workspace_dir
+            .join(super::definition::WORKFLOWS_DIR)
+            .join("runs.db");
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent).with_context(|| {
+                format!("Failed to create workflows directory: {}", parent.display())
+            })?;
+        }
+
+        let conn = Connection::open(&db_path)
+            .with_context(|| format!("Failed to open workflow DB: {}", db_path.display()))?;
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             PRAGMA synchronous  = NORMAL;
+             PRAGMA foreign_keys = ON;
+             CREATE TABLE IF NOT EXISTS workflow_runs (
+                id         TEXT PRIMARY KEY,
+                workflow   TEXT NOT NULL,
+                definition TEXT NOT NULL,
+                inputs     TEXT NOT NULL,
+                trigger    TEXT NOT NULL,
+                status     TEXT NOT NULL,
+                error      TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+             );
+             CREATE INDEX IF NOT EXISTS idx_workflow_runs_workflow ON workflow_runs(workflow, created_at);
+
+             CREATE TABLE IF NOT EXISTS workflow_steps (
+                run_id      TEXT NOT NULL,
+                step_id     TEXT NOT NULL,
+                status      TEXT NOT NULL,
+                output      TEXT,
+                error       TEXT,
+                started_at  TEXT,
+                finished_at TEXT,
+                PRIMARY KEY (run_id, step_id),
+                FOREIGN KEY (run_id) REFERENCES workflow_runs(id) ON DELETE CASCADE
+             );",
+        )
+        .context("Failed to initialize workflow schema")?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// Record a new run with every step pending.
+    pub fn create_run(
+        &self,
+        definition: &WorkflowDefinition,
+        inputs: &HashMap<String, String>,
+        trigger: &str,
+    ) -> Result<WorkflowRun> {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+        let mut conn = self.conn.lock();
+        let tx = conn.transaction()?;
+        tx.execute(
+            "INSERT INTO workflow_runs
+                (id, workflow, definition, inputs, trigger, status, error, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, NULL, ?7, ?7)",
+            params![
+                id,
+                definition.name,
+                serde_json::to_string(definition)?,
+                serde_json::to_string(inputs)?,
+                trigger,
+                RunStatus::Running.as_str(),
+                now,
+            ],
+        )
+        .context("Failed to insert workflow run")?;
+        for step in &definition.steps {
+            tx.execute(
+                "INSERT INTO workflow_steps (run_id, step_id, status) VALUES (?1, ?2, ?3)",
+                params![id, step.id, StepStatus::Pending.as_str()],
+            )?;
+        }
+        tx.commit()?;
+        drop(conn);
+
+        self.get_run(&id)
+    }
+
+    /// Load a run by id or unique id prefix.
+    pub fn get_run(&self, id: &str) -> Result<WorkflowRun> {
+        let conn = self.conn.lock();
+        let pattern = format!("{}%", id.replace('%', "").replace('_', "\\_"));
+        let mut stmt = conn.prepare(
+            "SELECT id, workflow, definition, inputs, trigger, status, error, created_at, updated_at
+             FROM workflow_runs WHERE id = ?1 OR id LIKE ?2 ESCAPE '\\' LIMIT 2",
+        )?;
+        let rows: Vec<RunRow> = stmt
+            .query_map(params![id, pattern], map_run_row)?
+            .collect::<rusqlite::Result<_>>()?;
+        let row = match rows.as_slice() {
+            [row] => row.clone(),
+            [] => anyhow::bail!("Workflow run '{id}' not found"),
+            _ => match rows.iter().find(|row| row.id == id) {
+                Some(row) => row.clone(),
+                None => anyhow::bail!("Workflow run id prefix '{id}' is ambiguous"),
+            },
+        };
+        drop(stmt);
+        let steps = load_steps(&conn, &row.id)?;
+        row.into_run(steps)
+    }
+
+    /// Most recent runs, optionally for one workflow.
+    pub fn list_runs(&self, workflow: Option<&str>, limit: usize) -> Result<Vec<WorkflowRun>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT id, workflow, definition, inputs, trigger, status, error, created_at, updated_at
+             FROM workflow_runs WHERE ?1 IS NULL OR workflow = ?1
+             ORDER BY created_at DESC, rowid DESC LIMIT ?2",
+        )?;
+        let rows: Vec<RunRow> = stmt
+            .query_map(
+                params![workflow, i64::try_from(limit).unwrap_or(i64::MAX)],
+                map_run_row,
+            )?
+            .collect::<rusqlite::Result<_>>()?;
+        drop(stmt);
+        rows.into_iter()
+            .map(|row| {
+                let steps = load_steps(&conn, &row.id)?;
+                row.into_run(steps)
+            })
+            .collect()
+    }
+
+    pub fn set_run_status(
+        &self,
+        run_id: &str,
+        status: RunStatus,
+        error: Option<&str>,
+    ) -> Result<()> {
+        self.conn
+            .lock()
+            .execute(
+                "UPDATE workflow_runs SET status = ?2, error = ?3, updated_at = ?4 WHERE id = ?1",
+                params![run_id, status.as_str(), error, Utc::now().to_rfc3339()],
+            )
+            .context("Failed to update workflow run status")?;
+        Ok(())
+    }
+
+    pub fn save_step(&self, run_id: &str, state: &StepState) -> Result<()> {
+        let conn = self.conn.lock();
+        conn.execute(
+            "UPDATE workflow_steps
+             SET status = ?3, output = ?4, error = ?5, started_at = ?6, finished_at = ?7
+             WHERE run_id = ?1 AND step_id = ?2",
+            params![
+                run_id,
+                state.step_id,
+                state.status.as_str(),
+                state.output.as_deref().map(truncate_step_output),
+                state.error,
+                state.started_at.map(|t| t.to_rfc3339()),
+                state.finished_at.map(|t| t.to_rfc3339()),
+            ],
+        )
+        .context("Failed to save workflow step state")?;
+        conn.execute(
+            "UPDATE workflow_runs SET updated_at = ?2 WHERE id = ?1",
+            params![run_id, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+}
+
+fn truncate_step_output(output: &str) -> String {
+    if output.len() <= MAX_STEP_OUTPUT_BYTES {
+        return output.to_string();
+    }
+    let mut end = MAX_STEP_OUTPUT_BYTES - TRUNCATED_OUTPUT_MARKER.len();
+    while !output.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}{TRUNCATED_OUTPUT_MARKER}", &output[..end])
+}
+
+#[derive(Clone)]
+struct RunRow {
+    id: String,
+    workflow: String,
+    definition: String,
+    inputs: String,
+    trigger: String,
+    status: String,
+    error: Option<String>,
+    created_at: String,
+    updated_at: String,
+}
+
+impl RunRow {
+    fn into_run(self, steps: Vec<StepState>) -> Result<WorkflowRun> {
+        Ok(WorkflowRun {
+            definition: serde_json::from_str(&self.definition)
+                .context("Corrupt workflow definition snapshot")?,
+            inputs: serde_json::from_str(&self.inputs).context("Corrupt workflow inputs")?,
+            status: RunStatus::parse(&self.status),
+            created_at: parse_rfc3339(&self.created_at)?,
+            updated_at: parse_rfc3339(&self.updated_at)?,
+            id: self.id,
+            workflow: self.workflow,
+            trigger: self.trigger,
+            error: self.error,
+            steps,
+        })
+    }
+}
+
+fn map_run_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<RunRow> {
+    Ok(RunRow {
+        id: row.get(0)?,
+        workflow: row.get(1)?,
+        definition: row.get(2)?,
+        inputs: row.get(3)?,
+        trigger: row.get(4)?,
+        status: row.get(5)?,
+        error: row.get(6)?,
+        created_at: row.get(7)?,
+        updated_at: row.get(8)?,
+    })
+}
+
+fn load_steps(conn: &Connection, run_id: &str) -> Result<Vec<StepState>> {
+    let mut stmt = conn.prepare(
+        "SELECT step_id, status, output, error, started_at, finished_at
+         FROM workflow_steps WHERE run_id = ?1 ORDER BY rowid",
+    )?;
+    let rows = stmt.query_map(params![run_id], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, Option<String>>(2)?,
+            row.get::<_, Option<String>>(3)?,
+            row.get::<_, Option<String>>(4)?,
+            row.get::<_, Option<String>>(5)?,
+        ))
+    })?;
+
+    let mut steps = Vec::new();
+    for row in rows {
+        let (step_id, status, output, error, started_at, finished_at) = row?;
+        steps.push(StepState {
+            step_id,
+            status: StepStatus::parse(&status),
+            output,
+            error,
+            started_at: started_at.as_deref().map(parse_rfc3339).transpose()?,
+            finished_at: finished_at.as_deref().map(parse_rfc3339).transpose()?,
+        });
+    }
+    Ok(steps)
+}
+
+fn parse_rfc3339(raw: &str) -> Result<DateTime<Utc>> {
+    let parsed = DateTime::parse_from_rfc3339(raw)
+        .with_context(|| format!("Invalid RFC3339 timestamp in workflow DB: {raw}"))?;
+    Ok(parsed.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn definition() -> WorkflowDefinition {
+        WorkflowDefinition::parse(
+            r#"
+name: demo
+steps:
+  - { id: first, type: agent, prompt: "one" }
+  - { id: second, type: agent, prompt: "{{steps.first.output}}", needs: [first] }
+"#,
+            "yaml",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn create_run_persists_pending_steps_and_snapshot() {
+        let tmp = TempDir::new().unwrap();
+        let store = WorkflowStore::open(tmp.path()).unwrap();
+        let inputs = HashMap::from([("k".to_string(), "v".to_string())]);
+        let run = store.create_run(&definition(), &inputs, "cli").unwrap();
+
+        assert_eq!(run.workflow, "demo");
+        assert_eq!(run.status, RunStatus::Running);
+        assert_eq!(run.inputs, inputs);
+        assert_eq!(run.definition, definition());
+        let ids: Vec<&str> = run.steps.iter().map(|s| s.step_id.as_str()).collect();
+        assert_eq!(ids, vec!["first", "second"]);
+        assert!(run.steps.iter().all(|s| s.status == StepStatus::Pending));
+    }
+
+    #[test]
+    fn step_state_survives_reopen() {
+        let tmp = TempDir::new().unwrap();
+        let run_id = {
+            let store = WorkflowStore::open(tmp.path()).unwrap();
+            let run = store
+                .create_run(&definition(), &HashMap::new(), "cron")
+                .unwrap();
+            let mut state = StepState::pending("first");
+            state.status = StepStatus::Succeeded;
+            state.output = Some("done".into());
+            state.finished_at = Some(Utc::now());
+            store.save_step(&run.id, &state).unwrap();
+            store
+                .set_run_status(&run.id, RunStatus::Failed, Some("boom"))
+                .unwrap();
+            run.id
+        };
+
+        let store = WorkflowStore::open(tmp.path()).unwrap();
+        let run = store.get_run(&run_id[..8]).unwrap();
+        assert_eq!(run.status, RunStatus::Failed);
+        assert_eq!(run.error.as_deref(), Some("boom"));
+        let first = run.step("first").unwrap();
+        assert_eq!(first.status, StepStatus::Succeeded);
+        assert_eq!(first.output.as_deref(), Some("done"));
+        assert_eq!(run.step("second").unwrap().status, StepStatus::Pending);
+    }
+
+    #[test]
+    fn list_runs_filters_by_workflow() {
+        let tmp = TempDir::new().unwrap();
+        let store = WorkflowStore::open(tmp.path()).unwrap();
+        store
+            .create_run(&definition(), &HashMap::new(), "cli")
+            .unwrap();
+        let mut other = definition();
+        other.name = "other".into();
+        store
+            .create_run(&other, &HashMap::new(), "webhook")
+            .unwrap();
+
+        assert_eq!(store.list_runs(None, 10).unwrap().len(), 2);
+        let runs = store.list_runs(Some("other"), 10).unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].trigger, "webhook");
+        assert!(store.get_run("missing").is_err());
+    }
+
+    #[test]
+    fn large_step_output_is_truncated() {
+        let big = "x".repeat(MAX_STEP_OUTPUT_BYTES + 100);
+        let truncated = truncate_step_output(&big);
+        assert!(truncated.len() <= MAX_STEP_OUTPUT_BYTES);
+        assert!(truncated.ends_with(TRUNCATED_OUTPUT_MARKER));
+    }
+}